@@ -1,7 +1,19 @@
-pub fn main() {
-    let number = 5 + 10 * 10;
-    let trstnumber = number - (19 / 10) - 1;
-    println!("{number}");
-    println!("Hello World");
-    println!("{trstnumber}");
+#[derive(Clone, Default)]
+struct HelloWorld {}
+
+impl HelloWorld {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let number = i32::wrapping_add(5, i32::wrapping_mul(10, 10));
+        let trstnumber = i32::wrapping_sub(i32::wrapping_sub(number, 19 / 10), 1);
+
+        println!("{number}");
+        println!("Hello World");
+        println!("{trstnumber}");
+    }
+}
+
+fn main() {
+    HelloWorld::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
 }
@@ -0,0 +1,49 @@
+// Exceptions of java.lang, java.io and java.util
+
+use crate::parser::nodes::variable::VariableType;
+
+/// The package and the super class of an exception class of the Java library, Throwable is
+/// the only one without super class
+///
+/// # Example
+///
+/// NumberFormatException -> ("java.lang", Some("IllegalArgumentException"))
+pub fn get_library_exception(name: &str) -> Option<(&'static str, Option<&'static str>)> {
+    let (package, super_class) = match name {
+        "Throwable" => ("java.lang", None),
+        "Exception" | "Error" => ("java.lang", Some("Throwable")),
+        "RuntimeException" | "InterruptedException" | "CloneNotSupportedException" => {
+            ("java.lang", Some("Exception"))
+        }
+        "IllegalArgumentException"
+        | "IllegalStateException"
+        | "ArithmeticException"
+        | "NullPointerException"
+        | "ClassCastException"
+        | "IndexOutOfBoundsException"
+        | "UnsupportedOperationException" => ("java.lang", Some("RuntimeException")),
+        "NumberFormatException" => ("java.lang", Some("IllegalArgumentException")),
+        "ArrayIndexOutOfBoundsException" | "StringIndexOutOfBoundsException" => {
+            ("java.lang", Some("IndexOutOfBoundsException"))
+        }
+        "AssertionError" => ("java.lang", Some("Error")),
+        "IOException" => ("java.io", Some("Exception")),
+        "FileNotFoundException" => ("java.io", Some("IOException")),
+        "UncheckedIOException" => ("java.io", Some("RuntimeException")),
+        "NoSuchElementException" | "ConcurrentModificationException" => {
+            ("java.util", Some("RuntimeException"))
+        }
+        "InputMismatchException" => ("java.util", Some("NoSuchElementException")),
+        _ => return None,
+    };
+    Some((package, super_class))
+}
+
+/// The return type of a method which every exception has, None for the ones which return
+/// nothing
+pub fn get_exception_method_type(name: &str) -> Option<VariableType> {
+    match name {
+        "getMessage" | "toString" => Some(VariableType::string()),
+        _ => None,
+    }
+}
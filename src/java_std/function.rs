@@ -0,0 +1,80 @@
+// java.util.function
+
+use crate::{
+    parser::{
+        context::Context,
+        convert::data_type::is_interface,
+        nodes::{
+            variable::{VariableType, WildcardBound},
+            MethodReturnType,
+        },
+    },
+    token::DataType,
+};
+
+/// The abstract method of a functional interface of the Java library, its values are closures
+pub struct FunctionalMethod {
+    pub name: &'static str,
+    pub params: Vec<VariableType>,
+    pub return_type: MethodReturnType,
+}
+
+/// The method of Function, Runnable and the other functional interfaces of the Java library,
+/// the type arguments are already used in its types
+///
+/// Interfaces of the input with the same name hide them
+///
+/// # Example
+///
+/// BiFunction<Integer, Integer, String> -> String apply(int, int)
+pub fn get_functional_method(context: &Context, r#type: &VariableType) -> Option<FunctionalMethod> {
+    let name = r#type.class_name()?;
+    if is_interface(context, name) {
+        return None;
+    }
+    // Wildcards like ? super T only matter for the Java compiler
+    let args: Vec<VariableType> = match r#type {
+        VariableType::Generic(_, args) => args
+            .iter()
+            .map(|arg| match arg {
+                VariableType::Wildcard(
+                    WildcardBound::Extends(bound) | WildcardBound::Super(bound),
+                ) => bound.unboxed(),
+                arg => arg.unboxed(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let int = VariableType::DataType(DataType::INT);
+    let boolean = VariableType::DataType(DataType::BOOLEAN);
+    let simple_name = name.rsplit('.').next().unwrap_or(name);
+    let (name, params, return_type) = match (simple_name, args.as_slice()) {
+        ("Function", [t, r]) => ("apply", vec![t.clone()], Some(r.clone())),
+        ("BiFunction", [t, u, r]) => ("apply", vec![t.clone(), u.clone()], Some(r.clone())),
+        ("UnaryOperator", [t]) => ("apply", vec![t.clone()], Some(t.clone())),
+        ("BinaryOperator", [t]) => ("apply", vec![t.clone(), t.clone()], Some(t.clone())),
+        ("Supplier", [t]) => ("get", vec![], Some(t.clone())),
+        ("Consumer", [t]) => ("accept", vec![t.clone()], None),
+        ("BiConsumer", [t, u]) => ("accept", vec![t.clone(), u.clone()], None),
+        ("Predicate", [t]) => ("test", vec![t.clone()], Some(boolean)),
+        ("BiPredicate", [t, u]) => ("test", vec![t.clone(), u.clone()], Some(boolean)),
+        ("Comparator", [t]) => ("compare", vec![t.clone(), t.clone()], Some(int)),
+        ("Runnable", []) => ("run", vec![], None),
+        ("IntUnaryOperator", []) => ("applyAsInt", vec![int.clone()], Some(int)),
+        ("IntBinaryOperator", []) => ("applyAsInt", vec![int.clone(), int.clone()], Some(int)),
+        ("IntPredicate", []) => ("test", vec![int], Some(boolean)),
+        ("IntFunction", [r]) => ("apply", vec![int], Some(r.clone())),
+        ("ToIntFunction", [t]) => ("applyAsInt", vec![t.clone()], Some(int)),
+        ("IntSupplier", []) => ("getAsInt", vec![], Some(int)),
+        ("IntConsumer", []) => ("accept", vec![int], None),
+        _ => return None,
+    };
+    Some(FunctionalMethod {
+        name,
+        params,
+        return_type: match return_type {
+            Some(r#type) => MethodReturnType::TYPE(r#type),
+            None => MethodReturnType::VOID,
+        },
+    })
+}
@@ -1,35 +1,76 @@
 use std::borrow::Cow;
 
 use crate::{
-    parser::nodes::{FunctionArgument, MethodArgumentType, MethodReturnType},
+    parser::{
+        context::Context,
+        convert::{
+            ident::convert_ident,
+            string::{convert_double_to_string, convert_format_string},
+        },
+        nodes::{variable::VariableType, FunctionArgument},
+    },
     prelude::Class,
+    token::DataType,
 };
 
 pub struct PrintStream;
 
 impl Class for PrintStream {
-    fn get_field(&self, name: &str) -> Option<Box<dyn Class>> {
-        todo!()
+    fn get_field(&self, _name: &str) -> Option<Box<dyn Class>> {
+        None
     }
 
     fn code_from_method(
         &self,
+        context: &Context,
         name: &str,
         args: Vec<FunctionArgument>,
     ) -> Option<Cow<'static, str>> {
-        match name {
-            "println" => match &args[0] {
-                FunctionArgument::STRING(s) => Some(format!("println!(\"{}\");", s).into()),
-                FunctionArgument::VARIABLE((r#type, var)) => {
-                    // because of regex
-                    let mut final_var = "{".to_string();
-                    final_var.push_str(var);
-                    final_var.push('}');
-                    Some(format!("println!(\"{}\");", final_var).into())
+        let r#macro = match name {
+            "println" => "println!",
+            "print" => "print!",
+            _ => return None,
+        };
+        let format_args = match args.first() {
+            None if name == "println" => String::new(),
+            Some(FunctionArgument::STRING(s)) => convert_format_string(s),
+            Some(FunctionArgument::VARIABLE((r#type, var))) => {
+                let var = convert_ident(context, var);
+                // Raw identifiers are not allowed inside of format strings
+                if var.starts_with("r#") || is_float(r#type) {
+                    display_args(context, r#type, &var)
+                } else {
+                    format!("\"{{{}}}\"", var)
                 }
-                _ => todo!(),
-            },
-            _ => None,
+            }
+            Some(FunctionArgument::DATATYPE((data_type, code))) => {
+                display_args(context, &VariableType::DataType(*data_type), code)
+            }
+            Some(FunctionArgument::EXPRESSION((r#type, code))) => {
+                display_args(context, r#type, code)
+            }
+            Some(FunctionArgument::FORMAT(format_args)) => format_args.clone(),
+            _ => return None,
+        };
+        if args.len() > 1 {
+            return None;
         }
+        Some(format!("{}({})", r#macro, format_args).into())
     }
 }
+
+/// The format string and argument which print a value, Java prints 1.0 for doubles where Rust
+/// only prints 1
+fn display_args(context: &Context, r#type: &VariableType, code: &str) -> String {
+    match is_float(r#type) {
+        true => format!("\"{{}}\", {}", convert_double_to_string(context, code)),
+        false => format!("\"{{}}\", {}", code),
+    }
+}
+
+fn is_float(r#type: &VariableType) -> bool {
+    matches!(
+        r#type,
+        VariableType::DataType(DataType::FLOAT | DataType::DOUBLE)
+    )
+}
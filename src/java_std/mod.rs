@@ -1 +1,3 @@
+pub mod exception;
+pub mod function;
 pub mod io;
@@ -1,297 +1,1040 @@
-use std::path::Path;
+use std::{iter::Peekable, path::Path, str::Chars};
 
-use crate::token::{CharLocationInfo, DataType, Token};
+use crate::{
+    source_map::{FileId, SourceMap, Span},
+    token::{DataType, Token, TokenType, Trivia, Visibility},
+};
 
-pub fn read_file(file: &Path) -> Vec<Token> {
+#[derive(Debug)]
+pub enum LexerErrorKind {
+    /// The input file could not be read
+    Io(std::io::Error),
+    UnexpectedChar,
+    InvalidEscape,
+    InvalidUnicodeEscape,
+    /// A UTF-16 surrogate without its other half, e.g. \uD83D alone
+    UnpairedSurrogate,
+    UnterminatedString,
+    UnterminatedChar,
+    UnterminatedComment,
+    UnterminatedTextBlock,
+    /// Text blocks need a new line after the opening quotes
+    InvalidTextBlockStart,
+    EmptyChar,
+    /// The char does not fit into a Java char, which is a single UTF-16 code unit
+    CharOutOfRange,
+    /// Holds the reason, the span covers the whole literal
+    InvalidNumber(String),
+}
+
+/// An error found while tokennizing, a file can have multiple of them
+#[derive(Debug)]
+pub struct LexerError {
+    pub kind: LexerErrorKind,
+    pub span: Span,
+    /// The char which caused the error, None at the end of the file
+    pub character: Option<char>,
+}
+
+impl LexerError {
+    /// The error with the location of its span, e.g. Main.java:1:5: Unexpected char '#'
+    pub fn display(&self, source_map: &SourceMap) -> String {
+        let location = source_map.location(self.span);
+        let message = match &self.kind {
+            LexerErrorKind::Io(error) => {
+                return format!("{}: Failed to read file: {}", location.path, error)
+            }
+            LexerErrorKind::UnexpectedChar => "Unexpected char",
+            LexerErrorKind::InvalidEscape => "Invalid escape sequence",
+            LexerErrorKind::InvalidUnicodeEscape => "Invalid unicode escape",
+            LexerErrorKind::UnpairedSurrogate => "Unpaired surrogate in unicode escape",
+            LexerErrorKind::UnterminatedString => "Unterminated string literal",
+            LexerErrorKind::UnterminatedChar => "Unterminated char literal",
+            LexerErrorKind::UnterminatedComment => "Unterminated block comment",
+            LexerErrorKind::UnterminatedTextBlock => "Unterminated text block",
+            LexerErrorKind::InvalidTextBlockStart => {
+                "Text block must start with a new line after the opening quotes"
+            }
+            LexerErrorKind::EmptyChar => "Empty char literal",
+            LexerErrorKind::CharOutOfRange => "Char literal does not fit into a Java char",
+            LexerErrorKind::InvalidNumber(reason) => return format!("{}: {}", location, reason),
+        };
+        match self.character {
+            Some(c) => format!("{}: {} {:?}", location, message, c),
+            None => format!("{}: {} at end of file", location, message),
+        }
+    }
+}
+
+/// Tokennizes a file, all lexical errors of the file are returned at once
+///
+/// Also returns the comments at the end of the file, they have no token after them
+pub fn read_file(
+    source_map: &mut SourceMap,
+    file: &Path,
+) -> Result<(Vec<Token>, Vec<Trivia>), Vec<LexerError>> {
     let path = file.display().to_string();
-    let content = std::fs::read_to_string(file).expect("Failed to read file");
-    let mut final_vec = Vec::new();
-    for (line_number, line) in content.lines().enumerate() {
-        final_vec.append(&mut tokennize_line(line.to_owned(), line_number, &path))
+    let content = match std::fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(error) => {
+            let file = source_map.add_file(path, "");
+            return Err(vec![LexerError {
+                kind: LexerErrorKind::Io(error),
+                span: Span::new(file, 0, 0),
+                character: None,
+            }]);
+        }
+    };
+    let file = source_map.add_file(path, &content);
+    let (translated, original_offsets, mut errors) = translate_unicode_escapes(&content, file);
+    let (tokens, end_comments, mut tokennize_errors) =
+        tokennize(&translated, file, &original_offsets);
+    errors.append(&mut tokennize_errors);
+    errors.sort_by_key(|error| error.span.start);
+    if errors.is_empty() {
+        Ok((tokens, end_comments))
+    } else {
+        Err(errors)
     }
-    final_vec
 }
 
-// We tokennize line by line so we can do better error handling/better compiler errors
-pub fn tokennize_line(string: String, line_number: usize, file: &String) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let mut chars = string.chars().peekable();
+/// Translates Java unicode escapes (`\uXXXX`) before lexing, like Java does (JLS 3.3)
+///
+/// This means unicode escapes are also allowed outside of string and char literals
+///
+/// # Example
+///
+/// char c = '\u0041'; -> char c = 'A';
+///
+/// Also returns where the offsets of the translated content differ from the original content,
+/// as pairs of (translated offset, original offset), so spans can point into the original file
+pub fn translate_unicode_escapes(
+    content: &str,
+    file: FileId,
+) -> (String, Vec<(usize, usize)>, Vec<LexerError>) {
+    let mut final_content = String::with_capacity(content.len());
+    let mut original_offsets = Vec::new();
+    let mut chars = CharStream::new(content, file, &[]);
+    // A backslash is only a unicode escape when its preceded by an even number of backslashes
+    let mut backslashes = 0;
+    // High surrogate waiting for its low surrogate, Rust chars can't hold single surrogates
+    let mut high_surrogate: Option<(u32, Span)> = None;
+    loop {
+        if final_content.len() as isize - chars.offset as isize
+            != original_offsets.last().map_or(0, |(translated, original)| {
+                *translated as isize - *original as isize
+            })
+        {
+            original_offsets.push((final_content.len(), chars.offset));
+        }
+        let Some(c) = chars.next() else {
+            break;
+        };
+        let span = chars.span();
+        if c != '\\' || backslashes % 2 == 1 || chars.peek() != Some(&'u') {
+            if let Some((_, high_info)) = high_surrogate.take() {
+                chars.error_at(LexerErrorKind::UnpairedSurrogate, high_info, Some('\\'));
+                final_content.push(char::REPLACEMENT_CHARACTER);
+            }
+            backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+            final_content.push(c);
+            continue;
+        }
 
-    let mut line_col = 0;
-    let mut is_quote_message = false;
-    while let Some(c) = chars.next() {
-        line_col += 1;
-        let char_info = CharLocationInfo {
-            file_path: file.clone(),
-            line_number: line_number + 1, // We want to start from line 1 and not 0
-            line_col,
+        // Java allows multiple u's, e.g. \uuuu0041
+        let mut escape = String::from("\\");
+        while chars.peek() == Some(&'u') {
+            escape.push(chars.next().unwrap());
+        }
+        let mut hex = String::new();
+        while hex.len() < 4 {
+            match chars.next_if(|c| c.is_ascii_hexdigit()) {
+                Some(c) => hex.push(c),
+                None => break,
+            }
+        }
+        let code = match u32::from_str_radix(&hex, 16) {
+            Ok(code) if hex.len() == 4 => code,
+            _ => {
+                let next = chars.peek().copied();
+                chars.error_at(LexerErrorKind::InvalidUnicodeEscape, span, next);
+                final_content.push_str(&escape);
+                final_content.push_str(&hex);
+                backslashes = 0;
+                continue;
+            }
         };
-        if c == '"' {
-            is_quote_message = !is_quote_message;
-
-            tokens.push(Token {
-                token_type: crate::token::TokenType::QUOTE,
-                value: None,
-                char_info,
-            });
-        } else if is_quote_message {
-            let mut ident = String::new();
-            ident.push(c);
-            while let Some(next) = chars.peek() {
-                if next.is_alphanumeric() {
-                    ident.push(chars.next().unwrap());
-                } else {
+        // A backslash from an unicode escape can't start another unicode escape
+        backslashes = 0;
+        let code = match (high_surrogate.take(), code) {
+            (Some((high, _)), 0xDC00..=0xDFFF) => {
+                0x10000 + ((high - 0xD800) << 10) + (code - 0xDC00)
+            }
+            (high, code) => {
+                if let Some((_, high_info)) = high {
+                    chars.error_at(LexerErrorKind::UnpairedSurrogate, high_info, Some(c));
+                    final_content.push(char::REPLACEMENT_CHARACTER);
+                }
+                match code {
+                    0xD800..=0xDBFF => {
+                        high_surrogate = Some((code, span));
+                        continue;
+                    }
+                    0xDC00..=0xDFFF => {
+                        chars.error_at(LexerErrorKind::UnpairedSurrogate, span, Some(c));
+                        final_content.push(char::REPLACEMENT_CHARACTER);
+                        continue;
+                    }
+                    _ => code,
+                }
+            }
+        };
+        final_content.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+    if let Some((_, high_info)) = high_surrogate {
+        chars.error_at(LexerErrorKind::UnpairedSurrogate, high_info, Some('\\'));
+        final_content.push(char::REPLACEMENT_CHARACTER);
+    }
+    (final_content, original_offsets, chars.errors)
+}
+
+/// Iterates over the chars of a whole file while keeping track of the offset and line
+struct CharStream<'a> {
+    chars: Peekable<Chars<'a>>,
+    file: FileId,
+    /// Byte offset after the char which was returned last by `next`
+    offset: usize,
+    /// Byte offset of the char which was returned last by `next`
+    last_offset: usize,
+    /// See `translate_unicode_escapes`, empty when the content is the original file content
+    original_offsets: &'a [(usize, usize)],
+    line_number: usize,
+    errors: Vec<LexerError>,
+}
+
+impl<'a> CharStream<'a> {
+    fn new(content: &'a str, file: FileId, original_offsets: &'a [(usize, usize)]) -> Self {
+        Self {
+            chars: content.chars().peekable(),
+            file,
+            offset: 0,
+            last_offset: 0,
+            original_offsets,
+            // We want to start from line 1 and not 0
+            line_number: 1,
+            errors: Vec::new(),
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.last_offset = self.offset;
+        self.offset += c.len_utf8();
+        // Java allows \n, \r and \r\n as line terminator
+        if c == '\n' || (c == '\r' && self.chars.peek() != Some(&'\n')) {
+            self.line_number += 1;
+        }
+        Some(c)
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.chars.peek()
+    }
+
+    fn next_if_eq(&mut self, expected: char) -> Option<char> {
+        self.next_if(|c| c == expected)
+    }
+
+    fn next_if(&mut self, func: impl FnOnce(char) -> bool) -> Option<char> {
+        match self.chars.peek() {
+            Some(&c) if func(c) => self.next(),
+            _ => None,
+        }
+    }
+
+    /// Reports an error at the char which was returned last by `next`
+    fn error(&mut self, kind: LexerErrorKind, character: Option<char>) {
+        let span = self.span();
+        self.error_at(kind, span, character);
+    }
+
+    fn error_at(&mut self, kind: LexerErrorKind, span: Span, character: Option<char>) {
+        self.errors.push(LexerError {
+            kind,
+            span,
+            character,
+        });
+    }
+
+    /// Span of the char which was returned last by `next`
+    fn span(&self) -> Span {
+        Span::new(
+            self.file,
+            self.original_offset(self.last_offset),
+            self.original_offset(self.offset),
+        )
+    }
+
+    fn original_offset(&self, offset: usize) -> usize {
+        let index = self
+            .original_offsets
+            .partition_point(|(translated, _)| *translated <= offset);
+        match index {
+            0 => offset,
+            _ => {
+                let (translated, original) = self.original_offsets[index - 1];
+                original + offset - translated
+            }
+        }
+    }
+}
+
+/// Tokennizes a whole file, this way comments and other constructs can go over multiple lines
+///
+/// Comments and blank lines are attached to the tokens as trivia, comments on the same line
+/// after a token are trailing trivia of that token, everything else is leading trivia of the next token.
+/// The trivia after the last token are returned on their own
+pub fn tokennize(
+    content: &str,
+    file: FileId,
+    original_offsets: &[(usize, usize)],
+) -> (Vec<Token>, Vec<Trivia>, Vec<LexerError>) {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = CharStream::new(content, file, original_offsets);
+
+    let mut pending_trivia = Vec::new();
+    // Line on which the last token or comment ended
+    let mut last_line = 0;
+
+    while let Some(c) = chars.next() {
+        let span = chars.span();
+        let line_number = chars.line_number;
+        let is_after_blank_line =
+            line_number > last_line + 1 && (!tokens.is_empty() || !pending_trivia.is_empty());
+        let token_count = tokens.len();
+
+        if c == '/' && matches!(chars.peek(), Some('/') | Some('*')) {
+            if let Some(comment) = parse_comment(&mut chars) {
+                match tokens.last_mut() {
+                    Some(last) if line_number == last_line && pending_trivia.is_empty() => {
+                        last.trailing_trivia.push(comment)
+                    }
+                    _ => {
+                        if is_after_blank_line {
+                            pending_trivia.push(Trivia::BlankLine);
+                        }
+                        pending_trivia.push(comment);
+                    }
+                }
+            }
+            last_line = chars.line_number;
+            continue;
+        } else if c == '"' && chars.peek() == Some(&'"') && chars.chars.clone().nth(1) == Some('"')
+        {
+            chars.next();
+            chars.next();
+            if let Some(token) = parse_text_block(&mut chars, span) {
+                tokens.push(token);
+            }
+        } else if c == '"' {
+            let mut raw = String::new();
+            let mut value = String::new();
+            let mut terminated = false;
+            while let Some(&c) = chars.peek() {
+                // String literals can not go over multiple lines
+                if c == '\n' || c == '\r' {
                     break;
                 }
+                chars.next();
+                if c == '"' {
+                    terminated = true;
+                    break;
+                }
+                raw.push(c);
+                if c == '\\' {
+                    match parse_escape(&mut chars, &mut raw) {
+                        Some(escaped) => value.push(escaped),
+                        None => chars.error(LexerErrorKind::InvalidEscape, raw.chars().last()),
+                    }
+                } else {
+                    value.push(c);
+                }
+            }
+            if !terminated {
+                let next = chars.peek().copied();
+                chars.error_at(LexerErrorKind::UnterminatedString, span, next);
             }
 
-            tokens.push(Token {
-                token_type: crate::token::TokenType::QUOTE_STRING,
-                value: Some(ident),
-                char_info,
-            });
-        } else if c.is_alphabetic() {
+            tokens.push(Token::new(
+                TokenType::STRING_LITERAL(raw),
+                Some(value),
+                span,
+            ));
+        } else if c == '\'' {
+            let mut raw = String::new();
+            let mut value = None;
+            match chars.next_if(|c| c != '\n' && c != '\r') {
+                Some('\\') => {
+                    raw.push('\\');
+                    value = parse_escape(&mut chars, &mut raw);
+                    if value.is_none() {
+                        chars.error(LexerErrorKind::InvalidEscape, raw.chars().last());
+                    }
+                }
+                Some('\'') => chars.error(LexerErrorKind::EmptyChar, Some('\'')),
+                Some(c) => {
+                    raw.push(c);
+                    value = Some(c);
+                }
+                None => {
+                    let next = chars.peek().copied();
+                    chars.error_at(LexerErrorKind::UnterminatedChar, span, next);
+                }
+            }
+            if !raw.is_empty() && chars.next_if_eq('\'').is_none() {
+                let next = chars.peek().copied();
+                chars.error_at(LexerErrorKind::UnterminatedChar, span, next);
+                // Skip the rest of a too long char literal (e.g. 'ab'), so it does not cause more errors
+                let rest: String = chars.chars.clone().take_while(|c| *c != '\n').collect();
+                if rest.contains('\'') {
+                    while chars.next().is_some_and(|c| c != '\'') {}
+                }
+                value = None;
+            }
+            match value {
+                // Java chars are UTF-16 code units
+                Some(c) if (c as u32) > 0xFFFF => {
+                    chars.error_at(LexerErrorKind::CharOutOfRange, span, Some(c))
+                }
+                Some(c) => tokens.push(Token::new(
+                    TokenType::CHAR_LITERAL(raw),
+                    Some(c.to_string()),
+                    span,
+                )),
+                None => {}
+            }
+        } else if is_java_identifier_start(c) {
             let mut ident = String::new();
             ident.push(c);
-            while let Some(next) = chars.peek() {
-                if next.is_alphanumeric() {
-                    ident.push(chars.next().unwrap());
-                } else {
-                    break;
-                }
+            while let Some(next) = chars.next_if(is_java_identifier_part) {
+                ident.push(next);
             }
-            tokens.push(parse_token(&ident, char_info));
-        } else if c.is_numeric() {
+            tokens.push(parse_token(&ident, span));
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.peek().is_some_and(|next| next.is_ascii_digit()))
+        {
             let mut num = String::new();
             num.push(c);
-            while let Some(next) = chars.peek() {
-                if next.is_numeric() {
-                    num.push(chars.next().unwrap());
+            while let Some(&next) = chars.peek() {
+                let last = num.chars().last().unwrap();
+                let is_hex = num.starts_with("0x") || num.starts_with("0X");
+                let is_exponent_sign = (next == '+' || next == '-')
+                    && (matches!(last, 'p' | 'P') || (!is_hex && matches!(last, 'e' | 'E')));
+                if next.is_ascii_alphanumeric()
+                    || next == '_'
+                    || (next == '.' && !num.contains('.'))
+                    || is_exponent_sign
+                {
+                    num.push(next);
+                    chars.next();
                 } else {
                     break;
                 }
             }
-            //   let int_value = num.parse::<i32>().unwrap();
-            tokens.push(Token {
-                token_type: crate::token::TokenType::INTLIT,
-                value: Some(num),
-                char_info,
-            });
+            match parse_number_literal(&num) {
+                Ok(literal) => tokens.push(Token::new(
+                    TokenType::NUMBER_LITERAL(literal.data_type),
+                    Some(num),
+                    span,
+                )),
+                Err(error) => {
+                    let span = span.to(chars.span());
+                    chars.error_at(LexerErrorKind::InvalidNumber(error), span, None)
+                }
+            }
         } else if c == '(' {
-            tokens.push(Token {
-                token_type: crate::token::TokenType::OPEN_BRACE,
-                value: None,
-                char_info,
-            });
+            tokens.push(Token::new(TokenType::OPEN_BRACE, None, span));
         } else if c == ')' {
-            tokens.push(Token {
-                token_type: crate::token::TokenType::CLOSE_BRACE,
-                value: None,
-                char_info,
-            });
+            tokens.push(Token::new(TokenType::CLOSE_BRACE, None, span));
         } else if c == '{' {
-            tokens.push(Token {
-                token_type: crate::token::TokenType::OPEN_BRACKET,
-                value: None,
-                char_info,
-            });
+            tokens.push(Token::new(TokenType::OPEN_BRACKET, None, span));
         } else if c == '}' {
-            tokens.push(Token {
-                token_type: crate::token::TokenType::CLOSE_BRACKET,
-                value: None,
-                char_info,
-            });
+            tokens.push(Token::new(TokenType::CLOSE_BRACKET, None, span));
         } else if c == ',' {
-            tokens.push(Token {
-                token_type: crate::token::TokenType::COMMA,
-                value: None,
-                char_info,
-            });
-        } else if string == "[]" {
-            // TODO fix array
-            tokens.push(Token {
-                token_type: crate::token::TokenType::ARRAY,
-                value: None,
-                char_info,
-            });
+            tokens.push(Token::new(TokenType::COMMA, None, span));
         } else if c == ';' {
-            tokens.push(Token {
-                token_type: crate::token::TokenType::SEMICOLON,
-                value: None,
-                char_info,
-            })
+            tokens.push(Token::new(TokenType::SEMICOLON, None, span))
         } else if c.is_whitespace() {
             continue;
-        } else if let Some(token) = parse_expr(c, char_info) {
+        } else if let Some(token) = parse_operator(c, &mut chars, span) {
             tokens.push(token)
         } else {
-            println!("Unexpected char")
+            chars.error(LexerErrorKind::UnexpectedChar, Some(c));
+        }
+
+        if let Some(token) = tokens.get_mut(token_count) {
+            if is_after_blank_line {
+                pending_trivia.insert(0, Trivia::BlankLine);
+            }
+            token.leading_trivia = std::mem::take(&mut pending_trivia);
+            token.span.end = chars.span().end;
+            last_line = chars.line_number;
         }
     }
-    tokens
+    (tokens, pending_trivia, chars.errors)
 }
 
-pub fn parse_token(string: &str, char_info: CharLocationInfo) -> Token {
-    if let Some(data_type) = parse_data_types(string, char_info.clone()) {
-        return data_type;
-    } else if let Some(visibility) = parse_visibility(string, char_info.clone()) {
-        return visibility;
-    } else if string == "return" {
-        return Token {
-            token_type: crate::token::TokenType::RETURN,
-            value: None,
-            char_info,
-        };
-    } else if string == "if" {
-        return Token {
-            token_type: crate::token::TokenType::IF,
-            value: None,
-            char_info,
-        };
-    } else if string == "else" {
-        return Token {
-            token_type: crate::token::TokenType::ELSE,
-            value: None,
-            char_info,
-        };
-    } else if string == "||" {
-        return Token {
-            token_type: crate::token::TokenType::OR,
-            value: None,
-            char_info,
-        };
-    } else if string == "&&" {
-        return Token {
-            token_type: crate::token::TokenType::AND,
-            value: None,
-            char_info,
-        };
-    } else if string == "class" {
-        return Token {
-            token_type: crate::token::TokenType::CLASS,
-            value: None,
-            char_info,
-        };
-    } else if string == "void" {
-        return Token {
-            token_type: crate::token::TokenType::VOID,
-            value: None,
-            char_info,
-        };
-    } else if string == "true" {
-        return Token {
-            token_type: crate::token::TokenType::TRUE,
-            value: None,
-            char_info,
-        };
-    } else if string == "false" {
-        return Token {
-            token_type: crate::token::TokenType::FALSE,
-            value: None,
-            char_info,
-        };
+/// Like `Character.isJavaIdentifierStart` in Java: letters, currency symbols and connector punctuation
+///
+/// Unicode categories are approximated with the std char methods and the tables below
+pub fn is_java_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || is_currency_symbol(c) || is_connector_punctuation(c)
+}
+
+/// Like `Character.isJavaIdentifierPart` in Java, also allows digits, combining marks and ignorable chars
+pub fn is_java_identifier_part(c: char) -> bool {
+    is_java_identifier_start(c)
+        || c.is_alphanumeric()
+        || is_combining_mark(c)
+        || is_identifier_ignorable(c)
+}
+
+/// Unicode category Sc, e.g. $ or €
+fn is_currency_symbol(c: char) -> bool {
+    matches!(c,
+        '$' | '\u{A2}'..='\u{A5}' | '\u{58F}' | '\u{60B}' | '\u{7FE}'..='\u{7FF}'
+        | '\u{9F2}'..='\u{9F3}' | '\u{9FB}' | '\u{AF1}' | '\u{BF9}' | '\u{E3F}' | '\u{17DB}'
+        | '\u{20A0}'..='\u{20C0}' | '\u{A838}' | '\u{FDFC}' | '\u{FE69}' | '\u{FF04}'
+        | '\u{FFE0}'..='\u{FFE1}' | '\u{FFE5}'..='\u{FFE6}' | '\u{11FDD}'..='\u{11FE0}'
+        | '\u{1E2FF}' | '\u{1ECB0}')
+}
+
+/// Unicode category Pc, e.g. _ or ‿
+pub fn is_connector_punctuation(c: char) -> bool {
+    matches!(c,
+        '_' | '\u{203F}'..='\u{2040}' | '\u{2054}' | '\u{FE33}'..='\u{FE34}'
+        | '\u{FE4D}'..='\u{FE4F}' | '\u{FF3F}')
+}
+
+/// The common blocks of the Unicode categories Mn and Mc, most other marks are alphabetic in Rust
+pub fn is_combining_mark(c: char) -> bool {
+    matches!(c,
+        '\u{300}'..='\u{36F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}' | '\u{FE20}'..='\u{FE2F}')
+}
+
+/// Like `Character.isIdentifierIgnorable` in Java, these chars are allowed in identifiers
+/// but have no meaning
+pub fn is_identifier_ignorable(c: char) -> bool {
+    matches!(c,
+        '\u{0}'..='\u{8}' | '\u{E}'..='\u{1B}' | '\u{7F}'..='\u{9F}' | '\u{AD}'
+        | '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2064}'
+        | '\u{FEFF}')
+}
+
+/// A Java number literal split into its parts, underscores are already removed
+///
+/// # Example
+///
+/// 0x1F_FFL -> radix: 16, integer: 1FFF, data_type: LONG
+///
+/// 1.5e3f -> radix: 10, integer: 1, fraction: 5, exponent: 3, data_type: FLOAT
+pub struct NumberLiteral {
+    pub data_type: DataType,
+    pub radix: u32,
+    /// Digits before the dot
+    pub integer: String,
+    /// Digits after the dot, None if there is no dot
+    pub fraction: Option<String>,
+    /// Exponent including the sign, for hex floats this is the binary exponent
+    pub exponent: Option<String>,
+}
+
+/// Validates the syntax of a Java number literal, the value is not range checked here
+pub fn parse_number_literal(raw: &str) -> Result<NumberLiteral, String> {
+    let invalid = |reason: &str| Err(format!("Invalid number literal {}: {}", raw, reason));
+
+    let lower = raw.to_ascii_lowercase();
+    let (radix, body) = if lower.starts_with("0x") {
+        (16, &raw[2..])
+    } else if lower.starts_with("0b") {
+        (2, &raw[2..])
+    } else {
+        (10, raw)
+    };
+
+    let is_float_radix = radix == 10 || radix == 16;
+    let has_binary_exponent = body.contains(['p', 'P']);
+    let (body, suffix) = match body.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('l') => (&body[..body.len() - 1], Some(DataType::LONG)),
+        Some('f') if radix == 10 || has_binary_exponent => {
+            (&body[..body.len() - 1], Some(DataType::FLOAT))
+        }
+        Some('d') if radix == 10 || has_binary_exponent => {
+            (&body[..body.len() - 1], Some(DataType::DOUBLE))
+        }
+        _ => (body, None),
+    };
+
+    let exponent_chars = if radix == 16 { ['p', 'P'] } else { ['e', 'E'] };
+    let (mantissa, exponent) = match body.split_once(exponent_chars) {
+        Some((mantissa, exponent)) if is_float_radix => (mantissa, Some(exponent)),
+        _ => (body, None),
+    };
+    let (integer, fraction) = match mantissa.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    // Underscores are only allowed between digits
+    let digits = |part: &str, radix: u32| -> Result<String, String> {
+        if part.starts_with('_') || part.ends_with('_') {
+            return Err(format!(
+                "Invalid number literal {}: underscores must be between digits",
+                raw
+            ));
+        }
+        if let Some(c) = part.chars().find(|c| *c != '_' && !c.is_digit(radix)) {
+            return Err(format!("Invalid number literal {}: unexpected {}", raw, c));
+        }
+        Ok(part.replace('_', ""))
+    };
+
+    let integer = digits(integer, radix)?;
+    let fraction = match fraction {
+        Some(fraction) => Some(digits(fraction, radix)?),
+        None => None,
+    };
+    let exponent = match exponent {
+        Some(exponent) => {
+            let (sign, value) = match exponent.strip_prefix('-') {
+                Some(value) => ("-", value),
+                None => ("", exponent.strip_prefix('+').unwrap_or(exponent)),
+            };
+            let value = digits(value, 10)?;
+            if value.is_empty() {
+                return invalid("exponent has no digits");
+            }
+            Some(format!("{}{}", sign, value))
+        }
+        None => None,
+    };
+
+    if integer.is_empty() && fraction.as_ref().is_none_or(|fraction| fraction.is_empty()) {
+        return invalid("no digits");
     }
-    Token {
-        token_type: crate::token::TokenType::UNKNOWN,
-        value: Some(string.to_string()),
-        char_info,
+
+    let is_float = fraction.is_some() || exponent.is_some();
+    if radix == 2 && is_float {
+        return invalid("binary literals can not be floating point");
+    }
+    if radix == 16 && is_float && exponent.is_none() {
+        return invalid("hexadecimal floating point literals need a binary exponent");
     }
+    let data_type = match suffix {
+        Some(DataType::LONG) if is_float => return invalid("floating point literal with L suffix"),
+        Some(data_type) => data_type,
+        None if is_float => DataType::DOUBLE,
+        None => DataType::INT,
+    };
+
+    let is_integer = matches!(data_type, DataType::INT | DataType::LONG);
+    // Integers starting with 0 are octal
+    if radix == 10 && is_integer && integer.len() > 1 && integer.starts_with('0') {
+        digits(&integer, 8)?;
+        return Ok(NumberLiteral {
+            data_type,
+            radix: 8,
+            integer: integer[1..].to_string(),
+            fraction,
+            exponent,
+        });
+    }
+
+    Ok(NumberLiteral {
+        data_type,
+        radix,
+        integer,
+        fraction,
+        exponent,
+    })
 }
 
-pub fn parse_visibility(string: &str, char_info: CharLocationInfo) -> Option<Token> {
-    match string {
-        "public" => Some(Token {
-            token_type: crate::token::TokenType::VISIBILITY(crate::token::Visibility::PUBLIC),
-            value: None,
-            char_info,
-        }),
-        "static" => Some(Token {
-            token_type: crate::token::TokenType::STATIC,
-            value: None,
-            char_info,
-        }),
-        "private" => Some(Token {
-            token_type: crate::token::TokenType::VISIBILITY(crate::token::Visibility::PRIVATE),
-            value: None,
-            char_info,
-        }),
+/// Parses a `//` line comment or a `/* */` block comment, the leading slash must already be consumed
+///
+/// Javadoc comments (`/** */`) are block comments too, but get their own trivia
+fn parse_comment(chars: &mut CharStream) -> Option<Trivia> {
+    let mut text = String::new();
+    match chars.next() {
+        Some('/') => {
+            while let Some(&c) = chars.peek() {
+                if c == '\n' || c == '\r' {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            Some(Trivia::LineComment(text))
+        }
+        Some('*') => {
+            while let Some(c) = chars.next() {
+                if c == '*' && chars.peek() == Some(&'/') {
+                    chars.next();
+                    // /**/ is an empty block comment and not Javadoc
+                    return match text.strip_prefix('*') {
+                        Some(doc) => Some(Trivia::DocComment(doc.to_string())),
+                        None => Some(Trivia::BlockComment(text)),
+                    };
+                }
+                text.push(c);
+            }
+            chars.error(LexerErrorKind::UnterminatedComment, None);
+            None
+        }
         _ => None,
     }
 }
 
-pub fn parse_data_types(string: &str, char_info: CharLocationInfo) -> Option<Token> {
+/// Parses a text block, the opening `"""` must already be consumed
+///
+/// # Example
+///
+/// String json = """
+///     {
+///         "name": "j2rust"
+///     }
+///     """;
+fn parse_text_block(chars: &mut CharStream, span: Span) -> Option<Token> {
+    // Only whitespace is allowed until the end of the opening line
+    while let Some(c) = chars.next() {
+        if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            break;
+        }
+        if !c.is_whitespace() {
+            chars.error(LexerErrorKind::InvalidTextBlockStart, Some(c));
+            // Skip the rest of a text block on one line (e.g. """a"""), its end is no new start
+            let rest: String = chars.chars.clone().take_while(|c| *c != '\n').collect();
+            if let Some(end) = rest.find("\"\"\"") {
+                for _ in 0..rest[..end].chars().count() + 3 {
+                    chars.next();
+                }
+            }
+            return None;
+        }
+    }
+
+    let mut raw = String::new();
+    let mut terminated = false;
+    while let Some(c) = chars.next() {
+        if c == '"' && chars.peek() == Some(&'"') && chars.chars.clone().nth(1) == Some('"') {
+            chars.next();
+            chars.next();
+            terminated = true;
+            break;
+        }
+        if c == '\\' {
+            // Keep escapes as they are, they are translated after the indentation is stripped
+            raw.push(c);
+            if let Some(escaped) = chars.next() {
+                raw.push(escaped);
+            }
+        } else if c == '\r' {
+            // All line terminators are normalized to \n
+            chars.next_if_eq('\n');
+            raw.push('\n');
+        } else {
+            raw.push(c);
+        }
+    }
+    if !terminated {
+        chars.error_at(LexerErrorKind::UnterminatedTextBlock, span, None);
+        return None;
+    }
+
+    let stripped = strip_text_block_indent(&raw);
+
+    // Now translate the escapes
+    let mut value = String::new();
+    let mut stripped_chars = CharStream::new(&stripped, chars.file, &[]);
+    let mut escape_raw = String::new();
+    while let Some(c) = stripped_chars.next() {
+        if c != '\\' {
+            value.push(c);
+        } else if stripped_chars.peek() == Some(&'\n') {
+            // A backslash at the end of a line joins the lines
+            stripped_chars.next();
+        } else if let Some(escaped) = parse_escape(&mut stripped_chars, &mut escape_raw) {
+            value.push(escaped);
+        } else {
+            // The indentation is already stripped, so we can only report the start of the text block
+            let character = escape_raw.chars().last();
+            chars.error_at(LexerErrorKind::InvalidEscape, span, character);
+        }
+    }
+
+    Some(Token::new(
+        TokenType::STRING_LITERAL(raw),
+        Some(value),
+        span,
+    ))
+}
+
+/// Removes the incidental whitespace of a text block, like `String::stripIndent` in Java
+///
+/// The line with the closing quotes also counts for the indentation, even when its blank
+fn strip_text_block_indent(content: &str) -> String {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let last = lines.len() - 1;
+    let indent = lines
+        .iter()
+        .enumerate()
+        .filter(|(i, line)| *i == last || !line.trim().is_empty())
+        .map(|(_, line)| line.chars().take_while(|c| c.is_whitespace()).count())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ""
+            } else {
+                let start = line
+                    .char_indices()
+                    .nth(indent)
+                    .map_or(line.len(), |(i, _)| i);
+                line[start..].trim_end()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decodes a Java escape sequence, the leading backslash must already be consumed
+///
+/// The consumed chars are also pushed to raw, so we can keep the source text
+fn parse_escape(chars: &mut CharStream, raw: &mut String) -> Option<char> {
+    let c = chars.next()?;
+    raw.push(c);
+    let escaped = match c {
+        'b' => '\u{8}',
+        't' => '\t',
+        'n' => '\n',
+        'f' => '\u{c}',
+        'r' => '\r',
+        's' => ' ',
+        '"' => '"',
+        '\'' => '\'',
+        '\\' => '\\',
+        // Only left over from an invalid unicode escape, which is already reported
+        'u' => {
+            while let Some(c) = chars.next_if(|c| c == 'u' || c.is_ascii_hexdigit()) {
+                raw.push(c);
+            }
+            char::REPLACEMENT_CHARACTER
+        }
+        // Octal escape, \0 to \377
+        '0'..='7' => {
+            let mut value = c.to_digit(8)?;
+            // A leading digit of 0-3 allows up to 3 digits, otherwise just 2
+            let max_digits = if c <= '3' { 3 } else { 2 };
+            for _ in 1..max_digits {
+                match chars.peek().and_then(|next| next.to_digit(8)) {
+                    Some(digit) => {
+                        raw.push(chars.next().unwrap());
+                        value = value * 8 + digit;
+                    }
+                    None => break,
+                }
+            }
+            char::from_u32(value)?
+        }
+        _ => return None,
+    };
+    Some(escaped)
+}
+
+pub fn parse_token(string: &str, span: Span) -> Token {
+    if let Some(data_type) = parse_data_types(string, span) {
+        return data_type;
+    } else if let Some(visibility) = parse_visibility(string, span) {
+        return visibility;
+    } else if let Some(token_type) = parse_keyword(string) {
+        return Token::new(token_type, None, span);
+    }
+    Token::new(TokenType::UNKNOWN, Some(string.to_string()), span)
+}
+
+/// Parses all reserved Java keywords, except data types and visibility modifiers
+///
+/// Contextual keywords like `var` or `record` are not reserved, they stay identifiers
+/// and are resolved by the parser, see `Token::is_contextual_keyword`
+pub fn parse_keyword(string: &str) -> Option<TokenType> {
+    let token_type = match string {
+        "abstract" => TokenType::ABSTRACT,
+        "assert" => TokenType::ASSERT,
+        "break" => TokenType::BREAK,
+        "case" => TokenType::CASE,
+        "catch" => TokenType::CATCH,
+        "class" => TokenType::CLASS,
+        "const" => TokenType::CONST,
+        "continue" => TokenType::CONTINUE,
+        "default" => TokenType::DEFAULT,
+        "do" => TokenType::DO,
+        "else" => TokenType::ELSE,
+        "enum" => TokenType::ENUM,
+        "extends" => TokenType::EXTENDS,
+        "final" => TokenType::FINAL,
+        "finally" => TokenType::FINALLY,
+        "for" => TokenType::FOR,
+        "goto" => TokenType::GOTO,
+        "if" => TokenType::IF,
+        "implements" => TokenType::IMPLEMENTS,
+        "import" => TokenType::IMPORT,
+        "instanceof" => TokenType::INSTANCEOF,
+        "interface" => TokenType::INTERFACE,
+        "native" => TokenType::NATIVE,
+        "new" => TokenType::NEW,
+        "package" => TokenType::PACKAGE,
+        "return" => TokenType::RETURN,
+        "strictfp" => TokenType::STRICTFP,
+        "super" => TokenType::SUPER,
+        "switch" => TokenType::SWITCH,
+        "synchronized" => TokenType::SYNCHRONIZED,
+        "this" => TokenType::THIS,
+        "throw" => TokenType::THROW,
+        "throws" => TokenType::THROWS,
+        "transient" => TokenType::TRANSIENT,
+        "try" => TokenType::TRY,
+        "void" => TokenType::VOID,
+        "volatile" => TokenType::VOLATILE,
+        "while" => TokenType::WHILE,
+        "_" => TokenType::UNDERSCORE,
+        "true" => TokenType::TRUE,
+        "false" => TokenType::FALSE,
+        "null" => TokenType::NULL,
+        _ => return None,
+    };
+    Some(token_type)
+}
+
+pub fn parse_visibility(string: &str, span: Span) -> Option<Token> {
     match string {
-        "boolean" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::BOOLEAN),
-            value: None,
-            char_info,
-        }),
-        "byte" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::BYTE),
-            value: None,
-            char_info,
-        }),
-        "short" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::SHORT),
-            value: None,
-            char_info,
-        }),
-        "int" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::INT),
-            value: None,
-            char_info,
-        }),
-        "long" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::LONG),
-            value: None,
-            char_info,
-        }),
-        "float" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::FLOAT),
-            value: None,
-            char_info,
-        }),
-        "double" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::DOUBLE),
-            value: None,
-            char_info,
-        }),
-        "char" => Some(Token {
-            token_type: crate::token::TokenType::DATATYPE(DataType::CHAR),
-            value: None,
-            char_info,
-        }),
+        "public" => Some(Token::new(
+            TokenType::VISIBILITY(Visibility::PUBLIC),
+            None,
+            span,
+        )),
+        "static" => Some(Token::new(TokenType::STATIC, None, span)),
+        "private" => Some(Token::new(
+            TokenType::VISIBILITY(Visibility::PRIVATE),
+            None,
+            span,
+        )),
+        "protected" => Some(Token::new(
+            TokenType::VISIBILITY(Visibility::PROTECTED),
+            None,
+            span,
+        )),
         _ => None,
     }
 }
 
-pub fn parse_expr(string: char, char_info: CharLocationInfo) -> Option<Token> {
+pub fn parse_data_types(string: &str, span: Span) -> Option<Token> {
     match string {
-        '=' => Some(Token {
-            token_type: crate::token::TokenType::EQUAL,
-            value: None,
-            char_info,
-        }),
-        '-' => Some(Token {
-            token_type: crate::token::TokenType::REMOVE,
-            value: None,
-            char_info,
-        }),
-        '*' => Some(Token {
-            token_type: crate::token::TokenType::MUL,
-            value: None,
-            char_info,
-        }),
-        '/' => Some(Token {
-            token_type: crate::token::TokenType::SUB,
-            value: None,
-            char_info,
-        }),
-        '+' => Some(Token {
-            token_type: crate::token::TokenType::ADD,
-            value: None,
-            char_info,
-        }),
+        "boolean" => Some(Token::new(
+            TokenType::DATATYPE(DataType::BOOLEAN),
+            None,
+            span,
+        )),
+        "byte" => Some(Token::new(TokenType::DATATYPE(DataType::BYTE), None, span)),
+        "short" => Some(Token::new(TokenType::DATATYPE(DataType::SHORT), None, span)),
+        "int" => Some(Token::new(TokenType::DATATYPE(DataType::INT), None, span)),
+        "long" => Some(Token::new(TokenType::DATATYPE(DataType::LONG), None, span)),
+        "float" => Some(Token::new(TokenType::DATATYPE(DataType::FLOAT), None, span)),
+        "double" => Some(Token::new(
+            TokenType::DATATYPE(DataType::DOUBLE),
+            None,
+            span,
+        )),
+        "char" => Some(Token::new(TokenType::DATATYPE(DataType::CHAR), None, span)),
         _ => None,
     }
 }
+
+/// All Java operators and separators which are not handled directly by the lexer
+///
+/// Longer operators come first, so we always take the longest match (e.g. `>>=` and not `>` `>=`)
+const OPERATORS: [&str; 44] = [
+    ">>>=", "<<=", ">>=", ">>>", "...", "==", "!=", "<=", ">=", "&&", "||", "++", "--", "+=", "-=",
+    "*=", "/=", "%=", "&=", "|=", "^=", "<<", ">>", "::", "->", "=", "+", "-", "*", "/", "%", "<",
+    ">", "!", "&", "|", "^", "~", "?", ":", ".", "@", "[", "]",
+];
+
+fn operator_token_type(operator: &str) -> Option<TokenType> {
+    let token_type = match operator {
+        "=" => TokenType::EQUAL,
+        "+" => TokenType::PLUS,
+        "-" => TokenType::MINUS,
+        "*" => TokenType::STAR,
+        "/" => TokenType::SLASH,
+        "%" => TokenType::PERCENT,
+        "==" => TokenType::EQUAL_EQUAL,
+        "!=" => TokenType::NOT_EQUAL,
+        "<" => TokenType::LESS,
+        "<=" => TokenType::LESS_EQUAL,
+        ">" => TokenType::GREATER,
+        ">=" => TokenType::GREATER_EQUAL,
+        "&&" => TokenType::AND,
+        "||" => TokenType::OR,
+        "!" => TokenType::NOT,
+        "&" => TokenType::BIT_AND,
+        "|" => TokenType::BIT_OR,
+        "^" => TokenType::BIT_XOR,
+        "~" => TokenType::BIT_NOT,
+        "<<" => TokenType::SHIFT_LEFT,
+        ">>" => TokenType::SHIFT_RIGHT,
+        ">>>" => TokenType::UNSIGNED_SHIFT_RIGHT,
+        "++" => TokenType::INCREMENT,
+        "--" => TokenType::DECREMENT,
+        "+=" => TokenType::PLUS_EQUAL,
+        "-=" => TokenType::MINUS_EQUAL,
+        "*=" => TokenType::STAR_EQUAL,
+        "/=" => TokenType::SLASH_EQUAL,
+        "%=" => TokenType::PERCENT_EQUAL,
+        "&=" => TokenType::AND_EQUAL,
+        "|=" => TokenType::OR_EQUAL,
+        "^=" => TokenType::XOR_EQUAL,
+        "<<=" => TokenType::SHIFT_LEFT_EQUAL,
+        ">>=" => TokenType::SHIFT_RIGHT_EQUAL,
+        ">>>=" => TokenType::UNSIGNED_SHIFT_RIGHT_EQUAL,
+        "?" => TokenType::QUESTION,
+        ":" => TokenType::COLON,
+        "::" => TokenType::DOUBLE_COLON,
+        "->" => TokenType::ARROW,
+        "." => TokenType::DOT,
+        "..." => TokenType::ELLIPSIS,
+        "@" => TokenType::AT,
+        "[" => TokenType::OPEN_SQUARE,
+        "]" => TokenType::CLOSE_SQUARE,
+        _ => return None,
+    };
+    Some(token_type)
+}
+
+/// Parses the longest operator starting with c, the other chars of the operator are consumed
+fn parse_operator(c: char, chars: &mut CharStream, span: Span) -> Option<Token> {
+    let mut lookahead = String::from(c);
+    lookahead.extend(chars.chars.clone().take(3));
+
+    let operator = OPERATORS
+        .iter()
+        .find(|operator| lookahead.starts_with(*operator))?;
+    for _ in 1..operator.len() {
+        chars.next();
+    }
+    Some(Token::new(operator_token_type(operator)?, None, span))
+}
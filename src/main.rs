@@ -1,44 +1,95 @@
+// Token and data type names follow the Java spelling (e.g. OPEN_BRACE, INT)
+#![allow(clippy::upper_case_acronyms, non_camel_case_types)]
+
 use std::{env, fs::File, io::Write, path::Path, process::Command};
 
-use parser::{convert::method::convert_method, nodes::method::NodeMethod};
+use parser::{
+    context::Context,
+    convert::{
+        compilation_unit::convert_compilation_unit, exception::take_warnings,
+        ident::reserve_identifiers,
+    },
+};
+use token::TokenType;
 
 mod java_std;
 mod lexer;
 mod parser;
 mod prelude;
+mod source_map;
 mod token;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
-        panic!("No input file");
+        eprintln!("No input file");
+        std::process::exit(1);
     }
     let input_file = Path::new(&args[1]);
     if !input_file.exists() {
-        panic!("Input file does not exist");
+        eprintln!("Input file does not exist");
+        std::process::exit(1);
     }
     println!("Using Input file {}", input_file.display());
 
-    let tokens = lexer::read_file(input_file);
+    let mut context = Context::default();
+    let (tokens, end_comments) = match lexer::read_file(&mut context.source_map, input_file) {
+        Ok(tokens) => tokens,
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error.display(&context.source_map));
+            }
+            eprintln!("Failed to tokennize file: {} errors", errors.len());
+            std::process::exit(1);
+        }
+    };
+    // Renamed identifiers may not collide with any identifier of the file
+    reserve_identifiers(
+        &context,
+        tokens
+            .iter()
+            .filter(|token| token.token_type == TokenType::UNKNOWN)
+            .filter_map(|token| token.value.as_deref()),
+    );
     // for token in tokens {
     //     println!("{}", token)
     // }
-    let nodes = parser::parse_tokens(&tokens).unwrap();
-    let mut final_code = vec![];
-    for node in nodes {
-        match node {
-            parser::Node::Class(node_class) => {}
-            parser::Node::Method(node_method) => {
-                let code = node_method.get_full_code();
-                println!("{}", code);
-                final_code.push(code);
-            }
+    let (unit, errors) = parser::parse_tokens(&context, &tokens, end_comments);
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{}", error.display(&context.source_map));
         }
+        eprintln!("Failed to parse file: {} errors", errors.len());
+        std::process::exit(1);
+    }
+    let final_code = match convert_compilation_unit(&context, &unit) {
+        Ok(final_code) => final_code,
+        Err(error) => {
+            eprintln!("{}", error.display(&context.source_map));
+            eprintln!("Failed to convert file");
+            std::process::exit(1);
+        }
+    };
+    for warning in take_warnings(&context) {
+        eprintln!("{}", warning.display(&context.source_map));
+    }
+    for code in &final_code {
+        println!("{}", code);
     }
     let output_file = input_file.with_extension("rs");
-    let mut file = File::create(&output_file).expect("Failed to create output file");
+    let mut file = match File::create(&output_file) {
+        Ok(file) => file,
+        Err(error) => {
+            eprintln!(
+                "Failed to create output file {}: {}",
+                output_file.display(),
+                error
+            );
+            std::process::exit(1);
+        }
+    };
     for line in final_code {
-        file.write(line.as_bytes()).unwrap();
+        file.write_all(line.as_bytes()).unwrap();
     }
     let _ = Command::new("rustfmt").arg(output_file).output();
 }
@@ -0,0 +1,67 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+};
+
+use crate::source_map::{SourceMap, Span};
+
+use super::{
+    convert::{data_type::Types, exception::Exceptions, ident::Identifiers},
+    ParseError,
+};
+
+/// The state of the translation of a file, the parser converts the body of a method while it
+/// parses it, so everything which parses or converts code gets it
+#[derive(Default)]
+pub struct Context {
+    /// All files which were read, tokens and errors only keep the id of their file
+    pub source_map: SourceMap,
+    /// The span of the last token of the file, errors at the end of the input are placed there
+    pub(super) end_span: Cell<Option<Span>>,
+    /// The errors of statements in lambda bodies and switch expressions, the expression parser
+    /// has no list of errors, so they are collected here
+    pub(super) nested_errors: RefCell<Vec<ParseError>>,
+    /// The static imports of the file as pairs of (member, qualified class), the member is None
+    /// for an import of all static members
+    pub(super) static_imports: RefCell<Vec<(Option<String>, String)>>,
+    /// The classes, interfaces and type parameters of the input
+    pub(super) types: Types,
+    /// The exceptions of the input and the try statements around the current code
+    pub(super) exceptions: Exceptions,
+    /// The identifiers of the input and the Rust names which were given to them
+    pub(super) identifiers: RefCell<Identifiers>,
+    /// The name of the current object, when it isn't self. Constructors build it in a local
+    /// variable
+    pub(super) receiver: RefCell<Option<&'static str>>,
+    /// Interfaces of the input which lambdas implement, closures get an impl of them
+    pub(super) closure_interfaces: RefCell<HashSet<String>>,
+    /// The name of the function which formats floats and doubles like Java, once it is used
+    pub(super) double_to_string: RefCell<Option<String>>,
+    /// Number of the last generated label of the current method, so they are unique
+    pub(super) generated_labels: Cell<usize>,
+}
+
+/// Gives a part of the context a value until it is dropped, then the part gets its previous
+/// value back, also when the code in between returns early with an error
+pub(super) struct Scoped<'a, T> {
+    cell: &'a RefCell<T>,
+    previous: Option<T>,
+}
+
+impl<'a, T> Scoped<'a, T> {
+    pub(super) fn new(cell: &'a RefCell<T>, value: T) -> Self {
+        let previous = cell.replace(value);
+        Self {
+            cell,
+            previous: Some(previous),
+        }
+    }
+}
+
+impl<T> Drop for Scoped<'_, T> {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            self.cell.replace(previous);
+        }
+    }
+}
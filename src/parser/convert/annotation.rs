@@ -0,0 +1,100 @@
+use crate::parser::nodes::annotation::{AnnotationValue, NodeAnnotation};
+
+use super::string::convert_string_literal;
+
+/// Converts annotations into Rust attributes, every attribute ends with a new line
+///
+/// @Override and @FunctionalInterface are only checked and @Test gets its own test function,
+/// annotations without a Rust attribute are kept as comments. Arguments only allow lint
+/// attributes, so @Deprecated is a comment there. The comments of an argument follow it, see
+/// convert_argument_comment
+///
+/// # Example
+///
+/// @Deprecated(since = "9") -> #[deprecated(since = "9")]
+///
+/// @SuppressWarnings({"unused", "unchecked"}) -> #[allow(unused)]
+///
+/// @Author("me") -> // @Author("me")
+pub fn convert_annotations(annotations: &[NodeAnnotation], is_argument: bool) -> String {
+    let mut final_code = String::new();
+    for annotation in annotations {
+        let attribute = match annotation.name.rsplit('.').next().unwrap_or_default() {
+            "Override" | "FunctionalInterface" | "Test" if !is_argument => continue,
+            _ => convert_attribute(annotation, is_argument),
+        };
+        match attribute {
+            Some(attribute) => final_code.push_str(&format!("{}\n", attribute)),
+            None if is_argument => {}
+            None => final_code.push_str(&format!("// {}\n", annotation.text)),
+        }
+    }
+    final_code
+}
+
+/// The annotations of an argument without a Rust attribute as a comment behind it, rustfmt
+/// would move a comment in front of it behind the previous argument
+///
+/// # Example
+///
+/// @Author("me") @Deprecated -> // @Author("me") @Deprecated
+pub fn convert_argument_comment(annotations: &[NodeAnnotation]) -> String {
+    let texts: Vec<&str> = annotations
+        .iter()
+        .filter(|annotation| convert_attribute(annotation, true).is_none())
+        .map(|annotation| annotation.text.as_str())
+        .collect();
+    match texts.is_empty() {
+        true => String::new(),
+        false => format!("// {}", texts.join(" ")),
+    }
+}
+
+fn convert_attribute(annotation: &NodeAnnotation, is_argument: bool) -> Option<String> {
+    match annotation.name.rsplit('.').next().unwrap_or_default() {
+        "Deprecated" if !is_argument => Some(convert_deprecated(annotation)),
+        "SuppressWarnings" => convert_suppress_warnings(annotation),
+        _ => None,
+    }
+}
+
+/// @Deprecated(since = "9", forRemoval = true) -> #[deprecated(since = "9", note = "...")]
+fn convert_deprecated(annotation: &NodeAnnotation) -> String {
+    let mut args = Vec::new();
+    if let Some(since) = annotation.strings("since").first() {
+        args.push(format!("since = {}", convert_string_literal(since)));
+    }
+    if matches!(annotation.value("forRemoval"), Some(AnnotationValue::Other(value)) if value == "true")
+    {
+        args.push("note = \"This will be removed in a future version\"".to_string());
+    }
+    match args.is_empty() {
+        true => "#[deprecated]".to_string(),
+        false => format!("#[deprecated({})]", args.join(", ")),
+    }
+}
+
+/// The warnings which have a Rust lint, None if no warning has one
+///
+/// # Example
+///
+/// @SuppressWarnings({"unused", "deprecation"}) -> #[allow(unused, deprecated)]
+fn convert_suppress_warnings(annotation: &NodeAnnotation) -> Option<String> {
+    let mut lints = Vec::new();
+    for warning in annotation.strings("value") {
+        let lint = match warning {
+            "all" => "warnings",
+            "unused" => "unused",
+            "deprecation" | "removal" => "deprecated",
+            // Unchecked casts, raw types and the other warnings have no Rust lint
+            _ => continue,
+        };
+        if !lints.contains(&lint) {
+            lints.push(lint);
+        }
+    }
+    match lints.is_empty() {
+        true => None,
+        false => Some(format!("#[allow({})]", lints.join(", "))),
+    }
+}
@@ -0,0 +1,2027 @@
+use std::borrow::Cow;
+
+use crate::{
+    parser::{
+        context::Context,
+        nodes::{
+            class::{component_field, ClassKind, ClassMember, NodeClass, SuperType},
+            expr::{Expr, ExprKind, UnaryOp, VariableScope},
+            method::NodeMethod,
+            variable::VariableType,
+            MethodReturnType, NodeComments,
+        },
+        ClassVariable, ParseError,
+    },
+    token::{DataType, Visibility},
+};
+
+use super::{
+    annotation::convert_annotations,
+    comment::{convert_leading_comments, convert_trailing_comments, convert_with_comments},
+    data_type::{
+        convert_class_name, convert_field_type, convert_fn_trait, convert_type_args,
+        convert_type_params, convert_variable_type, field_cell, is_copied_field, is_shared_class,
+        require_bound, require_type_args, resolve_class, with_class_scope, with_type_params,
+    },
+    exception::is_exception,
+    expr::{
+        convert_args, convert_array, convert_default_value, convert_expr, convert_expr_to,
+        convert_static_cell, convert_static_name,
+    },
+    ident::{convert_ident, generate_ident},
+    lambda::is_implemented_by_lambdas,
+    method::{convert_method, convert_visibility},
+    string::{convert_double_to_string, convert_string_literal},
+};
+
+/// Converts a class into a struct with its instance fields and an impl with everything else
+///
+/// Static fields which are no constants are placed behind the impl and nested classes behind
+/// their class, because Rust has no nested items
+///
+/// # Example
+///
+/// class Point { int x; int getX() { return x; } }
+///
+/// ->
+///
+/// pub struct Point(std::rc::Rc<PointFields>);
+///
+/// pub struct PointFields { x: std::cell::Cell<i32> }
+///
+/// impl Point { fn getX(&self) -> i32 { return self.x.get(); } }
+///
+/// Objects of classes with fields are shared like Java objects, the struct is a handle to the
+/// fields, which are cells, so every handle can change them
+///
+/// Methods of interfaces of the file which the class implements are placed in an
+/// `impl Interface for Class`, classes are all classes and interfaces of the file
+pub fn convert_class(
+    context: &Context,
+    class: &NodeClass,
+    classes: &[&NodeClass],
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    let rust_name = resolve_class(context, &class.name).unwrap_or_else(|| class.name.clone());
+    with_class_scope(context, &rust_name, || {
+        with_type_params(context, None, &class.type_params, || {
+            convert_declaration(context, class, classes)
+        })
+    })
+}
+
+/// Records what the code which is generated for a class needs from its type parameters, see
+/// require_bound. It has to be called for the top level classes before the first class is
+/// converted, because the bounds of a class are also the ones of the types which are used for it
+///
+/// Fields without a value start with their default value and the accessors of a record clone
+/// the components, the bodies of the methods were recorded when they were parsed
+pub fn require_class_bounds(context: &Context, class: &NodeClass) {
+    let rust_name = resolve_class(context, &class.name).unwrap_or_else(|| class.name.clone());
+    with_class_scope(context, &rust_name, || {
+        with_type_params(context, None, &class.type_params, || {
+            for super_type in class.super_class.iter().chain(&class.interfaces) {
+                require_type_args(
+                    context,
+                    &VariableType::Generic(super_type.name.clone(), super_type.args.clone()),
+                );
+            }
+            for component in &class.components {
+                require_bound(context, &component.r#type, "Clone");
+            }
+            for member in &class.members {
+                match member {
+                    ClassMember::Variable(variable) => {
+                        let variable = &variable.variable;
+                        require_type_args(context, &variable.r#type);
+                        if variable.value.is_none() {
+                            require_bound(context, &variable.r#type, "Default");
+                        }
+                    }
+                    ClassMember::Class(nested) => require_class_bounds(context, nested),
+                    _ => {}
+                }
+            }
+        })
+    })
+}
+
+/// Converts a class inside of its scope, so its nested classes are visible
+fn convert_declaration(
+    context: &Context,
+    class: &NodeClass,
+    classes: &[&NodeClass],
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    check_annotations(class, classes)?;
+    check_interface_methods(class, classes)?;
+    match class.kind {
+        ClassKind::Interface => return convert_interface(context, class, classes),
+        ClassKind::Enum => return convert_enum(context, class, classes),
+        ClassKind::Record => return convert_record(context, class, classes),
+        // Exceptions are variants of the exception enum, only their comments stay here
+        ClassKind::Class if is_exception(context, &class.name) => {
+            return Ok(vec![convert_leading_comments(
+                &class.comments.leading,
+                true,
+            )
+            .into()])
+        }
+        ClassKind::Class => {}
+    }
+    let name = convert_class_name(context, &class.name);
+    let visibility = convert_visibility(&class.visibility);
+    let interfaces = implemented_interfaces(class, classes);
+    let mut trait_items = vec![Vec::new(); interfaces.len()];
+
+    let mut fields = Vec::new();
+    let mut impl_items = Vec::new();
+    // Static members of generic classes get their own impl, see convert_static_impl
+    let is_generic = !class.type_params.is_empty();
+    let mut static_items = Vec::new();
+    let mut statics = Vec::new();
+    let mut constructors = Vec::new();
+    let mut initializers = Vec::new();
+    let mut static_initializers = Vec::new();
+    let mut nested = Vec::new();
+    for member in &class.members {
+        match member {
+            ClassMember::Variable(variable) if !variable.is_static => fields.push(variable),
+            ClassMember::Variable(variable) if variable.is_const() => match is_generic {
+                true => static_items.push(convert_const(context, variable)?),
+                false => impl_items.push(convert_const(context, variable)?),
+            },
+            ClassMember::Variable(variable) => {
+                let (code, initializer) = convert_static(context, class, variable)?;
+                statics.push(code);
+                static_initializers.extend(initializer);
+            }
+            ClassMember::Constructor(constructor)
+            | ClassMember::CompactConstructor(constructor) => constructors.push(constructor),
+            ClassMember::Method(method) if method.r#abstract => {
+                return Err(ParseError::new(
+                    class.span,
+                    format!(
+                        "Invalid class {}: abstract method {} is not supported yet",
+                        class.name, method.name
+                    ),
+                ))
+            }
+            ClassMember::Method(method) => match interfaces
+                .iter()
+                .position(|interface| !method.r#static && declares(interface, &method.name))
+            {
+                Some(i) => trait_items[i].push(convert_trait_method(
+                    context,
+                    interfaces[i],
+                    method,
+                    classes,
+                )),
+                None if method.r#static && is_generic => {
+                    static_items.push(method.get_full_code(context).into_owned())
+                }
+                None => impl_items.push(method.get_full_code(context).into_owned()),
+            },
+            ClassMember::Initializer(initializer) if initializer.r#static => {
+                static_initializers.push(convert_static_block(initializer))
+            }
+            ClassMember::Initializer(initializer) => initializers.push(initializer),
+            ClassMember::Class(class) => nested.push(class),
+        }
+    }
+
+    // Objects with fields are shared, the struct is a handle to a struct with the fields
+    let fields_name = is_shared_class(context, &class.name)
+        .then(|| generate_ident(context, &format!("{}Fields", name)));
+    let has_instance_members = !fields.is_empty()
+        || !initializers.is_empty()
+        || class
+            .members
+            .iter()
+            .any(|member| matches!(member, ClassMember::Method(method) if !method.r#static));
+    let is_created = has_instance_members || !constructors.is_empty();
+    // Classes with only static members are never created, so they need no constructor
+    if constructors.is_empty() && has_instance_members {
+        impl_items.insert(
+            0,
+            convert_constructor(
+                context,
+                &self_type(class),
+                None,
+                &fields,
+                &initializers,
+                fields_name.as_deref(),
+            )?,
+        );
+    }
+    for (i, constructor) in constructors.into_iter().enumerate() {
+        impl_items.insert(
+            i,
+            convert_constructor(
+                context,
+                &self_type(class),
+                Some(constructor),
+                &fields,
+                &initializers,
+                fields_name.as_deref(),
+            )?,
+        );
+    }
+    if !static_initializers.is_empty() {
+        match is_generic {
+            true => static_items.push(convert_static_initializer(&static_initializers)),
+            false => impl_items.push(convert_static_initializer(&static_initializers)),
+        }
+    }
+    let static_impl = convert_static_impl(context, class, static_items);
+
+    let mut final_code: Vec<Cow<'static, str>> = vec![
+        convert_leading_comments(&class.comments.leading, true).into(),
+        convert_annotations(&class.annotations, false).into(),
+    ];
+    let type_args = convert_type_args(context, &class.type_params);
+    let mut struct_code = match &fields_name {
+        Some(fields_name) => format!(
+            "#[derive(Clone, Default)]\n{visibility} struct {name}{type_args}(std::rc::Rc<{fields_name}{type_args}>);\n\n#[derive(Default)]\n{visibility} struct {fields_name}{type_args} {{\n"
+        ),
+        None => format!("#[derive(Clone, Default)]\n{visibility} struct {name}{type_args} {{\n"),
+    };
+    for field in &fields {
+        let r#type = &field.variable.r#type;
+        let code = format!(
+            "{}{} {}: {},",
+            convert_annotations(&field.annotations, false),
+            convert_visibility(&field.visibility),
+            convert_ident(context, &field.variable.name),
+            match fields_name {
+                Some(_) => convert_field_type(context, r#type),
+                None => convert_variable_type(context, r#type),
+            }
+        );
+        struct_code.push_str(&convert_with_comments(
+            &field.variable.comments,
+            &code,
+            true,
+        ));
+        struct_code.push('\n');
+    }
+    struct_code.push_str("}\n\n");
+    if let Some(fields_name) = &fields_name {
+        struct_code.push_str(&format!(
+            "{} {{\ntype Target = {fields_name}{type_args};\n\nfn deref(&self) -> &{fields_name}{type_args} {{\n&self.0\n}}\n}}\n\n",
+            convert_impl_header(context, class, Some("std::ops::Deref"), "")
+        ));
+    }
+    final_code.push(struct_code.into());
+
+    let mut impl_code = format!("{} {{\n", convert_impl_header(context, class, None, ""));
+    for item in impl_items {
+        impl_code.push_str(&item);
+        impl_code.push('\n');
+    }
+    impl_code.push_str(&convert_leading_comments(&class.end_comments, false));
+    impl_code.push('}');
+    impl_code.push_str(&convert_trailing_comments(&class.close_comments));
+    impl_code.push_str("\n\n");
+    final_code.push(impl_code.into());
+    final_code.extend(static_impl.map(Cow::from));
+
+    // Java prints every object, classes which can be created get Display
+    if is_created {
+        final_code.push(convert_class_display(context, class, fields_name.is_some()).into());
+    }
+    final_code.extend(convert_trait_impls(
+        context,
+        class,
+        &interfaces,
+        trait_items,
+    ));
+    final_code.extend(statics.into_iter().map(Cow::from));
+    final_code.extend(convert_tests(context, class).into_iter().map(Cow::from));
+    for class in nested {
+        final_code.extend(convert_class(context, class, classes)?);
+    }
+    Ok(final_code)
+}
+
+/// Static members of a generic class can't use its type parameters, so they get their own
+/// impl for the unit type. Rust can't infer the type arguments of Pair::twin(x) from a generic
+/// impl
+///
+/// # Example
+///
+/// record Pair<A, B>(A a, B b) { static <T> Pair<T, T> twin(T t) { ... } }
+///
+/// ->
+///
+/// impl Pair<(), ()> { fn twin<T: Clone>(t: T) -> Pair<T, T> { ... } }
+fn convert_static_impl(
+    context: &Context,
+    class: &NodeClass,
+    static_items: Vec<String>,
+) -> Option<String> {
+    if static_items.is_empty() {
+        return None;
+    }
+    let units = vec!["()"; class.type_params.len()];
+    Some(format!(
+        "impl {}<{}> {{\n{}\n}}\n\n",
+        convert_class_name(context, &class.name),
+        units.join(", "),
+        static_items.join("\n")
+    ))
+}
+
+/// Display prints the object with its toString, other objects are printed like in Java with
+/// the class name and their identity
+fn convert_class_display(context: &Context, class: &NodeClass, is_shared: bool) -> String {
+    let has_to_string = class.members.iter().any(|member| {
+        matches!(member, ClassMember::Method(method)
+            if !method.r#static && method.name == "toString" && method.args.is_empty())
+    });
+    let display = match (has_to_string, is_shared) {
+        (true, _) => "write!(f, \"{}\", self.toString())".to_string(),
+        (false, true) => format!(
+            "write!(f, \"{}@{{:x}}\", std::rc::Rc::as_ptr(&self.0) as *const () as usize)",
+            class.name
+        ),
+        (false, false) => format!("write!(f, \"{}@{{:p}}\", self)", class.name),
+    };
+    format!(
+        "{} {{\nfn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {{\n{}\n}}\n}}\n\n",
+        convert_impl_header(context, class, Some("std::fmt::Display"), ""),
+        display
+    )
+}
+
+/// Checks that a class implements the abstract methods of its interfaces, otherwise the impl of
+/// the trait would miss them
+fn check_interface_methods(class: &NodeClass, classes: &[&NodeClass]) -> Result<(), ParseError> {
+    if class.kind == ClassKind::Interface {
+        return Ok(());
+    }
+    let interfaces = implemented_interfaces(class, classes);
+    // A default method of an interface implements the methods of the ones it extends
+    let is_implemented = |name: &str| {
+        OBJECT_METHODS.contains(&name)
+            || declares(class, name)
+            || class
+                .constants
+                .iter()
+                .any(|constant| constant.members.iter().any(|member| {
+                    matches!(member, ClassMember::Method(method) if method.name == name)
+                }))
+            || class
+                .components
+                .iter()
+                .any(|component| component.name == name)
+            || interfaces.iter().any(|interface| {
+                interface.members.iter().any(|member| {
+                    matches!(member, ClassMember::Method(method) if !method.r#abstract && !method.r#static && method.name == name)
+                })
+            })
+    };
+    for interface in &interfaces {
+        for member in &interface.members {
+            let ClassMember::Method(method) = member else {
+                continue;
+            };
+            if method.r#abstract && !is_implemented(&method.name) {
+                return Err(ParseError::new(
+                    class.span,
+                    format!(
+                        "Invalid class {}: it doesn't implement the method {} of {}",
+                        class.name, method.name, interface.name
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks @Override and @FunctionalInterface like the Java compiler, Rust has no such check
+fn check_annotations(class: &NodeClass, classes: &[&NodeClass]) -> Result<(), ParseError> {
+    if let Some(annotation) = class
+        .annotations
+        .iter()
+        .find(|annotation| annotation.is("FunctionalInterface"))
+    {
+        let mut interfaces = implemented_interfaces(class, classes);
+        interfaces.push(class);
+        let abstract_methods = interfaces
+            .iter()
+            .flat_map(|interface| &interface.members)
+            .filter(|member| matches!(member, ClassMember::Method(method) if method.r#abstract))
+            .count();
+        if class.kind != ClassKind::Interface || abstract_methods != 1 {
+            return Err(ParseError::new(
+annotation.span,
+format!(
+                "Invalid @FunctionalInterface {}: it needs to be an interface with exactly one abstract method",
+                class.name
+            ),
+));
+        }
+    }
+    // The methods of the bodies of enum constants override the ones of the enum
+    let constant_methods = class
+        .constants
+        .iter()
+        .flat_map(|constant| &constant.members)
+        .map(|member| (member, true));
+    for (member, in_constant) in class
+        .members
+        .iter()
+        .map(|member| (member, false))
+        .chain(constant_methods)
+    {
+        let ClassMember::Method(method) = member else {
+            continue;
+        };
+        let Some(annotation) = method
+            .annotations
+            .iter()
+            .find(|annotation| annotation.is("Override"))
+        else {
+            continue;
+        };
+        let is_override = !method.r#static
+            && ((in_constant && declares(class, &method.name))
+                || overrides(class, &method.name, classes));
+        if !is_override {
+            return Err(ParseError::new(
+annotation.span,
+format!(
+                "Invalid method {} of {}: @Override, but it does not override or implement a method of a supertype",
+                method.name, class.name
+            ),
+));
+        }
+    }
+    Ok(())
+}
+
+/// Checks if a supertype of a class has a method, supertypes which are not in the file are
+/// not known, so they could have every method
+fn overrides(class: &NodeClass, name: &str, classes: &[&NodeClass]) -> bool {
+    // Every class extends Object and every record has accessors for its components
+    if OBJECT_METHODS.contains(&name)
+        || class
+            .components
+            .iter()
+            .any(|component| component.name == name)
+    {
+        return true;
+    }
+    class
+        .super_class
+        .iter()
+        .chain(&class.interfaces)
+        .any(|super_type| {
+            let super_name = simple_name(&super_type.name);
+            match classes.iter().find(|other| other.name == super_name) {
+                Some(super_type) => {
+                    declares(super_type, name) || overrides(super_type, name, classes)
+                }
+                None => true,
+            }
+        })
+}
+
+/// JUnit tests become test functions, because Rust can't run methods as tests
+///
+/// # Example
+///
+/// class CalculatorTest { @Test void adds() { ... } }
+///
+/// ->
+///
+/// #[test] fn CalculatorTest_adds() { CalculatorTest::new().adds(); }
+fn convert_tests(context: &Context, class: &NodeClass) -> Vec<String> {
+    class
+        .members
+        .iter()
+        .filter_map(|member| match member {
+            ClassMember::Method(method)
+                if method
+                    .annotations
+                    .iter()
+                    .any(|annotation| annotation.is("Test")) =>
+            {
+                Some(method)
+            }
+            _ => None,
+        })
+        .map(|method| {
+            let call = match method.r#static {
+                true => format!(
+                    "{}::{}",
+                    convert_class_name(context, &class.name),
+                    convert_ident(context, &method.name)
+                ),
+                false => format!(
+                    "{}::new().{}",
+                    convert_class_name(context, &class.name),
+                    convert_ident(context, &method.name)
+                ),
+            };
+            format!(
+                "#[test]\nfn {}() {{\n{}();\n}}\n\n",
+                generate_ident(
+                    context,
+                    &format!(
+                        "{}_{}",
+                        convert_class_name(context, &class.name),
+                        method.name
+                    )
+                ),
+                call
+            )
+        })
+        .collect()
+}
+
+/// The type of the objects of a class, a generic class has its type parameters as arguments
+///
+/// # Example
+///
+/// class Box<T> -> Box<T>
+fn self_type(class: &NodeClass) -> VariableType {
+    match class.type_params.is_empty() {
+        true => VariableType::Class(class.name.clone()),
+        false => VariableType::Generic(
+            class.name.clone(),
+            class
+                .type_params
+                .iter()
+                .map(|param| VariableType::Class(param.name.clone()))
+                .collect(),
+        ),
+    }
+}
+
+/// impl Class or impl Trait for Class, a generic class gets its type parameters with their
+/// bounds, extra_bound is needed by the trait, e.g. PartialEq
+///
+/// # Example
+///
+/// class Box<T> -> impl<T: Clone> Box<T>, when its code copies a T
+fn convert_impl_header(
+    context: &Context,
+    class: &NodeClass,
+    r#trait: Option<&str>,
+    extra_bound: &str,
+) -> String {
+    let bounds = match extra_bound.is_empty() {
+        true => Vec::new(),
+        false => vec![extra_bound],
+    };
+    format!(
+        "impl{} {}{}",
+        convert_type_params(context, None, &class.type_params, &bounds),
+        r#trait.map_or(String::new(), |r#trait| format!("{} for ", r#trait)),
+        convert_variable_type(context, &self_type(class))
+    )
+}
+
+/// Converts an enum into a Rust enum with its constants and an impl with its members and the
+/// methods which every Java enum has
+///
+/// The fields are the same for every constant, so they belong to a struct which the constants
+/// get through Deref
+///
+/// # Example
+///
+/// enum Coin { PENNY(1), DIME(10); int cents; Coin(int cents) { this.cents = cents; } }
+///
+/// ->
+///
+/// pub enum Coin { PENNY, DIME }
+///
+/// pub struct CoinFields { cents: i32 }
+///
+/// impl std::ops::Deref for Coin { type Target = CoinFields; ... }
+fn convert_enum(
+    context: &Context,
+    class: &NodeClass,
+    classes: &[&NodeClass],
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    let name = convert_class_name(context, &class.name);
+    let visibility = convert_visibility(&class.visibility);
+    let interfaces = implemented_interfaces(class, classes);
+    let mut trait_items = vec![Vec::new(); interfaces.len()];
+
+    let mut fields = Vec::new();
+    let mut impl_items = convert_enum_built_ins(context, class);
+    let mut statics = Vec::new();
+    let mut constructors = Vec::new();
+    let mut initializers = Vec::new();
+    let mut static_initializers = Vec::new();
+    let mut nested = Vec::new();
+    let mut methods = Vec::new();
+    for member in &class.members {
+        match member {
+            ClassMember::Variable(variable) if !variable.is_static => fields.push(variable),
+            ClassMember::Variable(variable) if variable.is_const() => {
+                impl_items.push(convert_const(context, variable)?)
+            }
+            ClassMember::Variable(variable) => {
+                let (code, initializer) = convert_static(context, class, variable)?;
+                statics.push(code);
+                static_initializers.extend(initializer);
+            }
+            ClassMember::Constructor(constructor)
+            | ClassMember::CompactConstructor(constructor) => constructors.push(constructor),
+            ClassMember::Method(method) if method.r#static => methods.push(method.clone()),
+            ClassMember::Method(method) => {
+                methods.push(convert_constant_bodies(context, class, method)?)
+            }
+            ClassMember::Initializer(initializer) if initializer.r#static => {
+                static_initializers.push(convert_static_block(initializer))
+            }
+            ClassMember::Initializer(initializer) => initializers.push(initializer),
+            ClassMember::Class(class) => nested.push(class),
+        }
+    }
+    // Methods which are only declared in the bodies of the constants
+    for constant in &class.constants {
+        for member in &constant.members {
+            let ClassMember::Method(method) = member else {
+                return Err(ParseError::new(
+                    class.span,
+                    format!(
+                        "Invalid enum {}: the body of {} can only have methods",
+                        class.name, constant.name
+                    ),
+                ));
+            };
+            if !methods.iter().any(|known| known.name == method.name) {
+                methods.push(convert_constant_bodies(context, class, method)?);
+            }
+        }
+    }
+    for method in &methods {
+        match interfaces
+            .iter()
+            .position(|interface| !method.r#static && declares(interface, &method.name))
+        {
+            Some(i) => trait_items[i].push(convert_trait_method(
+                context,
+                interfaces[i],
+                method,
+                classes,
+            )),
+            None => impl_items.push(method.get_full_code(context).into_owned()),
+        }
+    }
+    if !static_initializers.is_empty() {
+        impl_items.push(convert_static_initializer(&static_initializers));
+    }
+
+    let mut final_code: Vec<Cow<'static, str>> = vec![
+        convert_leading_comments(&class.comments.leading, true).into(),
+        convert_annotations(&class.annotations, false).into(),
+    ];
+    // The first constant stands in for null, e.g. as value of fields which are not set yet
+    let default = match class.constants.is_empty() {
+        true => "",
+        false => "Default, ",
+    };
+    let mut enum_code = format!(
+        "#[derive(Clone, Copy, Debug, {}PartialEq, Eq, Hash, PartialOrd, Ord)]\n{} enum {} {{\n",
+        default, visibility, name
+    );
+    for (i, constant) in class.constants.iter().enumerate() {
+        if i == 0 {
+            enum_code.push_str("#[default]\n");
+        }
+        let code = format!("{},", convert_ident(context, &constant.name));
+        enum_code.push_str(&convert_with_comments(&constant.comments, &code, true));
+        enum_code.push('\n');
+    }
+    enum_code.push_str("}\n\n");
+    final_code.push(enum_code.into());
+
+    if !fields.is_empty() || !constructors.is_empty() || !initializers.is_empty() {
+        final_code.push(
+            convert_enum_fields(context, class, &fields, &constructors, &initializers)?.into(),
+        );
+    }
+
+    let mut impl_code = format!("impl {} {{\n", name);
+    for item in impl_items {
+        impl_code.push_str(&item);
+        impl_code.push('\n');
+    }
+    impl_code.push_str(&convert_leading_comments(&class.end_comments, false));
+    impl_code.push('}');
+    impl_code.push_str(&convert_trailing_comments(&class.close_comments));
+    impl_code.push_str("\n\n");
+    final_code.push(impl_code.into());
+
+    // Java prints the name, unless toString is overridden
+    let has_to_string = methods
+        .iter()
+        .any(|method| method.name == "toString" && method.args.is_empty());
+    let display = match has_to_string {
+        true => "self.toString()",
+        false => "self.name()",
+    };
+    final_code.push(
+        format!(
+            "impl std::fmt::Display for {} {{
+fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {{
+write!(f, \"{{}}\", {})
+}}
+}}\n\n",
+            name, display
+        )
+        .into(),
+    );
+
+    final_code.extend(convert_trait_impls(
+        context,
+        class,
+        &interfaces,
+        trait_items,
+    ));
+    final_code.extend(statics.into_iter().map(Cow::from));
+    for class in nested {
+        final_code.extend(convert_class(context, class, classes)?);
+    }
+    Ok(final_code)
+}
+
+/// Converts a record into a struct with a field and an accessor for every component, equality,
+/// hashing and printing work like in Java
+///
+/// # Example
+///
+/// record Point(int x, int y) {}
+///
+/// ->
+///
+/// pub struct Point { x: i32, y: i32 }
+///
+/// impl Point { pub fn new(x: i32, y: i32) -> Point { Self { x, y } } pub fn x(&self) ... }
+///
+/// Debug and Display print Point[x=1, y=2]
+fn convert_record(
+    context: &Context,
+    class: &NodeClass,
+    classes: &[&NodeClass],
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    let name = convert_class_name(context, &class.name);
+    let visibility = convert_visibility(&class.visibility);
+    let interfaces = implemented_interfaces(class, classes);
+    let mut trait_items = vec![Vec::new(); interfaces.len()];
+    let fields: Vec<ClassVariable> = class.components.iter().map(component_field).collect();
+    let fields: Vec<&ClassVariable> = fields.iter().collect();
+
+    let mut impl_items = Vec::new();
+    // Static members of generic classes get their own impl, see convert_static_impl
+    let is_generic = !class.type_params.is_empty();
+    let mut static_items = Vec::new();
+    let mut statics = Vec::new();
+    let mut constructors = Vec::new();
+    let mut compact_constructor = None;
+    let mut static_initializers = Vec::new();
+    let mut nested = Vec::new();
+    let mut methods = Vec::new();
+    for member in &class.members {
+        match member {
+            ClassMember::Variable(variable) if !variable.is_static => {
+                return Err(ParseError::new(
+                    class.span,
+                    format!(
+                        "Invalid record {}: field {} has to be static",
+                        class.name, variable.variable.name
+                    ),
+                ))
+            }
+            ClassMember::Variable(variable) if variable.is_const() => match is_generic {
+                true => static_items.push(convert_const(context, variable)?),
+                false => impl_items.push(convert_const(context, variable)?),
+            },
+            ClassMember::Variable(variable) => {
+                let (code, initializer) = convert_static(context, class, variable)?;
+                statics.push(code);
+                static_initializers.extend(initializer);
+            }
+            ClassMember::Constructor(constructor) => constructors.push(constructor),
+            ClassMember::CompactConstructor(constructor) => compact_constructor = Some(constructor),
+            ClassMember::Method(method) if method.r#abstract => {
+                return Err(ParseError::new(
+                    class.span,
+                    format!(
+                        "Invalid record {}: abstract method {} is not supported yet",
+                        class.name, method.name
+                    ),
+                ))
+            }
+            ClassMember::Method(method) => methods.push(method),
+            ClassMember::Initializer(initializer) if initializer.r#static => {
+                static_initializers.push(convert_static_block(initializer))
+            }
+            ClassMember::Initializer(_) => {
+                return Err(ParseError::new(
+                    class.span,
+                    format!(
+                        "Invalid record {}: only static initializers are allowed",
+                        class.name
+                    ),
+                ))
+            }
+            ClassMember::Class(class) => nested.push(class),
+        }
+    }
+
+    // The canonical constructor has the components as arguments
+    let is_canonical = |constructor: &&NodeMethod| {
+        constructor.args.len() == class.components.len()
+            && constructor
+                .args
+                .iter()
+                .zip(&class.components)
+                .all(|(arg, component)| arg.r#type == component.r#type)
+    };
+    if !constructors.iter().any(is_canonical) {
+        impl_items.push(convert_compact_constructor(
+            context,
+            class,
+            compact_constructor,
+        ));
+    }
+    for constructor in constructors {
+        impl_items.push(convert_constructor(
+            context,
+            &self_type(class),
+            Some(constructor),
+            &fields,
+            &[],
+            None,
+        )?);
+    }
+    // Every component has an accessor, unless the record declares it itself
+    for component in &class.components {
+        let is_declared = methods.iter().any(|method| {
+            !method.r#static && method.name == component.name && method.args.is_empty()
+        });
+        if is_declared {
+            continue;
+        }
+        let value = match component.r#type {
+            VariableType::DataType(_) => {
+                format!("self.{}", convert_ident(context, &component.name))
+            }
+            _ => format!("self.{}.clone()", convert_ident(context, &component.name)),
+        };
+        impl_items.push(format!(
+            "pub fn {}(&self) -> {} {{\n{}\n}}",
+            convert_ident(context, &component.name),
+            convert_variable_type(context, &component.r#type),
+            value
+        ));
+    }
+    let is_declared = |name: &str| methods.iter().any(|method| method.name == name);
+    if !is_declared("equals") {
+        // The components of a generic record are only comparable for some type arguments
+        let bound = match class.type_params.is_empty() {
+            true => "",
+            false => " where Self: PartialEq",
+        };
+        impl_items.push(format!(
+            "pub fn equals(&self, other: {}) -> bool{} {{\n*self == other\n}}",
+            convert_variable_type(context, &self_type(class)),
+            bound
+        ));
+    }
+    if !is_declared("hashCode") {
+        impl_items.push(convert_record_hash_code(context, class, classes));
+    }
+    let has_to_string = is_declared("toString");
+    if !has_to_string {
+        // The components of a generic record are only printable for some type arguments
+        let bound = match class.type_params.is_empty() {
+            true => "",
+            false => " where Self: std::fmt::Debug",
+        };
+        impl_items.push(format!(
+            "pub fn toString(&self) -> String{} {{\nformat!(\"{{:?}}\", self)\n}}",
+            bound
+        ));
+    }
+    for method in &methods {
+        match interfaces
+            .iter()
+            .position(|interface| !method.r#static && declares(interface, &method.name))
+        {
+            Some(i) => trait_items[i].push(convert_trait_method(
+                context,
+                interfaces[i],
+                method,
+                classes,
+            )),
+            None if method.r#static && is_generic => {
+                static_items.push(method.get_full_code(context).into_owned())
+            }
+            None => impl_items.push(method.get_full_code(context).into_owned()),
+        }
+    }
+    if !static_initializers.is_empty() {
+        match is_generic {
+            true => static_items.push(convert_static_initializer(&static_initializers)),
+            false => impl_items.push(convert_static_initializer(&static_initializers)),
+        }
+    }
+    let static_impl = convert_static_impl(context, class, static_items);
+
+    let mut final_code: Vec<Cow<'static, str>> = vec![
+        convert_leading_comments(&class.comments.leading, true).into(),
+        convert_annotations(&class.annotations, false).into(),
+    ];
+    let mut struct_code = format!(
+        "#[derive(Clone, Default)]\n{} struct {}{} {{\n",
+        visibility,
+        name,
+        convert_type_args(context, &class.type_params)
+    );
+    for field in &fields {
+        struct_code.push_str(&format!(
+            "{}: {},\n",
+            convert_ident(context, &field.variable.name),
+            convert_variable_type(context, &field.variable.r#type)
+        ));
+    }
+    struct_code.push_str("}\n\n");
+    final_code.push(struct_code.into());
+
+    let mut impl_code = format!("{} {{\n", convert_impl_header(context, class, None, ""));
+    for item in impl_items {
+        impl_code.push_str(&item);
+        impl_code.push('\n');
+    }
+    impl_code.push_str(&convert_leading_comments(&class.end_comments, false));
+    impl_code.push('}');
+    impl_code.push_str(&convert_trailing_comments(&class.close_comments));
+    impl_code.push_str("\n\n");
+    final_code.push(impl_code.into());
+    final_code.extend(static_impl.map(Cow::from));
+
+    final_code.push(convert_record_traits(context, class, has_to_string).into());
+    final_code.extend(convert_trait_impls(
+        context,
+        class,
+        &interfaces,
+        trait_items,
+    ));
+    final_code.extend(statics.into_iter().map(Cow::from));
+    for class in nested {
+        final_code.extend(convert_class(context, class, classes)?);
+    }
+    Ok(final_code)
+}
+
+/// The canonical constructor of a record, the body of the compact constructor runs before the
+/// fields get the values of the arguments
+fn convert_compact_constructor(
+    context: &Context,
+    class: &NodeClass,
+    constructor: Option<&NodeMethod>,
+) -> String {
+    let header = NodeMethod {
+        name: "new".to_string(),
+        r#static: true,
+        return_type: MethodReturnType::TYPE(self_type(class)),
+        visibility: constructor.map_or(Visibility::PUBLIC, |constructor| {
+            constructor.visibility.clone()
+        }),
+        args: constructor.map_or(class.components.clone(), |constructor| {
+            constructor.args.clone()
+        }),
+        ..Default::default()
+    };
+    let mut final_code = String::new();
+    if let Some(constructor) = constructor {
+        final_code.push_str(&convert_leading_comments(
+            &constructor.comments.leading,
+            true,
+        ));
+        final_code.push_str(&convert_annotations(&constructor.annotations, false));
+    }
+    final_code.push_str(&convert_method(context, &header));
+    final_code.push('{');
+    if let Some(constructor) = constructor {
+        final_code.push_str(&convert_trailing_comments(&constructor.comments.trailing));
+    }
+    final_code.push('\n');
+    for line in constructor.iter().flat_map(|constructor| &constructor.code) {
+        final_code.push_str(line);
+        final_code.push('\n');
+    }
+    let fields: Vec<String> = class
+        .components
+        .iter()
+        .map(|component| convert_ident(context, &component.name))
+        .collect();
+    final_code.push_str(&format!("Self {{ {} }}\n}}", fields.join(", ")));
+    if let Some(constructor) = constructor {
+        final_code.push_str(&convert_trailing_comments(&constructor.close_comments));
+    }
+    final_code
+}
+
+/// Records are equal when all components are equal, doubles are compared by their bits like
+/// Double.compare does, so the record can also be Eq and Hash. Arrays are compared by their
+/// identity like in Java
+///
+/// toString prints the record like Point[x=1, y=2], unless the record overrides it
+fn convert_record_traits(context: &Context, class: &NodeClass, has_to_string: bool) -> String {
+    let mut equal = Vec::new();
+    let mut hash = String::new();
+    let mut format = Vec::new();
+    let mut values = String::new();
+    for component in &class.components {
+        let field = convert_ident(context, &component.name);
+        let is_float = matches!(
+            component.r#type,
+            VariableType::DataType(DataType::FLOAT | DataType::DOUBLE)
+        );
+        let is_array = matches!(component.r#type, VariableType::Array(_));
+        let value = match (is_float, is_array) {
+            (true, _) => format!("self.{}.to_bits()", field),
+            (_, true) => format!("std::rc::Rc::as_ptr(&self.{})", field),
+            _ => format!("self.{}", field),
+        };
+        equal.push(match (is_float, is_array) {
+            (true, _) => format!("{} == other.{}.to_bits()", value, field),
+            (_, true) => format!("std::rc::Rc::ptr_eq(&self.{0}, &other.{0})", field),
+            _ => format!("{} == other.{}", value, field),
+        });
+        hash.push_str(&format!("std::hash::Hash::hash(&{}, state);\n", value));
+        let placeholder = match &component.r#type {
+            VariableType::Array(_) => "{:?}",
+            _ => "{}",
+        };
+        format.push(format!("{}={}", component.name, placeholder));
+        match (is_float, is_array) {
+            (true, _) => values.push_str(&format!(
+                ", {}",
+                convert_double_to_string(context, &format!("self.{}", field))
+            )),
+            (_, true) => values.push_str(&format!(", self.{}.borrow()", field)),
+            _ => values.push_str(&format!(", self.{}", field)),
+        }
+    }
+    if equal.is_empty() {
+        equal.push("true".to_string());
+    }
+    let (display, display_bound) = match has_to_string {
+        true => ("write!(f, \"{}\", self.toString())", ""),
+        false => ("write!(f, \"{:?}\", self)", "std::fmt::Display"),
+    };
+    format!(
+        "{} {{
+fn eq(&self, other: &Self) -> bool {{
+{}
+}}
+}}
+
+{} {{}}
+
+{} {{
+fn hash<H: std::hash::Hasher>(&self, state: &mut H) {{
+{hash}}}
+}}
+
+{} {{
+fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {{
+write!(f, {}{values})
+}}
+}}
+
+{} {{
+fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {{
+{display}
+}}
+}}\n\n",
+        convert_impl_header(context, class, Some("PartialEq"), "PartialEq"),
+        equal.join(" && "),
+        convert_impl_header(context, class, Some("Eq"), "Eq"),
+        convert_impl_header(context, class, Some("std::hash::Hash"), "std::hash::Hash"),
+        convert_impl_header(context, class, Some("std::fmt::Debug"), "std::fmt::Display"),
+        convert_string_literal(&format!("{}[{}]", class.name, format.join(", "))),
+        convert_impl_header(context, class, Some("std::fmt::Display"), display_bound),
+    )
+}
+
+/// hashCode combines the hashes of the components like Java does, result * 31 + hash
+///
+/// # Example
+///
+/// record Point(int x, int y) -> (0 * 31 + self.x) * 31 + self.y
+///
+/// Types without a Java hash of their own, e.g. arrays, are hashed by Rust
+fn convert_record_hash_code(
+    context: &Context,
+    class: &NodeClass,
+    classes: &[&NodeClass],
+) -> String {
+    let mut result = "0i32".to_string();
+    for component in &class.components {
+        let field = format!("self.{}", convert_ident(context, &component.name));
+        let hash = match &component.r#type {
+            VariableType::DataType(DataType::BOOLEAN) => {
+                format!("if {} {{ 1231 }} else {{ 1237 }}", field)
+            }
+            VariableType::DataType(DataType::LONG) => {
+                format!("({0} ^ ({0} as u64 >> 32) as i64) as i32", field)
+            }
+            VariableType::DataType(DataType::FLOAT) => format!("{}.to_bits() as i32", field),
+            VariableType::DataType(DataType::DOUBLE) => format!(
+                "{{\nlet bits = {}.to_bits();\n(bits ^ (bits >> 32)) as i32\n}}",
+                field
+            ),
+            VariableType::DataType(_) => format!("{} as i32", field),
+            r#type if r#type.is_string() => format!(
+                "{}.encode_utf16().fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as i32))",
+                field
+            ),
+            VariableType::Class(name)
+                if classes
+                    .iter()
+                    .any(|other| other.name == *name && other.kind == ClassKind::Record) =>
+            {
+                format!("{}.hashCode()", field)
+            }
+            _ => format!(
+                "{{\nlet mut hasher = std::collections::hash_map::DefaultHasher::new();\nstd::hash::Hash::hash(&{}, &mut hasher);\nstd::hash::Hasher::finish(&hasher) as i32\n}}",
+                field
+            ),
+        };
+        result = format!("{}.wrapping_mul(31).wrapping_add({})", result, hash);
+    }
+    // Components with a type parameter are hashed by Rust, so it needs to be Hash
+    let bound = match class.type_params.is_empty() {
+        true => String::new(),
+        false => format!(
+            " where {}",
+            class
+                .type_params
+                .iter()
+                .map(|param| format!("{}: std::hash::Hash", convert_ident(context, &param.name)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    format!("pub fn hashCode(&self) -> i32{} {{\n{}\n}}", bound, result)
+}
+
+/// values(), valueOf(String), ordinal(), name() and compareTo(other), which every enum has
+fn convert_enum_built_ins(context: &Context, class: &NodeClass) -> Vec<String> {
+    let name = convert_class_name(context, &class.name);
+    let constants: Vec<String> = class
+        .constants
+        .iter()
+        .map(|constant| convert_ident(context, &constant.name))
+        .collect();
+    let values = constants
+        .iter()
+        .map(|constant| format!("Self::{}", constant))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut names = String::new();
+    let mut parsed = String::new();
+    for (constant, java_name) in constants.iter().zip(&class.constants) {
+        let literal = convert_string_literal(&java_name.name);
+        names.push_str(&format!("Self::{} => {},\n", constant, literal));
+        parsed.push_str(&format!("{} => Self::{},\n", literal, constant));
+    }
+    vec![
+        format!(
+            "pub fn values() -> {} {{\n{}\n}}",
+            convert_variable_type(
+                context,
+                &VariableType::Array(Box::new(VariableType::Class(class.name.clone())))
+            ),
+            convert_array(&format!("vec![{}]", values))
+        ),
+        format!(
+            "pub fn valueOf(name: String) -> {} {{
+match name.as_str() {{
+{}_ => panic!(\"No enum constant {}.{{}}\", name),
+}}
+}}",
+            name, parsed, class.name
+        ),
+        "pub fn ordinal(&self) -> i32 {\n*self as i32\n}".to_string(),
+        format!(
+            "pub fn name(&self) -> String {{\nmatch self {{\n{}}}\n.to_string()\n}}",
+            names
+        ),
+        format!(
+            "pub fn compareTo(&self, other: {}) -> i32 {{\nself.ordinal() - other.ordinal()\n}}",
+            name
+        ),
+    ]
+}
+
+/// The struct with the fields of an enum, its constructor and the Deref which gives every
+/// constant its own fields
+///
+/// The fields of all constants are created once and live until the program ends
+fn convert_enum_fields(
+    context: &Context,
+    class: &NodeClass,
+    fields: &[&ClassVariable],
+    constructors: &[&NodeMethod],
+    initializers: &[&NodeMethod],
+) -> Result<String, ParseError> {
+    let name = convert_class_name(context, &class.name);
+    let fields_name = generate_ident(context, &format!("{}Fields", name));
+    let fields_type = VariableType::Class(fields_name.clone());
+    let mut final_code = format!(
+        "#[derive(Clone, Default)]\n{} struct {} {{\n",
+        convert_visibility(&class.visibility),
+        fields_name
+    );
+    for field in fields {
+        let code = format!(
+            "{}{} {}: {},",
+            convert_annotations(&field.annotations, false),
+            convert_visibility(&field.visibility),
+            convert_ident(context, &field.variable.name),
+            convert_variable_type(context, &field.variable.r#type)
+        );
+        final_code.push_str(&convert_with_comments(
+            &field.variable.comments,
+            &code,
+            true,
+        ));
+        final_code.push('\n');
+    }
+    final_code.push_str("}\n\n");
+
+    final_code.push_str(&format!("impl {} {{\n", fields_name));
+    match constructors.is_empty() {
+        true => final_code.push_str(&convert_constructor(
+            context,
+            &fields_type,
+            None,
+            fields,
+            initializers,
+            None,
+        )?),
+        false => {
+            for constructor in constructors {
+                final_code.push_str(&convert_constructor(
+                    context,
+                    &fields_type,
+                    Some(constructor),
+                    fields,
+                    initializers,
+                    None,
+                )?);
+                final_code.push('\n');
+            }
+        }
+    }
+    final_code.push_str("}\n\n");
+
+    let mut values = Vec::new();
+    for constant in &class.constants {
+        // Overloaded constructors are told apart by their number of arguments
+        let params: Vec<VariableType> = constructors
+            .iter()
+            .find(|constructor| constructor.args.len() == constant.args.len())
+            .map(|constructor| {
+                constructor
+                    .args
+                    .iter()
+                    .map(|arg| arg.r#type.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        values.push(format!(
+            "{}::new({})",
+            fields_name,
+            convert_args(context, &constant.args, &params)?
+        ));
+    }
+    final_code.push_str(&format!(
+        "impl std::ops::Deref for {name} {{
+type Target = {fields_name};
+
+fn deref(&self) -> &{fields_name} {{
+thread_local! {{
+static FIELDS: &'static [{fields_name}] = vec![{}].leak();
+}}
+&FIELDS.with(|fields| *fields)[*self as usize]
+}}
+}}\n\n",
+        values.join(", ")
+    ));
+    Ok(final_code)
+}
+
+/// An instance method of an enum, the constants which have their own version of it get a match
+/// arm with their body, all others use the method of the enum
+///
+/// # Example
+///
+/// PLUS { int apply(int a, int b) { return a + b; } }, abstract int apply(int a, int b);
+///
+/// ->
+///
+/// fn apply(&self, a: i32, b: i32) -> i32 { match self { Self::PLUS => { return a + b; } } }
+fn convert_constant_bodies(
+    context: &Context,
+    class: &NodeClass,
+    method: &NodeMethod,
+) -> Result<NodeMethod, ParseError> {
+    let mut arms = Vec::new();
+    // The fields of the constants are shared, so calls of other methods don't change self
+    let mut merged = NodeMethod {
+        modifies_self: method.modifies_fields,
+        ..method.clone()
+    };
+    for constant in &class.constants {
+        let own = constant.members.iter().find_map(|member| match member {
+            ClassMember::Method(own)
+                if own.name == method.name && own.args.len() == method.args.len() =>
+            {
+                Some(own)
+            }
+            _ => None,
+        });
+        let Some(own) = own else {
+            continue;
+        };
+        let mut arm =
+            vec![format!("Self::{} => {{", convert_ident(context, &constant.name)).into()];
+        arm.push(convert_leading_comments(&own.comments.leading, false).into());
+        // The body may call the arguments differently than the method of the enum
+        for (own_arg, arg) in own.args.iter().zip(&method.args) {
+            if own_arg.name != arg.name {
+                arm.push(
+                    format!(
+                        "let {} = {};",
+                        convert_ident(context, &own_arg.name),
+                        convert_ident(context, &arg.name)
+                    )
+                    .into(),
+                );
+            }
+        }
+        arm.extend(own.code.iter().cloned());
+        arm.push("}".into());
+        arms.push(arm);
+        merged.modifies_self |= own.modifies_fields;
+        merged.modifies_fields |= own.modifies_fields;
+        merged.self_calls.extend(own.self_calls.iter().cloned());
+    }
+    if arms.is_empty() {
+        if method.r#abstract {
+            return Err(ParseError::new(
+                class.span,
+                format!(
+                    "Invalid enum {}: abstract method {} has no body",
+                    class.name, method.name
+                ),
+            ));
+        }
+        return Ok(merged);
+    }
+    let mut code: Vec<Cow<'static, str>> = vec!["match self {".into()];
+    let all_constants = arms.len() == class.constants.len();
+    for arm in arms {
+        code.extend(arm);
+    }
+    if !all_constants {
+        match method.r#abstract {
+            true => code.push("_ => unreachable!(),".into()),
+            false => {
+                code.push("_ => {".into());
+                code.extend(method.code.iter().cloned());
+                code.push("}".into());
+            }
+        }
+    }
+    code.push("}".into());
+    merged.code = code;
+    merged.r#abstract = false;
+    Ok(merged)
+}
+
+/// The impls of the interfaces which a class implements, items are the methods for each of them
+fn convert_trait_impls(
+    context: &Context,
+    class: &NodeClass,
+    interfaces: &[&NodeClass],
+    trait_items: Vec<Vec<String>>,
+) -> Vec<Cow<'static, str>> {
+    let mut final_code = Vec::new();
+    for (interface, items) in interfaces.iter().zip(trait_items) {
+        let super_type = class
+            .interfaces
+            .iter()
+            .find(|super_type| simple_name(&super_type.name) == interface.name);
+        let r#trait = match super_type {
+            Some(super_type) => convert_super_type(context, super_type),
+            // Interfaces which are only extended by an implemented one
+            None => convert_class_name(context, &interface.name),
+        };
+        let mut trait_code = format!(
+            "{} {{\n",
+            convert_impl_header(context, class, Some(&r#trait), "")
+        );
+        for item in items {
+            trait_code.push_str(&item);
+            trait_code.push('\n');
+        }
+        trait_code.push_str("}\n\n");
+        final_code.push(trait_code.into());
+    }
+    final_code
+}
+
+/// Converts an interface into a trait with its instance methods, constants and static methods
+/// belong to the trait object type, so the trait can still be used as `dyn`
+///
+/// # Example
+///
+/// interface Shape { int SIDES = 0; double area(); }
+///
+/// ->
+///
+/// pub trait Shape { fn area(&self) -> f64; }
+///
+/// impl dyn Shape { pub const SIDES: i32 = 0; }
+fn convert_interface(
+    context: &Context,
+    interface: &NodeClass,
+    classes: &[&NodeClass],
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    let name = convert_class_name(context, &interface.name);
+    let type_params = convert_type_params(context, None, &interface.type_params, &[]);
+    let type_args = convert_type_args(context, &interface.type_params);
+
+    let mut trait_items = Vec::new();
+    let mut impl_items = Vec::new();
+    let mut statics = Vec::new();
+    let mut nested = Vec::new();
+    for member in &interface.members {
+        match member {
+            ClassMember::Variable(variable) if variable.is_const() => {
+                impl_items.push(convert_const(context, variable)?)
+            }
+            ClassMember::Variable(variable) => {
+                match convert_static(context, interface, variable)? {
+                    (code, None) => statics.push(code),
+                    // Traits have no static_init
+                    (_, Some(_)) => {
+                        return Err(ParseError::new(
+                            interface.span,
+                            format!(
+                                "Invalid interface {}: field {} needs a constant value",
+                                interface.name, variable.variable.name
+                            ),
+                        ))
+                    }
+                }
+            }
+            ClassMember::Method(method) if method.r#static => {
+                impl_items.push(method.get_full_code(context).into_owned())
+            }
+            ClassMember::Method(method) => {
+                trait_items.push(convert_trait_method(context, interface, method, classes))
+            }
+            ClassMember::Class(class) => nested.push(class),
+            // Interfaces have no constructors and initializers
+            ClassMember::Constructor(_)
+            | ClassMember::CompactConstructor(_)
+            | ClassMember::Initializer(_) => {}
+        }
+    }
+
+    let mut final_code: Vec<Cow<'static, str>> = vec![
+        convert_leading_comments(&interface.comments.leading, true).into(),
+        convert_annotations(&interface.annotations, false).into(),
+    ];
+    let super_traits = match interface.interfaces.is_empty() {
+        true => String::new(),
+        false => format!(
+            ": {}",
+            interface
+                .interfaces
+                .iter()
+                .map(|super_type| convert_super_type(context, super_type))
+                .collect::<Vec<_>>()
+                .join(" + ")
+        ),
+    };
+    let mut trait_code = format!(
+        "{} trait {}{}{} {{\n",
+        convert_visibility(&interface.visibility),
+        name,
+        type_params,
+        super_traits
+    );
+    for item in trait_items {
+        trait_code.push_str(&item);
+        trait_code.push('\n');
+    }
+    trait_code.push_str(&convert_leading_comments(&interface.end_comments, false));
+    trait_code.push('}');
+    trait_code.push_str(&convert_trailing_comments(&interface.close_comments));
+    trait_code.push_str("\n\n");
+    final_code.push(trait_code.into());
+
+    if !impl_items.is_empty() {
+        let mut impl_code = format!("impl{} dyn {}{} {{\n", type_params, name, type_args);
+        for item in impl_items {
+            impl_code.push_str(&item);
+            impl_code.push('\n');
+        }
+        impl_code.push_str("}\n\n");
+        final_code.push(impl_code.into());
+    }
+
+    if is_implemented_by_lambdas(context, &interface.name) {
+        final_code.push(convert_closure_impl(context, interface, classes)?.into());
+    }
+
+    final_code.extend(statics.into_iter().map(Cow::from));
+    for class in nested {
+        final_code.extend(convert_class(context, class, classes)?);
+    }
+    Ok(final_code)
+}
+
+/// Lambdas which implement an interface are closures, so closures with the signature of its
+/// abstract method implement it
+///
+/// # Example
+///
+/// interface Operator { int apply(int a, int b); }
+///
+/// ->
+///
+/// impl<F: Fn(i32, i32) -> i32> Operator for F { fn apply(&self, a: i32, b: i32) -> i32 { self(a, b) } }
+fn convert_closure_impl(
+    context: &Context,
+    interface: &NodeClass,
+    classes: &[&NodeClass],
+) -> Result<String, ParseError> {
+    let abstract_methods: Vec<&NodeMethod> = interface
+        .members
+        .iter()
+        .filter_map(|member| match member {
+            ClassMember::Method(method) if method.r#abstract => Some(method),
+            _ => None,
+        })
+        .collect();
+    let [method] = abstract_methods.as_slice() else {
+        return Err(ParseError::new(
+            interface.span,
+            "Lambdas can only implement interfaces with one abstract method",
+        ));
+    };
+    if !method.type_params.is_empty() || !interface.interfaces.is_empty() {
+        return Err(ParseError::new(
+            interface.span,
+            "Lambdas can't implement generic methods or interfaces which extend others",
+        ));
+    }
+    let function = generate_ident(context, "F");
+    let params: Vec<VariableType> = method.args.iter().map(|arg| arg.r#type.clone()).collect();
+    let bound = format!(
+        "{}: {}",
+        function,
+        convert_fn_trait(context, &params, &method.return_type)
+    );
+    let type_params =
+        match convert_type_params(context, None, &interface.type_params, &[]).strip_suffix('>') {
+            Some(type_params) => format!("{}, {}>", type_params, bound),
+            None => format!("<{}>", bound),
+        };
+    let args: Vec<String> = method
+        .args
+        .iter()
+        .map(|arg| convert_ident(context, &arg.name))
+        .collect();
+    let method = NodeMethod {
+        visibility: Visibility::NONE,
+        modifies_self: trait_method_modifies_self(
+            interface,
+            &method.name,
+            classes,
+            &mut Vec::new(),
+        ),
+        code: vec![format!("self({})", args.join(", ")).into()],
+        r#abstract: false,
+        comments: NodeComments::default(),
+        ..(*method).clone()
+    };
+    Ok(format!(
+        "impl{} {}{} for {} {{\n{}\n}}\n\n",
+        type_params,
+        convert_class_name(context, &interface.name),
+        convert_type_args(context, &interface.type_params),
+        function,
+        method.get_full_code(context)
+    ))
+}
+
+/// A method of a trait or of its impl, they have no visibility and all of them need the same
+/// receiver
+fn convert_trait_method(
+    context: &Context,
+    interface: &NodeClass,
+    method: &NodeMethod,
+    classes: &[&NodeClass],
+) -> String {
+    NodeMethod {
+        visibility: Visibility::NONE,
+        modifies_self: trait_method_modifies_self(
+            interface,
+            &method.name,
+            classes,
+            &mut Vec::new(),
+        ),
+        ..method.clone()
+    }
+    .get_full_code(context)
+    .into_owned()
+}
+
+/// A method of a trait needs `&mut self` if one of its implementations changes a field, or
+/// calls a method which needs `&mut self`
+///
+/// visited are the interfaces and methods which are already checked
+fn trait_method_modifies_self(
+    interface: &NodeClass,
+    name: &str,
+    classes: &[&NodeClass],
+    visited: &mut Vec<(String, String)>,
+) -> bool {
+    let key = (interface.name.clone(), name.to_string());
+    if visited.contains(&key) {
+        return false;
+    }
+    visited.push(key);
+    classes
+        .iter()
+        .filter(|class| {
+            class.name == interface.name
+                || implemented_interfaces(class, classes)
+                    .iter()
+                    .any(|implemented| implemented.name == interface.name)
+        })
+        .any(|class| {
+            class.members.iter().any(|member| match member {
+                ClassMember::Method(method) if !method.r#static && method.name == name => {
+                    method.modifies_fields
+                        || method
+                            .self_calls
+                            .iter()
+                            .any(|call| method_modifies_self(class, call, classes, visited))
+                }
+                _ => false,
+            })
+        })
+}
+
+/// Checks if a method of a class or interface which is called on the current object needs
+/// `&mut self`
+fn method_modifies_self(
+    class: &NodeClass,
+    name: &str,
+    classes: &[&NodeClass],
+    visited: &mut Vec<(String, String)>,
+) -> bool {
+    let mut interfaces = implemented_interfaces(class, classes);
+    if class.kind == ClassKind::Interface {
+        interfaces.push(class);
+    }
+    if let Some(interface) = interfaces
+        .into_iter()
+        .find(|interface| declares(interface, name))
+    {
+        return trait_method_modifies_self(interface, name, classes, visited);
+    }
+    class.members.iter().any(|member| {
+        matches!(member, ClassMember::Method(method)
+            if !method.r#static && method.name == name && method.modifies_self)
+    })
+}
+
+/// The interfaces of the file which a class or interface implements, also the ones which they
+/// extend
+///
+/// Interfaces of other files are skipped, their methods are not known
+fn implemented_interfaces<'a>(class: &NodeClass, classes: &[&'a NodeClass]) -> Vec<&'a NodeClass> {
+    let mut interfaces: Vec<&NodeClass> = Vec::new();
+    let mut pending: Vec<&str> = class
+        .interfaces
+        .iter()
+        .map(|super_type| simple_name(&super_type.name))
+        .collect();
+    while let Some(name) = pending.pop() {
+        let interface = classes
+            .iter()
+            .find(|other| other.kind == ClassKind::Interface && other.name == name);
+        if let Some(interface) = interface {
+            if !interfaces.iter().any(|known| known.name == name) {
+                interfaces.push(interface);
+                pending.extend(
+                    interface
+                        .interfaces
+                        .iter()
+                        .map(|super_type| simple_name(&super_type.name)),
+                );
+            }
+        }
+    }
+    interfaces
+}
+
+/// Checks if an interface has an instance method with this name
+fn declares(interface: &NodeClass, name: &str) -> bool {
+    interface.members.iter().any(|member| {
+        matches!(member, ClassMember::Method(method) if !method.r#static && method.name == name)
+    })
+}
+
+/// The methods of Object which a class can override
+const OBJECT_METHODS: [&str; 4] = ["toString", "equals", "hashCode", "clone"];
+
+/// java.lang.Runnable -> Runnable
+fn simple_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Comparable<Point> -> Comparable<Point>
+fn convert_super_type(context: &Context, super_type: &SuperType) -> String {
+    let name = convert_class_name(context, &super_type.name);
+    match super_type.args.is_empty() {
+        true => name,
+        false => format!(
+            "{}<{}>",
+            name,
+            super_type
+                .args
+                .iter()
+                .map(|r#type| convert_variable_type(context, r#type))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// static final int MAX = 10; -> const MAX: i32 = 10;
+fn convert_const(context: &Context, variable: &ClassVariable) -> Result<String, ParseError> {
+    let r#type = match variable.variable.r#type.is_string() {
+        true => "&str".to_string(),
+        false => convert_variable_type(context, &variable.variable.r#type),
+    };
+    let value = match &variable.variable.value {
+        // String constants stay literals
+        Some(value) if variable.variable.r#type.is_string() => convert_expr(context, value)?,
+        Some(value) => convert_expr_to(context, value, &variable.variable.r#type)?,
+        None => convert_default_value(context, &variable.variable.r#type),
+    };
+    let code = format!(
+        "{}{} const {}: {} = {};",
+        convert_annotations(&variable.annotations, false),
+        convert_visibility(&variable.visibility),
+        convert_ident(context, &variable.variable.name),
+        r#type,
+        value
+    );
+    Ok(convert_with_comments(
+        &variable.variable.comments,
+        &code,
+        true,
+    ))
+}
+
+/// static int count = 0; -> thread_local! { static MAIN_COUNT: &'static Cell<i32> = ..; }
+/// Statics are cells like the fields of shared objects, the thread local only hands out the
+/// leaked cell. Values which need other code run in static_init, the static starts with its
+/// default value. The second value is the code for static_init
+fn convert_static(
+    context: &Context,
+    class: &NodeClass,
+    variable: &ClassVariable,
+) -> Result<(String, Option<String>), ParseError> {
+    let r#type = &variable.variable.r#type;
+    let name = convert_static_name(context, &class.name, &variable.variable.name);
+    let has_default = match r#type {
+        VariableType::DataType(_) | VariableType::Array(_) | VariableType::Nullable(_) => true,
+        r#type => r#type.is_string(),
+    };
+    let (value, initializer) = match &variable.variable.value {
+        Some(value) if is_const_value(value) => (convert_expr_to(context, value, r#type)?, None),
+        Some(value) if has_default => (
+            convert_default_value(context, r#type),
+            Some(format!(
+                "{}.{}({});",
+                convert_static_cell(context, &class.name, &variable.variable.name),
+                match is_copied_field(r#type) {
+                    true => "set",
+                    false => "replace",
+                },
+                convert_expr_to(context, value, r#type)?
+            )),
+        ),
+        None if has_default => (convert_default_value(context, r#type), None),
+        _ => {
+            return Err(ParseError::new(
+                class.span,
+                format!(
+                    "Invalid class {}: static field {} needs a constant value, only primitives, Strings, arrays and fields which can be null can get their value later",
+                    class.name, variable.variable.name
+                ),
+            ))
+        }
+    };
+    let code = format!(
+        "thread_local! {{\n{}{}static {}: &'static {} = Box::leak(Box::new({}::new({})));\n}}",
+        convert_annotations(&variable.annotations, false),
+        match convert_visibility(&variable.visibility) {
+            "" => String::new(),
+            visibility => format!("{} ", visibility),
+        },
+        name,
+        convert_field_type(context, r#type),
+        field_cell(r#type),
+        value
+    );
+    Ok((
+        convert_with_comments(&variable.variable.comments, &code, true) + "\n",
+        initializer,
+    ))
+}
+
+/// Checks if Rust can compute a value at compile time, Strings are created at runtime
+fn is_const_value(value: &Expr) -> bool {
+    if value.r#type().is_string() {
+        return false;
+    }
+    match &value.kind {
+        ExprKind::Literal(_) => true,
+        ExprKind::Variable {
+            scope: VariableScope::Const(_),
+            ..
+        } => true,
+        ExprKind::Unary(op, operand) => {
+            matches!(
+                op,
+                UnaryOp::Minus | UnaryOp::Plus | UnaryOp::Not | UnaryOp::BitNot
+            ) && is_const_value(operand)
+        }
+        ExprKind::Binary(_, left, right) => is_const_value(left) && is_const_value(right),
+        ExprKind::Ternary(condition, then, otherwise) => {
+            is_const_value(condition) && is_const_value(then) && is_const_value(otherwise)
+        }
+        ExprKind::Cast(VariableType::DataType(_), value) => is_const_value(value),
+        _ => false,
+    }
+}
+
+/// Checks if a class needs a static_init, for static blocks or static fields which get their
+/// value at runtime
+pub fn has_static_init(class: &NodeClass) -> bool {
+    class.members.iter().any(|member| match member {
+        ClassMember::Initializer(initializer) => initializer.r#static,
+        ClassMember::Variable(variable) if variable.is_static && !variable.is_const() => variable
+            .variable
+            .value
+            .as_ref()
+            .is_some_and(|value| !is_const_value(value)),
+        _ => false,
+    })
+}
+
+/// Constructors become `new`, the fields get their value first, then the initializer blocks
+/// and the body of the constructor run, like in Java
+///
+/// constructor is None for the default constructor, r#type is the type of the new object.
+/// Shared objects are created with their handle, fields_name is the struct of their fields
+fn convert_constructor(
+    context: &Context,
+    r#type: &VariableType,
+    constructor: Option<&NodeMethod>,
+    fields: &[&ClassVariable],
+    initializers: &[&NodeMethod],
+    fields_name: Option<&str>,
+) -> Result<String, ParseError> {
+    let header = NodeMethod {
+        name: "new".to_string(),
+        overload: constructor.and_then(|constructor| constructor.overload.clone()),
+        r#static: true,
+        return_type: MethodReturnType::TYPE(r#type.clone()),
+        // The default constructor is public, so other classes of the file can use it
+        visibility: constructor.map_or(Visibility::PUBLIC, |constructor| {
+            constructor.visibility.clone()
+        }),
+        args: constructor.map_or(Vec::new(), |constructor| constructor.args.clone()),
+        throws: constructor.map_or(Vec::new(), |constructor| constructor.throws.clone()),
+        ..Default::default()
+    };
+    // A constructor which declares exceptions returns a Result
+    let result = match header.throws.is_empty() {
+        true => "this",
+        false => "Ok(this)",
+    };
+    let mut final_code = String::new();
+    if let Some(constructor) = constructor {
+        final_code.push_str(&convert_leading_comments(
+            &constructor.comments.leading,
+            true,
+        ));
+        final_code.push_str(&convert_annotations(&constructor.annotations, false));
+    }
+    final_code.push_str(&convert_method(context, &header));
+    final_code.push('{');
+    if let Some(constructor) = constructor {
+        final_code.push_str(&convert_trailing_comments(&constructor.comments.trailing));
+    }
+    final_code.push('\n');
+
+    // this(...) creates the object with the other constructor, which also runs the initializers
+    if let Some(constructor) = constructor.filter(|constructor| constructor.delegates) {
+        for line in &constructor.code {
+            final_code.push_str(line);
+            final_code.push('\n');
+        }
+        final_code.push_str(&format!("{}\n}}", result));
+        final_code.push_str(&convert_trailing_comments(&constructor.close_comments));
+        return Ok(final_code);
+    }
+    let body: Vec<&NodeMethod> = initializers.iter().copied().chain(constructor).collect();
+    let mutability = match body.iter().any(|method| method.modifies_self) {
+        true => "mut ",
+        false => "",
+    };
+    match fields_name {
+        Some(fields_name) => final_code.push_str(&format!(
+            "let this = Self(std::rc::Rc::new({} {{\n",
+            fields_name
+        )),
+        None => final_code.push_str(&format!("let {}this = Self {{\n", mutability)),
+    }
+    for field in fields {
+        let r#type = &field.variable.r#type;
+        let mut value = match &field.variable.value {
+            Some(value) => convert_expr_to(context, value, r#type)?,
+            None => convert_default_value(context, r#type),
+        };
+        if fields_name.is_some() {
+            value = format!("{}::new({})", field_cell(r#type), value);
+        }
+        final_code.push_str(&format!(
+            "{}: {},\n",
+            convert_ident(context, &field.variable.name),
+            value
+        ));
+    }
+    match fields_name {
+        Some(_) => final_code.push_str("}));\n"),
+        None => final_code.push_str("};\n"),
+    }
+    for method in body {
+        for line in &method.code {
+            final_code.push_str(line);
+            final_code.push('\n');
+        }
+    }
+    final_code.push_str(&format!("{}\n}}", result));
+    if let Some(constructor) = constructor {
+        final_code.push_str(&convert_trailing_comments(&constructor.close_comments));
+    }
+    Ok(final_code)
+}
+
+/// All static blocks and late static values of a class in the order of the class, they are
+/// called before the main method
+fn convert_static_initializer(initializers: &[String]) -> String {
+    let mut final_code = String::from("pub fn static_init() {\n");
+    for initializer in initializers {
+        final_code.push_str(initializer);
+        final_code.push('\n');
+    }
+    final_code.push('}');
+    final_code
+}
+
+/// The code of a static block for static_init
+fn convert_static_block(initializer: &NodeMethod) -> String {
+    let mut final_code = convert_leading_comments(&initializer.comments.leading, false);
+    for line in &initializer.code {
+        final_code.push_str(line);
+        final_code.push('\n');
+    }
+    final_code.pop();
+    final_code
+}
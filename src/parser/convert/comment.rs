@@ -0,0 +1,77 @@
+use crate::{parser::nodes::NodeComments, token::Trivia};
+
+/// Converts comments which are placed above the code, every comment ends with a new line
+///
+/// Javadoc becomes `///` when docs is true, this should only be used for items (e.g. fn),
+/// otherwise it becomes a normal `//` comment
+pub fn convert_leading_comments(comments: &[Trivia], docs: bool) -> String {
+    let mut final_code = String::new();
+    for comment in comments {
+        match comment {
+            Trivia::LineComment(text) => final_code.push_str(&format!("//{}\n", text)),
+            Trivia::BlockComment(text) => final_code.push_str(&format!("/*{}*/\n", text)),
+            Trivia::DocComment(text) => {
+                let prefix = if docs { "///" } else { "//" };
+                for line in convert_doc_lines(text) {
+                    if line.is_empty() {
+                        final_code.push_str(&format!("{}\n", prefix));
+                    } else {
+                        final_code.push_str(&format!("{} {}\n", prefix, line));
+                    }
+                }
+            }
+            Trivia::BlankLine => final_code.push('\n'),
+        }
+    }
+    final_code
+}
+
+/// Converts comments which are placed behind the code on the same line, including the leading space
+///
+/// Only the last comment can be a `//` comment, all others become `/* */` comments
+pub fn convert_trailing_comments(comments: &[Trivia]) -> String {
+    let comments: Vec<&Trivia> = comments
+        .iter()
+        .filter(|comment| **comment != Trivia::BlankLine)
+        .collect();
+    let mut final_code = String::new();
+    for (i, comment) in comments.iter().enumerate() {
+        let is_last = i == comments.len() - 1;
+        match comment {
+            Trivia::LineComment(text) if is_last => final_code.push_str(&format!(" //{}", text)),
+            Trivia::LineComment(text) => final_code.push_str(&format!(" /*{} */", text)),
+            Trivia::BlockComment(text) => final_code.push_str(&format!(" /*{}*/", text)),
+            Trivia::DocComment(text) => final_code.push_str(&format!(" /*{}*/", text)),
+            Trivia::BlankLine => {}
+        }
+    }
+    final_code
+}
+
+/// Puts the leading comments above the code and the trailing comments behind it
+pub fn convert_with_comments(comments: &NodeComments, code: &str, docs: bool) -> String {
+    format!(
+        "{}{}{}",
+        convert_leading_comments(&comments.leading, docs),
+        code,
+        convert_trailing_comments(&comments.trailing)
+    )
+}
+
+/// Removes the leading `*` of every Javadoc line and empty lines at the start and the end
+fn convert_doc_lines(text: &str) -> Vec<&str> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        })
+        .collect();
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].to_vec(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+
+use crate::parser::{
+    context::Context,
+    nodes::{
+        class::{ClassMember, NodeClass},
+        compilation_unit::CompilationUnit,
+        import::NodeImport,
+    },
+    ParseError,
+};
+
+use super::{
+    class::{convert_class, has_static_init, require_class_bounds},
+    comment::convert_leading_comments,
+    data_type::{convert_type_path, resolve_class, with_class_scope},
+    exception::convert_exceptions,
+    expr::convert_array,
+    ident::convert_ident,
+    string::convert_double_to_string_fn,
+};
+
+/// Converts a whole file, every Java package is expected to be a Rust module of the same crate
+///
+/// # Example
+///
+/// import com.example.util.Helper; -> // use crate::com::example::util::Helper;
+pub fn convert_compilation_unit(
+    context: &Context,
+    unit: &CompilationUnit,
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    let mut final_code: Vec<Cow<'static, str>> =
+        vec![convert_leading_comments(&unit.comments, false).into()];
+    // The output is a single file, so the imported classes only exist once they are added
+    // to the crate, until then the uses would not compile
+    let uses: Vec<String> = unit
+        .imports
+        .iter()
+        .filter_map(|import| convert_import(context, import))
+        .collect();
+    if !uses.is_empty() {
+        final_code
+            .push("// The imported classes are not translated, add them to the crate:\n".into());
+        for code in uses {
+            final_code.push(format!("// {}", code).into());
+        }
+    }
+    let mut classes = Vec::new();
+    for class in &unit.types {
+        collect_classes(class, &mut classes);
+    }
+    for class in &unit.types {
+        require_class_bounds(context, class);
+    }
+    for class in &unit.types {
+        final_code.extend(convert_class(context, class, &classes)?);
+    }
+    if let Some(code) = convert_exceptions(context, unit.package.as_deref()) {
+        final_code.push(code.into());
+    }
+    if let Some(code) = convert_double_to_string_fn(context) {
+        final_code.push(code.into());
+    }
+    if let Some(code) = convert_entry_point(context, unit) {
+        final_code.push(code.into());
+    }
+    final_code.push(convert_leading_comments(&unit.end_comments, false).into());
+    Ok(final_code)
+}
+
+/// Rust needs a main function outside of the impl, which calls the main method of the first
+/// class which has one
+///
+/// Rust has no class loading, so all static initializers run before main. An exception which
+/// main throws is printed like Java does, without the stack trace
+fn convert_entry_point(context: &Context, unit: &CompilationUnit) -> Option<String> {
+    let mut classes = Vec::new();
+    for class in &unit.types {
+        collect_classes(class, &mut classes);
+    }
+    let (class, main) = classes.iter().find_map(|class| {
+        class.members.iter().find_map(|member| match member {
+            ClassMember::Method(method) if method.r#static && method.name == "main" => {
+                Some((class, method))
+            }
+            _ => None,
+        })
+    })?;
+
+    let mut final_code = String::from(
+        "fn main() {
+",
+    );
+    let mut initialized = Vec::new();
+    for class in &unit.types {
+        collect_static_initializers(context, class, &mut initialized);
+    }
+    for class in initialized {
+        final_code.push_str(&format!(
+            "{}::static_init();
+",
+            class
+        ));
+    }
+    let args = match main.args.is_empty() {
+        true => "",
+        false => &convert_array("std::env::args().skip(1).collect()"),
+    };
+    let call = format!(
+        "{}::main({})",
+        convert_type_path(context, &class.name),
+        args
+    );
+    match main.throws.is_empty() {
+        true => final_code.push_str(&format!("{};\n}}\n", call)),
+        false => final_code.push_str(&format!(
+            "if let Err(error) = {} {{
+eprintln!(\"Exception in thread \\\"main\\\" {{}}\", error);
+std::process::exit(1);
+}}
+}}
+",
+            call
+        )),
+    }
+    Some(final_code)
+}
+
+fn collect_classes<'a>(class: &'a NodeClass, classes: &mut Vec<&'a NodeClass>) {
+    classes.push(class);
+    for member in &class.members {
+        if let ClassMember::Class(nested) = member {
+            collect_classes(nested, classes);
+        }
+    }
+}
+
+/// The Rust names of a class and its nested classes which have a static initializer
+fn collect_static_initializers(context: &Context, class: &NodeClass, classes: &mut Vec<String>) {
+    let rust_name = resolve_class(context, &class.name).unwrap_or_else(|| class.name.clone());
+    if has_static_init(class) {
+        classes.push(convert_ident(context, &rust_name));
+    }
+    with_class_scope(context, &rust_name, || {
+        for member in &class.members {
+            if let ClassMember::Class(nested) = member {
+                collect_static_initializers(context, nested, classes);
+            }
+        }
+    });
+}
+
+/// Imports of the Java standard library are resolved through the prelude, so they need no use
+fn convert_import(context: &Context, import: &NodeImport) -> Option<String> {
+    if import.name.starts_with("java.") || import.name.starts_with("javax.") {
+        return None;
+    }
+    // The classes of the file are in the same module
+    if resolve_class(context, &import.name).is_some() {
+        return None;
+    }
+    // Static members become associated items, which can't be imported in Rust, their uses
+    // name the class instead
+    if import.is_static {
+        return None;
+    }
+    let path = import
+        .name
+        .split('.')
+        .map(|name| convert_ident(context, name))
+        .collect::<Vec<_>>()
+        .join("::");
+    match import.on_demand {
+        true => Some(format!("use crate::{}::*;\n", path)),
+        false => Some(format!("use crate::{};\n", path)),
+    }
+}
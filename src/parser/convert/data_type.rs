@@ -1,4 +1,519 @@
-use crate::token::DataType;
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, HashSet},
+};
+
+use crate::{
+    java_std::function::get_functional_method,
+    lexer::{parse_number_literal, NumberLiteral},
+    parser::{
+        context::{Context, Scoped},
+        nodes::{
+            variable::{TypeParam, VariableType, WildcardBound},
+            MethodReturnType,
+        },
+    },
+    token::DataType,
+};
+
+use super::{
+    exception::{exception_type, is_exception},
+    ident::convert_ident,
+};
+
+/// The types of the input, see Context
+#[derive(Default)]
+pub struct Types {
+    /// Names of the interfaces of the input, they are traits in Rust
+    interfaces: RefCell<HashSet<String>>,
+    /// Type parameters of the current classes and method with the Rust name of their class or
+    /// Class::method, the innermost ones are last
+    type_params: RefCell<Vec<(String, Vec<TypeParam>)>>,
+    /// What the code of a class or generic method needs from its type parameters, by the
+    /// owner of type_params, in the order of the parameters
+    type_param_needs: RefCell<HashMap<String, ParamNeeds>>,
+    /// Simple name, Rust name of the outer class and Rust name of every class of the input
+    classes: RefCell<Vec<(String, Option<String>, String)>>,
+    /// Rust names of the classes around the current code, the innermost one is last
+    class_scope: RefCell<Vec<String>>,
+    /// Rust names of the classes whose objects are shared, because they have fields
+    shared_classes: RefCell<HashSet<String>>,
+}
+
+/// Registers the classes of the input with the Rust name of their outer class, nested classes
+/// are moved to the top level of the module, so their name gets the outer one as prefix
+///
+/// # Example
+///
+/// class Outer { class Inner {} } -> Outer, OuterInner
+pub fn declare_classes(
+    context: &Context,
+    classes: impl IntoIterator<Item = (String, Option<String>)>,
+) {
+    context
+        .types
+        .classes
+        .borrow_mut()
+        .extend(classes.into_iter().map(|(name, outer)| {
+            let rust_name = format!("{}{}", outer.as_deref().unwrap_or_default(), name);
+            (name, outer, rust_name)
+        }));
+}
+
+/// Parses and converts everything in `convert` inside of a class, its nested classes can be used
+/// by their simple name until `convert` returns
+pub fn with_class_scope<T>(context: &Context, rust_name: &str, convert: impl FnOnce() -> T) -> T {
+    let mut scope = context.types.class_scope.borrow().clone();
+    scope.push(rust_name.to_string());
+    let _scope = Scoped::new(&context.types.class_scope, scope);
+    convert()
+}
+
+/// The Rust name of a class of the input, simple names are looked up in the classes around the
+/// current code first. Qualified names can start with a package, it is skipped
+///
+/// # Example
+///
+/// Inner inside of Outer -> OuterInner
+///
+/// com.example.Outer.Inner -> OuterInner
+pub fn resolve_class(context: &Context, name: &str) -> Option<String> {
+    let segments: Vec<&str> = name.split('.').collect();
+    (0..segments.len()).find_map(|start| {
+        let mut rust_name = resolve_simple_class(context, segments[start])?;
+        for segment in &segments[start + 1..] {
+            rust_name = nested_class(context, &rust_name, segment)?;
+        }
+        Some(rust_name)
+    })
+}
+
+fn resolve_simple_class(context: &Context, name: &str) -> Option<String> {
+    let scope = context.types.class_scope.borrow().clone();
+    scope
+        .iter()
+        .rev()
+        .find_map(|outer| nested_class(context, outer, name))
+        .or_else(|| find_class(context, name, None))
+}
+
+fn nested_class(context: &Context, outer: &str, name: &str) -> Option<String> {
+    find_class(context, name, Some(outer))
+}
+
+fn find_class(context: &Context, name: &str, outer: Option<&str>) -> Option<String> {
+    context
+        .types
+        .classes
+        .borrow()
+        .iter()
+        .find(|(simple, class_outer, _)| simple == name && class_outer.as_deref() == outer)
+        .map(|(_, _, rust_name)| rust_name.clone())
+}
+
+/// Converts the name of a class, classes of the input get their Rust name and other ones only
+/// keep their simple name, e.g. java.util.List -> List
+pub fn convert_class_name(context: &Context, name: &str) -> String {
+    match resolve_class(context, name) {
+        Some(rust_name) => convert_ident(context, &rust_name),
+        None => convert_ident(context, name.rsplit('.').next().unwrap_or(name)),
+    }
+}
+
+/// Registers the classes whose objects are shared, like Java references. Such a class is a
+/// handle to its fields, the fields are cells which can be changed through every handle
+///
+/// # Example
+///
+/// class Point { int x; } -> struct Point(std::rc::Rc<PointFields>);
+pub fn declare_shared_classes(context: &Context, rust_names: impl IntoIterator<Item = String>) {
+    context.types.shared_classes.borrow_mut().extend(rust_names);
+}
+
+/// Checks if objects of a class of the input are shared
+pub fn is_shared_class(context: &Context, name: &str) -> bool {
+    resolve_class(context, name)
+        .is_some_and(|rust_name| context.types.shared_classes.borrow().contains(&rust_name))
+}
+
+/// Checks if a type is a shared class, type parameters hide classes with the same name
+pub fn is_shared_type(context: &Context, r#type: &VariableType) -> bool {
+    match r#type {
+        VariableType::Class(name) | VariableType::Generic(name, _) => {
+            get_type_param(context, name).is_none() && is_shared_class(context, name)
+        }
+        _ => false,
+    }
+}
+
+/// Checks if the current code is inside of a shared class, so its fields are cells
+pub fn in_shared_class(context: &Context) -> bool {
+    let class = context.types.class_scope.borrow().last().cloned();
+    class.is_some_and(|rust_name| context.types.shared_classes.borrow().contains(&rust_name))
+}
+
+/// Fields of shared objects are cells, primitives are copied out of a Cell and other values are
+/// borrowed from a RefCell
+///
+/// # Example
+///
+/// int -> std::cell::Cell<i32>, String -> std::cell::RefCell<String>
+pub fn convert_field_type(context: &Context, r#type: &VariableType) -> String {
+    format!(
+        "{}<{}>",
+        field_cell(r#type),
+        convert_variable_type(context, r#type)
+    )
+}
+
+/// The cell of a field of a shared object, see `convert_field_type`
+pub fn field_cell(r#type: &VariableType) -> &'static str {
+    match is_copied_field(r#type) {
+        true => "std::cell::Cell",
+        false => "std::cell::RefCell",
+    }
+}
+
+/// Checks if a field of a shared object is in a Cell
+pub fn is_copied_field(r#type: &VariableType) -> bool {
+    matches!(r#type.unboxed(), VariableType::DataType(_))
+}
+
+/// Registers the interfaces of the input, has to be called before the first type is converted,
+/// because an interface can be used before its declaration
+pub fn declare_interfaces(context: &Context, names: impl IntoIterator<Item = String>) {
+    context.types.interfaces.borrow_mut().extend(names);
+}
+
+pub fn is_interface(context: &Context, name: &str) -> bool {
+    context.types.interfaces.borrow().contains(name)
+}
+
+/// Parses and converts everything in `convert` with the type parameters of a class or of a
+/// method of the current class, they are visible until `convert` returns
+pub fn with_type_params<T>(
+    context: &Context,
+    method: Option<&str>,
+    params: &[TypeParam],
+    convert: impl FnOnce() -> T,
+) -> T {
+    let owner = type_param_owner(context, method);
+    {
+        let mut needs = context.types.type_param_needs.borrow_mut();
+        let needs = needs.entry(owner.clone()).or_default();
+        for param in params {
+            if !needs.iter().any(|(name, _)| *name == param.name) {
+                needs.push((param.name.clone(), BTreeSet::new()));
+            }
+        }
+    }
+    let mut type_params = context.types.type_params.borrow().clone();
+    type_params.push((owner, params.to_vec()));
+    let _type_params = Scoped::new(&context.types.type_params, type_params);
+    convert()
+}
+
+/// The Rust name of the current class, with the name of a method for its type parameters
+///
+/// # Example
+///
+/// Box -> Box, with -> Box::with
+fn type_param_owner(context: &Context, method: Option<&str>) -> String {
+    let class = context
+        .types
+        .class_scope
+        .borrow()
+        .last()
+        .cloned()
+        .unwrap_or_default();
+    match method {
+        Some(method) => format!("{}::{}", class, method),
+        None => class,
+    }
+}
+
+/// The visible type parameter with this name, inner ones hide outer ones
+pub fn get_type_param(context: &Context, name: &str) -> Option<TypeParam> {
+    find_type_param(context, name).map(|(_, param)| param)
+}
+
+fn find_type_param(context: &Context, name: &str) -> Option<(String, TypeParam)> {
+    let type_params = context.types.type_params.borrow();
+    type_params.iter().rev().find_map(|(owner, params)| {
+        let param = params.iter().find(|param| param.name == name)?;
+        Some((owner.clone(), param.clone()))
+    })
+}
+
+/// The names of the type parameters of a class or method with what their code needs
+type ParamNeeds = Vec<(String, BTreeSet<Need>)>;
+
+/// What code needs from the values of a type parameter
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Need {
+    /// A trait, e.g. Clone for copies and std::fmt::Display for string concatenation
+    Bound(&'static str),
+    /// The bounds of a type parameter of another class or method, it is used as its type
+    /// argument. The owner of type_params and the index of the parameter
+    Param(String, usize),
+}
+
+/// Records that the code needs a trait from the values of a type, e.g. Clone for copies and
+/// std::fmt::Display for string concatenation. Only type parameters get bounds for it
+///
+/// Generic types derive Clone and Default, so their type arguments need it too, the other
+/// traits are implemented with the bounds of the type parameters
+///
+/// Wildcards of method arguments become new type parameters, see replace_wildcards, they
+/// share the needs of the parameter ? of the method
+pub fn require_bound(context: &Context, r#type: &VariableType, bound: &'static str) {
+    match r#type {
+        VariableType::Class(name) => {
+            if let Some((owner, param)) = find_type_param(context, name) {
+                add_need(context, &owner, &param.name, Need::Bound(bound));
+            }
+        }
+        VariableType::Generic(_, args) => {
+            if matches!(bound, "Clone" | "Default") {
+                for arg in args {
+                    require_bound(context, arg, bound);
+                }
+            }
+            require_type_args(context, r#type);
+        }
+        // None is the default of an Option
+        VariableType::Nullable(r#type) if bound != "Default" => {
+            require_bound(context, r#type, bound)
+        }
+        VariableType::Wildcard(WildcardBound::None | WildcardBound::Extends(_)) => {
+            add_need(context, &wildcard_owner(context), "?", Need::Bound(bound))
+        }
+        _ => {}
+    }
+}
+
+/// Records that a type is used as the type argument of a type parameter, so it needs the
+/// bounds of that parameter. The owner is the Rust name of the class or Class::method, see
+/// with_type_params
+pub fn require_param_bounds(context: &Context, r#type: &VariableType, owner: &str, index: usize) {
+    let need = Need::Param(owner.to_string(), index);
+    match r#type {
+        VariableType::Class(name) => {
+            if let Some((param_owner, param)) = find_type_param(context, name) {
+                add_need(context, &param_owner, &param.name, need);
+            }
+        }
+        VariableType::Wildcard(WildcardBound::None | WildcardBound::Extends(_)) => {
+            add_need(context, &wildcard_owner(context), "?", need)
+        }
+        _ => require_type_args(context, r#type),
+    }
+}
+
+/// Records that the type arguments of a generic class of the file need the bounds of its type
+/// parameters, e.g. the T of Box<T> in a method signature
+pub fn require_type_args(context: &Context, r#type: &VariableType) {
+    match r#type {
+        VariableType::Generic(name, args) => {
+            let owner = resolve_class(context, name);
+            for (i, arg) in args.iter().enumerate() {
+                match &owner {
+                    Some(owner) => require_param_bounds(context, arg, owner, i),
+                    None => require_type_args(context, arg),
+                }
+            }
+        }
+        VariableType::Array(element) | VariableType::Nullable(element) => {
+            require_type_args(context, element)
+        }
+        _ => {}
+    }
+}
+
+/// A wildcard of an argument of a method of the current class became the type parameter name,
+/// it needs what the code needs from wildcards, see require_bound
+pub fn declare_wildcard_param(context: &Context, method: &str, name: &str) {
+    let owner = type_param_owner(context, Some(method));
+    let needs = context
+        .types
+        .type_param_needs
+        .borrow()
+        .get(&owner)
+        .and_then(|params| params.iter().find(|(param, _)| param == "?"))
+        .map(|(_, needs)| needs.clone())
+        .unwrap_or_default();
+    for need in needs {
+        add_need(context, &owner, name, need);
+    }
+}
+
+/// The innermost owner of type parameters, wildcards belong to its method
+fn wildcard_owner(context: &Context) -> String {
+    let owner = context
+        .types
+        .type_params
+        .borrow()
+        .last()
+        .map(|(owner, _)| owner.clone());
+    owner.unwrap_or_else(|| type_param_owner(context, None))
+}
+
+fn add_need(context: &Context, owner: &str, name: &str, need: Need) {
+    let mut needs = context.types.type_param_needs.borrow_mut();
+    let params = needs.entry(owner.to_string()).or_default();
+    match params.iter_mut().find(|(param, _)| param == name) {
+        Some((_, param_needs)) => {
+            param_needs.insert(need);
+        }
+        None => params.push((name.to_string(), BTreeSet::from([need]))),
+    }
+}
+
+/// The traits which the code needs from a type parameter, including the ones of the
+/// parameters which it is used for
+fn needed_bounds(context: &Context, owner: &str, name: &str) -> BTreeSet<&'static str> {
+    let mut bounds = BTreeSet::new();
+    let mut visited = HashSet::new();
+    let mut pending = vec![(owner.to_string(), name.to_string())];
+    while let Some(param) = pending.pop() {
+        if !visited.insert(param.clone()) {
+            continue;
+        }
+        let needs = context
+            .types
+            .type_param_needs
+            .borrow()
+            .get(&param.0)
+            .and_then(|params| params.iter().find(|(name, _)| *name == param.1))
+            .map(|(_, needs)| needs.clone())
+            .unwrap_or_default();
+        for need in needs {
+            match need {
+                Need::Bound(bound) => {
+                    bounds.insert(bound);
+                }
+                Need::Param(owner, index) => {
+                    let name = context
+                        .types
+                        .type_param_needs
+                        .borrow()
+                        .get(&owner)
+                        .and_then(|params| params.get(index))
+                        .map(|(name, _)| name.clone());
+                    pending.extend(name.map(|name| (owner, name)));
+                }
+            }
+        }
+    }
+    bounds
+}
+
+/// Converts the type parameters of the current class or of one of its methods with their
+/// bounds, extra_bounds are added to the bounds of every parameter, e.g. PartialEq for an
+/// impl of it
+///
+/// Interfaces of the file become trait bounds and Comparable becomes PartialOrd. Rust has no
+/// inheritance, so other bounds like classes are dropped, the type can then only use the
+/// methods of the other bounds. These are the traits which the code needs, see require_bound
+///
+/// # Example
+///
+/// <T extends Shape> -> <T: Shape + Clone>, when the code copies a T
+pub fn convert_type_params(
+    context: &Context,
+    method: Option<&str>,
+    params: &[TypeParam],
+    extra_bounds: &[&str],
+) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let owner = type_param_owner(context, method);
+    let params: Vec<String> = params
+        .iter()
+        .map(|param| {
+            let mut bounds: Vec<String> = param
+                .bounds
+                .iter()
+                .filter_map(|bound| convert_bound(context, bound))
+                .collect();
+            bounds.extend(
+                needed_bounds(context, &owner, &param.name)
+                    .into_iter()
+                    .map(str::to_string),
+            );
+            bounds.extend(extra_bounds.iter().map(|bound| bound.to_string()));
+            match bounds.is_empty() {
+                true => convert_ident(context, &param.name),
+                false => format!(
+                    "{}: {}",
+                    convert_ident(context, &param.name),
+                    bounds.join(" + ")
+                ),
+            }
+        })
+        .collect();
+    format!("<{}>", params.join(", "))
+}
+
+/// <T, U> behind the name of a generic type
+pub fn convert_type_args(context: &Context, params: &[TypeParam]) -> String {
+    match params.is_empty() {
+        true => String::new(),
+        false => format!(
+            "<{}>",
+            params
+                .iter()
+                .map(|param| convert_ident(context, &param.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// The trait of a bound, None if it can't be a trait
+pub fn convert_bound(context: &Context, bound: &VariableType) -> Option<String> {
+    let name = bound.class_name()?;
+    if is_comparable(context, bound) {
+        return Some("PartialOrd".to_string());
+    }
+    if !is_interface(context, name) {
+        return None;
+    }
+    Some(match bound {
+        VariableType::Generic(_, args) => format!(
+            "{}<{}>",
+            convert_class_name(context, name),
+            args.iter()
+                .map(|r#type| convert_variable_type(context, r#type))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => convert_class_name(context, name),
+    })
+}
+
+/// Checks if a bound is java.lang.Comparable, its compareTo becomes partial_cmp
+pub fn is_comparable(context: &Context, bound: &VariableType) -> bool {
+    matches!(
+        bound.class_name(),
+        Some("Comparable" | "java.lang.Comparable")
+    ) && !is_interface(context, "Comparable")
+}
+
+/// The path of a type for its associated items, traits need `<dyn Trait>`
+///
+/// # Example
+///
+/// Point -> Point
+///
+/// Shape -> <dyn Shape>
+pub fn convert_type_path(context: &Context, name: &str) -> String {
+    match is_interface(context, name) {
+        true => format!("<dyn {}>", convert_class_name(context, name)),
+        false => convert_class_name(context, name),
+    }
+}
 
 pub fn convert_data_type(data_type: &DataType) -> &'static str {
     match data_type {
@@ -12,3 +527,237 @@ pub fn convert_data_type(data_type: &DataType) -> &'static str {
         DataType::BOOLEAN => "bool",
     }
 }
+
+/// Converts a Java number literal into a Rust literal, also checks if the value fits into its type
+///
+/// negated should be true when the literal is the operand of an unary minus,
+/// only then `2147483648` and `9223372036854775808L` are allowed
+///
+/// # Example
+///
+/// 10L -> 10i64
+///
+/// 1.5f -> 1.5f32
+///
+/// 017 -> 0o17
+pub fn convert_number_literal(raw: &str, negated: bool) -> Result<String, String> {
+    let literal = parse_number_literal(raw)?;
+    match literal.data_type {
+        DataType::INT | DataType::LONG => convert_integer_literal(raw, &literal, negated),
+        _ => convert_float_literal(raw, &literal),
+    }
+}
+
+fn out_of_range(raw: &str, data_type: &DataType) -> String {
+    format!(
+        "Number literal {} is out of range for {}",
+        raw,
+        convert_data_type(data_type)
+    )
+}
+
+fn convert_integer_literal(
+    raw: &str,
+    literal: &NumberLiteral,
+    negated: bool,
+) -> Result<String, String> {
+    let (signed_max, unsigned_max, unsigned_type) = match literal.data_type {
+        DataType::LONG => (i64::MAX as u128, u64::MAX as u128, "u64"),
+        _ => (i32::MAX as u128, u32::MAX as u128, "u32"),
+    };
+    let value = u128::from_str_radix(&literal.integer, literal.radix)
+        .map_err(|_| out_of_range(raw, &literal.data_type))?;
+
+    // Decimal literals are always positive, but hex, octal and binary literals may use the sign bit
+    let max = match literal.radix {
+        10 if negated => signed_max + 1,
+        10 => signed_max,
+        _ => unsigned_max,
+    };
+    if value > max {
+        return Err(out_of_range(raw, &literal.data_type));
+    }
+
+    let digits = match literal.radix {
+        16 => format!("0x{}", literal.integer),
+        8 => format!("0o{}", literal.integer),
+        2 => format!("0b{}", literal.integer),
+        _ => literal.integer.clone(),
+    };
+    let signed_type = convert_data_type(&literal.data_type);
+    if literal.radix != 10 && value > signed_max {
+        // e.g. 0xFFFFFFFF is -1 in Java
+        return Ok(format!("({}{} as {})", digits, unsigned_type, signed_type));
+    }
+    Ok(match literal.data_type {
+        // i32 is the default integer type in Rust, so we don't need a suffix
+        DataType::INT => digits,
+        _ => format!("{}{}", digits, signed_type),
+    })
+}
+
+fn convert_float_literal(raw: &str, literal: &NumberLiteral) -> Result<String, String> {
+    let mantissa_digits = format!(
+        "{}{}",
+        literal.integer,
+        literal.fraction.as_deref().unwrap_or_default()
+    );
+    let is_zero = mantissa_digits.chars().all(|c| c == '0');
+    let exponent = literal.exponent.as_deref().unwrap_or("0");
+
+    let (value, is_infinite) = match literal.radix {
+        16 => {
+            // Hex floats: mantissa * 2^(exponent - 4 * fraction digits)
+            let mantissa = u128::from_str_radix(&mantissa_digits, 16)
+                .map_err(|_| out_of_range(raw, &literal.data_type))?
+                as f64;
+            let exponent = exponent.parse::<i32>().unwrap_or(i32::MAX)
+                - 4 * literal.fraction.as_ref().map_or(0, |f| f.len()) as i32;
+            let value = mantissa * 2f64.powi(exponent);
+            match literal.data_type {
+                DataType::FLOAT => (format!("{:?}", value as f32), (value as f32).is_infinite()),
+                _ => (format!("{:?}", value), value.is_infinite()),
+            }
+        }
+        _ => {
+            let integer = match literal.integer.is_empty() {
+                true => "0",
+                false => &literal.integer,
+            };
+            let fraction = match literal.fraction.as_deref() {
+                Some(fraction) if !fraction.is_empty() => fraction,
+                _ => "0",
+            };
+            let mut value = format!("{}.{}", integer, fraction);
+            if literal.exponent.is_some() {
+                value.push_str(&format!("e{}", exponent));
+            }
+            let is_infinite = match literal.data_type {
+                DataType::FLOAT => value.parse::<f32>().map_or(true, f32::is_infinite),
+                _ => value.parse::<f64>().map_or(true, f64::is_infinite),
+            };
+            let is_rounded_to_zero = !is_zero
+                && match literal.data_type {
+                    DataType::FLOAT => value.parse::<f32>().is_ok_and(|value| value == 0.0),
+                    _ => value.parse::<f64>().is_ok_and(|value| value == 0.0),
+                };
+            if is_rounded_to_zero {
+                return Err(format!(
+                    "Number literal {} is too small for {}",
+                    raw,
+                    convert_data_type(&literal.data_type)
+                ));
+            }
+            (value, is_infinite)
+        }
+    };
+    if is_infinite {
+        return Err(out_of_range(raw, &literal.data_type));
+    }
+
+    Ok(match literal.data_type {
+        DataType::FLOAT => format!("{}f32", value),
+        // f64 is the default float type in Rust, so we don't need a suffix
+        _ => value,
+    })
+}
+
+/// The Fn trait of closures with these arguments and this return type
+///
+/// # Example
+///
+/// (int, String) -> boolean -> Fn(i32, String) -> bool
+pub fn convert_fn_trait(
+    context: &Context,
+    params: &[VariableType],
+    return_type: &MethodReturnType,
+) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|r#type| convert_variable_type(context, r#type))
+        .collect();
+    match return_type {
+        MethodReturnType::VOID => format!("Fn({})", params.join(", ")),
+        MethodReturnType::TYPE(r#type) => format!(
+            "Fn({}) -> {}",
+            params.join(", "),
+            convert_variable_type(context, r#type)
+        ),
+    }
+}
+
+/// Converts a Java type into the Rust type we use for it
+///
+/// # Example
+///
+/// int[] -> Vec<i32>
+///
+/// String -> String
+///
+/// Integer -> i32
+///
+/// Shape -> std::rc::Rc<dyn Shape>, when Shape is an interface
+///
+/// Box<Integer> -> Box<i32>
+///
+/// Function<Integer, String> -> std::rc::Rc<dyn Fn(i32) -> String>
+///
+/// Node, when it can be null -> Option<Node>
+pub fn convert_variable_type(context: &Context, r#type: &VariableType) -> String {
+    // Values of functional interfaces of the Java library are shared closures, because they
+    // are copied like other objects
+    if let Some(method) = get_functional_method(context, r#type) {
+        return format!(
+            "std::rc::Rc<dyn {}>",
+            convert_fn_trait(context, &method.params, &method.return_type)
+        );
+    }
+    match r#type {
+        VariableType::DataType(data_type) => convert_data_type(data_type).to_string(),
+        VariableType::Class(_) if r#type.is_string() => "String".to_string(),
+        // Values of every type are shared trait objects, instanceof downcasts them
+        VariableType::Class(_) if r#type.is_object() => {
+            "std::rc::Rc<dyn std::any::Any>".to_string()
+        }
+        // Values of an interface are shared trait objects, like the ones of Object
+        VariableType::Class(name) if is_interface(context, name) => {
+            format!("std::rc::Rc<dyn {}>", convert_class_name(context, name))
+        }
+        // All exceptions are variants of one enum
+        VariableType::Class(name) if is_exception(context, name) => {
+            exception_type(context).to_string()
+        }
+        VariableType::Class(name) => match r#type.unboxed() {
+            VariableType::DataType(data_type) => convert_data_type(&data_type).to_string(),
+            _ => convert_class_name(context, name),
+        },
+        VariableType::Generic(name, args) => {
+            let args: Vec<String> = args
+                .iter()
+                .map(|r#type| convert_variable_type(context, r#type))
+                .collect();
+            let code = format!("{}<{}>", convert_class_name(context, name), args.join(", "));
+            match is_interface(context, name) {
+                true => format!("std::rc::Rc<dyn {}>", code),
+                false => code,
+            }
+        }
+        // Wildcards of method arguments become type parameters, the other ones are replaced by
+        // their bound, because Rust has no variance for them. ? super T can only be T
+        VariableType::Wildcard(WildcardBound::Extends(bound) | WildcardBound::Super(bound)) => {
+            convert_variable_type(context, bound)
+        }
+        VariableType::Wildcard(WildcardBound::None) => {
+            convert_variable_type(context, &VariableType::Class("Object".to_string()))
+        }
+        // Arrays are shared like in Java, every copy is a handle of the same elements
+        VariableType::Array(element) => format!(
+            "std::rc::Rc<std::cell::RefCell<Vec<{}>>>",
+            convert_variable_type(context, element)
+        ),
+        VariableType::Nullable(r#type) => {
+            format!("Option<{}>", convert_variable_type(context, r#type))
+        }
+        VariableType::Inferred => "_".to_string(),
+    }
+}
@@ -0,0 +1,350 @@
+use std::cell::{Cell, RefCell};
+
+use crate::{
+    java_std::exception::get_library_exception,
+    parser::{
+        context::{Context, Scoped},
+        nodes::expr::Expr,
+        ParseError,
+    },
+    source_map::Span,
+};
+
+use super::ident::convert_ident;
+
+/// The exceptions of the input, see Context
+#[derive(Default)]
+pub struct Exceptions {
+    /// The classes of the input with the class they extend, they hide the exceptions of the
+    /// library
+    classes: RefCell<Vec<(String, Option<String>)>>,
+    /// The output uses the Exception enum
+    used: Cell<bool>,
+    /// The exception classes which are created, every one is a variant of the Exception enum
+    variants: RefCell<Vec<String>>,
+    /// The current method declares exceptions, so it returns a Result
+    declares_exceptions: RefCell<bool>,
+    /// The label of the block of the innermost try around the current code, a thrown exception
+    /// leaves this block
+    try_label: RefCell<Option<String>>,
+    /// The throw statements which become a panic and the catches which can't catch the panics,
+    /// the output still compiles, but it behaves differently
+    warnings: RefCell<Vec<ParseError>>,
+    /// The exceptions which are thrown in try blocks as an Err, None for a call or a rethrow
+    /// which can throw any exception
+    raised: RefCell<Vec<Option<String>>>,
+}
+
+/// Registers the classes of the input with the class they extend, has to be called before the
+/// first type is parsed, because an exception can be used before its declaration
+pub fn declare_super_classes(
+    context: &Context,
+    classes: impl IntoIterator<Item = (String, Option<String>)>,
+) {
+    context.exceptions.classes.borrow_mut().extend(classes);
+}
+
+/// Checks if a class is Throwable or extends it
+pub fn is_exception(context: &Context, name: &str) -> bool {
+    super_classes(context, name)
+        .last()
+        .is_some_and(|name| name == "Throwable")
+}
+
+/// The simple names of the class and all classes it extends, the last one is Throwable for
+/// exceptions
+///
+/// # Example
+///
+/// java.io.FileNotFoundException -> [FileNotFoundException, IOException, Exception, Throwable]
+fn super_classes(context: &Context, name: &str) -> Vec<String> {
+    let mut classes: Vec<String> = Vec::new();
+    let mut current = Some(name.rsplit('.').next().unwrap_or(name).to_string());
+    while let Some(name) = current.take() {
+        // Invalid input can extend itself
+        if classes.contains(&name) {
+            break;
+        }
+        let declared = context
+            .exceptions
+            .classes
+            .borrow()
+            .iter()
+            .find(|(class, _)| *class == name)
+            .map(|(_, super_class)| super_class.clone());
+        current = match declared {
+            Some(super_class) => super_class,
+            None => get_library_exception(&name)
+                .and_then(|(_, super_class)| super_class)
+                .map(str::to_string),
+        };
+        classes.push(name);
+    }
+    classes
+}
+
+/// The name of the enum which holds all exceptions, the enum is generated when it is used
+pub fn exception_type(context: &Context) -> &'static str {
+    context.exceptions.used.set(true);
+    "Exception"
+}
+
+/// Creates an exception, message is the code of the message
+///
+/// # Example
+///
+/// new IllegalStateException("empty") -> Exception::IllegalStateException(Some("empty".to_string()))
+pub fn convert_new_exception(context: &Context, class: &str, message: Option<&str>) -> String {
+    let class = class.rsplit('.').next().unwrap_or(class);
+    {
+        let mut variants = context.exceptions.variants.borrow_mut();
+        if !variants.iter().any(|variant| variant == class) {
+            variants.push(class.to_string());
+        }
+    }
+    let message = match message {
+        Some(message) => format!("Some({})", message),
+        None => "None".to_string(),
+    };
+    format!(
+        "{}::{}({})",
+        exception_type(context),
+        convert_ident(context, class),
+        message
+    )
+}
+
+/// Parses and converts everything in `convert` for a method which declares exceptions or not,
+/// the try statements of the method around are not visible
+pub fn with_declared_exceptions<T>(
+    context: &Context,
+    declares: bool,
+    convert: impl FnOnce() -> T,
+) -> T {
+    let _declares = Scoped::new(&context.exceptions.declares_exceptions, declares);
+    let _label = Scoped::new(&context.exceptions.try_label, None);
+    convert()
+}
+
+/// Converts everything in `convert` inside the block of a try statement
+pub fn with_try_label<T>(context: &Context, label: &str, convert: impl FnOnce() -> T) -> T {
+    let _label = Scoped::new(&context.exceptions.try_label, Some(label.to_string()));
+    convert()
+}
+
+/// Checks if the current method declares exceptions, so it returns a Result
+pub fn declares_exceptions(context: &Context) -> bool {
+    *context.exceptions.declares_exceptions.borrow()
+}
+
+/// Checks if the current code is inside the block of a try statement
+pub fn in_try_block(context: &Context) -> bool {
+    context.exceptions.try_label.borrow().is_some()
+}
+
+/// Throws an exception, error is its code. Exceptions which the method doesn't declare are
+/// unchecked, so they become a panic
+///
+/// # Example
+///
+/// throw e; -> return Err(e); in a method which declares exceptions
+///
+/// throw e; -> break 'try1 Err(e); in a try block
+pub fn convert_throw(context: &Context, error: &str) -> String {
+    raise(context, None);
+    throw(context, error)
+}
+
+fn throw(context: &Context, error: &str) -> String {
+    let label = context.exceptions.try_label.borrow().clone();
+    match label {
+        Some(label) => format!("break {} Err({});", label, error),
+        None if declares_exceptions(context) => format!("return Err({});", error),
+        None => format!("panic!(\"{{}}\", {});", error),
+    }
+}
+
+/// Converts a throw statement, see convert_throw
+///
+/// A method which doesn't declare the exception can't return it, so it panics and the catch
+/// blocks of the callers don't run. Such a throw is reported as a warning
+pub fn convert_throw_statement(context: &Context, expr: &Expr, error: &str) -> String {
+    let class = expr.r#type();
+    let class = class.class_name();
+    raise(context, class);
+    if !in_try_block(context) && !declares_exceptions(context) {
+        let class = class.unwrap_or("exception");
+        context
+            .exceptions
+            .warnings
+            .borrow_mut()
+            .push(ParseError::new(
+                expr.span,
+                format!(
+                "Warning: {} is not declared, so it becomes a panic, which the callers can't catch",
+                class
+            ),
+            ));
+    }
+    throw(context, error)
+}
+
+/// Remembers an exception which is thrown as an Err inside a try block
+fn raise(context: &Context, class: Option<&str>) {
+    if in_try_block(context) {
+        let class = class.map(str::to_string);
+        context.exceptions.raised.borrow_mut().push(class);
+    }
+}
+
+/// The number of exceptions which were thrown in try blocks, the ones after it are thrown by the
+/// code which is converted afterwards
+pub fn raised_count(context: &Context) -> usize {
+    context.exceptions.raised.borrow().len()
+}
+
+/// Warns about the caught classes which only a panic can raise, a panic isn't an Err, so the
+/// catch doesn't run. start is the raised_count before the try block
+///
+/// Checked exceptions have to be thrown by the block, the compiler of Java checks this, but
+/// unchecked ones like ArithmeticException are raised by the operations themselves
+pub fn check_caught(context: &Context, start: usize, classes: &[(String, Span)]) {
+    let raised: Vec<Option<String>> = context.exceptions.raised.borrow()[start..].to_vec();
+    for (class, span) in classes {
+        let caught = super_classes(context, class);
+        // Exception and Throwable cover the unchecked exceptions as well
+        let unchecked = caught.len() <= 2
+            || caught
+                .iter()
+                .any(|name| name == "RuntimeException" || name == "Error");
+        let thrown = raised.iter().any(|raised| match raised {
+            Some(raised) => {
+                caught.contains(raised) || super_classes(context, raised).contains(class)
+            }
+            None => true,
+        });
+        if unchecked && !thrown {
+            context.exceptions.warnings.borrow_mut().push(ParseError::new(
+                *span,
+                format!(
+                    "Warning: the try block can only raise {} as a panic, which the catch can't catch",
+                    class
+                ),
+            ));
+        }
+    }
+}
+
+/// The warnings of the converted file, they are removed
+pub fn take_warnings(context: &Context) -> Vec<ParseError> {
+    context.exceptions.warnings.take()
+}
+
+/// The value of a call of a method which declares exceptions, its exception is thrown again
+///
+/// # Example
+///
+/// read() -> read()? in a method which declares exceptions
+pub fn convert_throwing_call(context: &Context, call: &str) -> String {
+    raise(context, None);
+    let label = context.exceptions.try_label.borrow().clone();
+    match label {
+        Some(label) => format!(
+            "match {} {{ Ok(value) => value, Err(error) => break {} Err(error) }}",
+            call, label
+        ),
+        None if declares_exceptions(context) => format!("{}?", call),
+        None => format!("{}.unwrap_or_else(|error| panic!(\"{{}}\", error))", call),
+    }
+}
+
+/// Converts all exceptions into one enum whose variants hold the message, None if the output
+/// doesn't use exceptions
+///
+/// Java prints exceptions with the full class name, the classes of the input are in package
+///
+/// # Example
+///
+/// new IllegalStateException("empty") -> enum Exception { IllegalStateException(Option<String>) }
+pub fn convert_exceptions(context: &Context, package: Option<&str>) -> Option<String> {
+    if !context.exceptions.used.get() {
+        return None;
+    }
+    let variants = context.exceptions.variants.borrow().clone();
+    let mut declarations = String::new();
+    let mut messages = String::new();
+    let mut names = String::new();
+    let mut instances = String::new();
+    for variant in &variants {
+        let ident = convert_ident(context, variant);
+        let package = match get_library_exception(variant) {
+            Some((package, _)) if !is_declared(context, variant) => Some(package),
+            _ => package,
+        };
+        let name = match package {
+            Some(package) => format!("{}.{}", package, variant),
+            None => variant.clone(),
+        };
+        let super_classes: Vec<String> = super_classes(context, variant)
+            .iter()
+            .map(|class| format!("{:?}", class))
+            .collect();
+        declarations.push_str(&format!("{}(Option<String>),\n", ident));
+        messages.push_str(&format!(
+            "Self::{}(ref message) => message.clone(),\n",
+            ident
+        ));
+        names.push_str(&format!("Self::{}(_) => {:?},\n", ident, name));
+        instances.push_str(&format!(
+            "Self::{}(_) => &[{}],\n",
+            ident,
+            super_classes.join(", ")
+        ));
+    }
+    Some(format!(
+        "#[derive(Clone, Debug, PartialEq)]
+enum Exception {{
+{declarations}}}
+
+impl Exception {{
+    pub fn getMessage(&self) -> String {{
+        self.message().unwrap_or_else(|| \"null\".to_string())
+    }}
+    pub fn toString(&self) -> String {{
+        self.to_string()
+    }}
+    pub fn printStackTrace(&self) {{
+        eprintln!(\"{{}}\", self);
+    }}
+    fn message(&self) -> Option<String> {{
+        match *self {{
+{messages}        }}
+    }}
+    fn class_name(&self) -> &'static str {{
+        match *self {{
+{names}        }}
+    }}
+    fn is_instance_of(&self, class: &str) -> bool {{
+        let classes: &[&str] = match *self {{
+{instances}        }};
+        classes.contains(&class)
+    }}
+}}
+
+impl std::fmt::Display for Exception {{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {{
+        match self.message() {{
+            Some(message) => write!(f, \"{{}}: {{}}\", self.class_name(), message),
+            None => write!(f, \"{{}}\", self.class_name()),
+        }}
+    }}
+}}
+"
+    ))
+}
+
+/// Checks if the input declares a class with this name
+fn is_declared(context: &Context, name: &str) -> bool {
+    let classes = context.exceptions.classes.borrow();
+    classes.iter().any(|(class, _)| class == name)
+}
@@ -0,0 +1,2041 @@
+use crate::{
+    java_std::function::get_functional_method,
+    parser::{
+        context::{Context, Scoped},
+        nodes::{
+            expr::{
+                binary_promotion, BinaryOp, Expr, ExprKind, Literal, Pattern, RecordComponent,
+                UnaryOp, VariableScope,
+            },
+            variable::VariableType,
+            FunctionArgument,
+        },
+        ParseError,
+    },
+    prelude::{code_from_string_method, get_prelude_class, Class},
+    source_map::Span,
+    token::DataType,
+};
+
+use super::{
+    data_type::{
+        convert_data_type, convert_number_literal, convert_type_path, convert_variable_type,
+        get_type_param, in_shared_class, is_comparable, is_copied_field, is_interface,
+        is_shared_type, require_bound, resolve_class,
+    },
+    exception::{
+        convert_new_exception, convert_throw, convert_throwing_call, in_try_block, is_exception,
+    },
+    ident::{convert_ident, generate_ident, generate_static_ident},
+    lambda::{convert_closure, convert_lambda},
+    statement::convert_switch_expression,
+    string::{convert_char_literal, convert_double_to_string, convert_string_literal},
+};
+
+/// Converts everything in `convert` with another name for the current object
+///
+/// # Example
+///
+/// this.x = x; -> this.x = x; in a constructor, instead of self.x = x;
+pub fn with_receiver<T>(
+    context: &Context,
+    receiver: &'static str,
+    convert: impl FnOnce() -> T,
+) -> T {
+    let _receiver = Scoped::new(&context.receiver, Some(receiver));
+    convert()
+}
+
+/// The name of the current object, e.g. self
+pub fn receiver(context: &Context) -> &'static str {
+    context.receiver.borrow().unwrap_or("self")
+}
+
+/// Static variables are placed outside of the impl, so they get the class name as prefix
+///
+/// # Example
+///
+/// Main.count -> MAIN_COUNT, or MAIN_COUNT1 when another static already has that name
+pub fn convert_static_name(context: &Context, class: &str, name: &str) -> String {
+    // Nested classes have the name of their outer class as prefix
+    let class = resolve_class(context, class).unwrap_or_else(|| class.to_string());
+    let mut static_name = String::new();
+    let mut previous_lowercase = false;
+    for c in class.chars().chain(Some('_')).chain(name.chars()) {
+        if c.is_uppercase() && previous_lowercase {
+            static_name.push('_');
+        }
+        previous_lowercase = c.is_lowercase() || c.is_numeric();
+        static_name.extend(c.to_uppercase());
+    }
+    generate_static_ident(context, &class, name, &convert_ident(context, &static_name))
+}
+
+/// The cell of a static variable, which is a cell like a field of a shared object
+///
+/// # Example
+///
+/// Main.count -> MAIN_COUNT.with(|cell| *cell)
+pub fn convert_static_cell(context: &Context, class: &str, name: &str) -> String {
+    format!(
+        "{}.with(|cell| *cell)",
+        convert_static_name(context, class, name)
+    )
+}
+
+/// Rust precedence of the generated code, higher binds stronger
+const ASSIGN: u8 = 1;
+const CAST: u8 = 12;
+const UNARY: u8 = 13;
+/// Literals, variables, method calls and everything else which never needs braces
+const ATOM: u8 = 14;
+
+fn binary_precedence(op: BinaryOp) -> u8 {
+    match op {
+        BinaryOp::Or => 3,
+        BinaryOp::And => 4,
+        _ if op.is_comparison() => 5,
+        BinaryOp::BitOr => 6,
+        BinaryOp::BitXor => 7,
+        BinaryOp::BitAnd => 8,
+        _ if op.is_shift() => 9,
+        BinaryOp::Add | BinaryOp::Sub => 10,
+        _ => 11,
+    }
+}
+
+fn binary_operator(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::Rem => "%",
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::ShiftLeft => "<<",
+        // The unsigned shift is converted on its own, this is only used for the shift itself
+        BinaryOp::ShiftRight | BinaryOp::UnsignedShiftRight => ">>",
+        BinaryOp::Less => "<",
+        BinaryOp::Greater => ">",
+        BinaryOp::LessEqual => "<=",
+        BinaryOp::GreaterEqual => ">=",
+        BinaryOp::Equal => "==",
+        BinaryOp::NotEqual => "!=",
+        BinaryOp::BitAnd => "&",
+        BinaryOp::BitXor => "^",
+        BinaryOp::BitOr => "|",
+        BinaryOp::And => "&&",
+        BinaryOp::Or => "||",
+    }
+}
+
+/// The method of int and long for an operation which can overflow, Java ignores the overflow
+/// and only uses the lowest bits of the distance of a shift, Rust would panic for both
+fn wrapping_method(op: BinaryOp) -> Option<&'static str> {
+    match op {
+        BinaryOp::Add => Some("wrapping_add"),
+        BinaryOp::Sub => Some("wrapping_sub"),
+        BinaryOp::Mul => Some("wrapping_mul"),
+        BinaryOp::ShiftLeft => Some("wrapping_shl"),
+        BinaryOp::ShiftRight | BinaryOp::UnsignedShiftRight => Some("wrapping_shr"),
+        _ => None,
+    }
+}
+
+/// Checks if an operation on a value of the type is converted with its wrapping method
+fn is_wrapping(op: BinaryOp, r#type: &VariableType) -> bool {
+    wrapping_method(op).is_some()
+        && matches!(
+            r#type,
+            VariableType::DataType(DataType::INT | DataType::LONG)
+        )
+}
+
+/// Converts an expression into Rust code
+///
+/// # Example
+///
+/// a >>> 2 -> ((a as u32) >> 2) as i32
+pub fn convert_expr(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    Ok(convert(context, expr)?.0)
+}
+
+/// Converts an expression whose methods or fields are used, it is wrapped in braces if needed
+///
+/// # Example
+///
+/// a + b -> (a + b)
+pub fn convert_atom(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    operand_code(context, expr, ATOM)
+}
+
+/// Converts an expression which is used as statement, without the ;
+///
+/// Java allows increments and assignments as statements, Rust only as expressions with unit value
+///
+/// # Example
+///
+/// i++ -> i += 1
+pub fn convert_expr_statement(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    match &expr.kind {
+        ExprKind::Unary(op, target) => match increment_op(*op) {
+            Some(op) => convert_assign(context, Some(op), target, &increment_step(target)),
+            None => convert_expr(context, expr),
+        },
+        // The value is not used, so only the exception is checked
+        ExprKind::MethodCall {
+            target,
+            name,
+            args,
+            params,
+            throws: true,
+            overload,
+            ..
+        } if in_try_block(context) => {
+            let name = overload.as_ref().unwrap_or(name);
+            let (call, _) =
+                convert_method_call(context, expr, target.as_deref(), name, args, params)?;
+            Ok(format!(
+                "if let Err(error) = {} {{\n{}\n}}",
+                call,
+                convert_throw(context, "error")
+            ))
+        }
+        _ => convert_expr(context, expr),
+    }
+}
+
+/// Converts an expression which is assigned to a variable of the given type,
+/// this adds the conversions which Java does implicitly
+///
+/// # Example
+///
+/// long l = 5; -> let l = 5i64;
+///
+/// String s = "a"; -> let s = "a".to_string();
+///
+/// Object o = 5; -> let o = std::rc::Rc::new(5) as std::rc::Rc<dyn std::any::Any>;
+pub fn convert_expr_to(
+    context: &Context,
+    expr: &Expr,
+    r#type: &VariableType,
+) -> Result<String, ParseError> {
+    match (&expr.kind, r#type) {
+        (_, VariableType::Nullable(_)) => convert_nullable(context, expr, r#type),
+        // String constants are &str
+        (
+            ExprKind::Literal(Literal::String(_))
+            | ExprKind::Variable {
+                scope: VariableScope::Const(_),
+                ..
+            },
+            _,
+        ) if r#type.is_string() => Ok(format!(
+            "{}.to_string()",
+            operand_code(context, expr, ATOM)?
+        )),
+        // Java Strings are shared, in Rust every variable needs its own copy
+        (ExprKind::Variable { .. } | ExprKind::FieldAccess(..) | ExprKind::ArrayIndex(..), _)
+            if r#type.is_string() =>
+        {
+            convert_clone(context, expr)
+        }
+        (_, _) if is_copied_type(context, r#type) && is_place(expr) => convert_clone(context, expr),
+        // The current object is borrowed, the value is another handle of it, objects without
+        // fields have no state, so a copy is the same
+        (ExprKind::This, VariableType::Class(_) | VariableType::Generic(..)) => {
+            Ok(format!("{}.clone()", receiver(context)))
+        }
+        (ExprKind::ArrayInit(VariableType::Inferred, values), VariableType::Array(_)) => {
+            convert_array_init(context, r#type, values)
+        }
+        (ExprKind::Switch(switch), _) => convert_switch_expression(context, switch, r#type),
+        (_, _) if r#type.is_object() => convert_object(context, expr, r#type),
+        (_, VariableType::Class(name) | VariableType::Generic(name, _))
+            if is_interface(context, name) =>
+        {
+            convert_object(context, expr, r#type)
+        }
+        (_, VariableType::DataType(data_type)) => match expr.r#type() {
+            VariableType::DataType(from) if from != *data_type => {
+                Ok(convert_cast(context, *data_type, expr)?.0)
+            }
+            _ => convert_expr(context, expr),
+        },
+        _ => convert_expr(context, expr),
+    }
+}
+
+/// Converts a value which is assigned to an Object or an interface, other values are put into
+/// a new shared trait object
+fn convert_object(
+    context: &Context,
+    expr: &Expr,
+    target: &VariableType,
+) -> Result<String, ParseError> {
+    let r#type = expr.r#type();
+    let is_place = is_place(expr);
+    // Values of the interface are already trait objects, e.g. Src<Integer> for Src<? extends Integer>
+    let is_same_interface = r#type.class_name() == target.class_name()
+        && r#type
+            .class_name()
+            .is_some_and(|name| is_interface(context, name));
+    if r#type.is_object() || r#type == *target || is_same_interface {
+        return match is_place {
+            true => convert_clone(context, expr),
+            false => convert_expr(context, expr),
+        };
+    }
+    let value = match (&r#type, is_place) {
+        (VariableType::DataType(_), _) | (_, false) => convert_expr_to(context, expr, &r#type)?,
+        // Strings are copied, objects of shared classes get another handle
+        (_, true) => convert_clone(context, expr)?,
+    };
+    Ok(format!(
+        "std::rc::Rc::new({}) as {}",
+        value,
+        convert_variable_type(context, target)
+    ))
+}
+
+/// Clones a variable, field or array element, a field in a RefCell is borrowed for it
+fn convert_clone(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    require_bound(context, &expr.r#type(), "Clone");
+    // The value of an Option is already cloned to unwrap it
+    if expr.is_nullable() {
+        return convert_expr(context, expr);
+    }
+    Ok(format!("{}.clone()", convert_receiver(context, expr)?))
+}
+
+/// Converts the object of a method call or an index, a field in a RefCell is only borrowed,
+/// because Rust dereferences it for the call
+///
+/// # Example
+///
+/// items[0] -> self.items.borrow()[0]
+fn convert_receiver(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    if !is_copied_field(&expr.r#type()) && !expr.is_nullable() {
+        if let Some(field) = convert_shared_field(context, expr)? {
+            return Ok(format!("{}.borrow()", field));
+        }
+    }
+    operand_code(context, expr, ATOM)
+}
+
+/// Checks if an expression is a variable, field or array element, constants are values
+fn is_place(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Variable { scope, .. } => !matches!(scope, VariableScope::Const(_)),
+        ExprKind::FieldAccess(..) | ExprKind::ArrayIndex(..) => true,
+        _ => false,
+    }
+}
+
+/// Converts a field of an object, fields of shared objects are read from their cell
+///
+/// # Example
+///
+/// p.x -> p.x.get(), p.name -> *p.name.borrow(), when p is shared
+fn convert_field(
+    context: &Context,
+    object: &str,
+    name: &str,
+    r#type: &VariableType,
+    shared: bool,
+) -> (String, u8) {
+    let field = format!("{}.{}", object, convert_ident(context, name));
+    match shared {
+        true => convert_cell_value(field, r#type),
+        false => (field, ATOM),
+    }
+}
+
+/// Reads the value of a cell, e.g. of a field of a shared object or a static variable
+fn convert_cell_value(cell: String, r#type: &VariableType) -> (String, u8) {
+    match is_copied_field(r#type) {
+        true => (format!("{}.get()", cell), ATOM),
+        false => (format!("*{}.borrow()", cell), UNARY),
+    }
+}
+
+/// Checks if an expression is an object of a shared class, whose fields are cells
+fn is_shared_object(context: &Context, expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::This => in_shared_class(context),
+        _ => is_shared_type(context, &expr.r#type()),
+    }
+}
+
+/// The field of a shared object which an expression is, with the object, e.g. self.x, or the
+/// cell of a static variable
+fn convert_shared_field(context: &Context, expr: &Expr) -> Result<Option<String>, ParseError> {
+    Ok(match &expr.kind {
+        ExprKind::Variable {
+            name,
+            scope: VariableScope::Static(class),
+            ..
+        } => Some(convert_static_cell(context, class, name)),
+        ExprKind::Variable {
+            name,
+            scope: VariableScope::Field,
+            ..
+        } if in_shared_class(context) => Some(format!(
+            "{}.{}",
+            receiver(context),
+            convert_ident(context, name)
+        )),
+        ExprKind::FieldAccess(object, name, _) if is_shared_object(context, object) => {
+            Some(format!(
+                "{}.{}",
+                operand_code(context, object, ATOM)?,
+                convert_ident(context, name)
+            ))
+        }
+        _ => None,
+    })
+}
+
+/// Checks if an expression borrows a RefCell of a shared object or an array, which then can't
+/// be changed until the statement ends
+pub fn reads_borrowed_cell(context: &Context, expr: &Expr) -> bool {
+    let is_borrowed = match &expr.kind {
+        ExprKind::Variable {
+            scope: VariableScope::Field,
+            r#type,
+            ..
+        } => in_shared_class(context) && !is_copied_field(r#type),
+        ExprKind::Variable {
+            scope: VariableScope::Static(_),
+            r#type,
+            ..
+        } => !is_copied_field(r#type),
+        ExprKind::FieldAccess(object, name, _) => match object.r#type() {
+            VariableType::Array(_) => name == "length",
+            _ => is_shared_object(context, object) && !is_copied_field(&nullable_type(expr)),
+        },
+        ExprKind::ArrayIndex(..) => true,
+        _ => false,
+    };
+    is_borrowed
+        || expr
+            .children()
+            .into_iter()
+            .any(|expr| reads_borrowed_cell(context, expr))
+}
+
+/// The header of a loop or a match keeps its temporaries until the end, so a value which borrows
+/// a cell is computed first and the body can still change the cell
+///
+/// # Example
+///
+/// switch (a[0]) -> match { let selector = a.borrow()[0]; selector } {
+pub fn release_borrows(context: &Context, name: &str, code: String, exprs: &[&Expr]) -> String {
+    match exprs.iter().any(|expr| reads_borrowed_cell(context, expr)) {
+        true => format!("{{\nlet {0} = {1};\n{0}\n}}", name, code),
+        false => code,
+    }
+}
+
+/// Converts an expression into an argument for a method of a prelude class
+pub fn convert_function_argument(
+    context: &Context,
+    expr: &Expr,
+) -> Result<FunctionArgument, ParseError> {
+    // The methods of the Java library print their arguments
+    require_bound(context, &expr.r#type(), "std::fmt::Display");
+    Ok(match &expr.kind {
+        _ if expr.is_nullable() && !matches!(expr.kind, ExprKind::Literal(Literal::Null)) => {
+            FunctionArgument::EXPRESSION((
+                VariableType::string(),
+                convert_null_string(context, expr)?,
+            ))
+        }
+        ExprKind::Literal(Literal::String(value)) => FunctionArgument::STRING(value.clone()),
+        ExprKind::Variable {
+            name,
+            r#type,
+            scope: VariableScope::Local,
+        } => FunctionArgument::VARIABLE((r#type.clone(), name.clone())),
+        ExprKind::Binary(BinaryOp::Add, _, _) if expr.r#type().is_string() => {
+            FunctionArgument::FORMAT(convert_format_args(context, expr)?)
+        }
+        _ => match expr.r#type() {
+            VariableType::DataType(data_type) => {
+                FunctionArgument::DATATYPE((data_type, convert_expr(context, expr)?))
+            }
+            r#type => FunctionArgument::EXPRESSION((r#type, convert_expr(context, expr)?)),
+        },
+    })
+}
+
+/// Returns the code and its Rust precedence, a value which can be null is unwrapped, so null
+/// panics where Java throws a NullPointerException
+fn convert(context: &Context, expr: &Expr) -> Result<(String, u8), ParseError> {
+    match &expr.kind {
+        ExprKind::Literal(Literal::Null) => convert_value(context, expr),
+        ExprKind::Variable { .. } | ExprKind::FieldAccess(..) if expr.is_nullable() => {
+            require_bound(context, &expr.r#type(), "Clone");
+            Ok((
+                format!("{}.clone().unwrap()", convert_option(context, expr)?),
+                ATOM,
+            ))
+        }
+        _ if expr.is_nullable() => {
+            Ok((format!("{}.unwrap()", convert_option(context, expr)?), ATOM))
+        }
+        _ => convert_value(context, expr),
+    }
+}
+
+/// Converts a value which can be null into its Option, variables and fields are only borrowed
+///
+/// # Example
+///
+/// next -> self.next.borrow(), when next is a field of a shared class
+fn convert_option(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    if let Some(field) = convert_shared_field(context, expr)? {
+        return Ok(format!("{}.borrow()", field));
+    }
+    match &expr.kind {
+        ExprKind::Literal(Literal::Null) => Ok("None".to_string()),
+        ExprKind::Ternary(..) => Ok(format!(
+            "({})",
+            convert_nullable(context, expr, &nullable_type(expr))?
+        )),
+        _ => {
+            let (code, precedence) = convert_value(context, expr)?;
+            Ok(wrap(code, precedence, ATOM))
+        }
+    }
+}
+
+/// Converts a value which is assigned to a variable which can be null into an Option
+///
+/// # Example
+///
+/// null -> None, new Node(1) -> Some(Node::new(1)), next -> self.next.borrow().clone()
+fn convert_nullable(
+    context: &Context,
+    expr: &Expr,
+    r#type: &VariableType,
+) -> Result<String, ParseError> {
+    match &expr.kind {
+        ExprKind::Literal(Literal::Null) => Ok("None".to_string()),
+        ExprKind::Ternary(condition, then, otherwise) => Ok(format!(
+            "if {} {{ {} }} else {{ {} }}",
+            convert_expr(context, condition)?,
+            convert_nullable(context, then, r#type)?,
+            convert_nullable(context, otherwise, r#type)?
+        )),
+        ExprKind::MethodCall { .. } if expr.is_nullable() => convert_option(context, expr),
+        _ if expr.is_nullable() => {
+            require_bound(context, &expr.r#type(), "Clone");
+            Ok(format!("{}.clone()", convert_option(context, expr)?))
+        }
+        _ => Ok(format!(
+            "Some({})",
+            convert_expr_to(context, expr, r#type.non_null())?
+        )),
+    }
+}
+
+/// The type of a variable which gets the value, it is Nullable when the value can be null
+fn nullable_type(expr: &Expr) -> VariableType {
+    match expr.is_nullable() {
+        true => expr.r#type().nullable(),
+        false => expr.r#type(),
+    }
+}
+
+/// Converts a value which can be null into a String, null is printed like in Java
+///
+/// # Example
+///
+/// name -> name.as_ref().map_or("null".to_string(), |value| value.to_string())
+fn convert_null_string(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    let value = match expr.r#type().unboxed() {
+        VariableType::DataType(DataType::FLOAT | DataType::DOUBLE) => {
+            convert_double_to_string(context, "value")
+        }
+        _ => "value.to_string()".to_string(),
+    };
+    Ok(format!(
+        "{}.as_ref().map_or(\"null\".to_string(), |value| {})",
+        convert_option(context, expr)?,
+        value
+    ))
+}
+
+/// Compares values of which one can be null, a value which can't be null is never null
+///
+/// # Example
+///
+/// next == null -> self.next.borrow().is_none()
+fn convert_null_comparison(
+    context: &Context,
+    op: BinaryOp,
+    left: &Expr,
+    right: &Expr,
+) -> Result<(String, u8), ParseError> {
+    let is_null = |expr: &Expr| matches!(expr.kind, ExprKind::Literal(Literal::Null));
+    let is_equal = op == BinaryOp::Equal;
+    let (value, other) = match is_null(left) {
+        true => (right, left),
+        false => (left, right),
+    };
+    if !is_null(other) {
+        let code = format!(
+            "{} {} {}",
+            convert_identity(context, value)?,
+            binary_operator(op),
+            convert_identity(context, other)?
+        );
+        return Ok((code, binary_precedence(op)));
+    }
+    Ok(match (is_null(value), value.is_nullable()) {
+        (true, _) => (is_equal.to_string(), ATOM),
+        (false, true) => {
+            let method = if is_equal { "is_none" } else { "is_some" };
+            (
+                format!("{}.{}()", convert_option(context, value)?, method),
+                ATOM,
+            )
+        }
+        (false, false) if is_place(value) || matches!(value.kind, ExprKind::This) => {
+            ((!is_equal).to_string(), ATOM)
+        }
+        // The value is still computed
+        (false, false) => (
+            format!(
+                "{{ let _ = {}; {} }}",
+                convert_expr(context, value)?,
+                !is_equal
+            ),
+            ATOM,
+        ),
+    })
+}
+
+/// The Option which == compares for a value which can be null, objects are compared by their
+/// address, because Java compares their identity
+///
+/// # Example
+///
+/// next -> self.next.borrow().as_ref().map(|value| std::rc::Rc::as_ptr(&value.0).cast::<()>())
+fn convert_identity(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    let option = match expr.is_nullable() {
+        true => format!("{}.as_ref()", convert_option(context, expr)?),
+        false => format!("Some(&{})", convert_expr_to(context, expr, &expr.r#type())?),
+    };
+    let address = match is_shared_object(context, expr) {
+        true => "std::rc::Rc::as_ptr(&value.0)",
+        false if is_shared_handle(context, &expr.r#type()) => "std::rc::Rc::as_ptr(value)",
+        false => return Ok(option),
+    };
+    Ok(format!("{}.map(|value| {}.cast::<()>())", option, address))
+}
+
+/// Converts an expression whose value can't be null
+fn convert_value(context: &Context, expr: &Expr) -> Result<(String, u8), ParseError> {
+    let converted = match &expr.kind {
+        ExprKind::Literal(literal) => (convert_literal(literal, expr.span)?, ATOM),
+        ExprKind::Variable { name, scope, .. } => match scope {
+            VariableScope::Local => (convert_ident(context, name), ATOM),
+            VariableScope::Field => convert_field(
+                context,
+                receiver(context),
+                name,
+                &expr.r#type(),
+                in_shared_class(context),
+            ),
+            VariableScope::Const(class) => (
+                format!(
+                    "{}::{}",
+                    convert_type_path(context, class),
+                    convert_ident(context, name)
+                ),
+                ATOM,
+            ),
+            VariableScope::Static(class) => {
+                convert_cell_value(convert_static_cell(context, class, name), &expr.r#type())
+            }
+        },
+        ExprKind::Class(name) => (convert_type_path(context, name), ATOM),
+        ExprKind::This => (receiver(context).to_string(), ATOM),
+        ExprKind::Unary(op, operand) => convert_unary(context, *op, operand)?,
+        ExprKind::Binary(BinaryOp::Add, _, _) if expr.r#type().is_string() => (
+            format!("format!({})", convert_format_args(context, expr)?),
+            ATOM,
+        ),
+        // The variables of a pattern are only visible in the closure which gets the value
+        ExprKind::Binary(BinaryOp::And, _, _) if !expr.bindings_when_true().is_empty() => {
+            convert_pattern_chain(context, BinaryOp::And, &flatten(expr, BinaryOp::And))?
+        }
+        ExprKind::Binary(BinaryOp::Or, _, _) if !expr.bindings_when_false().is_empty() => {
+            convert_pattern_chain(context, BinaryOp::Or, &flatten(expr, BinaryOp::Or))?
+        }
+        ExprKind::Binary(op, left, right) => convert_binary(context, *op, left, right)?,
+        ExprKind::Ternary(condition, then, otherwise) => {
+            let r#type = expr.r#type();
+            (
+                format!(
+                    "if {} {{ {} }} else {{ {} }}",
+                    convert_expr(context, condition)?,
+                    convert_expr_to(context, then, &r#type)?,
+                    convert_expr_to(context, otherwise, &r#type)?
+                ),
+                ASSIGN,
+            )
+        }
+        ExprKind::Assign(op, target, value) => {
+            (convert_assign(context, *op, target, value)?, ASSIGN)
+        }
+        ExprKind::Cast(r#type, operand) if operand.r#type().is_object() && !r#type.is_object() => {
+            (convert_downcast(context, r#type, operand, expr.span)?, ATOM)
+        }
+        ExprKind::Cast(VariableType::DataType(data_type), operand) => {
+            convert_cast(context, *data_type, operand)?
+        }
+        // Casts to a super type, the value becomes a trait object for Object and interfaces
+        ExprKind::Cast(r#type, operand) => match r#type.class_name() {
+            Some(name) if r#type.is_object() || is_interface(context, name) => {
+                (convert_expr_to(context, operand, r#type)?, CAST)
+            }
+            _ => convert(context, operand)?,
+        },
+        // The components of a record can have their own checks
+        ExprKind::InstanceOf(operand, pattern @ Pattern::Record(..)) => {
+            let (_, downcast) = convert_pattern(context, operand, pattern)?;
+            (format!("{}.is_some()", downcast), ATOM)
+        }
+        ExprKind::InstanceOf(operand, pattern) => (
+            format!(
+                "{}.is::<{}>()",
+                convert_any(context, operand)?,
+                convert_variable_type(context, pattern.r#type())
+            ),
+            ATOM,
+        ),
+        ExprKind::MethodCall {
+            target,
+            name,
+            args,
+            params,
+            throws,
+            overload,
+            ..
+        } => {
+            let name = overload.as_ref().unwrap_or(name);
+            let (call, precedence) =
+                convert_method_call(context, expr, target.as_deref(), name, args, params)?;
+            match (throws, in_try_block(context)) {
+                (false, _) => (call, precedence),
+                // A match has to be put into braces when it is an operand
+                (true, true) => (
+                    convert_throwing_call(context, &wrap(call, precedence, ATOM)),
+                    ASSIGN,
+                ),
+                (true, false) => (
+                    convert_throwing_call(context, &wrap(call, precedence, ATOM)),
+                    ATOM,
+                ),
+            }
+        }
+        ExprKind::FieldAccess(target, name, _) => match target.r#type() {
+            VariableType::Array(_) if name == "length" => (
+                format!(
+                    "{}.borrow().len() as i32",
+                    convert_receiver(context, target)?
+                ),
+                CAST,
+            ),
+            // Constants of classes and interfaces of the file
+            _ if matches!(&target.kind, ExprKind::Class(class) if get_prelude_class(class).is_none()) => {
+                (
+                    format!(
+                        "{}::{}",
+                        operand_code(context, target, ATOM)?,
+                        convert_ident(context, name)
+                    ),
+                    ATOM,
+                )
+            }
+            _ => convert_field(
+                context,
+                &operand_code(context, target, ATOM)?,
+                name,
+                &expr.r#type(),
+                is_shared_object(context, target),
+            ),
+        },
+        ExprKind::ArrayIndex(array, index) => (
+            format!(
+                "{}.borrow()[{}]",
+                convert_receiver(context, array)?,
+                convert_index(context, index)?
+            ),
+            ATOM,
+        ),
+        ExprKind::New {
+            r#type: VariableType::Class(class),
+            args,
+            ..
+        } if is_exception(context, class) => (
+            convert_new_exception_expr(context, expr, class, args)?,
+            ATOM,
+        ),
+        ExprKind::New {
+            r#type,
+            args,
+            params,
+            overload,
+            throws,
+        } => {
+            // The constructors of other classes are not known, their arguments are passed like
+            // to a method which takes their types, so are the ones of raw types like new Box<>()
+            let params: Vec<VariableType> = match params.len() == args.len() || params.is_empty() {
+                true => args
+                    .iter()
+                    .enumerate()
+                    .map(|(i, arg)| match params.get(i) {
+                        None | Some(VariableType::Inferred) => arg.r#type(),
+                        Some(param) => param.clone(),
+                    })
+                    .collect(),
+                false => params.clone(),
+            };
+            let args = convert_args(context, args, &params)?;
+            let r#type = match r#type {
+                // Box::<String>::new, in expressions type arguments need ::
+                VariableType::Generic(name, type_args) => format!(
+                    "{}::<{}>",
+                    convert_variable_type(context, &VariableType::Class(name.clone())),
+                    type_args
+                        .iter()
+                        .map(|r#type| convert_variable_type(context, r#type))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                r#type => convert_variable_type(context, r#type),
+            };
+            let call = format!(
+                "{}::{}({})",
+                r#type,
+                overload.as_deref().unwrap_or("new"),
+                args
+            );
+            match (
+                r#type.as_str(),
+                args.is_empty(),
+                throws,
+                in_try_block(context),
+            ) {
+                ("String", false, ..) => (format!("String::from({})", args), ATOM),
+                (_, _, false, _) => (call, ATOM),
+                // A match has to be put into braces when it is an operand
+                (_, _, true, true) => (convert_throwing_call(context, &call), ASSIGN),
+                (_, _, true, false) => (convert_throwing_call(context, &call), ATOM),
+            }
+        }
+        ExprKind::NewArray(r#type, dimensions) => {
+            (convert_new_array(context, r#type, dimensions)?, ATOM)
+        }
+        ExprKind::ArrayInit(r#type, values) => (convert_array_init(context, r#type, values)?, ATOM),
+        ExprKind::Switch(switch) => (
+            convert_switch_expression(context, switch, &switch.r#type())?,
+            ASSIGN,
+        ),
+        ExprKind::Lambda(lambda) => match lambda.r#type {
+            VariableType::Inferred => (convert_lambda(context, lambda)?, ASSIGN),
+            _ => (convert_lambda(context, lambda)?, CAST),
+        },
+    };
+    Ok(converted)
+}
+
+/// The operands of a chain of && or || without braces
+///
+/// # Example
+///
+/// a && (b && c) -> [a, b, c]
+pub fn flatten(expr: &Expr, op: BinaryOp) -> Vec<&Expr> {
+    match &expr.kind {
+        ExprKind::Binary(inner, left, right) if *inner == op => {
+            let mut operands = flatten(left, op);
+            operands.extend(flatten(right, op));
+            operands
+        }
+        _ => vec![expr],
+    }
+}
+
+/// Converts the operands of an && chain, or of an || chain for negated patterns
+///
+/// # Example
+///
+/// o instanceof Point p && p.x > 0 -> o.downcast_ref::<Point>().is_some_and(|p| p.x > 0)
+///
+/// !(o instanceof Point p) || p.x > 0 -> o.downcast_ref::<Point>().is_none_or(|p| p.x > 0)
+fn convert_pattern_chain(
+    context: &Context,
+    op: BinaryOp,
+    operands: &[&Expr],
+) -> Result<(String, u8), ParseError> {
+    let (first, rest) = operands.split_first().unwrap();
+    if rest.is_empty() {
+        return convert(context, first);
+    }
+    // For || only a negated pattern declares variables for the operands after it
+    let checked = match (op, &first.kind) {
+        (BinaryOp::And, _) => Some(*first),
+        (BinaryOp::Or, ExprKind::Unary(UnaryOp::Not, operand)) => Some(&**operand),
+        _ => None,
+    };
+    if let Some(Expr {
+        kind:
+            ExprKind::InstanceOf(operand, pattern @ (Pattern::Type(_, Some(_)) | Pattern::Record(..))),
+        ..
+    }) = checked
+    {
+        let (binding, downcast) = convert_pattern(context, operand, pattern)?;
+        let method = match op {
+            BinaryOp::And => "is_some_and",
+            _ => "is_none_or",
+        };
+        let (rest, _) = convert_pattern_chain(context, op, rest)?;
+        return Ok((
+            format!("{}.{}(|{}| {})", downcast, method, binding, rest),
+            ATOM,
+        ));
+    }
+    let precedence = binary_precedence(op);
+    let (rest, rest_precedence) = convert_pattern_chain(context, op, rest)?;
+    let code = format!(
+        "{} {} {}",
+        operand_code(context, first, precedence)?,
+        binary_operator(op),
+        wrap(rest, rest_precedence, precedence)
+    );
+    Ok((code, precedence))
+}
+
+/// Converts the check of a pattern, returns the Rust pattern for the matched value and the
+/// code which gives Some(value) when the pattern matches
+///
+/// Primitives are copied out of the object, all other values are borrowed. A record pattern
+/// gives a tuple with the values of its components
+///
+/// # Example
+///
+/// o instanceof Point p -> ("p", "o.downcast_ref::<Point>()")
+///
+/// o instanceof Point(int x, var y) -> ("(x, y)", "o.downcast_ref::<Point>().map(|record| (record.x(), record.y()))")
+pub fn convert_pattern(
+    context: &Context,
+    operand: &Expr,
+    pattern: &Pattern,
+) -> Result<(String, String), ParseError> {
+    let downcast = format!(
+        "{}.downcast_ref::<{}>()",
+        convert_any(context, operand)?,
+        convert_variable_type(context, pattern.r#type())
+    );
+    match pattern {
+        Pattern::Type(r#type, name) => {
+            let downcast = match r#type.unboxed() {
+                VariableType::DataType(_) => downcast + ".copied()",
+                _ => downcast,
+            };
+            Ok((convert_binding(context, name), downcast))
+        }
+        Pattern::Record(_, components) => {
+            let (binding, values, checked) = convert_record_components(context, components);
+            let downcast = match checked {
+                true => format!("{}.and_then(|record| Some({}))", downcast, values),
+                false => format!("{}.map(|record| {})", downcast, values),
+            };
+            Ok((binding, downcast))
+        }
+    }
+}
+
+fn convert_binding(context: &Context, name: &Option<String>) -> String {
+    match name {
+        Some(name) => convert_ident(context, name),
+        None => "_".to_string(),
+    }
+}
+
+/// The tuple pattern and the tuple of values of the components of the record in `record`,
+/// the bool is true when a component is checked with `?`
+fn convert_record_components(
+    context: &Context,
+    components: &[RecordComponent],
+) -> (String, String, bool) {
+    let mut bindings = Vec::new();
+    let mut values = Vec::new();
+    let mut checked = false;
+    for component in components {
+        let value = format!("record.{}()", convert_ident(context, &component.name));
+        let is_declared = component.pattern.r#type().unboxed() == component.r#type.unboxed();
+        match &component.pattern {
+            Pattern::Type(_, name) if is_declared => {
+                bindings.push(convert_binding(context, name));
+                values.push(value);
+            }
+            // The component has a more general type, e.g. Object
+            Pattern::Type(r#type, name) => {
+                checked = true;
+                bindings.push(convert_binding(context, name));
+                values.push(format!(
+                    "{}.downcast_ref::<{}>().cloned()?",
+                    any_code(value, &component.r#type),
+                    convert_variable_type(context, r#type)
+                ));
+            }
+            Pattern::Record(r#type, nested) => {
+                let (binding, tuple, nested_checked) = convert_record_components(context, nested);
+                let record = match is_declared {
+                    true => format!("let record = {};", value),
+                    false => {
+                        checked = true;
+                        format!(
+                            "let component = {};\nlet record = {}.downcast_ref::<{}>()?;",
+                            value,
+                            any_code("component".to_string(), &component.r#type),
+                            convert_variable_type(context, r#type)
+                        )
+                    }
+                };
+                checked |= nested_checked;
+                bindings.push(binding);
+                values.push(format!("{{\n{}\n{}\n}}", record, tuple));
+            }
+        }
+    }
+    // A tuple with one element needs a trailing comma
+    let tuple = |items: Vec<String>| match items.len() {
+        1 => format!("({},)", items[0]),
+        _ => format!("({})", items.join(", ")),
+    };
+    (tuple(bindings), tuple(values), checked)
+}
+
+/// The value as `dyn Any`, which can be checked for its type
+///
+/// # Example
+///
+/// o -> o, when o is an Object
+///
+/// s -> (&s as &dyn std::any::Any), when s is a String
+fn convert_any(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    match expr.r#type().is_object() {
+        true => operand_code(context, expr, ATOM),
+        false => Ok(any_code(
+            operand_code(context, expr, UNARY)?,
+            &expr.r#type(),
+        )),
+    }
+}
+
+/// The converted value as `dyn Any`, the code binds at least as strong as a unary operator
+fn any_code(code: String, r#type: &VariableType) -> String {
+    match r#type.is_object() {
+        true => code,
+        false => format!("(&{} as &dyn std::any::Any)", code),
+    }
+}
+
+/// Converts an operand and wraps it in braces, when it binds weaker than min_precedence
+fn operand_code(context: &Context, expr: &Expr, min_precedence: u8) -> Result<String, ParseError> {
+    let (code, precedence) = convert(context, expr)?;
+    Ok(wrap(code, precedence, min_precedence))
+}
+
+fn wrap(code: String, precedence: u8, min_precedence: u8) -> String {
+    if precedence < min_precedence {
+        format!("({})", code)
+    } else {
+        code
+    }
+}
+
+fn convert_literal(literal: &Literal, span: Span) -> Result<String, ParseError> {
+    Ok(match literal {
+        Literal::Number(_, raw) => {
+            convert_number_literal(raw, false).map_err(|error| ParseError::new(span, error))?
+        }
+        Literal::String(value) => convert_string_literal(value),
+        Literal::Char(value) => convert_char_literal(*value),
+        Literal::Bool(value) => value.to_string(),
+        Literal::Null => {
+            return Err(ParseError::new(
+                span,
+                "Unsupported null: only fields, variables which start with a value that can be \
+                null, and arguments and return values which are compared with null can be null",
+            ))
+        }
+    })
+}
+
+fn convert_unary(
+    context: &Context,
+    op: UnaryOp,
+    operand: &Expr,
+) -> Result<(String, u8), ParseError> {
+    let target = || operand_code(context, operand, ATOM);
+    Ok(match op {
+        UnaryOp::Minus => match &operand.kind {
+            // e.g. -2147483648 is only in range when its negated
+            ExprKind::Literal(Literal::Number(_, raw)) => {
+                let code = convert_number_literal(raw, true)
+                    .map_err(|error| ParseError::new(operand.span, error))?;
+                (format!("-{}", code), UNARY)
+            }
+            // -Integer.MIN_VALUE is Integer.MIN_VALUE in Java
+            _ => match operand.r#type().unboxed() {
+                VariableType::DataType(DataType::LONG) => (
+                    format!("i64::wrapping_neg({})", convert_expr(context, operand)?),
+                    ATOM,
+                ),
+                VariableType::DataType(DataType::FLOAT | DataType::DOUBLE) => (
+                    format!("-{}", operand_code(context, operand, UNARY)?),
+                    UNARY,
+                ),
+                _ => (
+                    format!(
+                        "i32::wrapping_neg({})",
+                        promoted_operand(context, operand, ASSIGN)?
+                    ),
+                    ATOM,
+                ),
+            },
+        },
+        UnaryOp::Plus => promoted(context, operand)?,
+        // ! is also the bitwise not in Rust
+        UnaryOp::Not | UnaryOp::BitNot => (
+            format!("!{}", promoted_operand(context, operand, UNARY)?),
+            UNARY,
+        ),
+        UnaryOp::PreIncrement | UnaryOp::PreDecrement => {
+            let update = convert_increment(context, op, operand)?;
+            (format!("{{ {}; {} }}", update, target()?), ATOM)
+        }
+        UnaryOp::PostIncrement | UnaryOp::PostDecrement => {
+            let update = convert_increment(context, op, operand)?;
+            (
+                format!("{{ let old = {}; {}; old }}", target()?, update),
+                ATOM,
+            )
+        }
+    })
+}
+
+/// i++ is i += 1
+fn convert_increment(context: &Context, op: UnaryOp, operand: &Expr) -> Result<String, ParseError> {
+    let op = increment_op(op).expect("an increment or decrement");
+    convert_assign_code(context, Some(op), operand, &increment_step(operand))
+}
+
+/// The operation which an increment or decrement applies
+fn increment_op(op: UnaryOp) -> Option<BinaryOp> {
+    match op {
+        UnaryOp::PreIncrement | UnaryOp::PostIncrement => Some(BinaryOp::Add),
+        UnaryOp::PreDecrement | UnaryOp::PostDecrement => Some(BinaryOp::Sub),
+        _ => None,
+    }
+}
+
+/// The 1 which an increment adds, it has the type of the operand, e.g. 1.0 for a double
+fn increment_step(operand: &Expr) -> Expr {
+    let (data_type, raw) = match operand.r#type().unboxed() {
+        VariableType::DataType(DataType::LONG) => (DataType::LONG, "1L"),
+        VariableType::DataType(DataType::FLOAT) => (DataType::FLOAT, "1.0f"),
+        VariableType::DataType(DataType::DOUBLE) => (DataType::DOUBLE, "1.0"),
+        _ => (DataType::INT, "1"),
+    };
+    Expr {
+        kind: ExprKind::Literal(Literal::Number(data_type, raw.to_string())),
+        span: operand.span,
+    }
+}
+
+/// byte, short and char operands become int, like in Java
+fn promoted(context: &Context, operand: &Expr) -> Result<(String, u8), ParseError> {
+    match operand.r#type() {
+        VariableType::DataType(DataType::BYTE | DataType::SHORT | DataType::CHAR) => {
+            convert_cast(context, DataType::INT, operand)
+        }
+        _ => convert(context, operand),
+    }
+}
+
+fn promoted_operand(
+    context: &Context,
+    operand: &Expr,
+    min_precedence: u8,
+) -> Result<String, ParseError> {
+    let (code, precedence) = promoted(context, operand)?;
+    Ok(wrap(code, precedence, min_precedence))
+}
+
+fn convert_binary(
+    context: &Context,
+    op: BinaryOp,
+    left: &Expr,
+    right: &Expr,
+) -> Result<(String, u8), ParseError> {
+    if matches!(op, BinaryOp::Equal | BinaryOp::NotEqual)
+        && (left.is_nullable() || right.is_nullable())
+    {
+        return convert_null_comparison(context, op, left, right);
+    }
+    let precedence = binary_precedence(op);
+    // Comparisons can't be chained in Rust, so both sides need braces
+    let left_precedence = if op.is_comparison() {
+        precedence + 1
+    } else {
+        precedence
+    };
+
+    if op.is_shift() {
+        let r#type = match left.r#type() {
+            VariableType::DataType(DataType::LONG) => DataType::LONG,
+            _ => DataType::INT,
+        };
+        let right_code = convert_shift_distance(context, right)?;
+        let method = wrapping_method(op).expect("a shift");
+        if op == BinaryOp::UnsignedShiftRight {
+            let left_code = converted_operand(context, left, r#type, CAST)?;
+            let unsigned = match r#type {
+                DataType::LONG => "u64",
+                _ => "u32",
+            };
+            // -16 as u32 would make the literal itself unsigned
+            let left_code = if is_plain_literal(left) {
+                format!("{}{}", left_code, convert_data_type(&r#type))
+            } else {
+                left_code
+            };
+            let code = format!(
+                "{}::{}({} as {}, {}) as {}",
+                unsigned,
+                method,
+                left_code,
+                unsigned,
+                right_code,
+                convert_data_type(&r#type)
+            );
+            return Ok((code, CAST));
+        }
+        let code = format!(
+            "{}::{}({}, {})",
+            convert_data_type(&r#type),
+            method,
+            converted_operand(context, left, r#type, ASSIGN)?,
+            right_code
+        );
+        return Ok((code, ATOM));
+    }
+
+    // == compares the identity of shared objects, like Java does for all objects
+    if matches!(op, BinaryOp::Equal | BinaryOp::NotEqual)
+        && is_shared_object(context, left)
+        && is_shared_object(context, right)
+    {
+        let code = format!(
+            "{}std::rc::Rc::ptr_eq(&{}.0, &{}.0)",
+            if op == BinaryOp::NotEqual { "!" } else { "" },
+            operand_code(context, left, ATOM)?,
+            operand_code(context, right, ATOM)?
+        );
+        return Ok((code, UNARY));
+    }
+    // Objects, interfaces, lambdas and arrays are already an Rc
+    if matches!(op, BinaryOp::Equal | BinaryOp::NotEqual)
+        && is_shared_handle(context, &left.r#type())
+        && is_shared_handle(context, &right.r#type())
+    {
+        let code = format!(
+            "{}std::rc::Rc::ptr_eq(&{}, &{})",
+            if op == BinaryOp::NotEqual { "!" } else { "" },
+            operand_code(context, left, ATOM)?,
+            operand_code(context, right, ATOM)?
+        );
+        return Ok((code, UNARY));
+    }
+    let (left_type, right_type) = (left.r#type(), right.r#type());
+    let is_numeric = |r#type: &VariableType| matches!(r#type, VariableType::DataType(data_type) if *data_type != DataType::BOOLEAN);
+    let (left_code, right_code) = if is_numeric(&left_type) && is_numeric(&right_type) {
+        let VariableType::DataType(r#type) = binary_promotion(left_type, right_type) else {
+            unreachable!()
+        };
+        if is_wrapping(op, &VariableType::DataType(r#type)) {
+            let code = format!(
+                "{}::{}({}, {})",
+                convert_data_type(&r#type),
+                wrapping_method(op).unwrap(),
+                converted_operand(context, left, r#type, ASSIGN)?,
+                converted_operand(context, right, r#type, ASSIGN)?
+            );
+            return Ok((code, ATOM));
+        }
+        (
+            converted_operand(context, left, r#type, left_precedence)?,
+            converted_operand(context, right, r#type, precedence + 1)?,
+        )
+    } else {
+        (
+            compared_operand(context, op, left, left_precedence)?,
+            compared_operand(context, op, right, precedence + 1)?,
+        )
+    };
+    let code = format!("{} {} {}", left_code, binary_operator(op), right_code);
+    Ok((code, precedence))
+}
+
+/// Checks if values of the type are an Rc, whose identity == compares
+fn is_shared_handle(context: &Context, r#type: &VariableType) -> bool {
+    match r#type {
+        VariableType::Array(_) => true,
+        VariableType::Class(name) | VariableType::Generic(name, _) => {
+            r#type.is_object()
+                || (get_type_param(context, name).is_none() && is_interface(context, name))
+                || get_functional_method(context, r#type).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// self is a reference, so it is dereferenced to be compared with a value, e.g. an enum constant
+fn compared_operand(
+    context: &Context,
+    op: BinaryOp,
+    operand: &Expr,
+    min_precedence: u8,
+) -> Result<String, ParseError> {
+    match operand.kind {
+        ExprKind::This if receiver(context) == "self" && op.is_comparison() => {
+            Ok("*self".to_string())
+        }
+        _ => operand_code(context, operand, min_precedence),
+    }
+}
+
+/// The distance of a shift as u32, the wrapping shifts only use its lowest bits like Java
+///
+/// # Example
+///
+/// 33 -> 33, n -> n as u32
+fn convert_shift_distance(context: &Context, distance: &Expr) -> Result<String, ParseError> {
+    match &distance.kind {
+        ExprKind::Literal(Literal::Number(DataType::INT, _)) if is_plain_literal(distance) => {
+            convert_expr(context, distance)
+        }
+        // -1 as u32 would make the literal itself unsigned
+        _ if is_plain_literal(distance) => {
+            Ok(format!("{}i32 as u32", convert_expr(context, distance)?))
+        }
+        _ => Ok(format!("{} as u32", operand_code(context, distance, CAST)?)),
+    }
+}
+
+/// Converts an operand to the given type, when it has an other numeric type
+fn converted_operand(
+    context: &Context,
+    operand: &Expr,
+    r#type: DataType,
+    min_precedence: u8,
+) -> Result<String, ParseError> {
+    match operand.r#type() {
+        VariableType::DataType(from) if from != r#type => {
+            let (code, precedence) = convert_cast(context, r#type, operand)?;
+            Ok(wrap(code, precedence, min_precedence))
+        }
+        _ => operand_code(context, operand, min_precedence),
+    }
+}
+
+/// A cast of an Object checks the type of the value like instanceof, Java throws a
+/// ClassCastException when it has another type
+///
+/// # Example
+///
+/// (String) o -> o.downcast_ref::<String>().cloned().unwrap_or_else(|| panic!(..))
+fn convert_downcast(
+    context: &Context,
+    r#type: &VariableType,
+    operand: &Expr,
+    span: Span,
+) -> Result<String, ParseError> {
+    if r#type
+        .class_name()
+        .is_some_and(|name| get_type_param(context, name).is_some())
+    {
+        return Err(ParseError::new(
+            span,
+            "Invalid cast: a cast of an Object to a type parameter can't be checked",
+        ));
+    }
+    let rust_type = convert_variable_type(context, r#type);
+    Ok(format!(
+        "{}.downcast_ref::<{}>().cloned().unwrap_or_else(|| panic!(\"java.lang.ClassCastException: the value is no {}\"))",
+        convert_any(context, operand)?,
+        rust_type,
+        r#type.class_name().unwrap_or(&rust_type)
+    ))
+}
+
+/// Casts between primitive types, Rust `as` behaves like Java for most of them
+fn convert_cast(
+    context: &Context,
+    r#type: DataType,
+    operand: &Expr,
+) -> Result<(String, u8), ParseError> {
+    let from = match operand.r#type() {
+        VariableType::DataType(from) => Some(from),
+        _ => None,
+    };
+    if from == Some(r#type) || r#type == DataType::BOOLEAN {
+        return convert(context, operand);
+    }
+    let rust_type = convert_data_type(&r#type);
+    match (from, r#type) {
+        // Only u8 can be casted to char in Rust
+        (_, DataType::CHAR) => Ok((
+            format!(
+                "char::from_u32({} as u32 & 0xFFFF).unwrap_or(char::REPLACEMENT_CHARACTER)",
+                operand_code(context, operand, CAST)?
+            ),
+            ATOM,
+        )),
+        // An int literal gets the suffix of the type, e.g. 5 -> 5i64
+        (Some(DataType::INT), DataType::LONG) if is_plain_literal(operand) => {
+            Ok((format!("{}i64", convert_expr(context, operand)?), ATOM))
+        }
+        // Without a suffix Rust would infer the target type for the literal, e.g. -16 as u32
+        (Some(DataType::INT), _) if is_plain_literal(operand) => Ok((
+            format!("{}i32 as {}", convert_expr(context, operand)?, rust_type),
+            CAST,
+        )),
+        (Some(DataType::CHAR), DataType::FLOAT | DataType::DOUBLE) => Ok((
+            format!(
+                "{} as u32 as {}",
+                operand_code(context, operand, CAST)?,
+                rust_type
+            ),
+            CAST,
+        )),
+        _ => Ok((
+            format!("{} as {}", operand_code(context, operand, CAST)?, rust_type),
+            CAST,
+        )),
+    }
+}
+
+/// A number literal which is converted without braces or suffix
+fn is_plain_literal(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Literal(Literal::Number(DataType::INT, raw)) => {
+            convert_number_literal(raw, false).is_ok_and(|code| !code.starts_with('('))
+        }
+        ExprKind::Unary(UnaryOp::Minus, operand) => {
+            matches!(operand.kind, ExprKind::Literal(_)) && is_plain_literal(operand)
+        }
+        _ => false,
+    }
+}
+
+fn convert_assign(
+    context: &Context,
+    op: Option<BinaryOp>,
+    target: &Expr,
+    value: &Expr,
+) -> Result<String, ParseError> {
+    // Java evaluates the index before the value, Rust assigns to the element after evaluating
+    // the value, so an index which the value could change is evaluated first
+    if let ExprKind::ArrayIndex(array, index) = &target.kind {
+        if !matches!(index.kind, ExprKind::Literal(_))
+            && (has_side_effects(value) || has_side_effects(index))
+        {
+            let name = generate_ident(context, "index");
+            let bound_index = Expr {
+                kind: ExprKind::Variable {
+                    name: name.clone(),
+                    r#type: VariableType::DataType(DataType::INT),
+                    scope: VariableScope::Local,
+                },
+                span: index.span,
+            };
+            let target = Expr {
+                kind: ExprKind::ArrayIndex(array.clone(), Box::new(bound_index)),
+                span: target.span,
+            };
+            return Ok(format!(
+                "{{\nlet {} = {};\n{};\n}}",
+                convert_ident(context, &name),
+                convert_expr(context, index)?,
+                convert_assign_code(context, op, &target, value)?
+            ));
+        }
+    }
+    convert_assign_code(context, op, target, value)
+}
+
+/// Checks if evaluating an expression changes a variable, e.g. i++ or a = b
+fn has_side_effects(expr: &Expr) -> bool {
+    match expr.kind {
+        ExprKind::Assign(..)
+        | ExprKind::Unary(
+            UnaryOp::PreIncrement
+            | UnaryOp::PreDecrement
+            | UnaryOp::PostIncrement
+            | UnaryOp::PostDecrement,
+            _,
+        ) => true,
+        _ => expr.children().into_iter().any(has_side_effects),
+    }
+}
+
+/// Checks if x op= y needs to be x = (T)(x op y), because x op y has an other type than x
+///
+/// # Example
+///
+/// int x; x *= 1.5; -> x = (x as f64 * 1.5) as i32;
+fn is_narrowing_assign(op: BinaryOp, target_type: &VariableType, value_type: VariableType) -> bool {
+    match target_type {
+        VariableType::DataType(DataType::BYTE | DataType::SHORT | DataType::CHAR) => true,
+        VariableType::DataType(DataType::BOOLEAN) => false,
+        VariableType::DataType(_) if op.is_shift() => false,
+        VariableType::DataType(_) => {
+            matches!(value_type, VariableType::DataType(value) if value != DataType::BOOLEAN)
+                && binary_promotion(target_type.clone(), value_type) != *target_type
+        }
+        _ => false,
+    }
+}
+
+fn convert_assign_code(
+    context: &Context,
+    op: Option<BinaryOp>,
+    target: &Expr,
+    value: &Expr,
+) -> Result<String, ParseError> {
+    // x op= y is x = Some(x op y), when x can be null
+    if let (Some(op), true) = (op, target.is_nullable()) {
+        let operation = Expr {
+            kind: ExprKind::Binary(op, Box::new(target.clone()), Box::new(value.clone())),
+            span: target.span.to(value.span),
+        };
+        return convert_assign_code(context, None, target, &operation);
+    }
+    let target_type = nullable_type(target);
+    // A Cell gets the new value, x op= y is x = x op y
+    if is_copied_field(&target_type) {
+        if let Some(field) = convert_shared_field(context, target)? {
+            let value = match op {
+                None => convert_expr_to(context, value, &target_type)?,
+                Some(op) => convert_operation(context, op, target, value)?,
+            };
+            return Ok(format!("{}.set({})", field, value));
+        }
+    }
+    let target_code = convert_place(context, target)?;
+    let (operator, value_code, reads_target) = match op {
+        None => (
+            "=".to_string(),
+            convert_expr_to(context, value, &target_type)?,
+            false,
+        ),
+        // a += b is a = a + b, which is a format! for strings
+        Some(BinaryOp::Add) if target_type.is_string() => {
+            let concat = Expr {
+                kind: ExprKind::Binary(
+                    BinaryOp::Add,
+                    Box::new(target.clone()),
+                    Box::new(value.clone()),
+                ),
+                span: target.span.to(value.span),
+            };
+            ("=".to_string(), convert_expr(context, &concat)?, true)
+        }
+        // Java reads x before it evaluates y, Rust evaluates y first for x op= y. Operations
+        // which can overflow use the wrapping methods
+        Some(op)
+            if is_wrapping(op, &target_type)
+                || is_narrowing_assign(op, &target_type, value.r#type())
+                || (has_side_effects(value)
+                    && matches!(target_type, VariableType::DataType(_))) =>
+        {
+            (
+                "=".to_string(),
+                convert_operation(context, op, target, value)?,
+                true,
+            )
+        }
+        Some(op) => {
+            let value = match op.is_shift() {
+                true => operand_code(context, value, ASSIGN)?,
+                false => convert_expr_to(context, value, &target_type)?,
+            };
+            (format!("{}=", binary_operator(op)), value, false)
+        }
+    };
+    // The RefCell of the target can't be borrowed for the value while it is changed, so the
+    // value is computed first
+    if convert_borrowed_place(context, target)?.is_some()
+        && (reads_target || reads_borrowed_cell(context, value))
+    {
+        let name = convert_ident(context, &generate_ident(context, "value"));
+        return Ok(format!(
+            "{{\nlet {} = {};\n{} {} {};\n}}",
+            name, value_code, target_code, operator, name
+        ));
+    }
+    Ok(format!("{} {} {}", target_code, operator, value_code))
+}
+
+/// x op y with the type of x, for x op= y
+fn convert_operation(
+    context: &Context,
+    op: BinaryOp,
+    target: &Expr,
+    value: &Expr,
+) -> Result<String, ParseError> {
+    let operation = Expr {
+        kind: ExprKind::Binary(op, Box::new(target.clone()), Box::new(value.clone())),
+        span: target.span.to(value.span),
+    };
+    match target.r#type().unboxed() {
+        VariableType::DataType(data_type) => Ok(convert_cast(context, data_type, &operation)?.0),
+        _ => convert_expr(context, &operation),
+    }
+}
+
+/// Converts an expression which gets a new value
+fn convert_place(context: &Context, target: &Expr) -> Result<String, ParseError> {
+    match convert_borrowed_place(context, target)? {
+        Some(place) if matches!(target.kind, ExprKind::ArrayIndex(..)) => Ok(place),
+        Some(place) => Ok(format!("*{}", place)),
+        None if target.is_nullable() => convert_option(context, target),
+        None => operand_code(context, target, ATOM),
+    }
+}
+
+/// A RefCell field of a shared object or an element of an array, borrowed to change it
+///
+/// # Example
+///
+/// items[i] -> self.items.borrow().borrow_mut()[i as usize]
+fn convert_borrowed_place(context: &Context, target: &Expr) -> Result<Option<String>, ParseError> {
+    match &target.kind {
+        ExprKind::ArrayIndex(array, index) => Ok(Some(format!(
+            "{}.borrow_mut()[{}]",
+            convert_receiver(context, array)?,
+            convert_index(context, index)?
+        ))),
+        _ if is_copied_field(&nullable_type(target)) => Ok(None),
+        _ => {
+            Ok(convert_shared_field(context, target)?
+                .map(|field| format!("{}.borrow_mut()", field)))
+        }
+    }
+}
+
+/// Java arrays are indexed with int, Rust needs usize
+fn convert_index(context: &Context, index: &Expr) -> Result<String, ParseError> {
+    match &index.kind {
+        // Rust infers the type of literals
+        ExprKind::Literal(Literal::Number(DataType::INT, _)) if is_plain_literal(index) => {
+            convert_expr(context, index)
+        }
+        _ => Ok(format!("{} as usize", operand_code(context, index, CAST)?)),
+    }
+}
+
+/// Arguments of methods and constructors of classes of the file, which take String and not &str
+///
+/// params are the declared types of the arguments, empty when the method is not known
+pub fn convert_args(
+    context: &Context,
+    args: &[Expr],
+    params: &[VariableType],
+) -> Result<String, ParseError> {
+    let args: Result<Vec<String>, ParseError> = args
+        .iter()
+        .enumerate()
+        .map(|(i, arg)| match (params.get(i), arg.r#type()) {
+            // The caller keeps its value, objects of shared classes get another handle
+            (Some(param @ (VariableType::Class(_) | VariableType::Generic(..))), r#type)
+                if args.len() == params.len() && r#type == *param && is_place(arg) =>
+            {
+                convert_clone(context, arg)
+            }
+            // Varargs can have another number of arguments
+            (Some(param), _) if args.len() == params.len() => convert_expr_to(context, arg, param),
+            (_, r#type) if r#type.is_string() || r#type.is_object() => {
+                convert_expr_to(context, arg, &r#type)
+            }
+            _ => convert_expr(context, arg),
+        })
+        .collect();
+    Ok(args?.join(", "))
+}
+
+/// Arguments of static methods of classes of the file, a closure which the method only calls
+/// is passed as impl Fn, a shared one is borrowed as &dyn Fn
+///
+/// # Example
+///
+/// twice(x -> x + 1, 2) -> Main::twice(|x: i32| x + 1, 2)
+///
+/// twice(f, 2) -> Main::twice(&*f, 2)
+fn convert_static_args(
+    context: &Context,
+    expr: &Expr,
+    args: &[Expr],
+    params: &[VariableType],
+) -> Result<String, ParseError> {
+    let ExprKind::MethodCall { closure_params, .. } = &expr.kind else {
+        return convert_args(context, args, params);
+    };
+    if !closure_params.contains(&true) || args.len() != closure_params.len() {
+        return convert_args(context, args, params);
+    }
+    let args: Result<Vec<String>, ParseError> = args
+        .iter()
+        .zip(closure_params)
+        .enumerate()
+        .map(|(i, (arg, is_closure))| match (is_closure, &arg.kind) {
+            (true, ExprKind::Lambda(lambda)) => convert_closure(context, lambda),
+            (true, _) => Ok(format!("&*{}", operand_code(context, arg, UNARY)?)),
+            (false, _) => convert_args(
+                context,
+                std::slice::from_ref(arg),
+                params.get(i..=i).unwrap_or_default(),
+            ),
+        })
+        .collect();
+    Ok(args?.join(", "))
+}
+
+/// Resolves the class of a call target, e.g. System.out -> PrintStream
+fn resolve_prelude_class(expr: &Expr) -> Option<Box<dyn Class>> {
+    match &expr.kind {
+        ExprKind::Class(name) => get_prelude_class(name),
+        ExprKind::FieldAccess(target, name, _) => resolve_prelude_class(target)?.get_field(name),
+        _ => None,
+    }
+}
+
+/// Returns the code of the call and its Rust precedence
+fn convert_method_call(
+    context: &Context,
+    expr: &Expr,
+    target: Option<&Expr>,
+    name: &str,
+    args: &[Expr],
+    params: &[VariableType],
+) -> Result<(String, u8), ParseError> {
+    let Some(target) = target else {
+        return Ok((
+            format!(
+                "{}({})",
+                convert_ident(context, name),
+                convert_args(context, args, params)?
+            ),
+            ATOM,
+        ));
+    };
+    // Static methods of classes of the file
+    if let ExprKind::Class(class) = &target.kind {
+        if get_prelude_class(class).is_none() {
+            return Ok((
+                format!(
+                    "{}::{}({})",
+                    convert_type_path(context, class),
+                    convert_ident(context, name),
+                    convert_static_args(context, expr, args, params)?
+                ),
+                ATOM,
+            ));
+        }
+    }
+    // Values of functional interfaces of the Java library are closures, f.apply(x) -> f(x)
+    if let Some(method) = get_functional_method(context, &target.r#type()) {
+        if method.name == name {
+            let function = match &target.kind {
+                ExprKind::Variable {
+                    scope: VariableScope::Local,
+                    ..
+                } => convert_expr(context, target)?,
+                _ => format!("({})", convert_expr(context, target)?),
+            };
+            return Ok((
+                format!(
+                    "{}({})",
+                    function,
+                    convert_args(context, args, &method.params)?
+                ),
+                ATOM,
+            ));
+        }
+    }
+    if target.r#type().is_string() && args.is_empty() {
+        if let Some(code) = code_from_string_method(&convert_receiver(context, target)?, name) {
+            return Ok((code.into_owned(), CAST));
+        }
+    }
+    // Type parameters which extend Comparable are PartialOrd
+    if name == "compareTo" && args.len() == 1 && is_comparable_param(context, &target.r#type()) {
+        return Ok((
+            format!(
+                "{}.partial_cmp(&{}).map_or(0, |ordering| ordering as i32)",
+                operand_code(context, target, ATOM)?,
+                operand_code(context, &args[0], UNARY)?
+            ),
+            ATOM,
+        ));
+    }
+    if let Some(class) = resolve_prelude_class(target) {
+        let args: Result<Vec<FunctionArgument>, ParseError> = args
+            .iter()
+            .map(|arg| convert_function_argument(context, arg))
+            .collect();
+        return match class.code_from_method(context, name, args?) {
+            Some(code) => Ok((code.into_owned(), ATOM)),
+            None => Err(ParseError::new(
+                expr.span,
+                format!("Failed to get method {}", name),
+            )),
+        };
+    }
+    Ok((
+        format!(
+            "{}.{}({})",
+            convert_receiver(context, target)?,
+            convert_ident(context, name),
+            convert_args(context, args, params)?
+        ),
+        ATOM,
+    ))
+}
+
+/// Checks if a type is a type parameter which extends Comparable
+fn is_comparable_param(context: &Context, r#type: &VariableType) -> bool {
+    let VariableType::Class(name) = r#type else {
+        return false;
+    };
+    get_type_param(context, name).is_some_and(|param| {
+        param
+            .bounds
+            .iter()
+            .any(|bound| is_comparable(context, bound))
+    })
+}
+
+/// Values of type parameters, generic classes, shared classes and functional interfaces are
+/// objects, a variable gets its own clone, which is another handle for shared objects
+fn is_copied_type(context: &Context, r#type: &VariableType) -> bool {
+    if get_functional_method(context, r#type).is_some() || is_shared_type(context, r#type) {
+        return true;
+    }
+    match r#type {
+        VariableType::Class(name) => get_type_param(context, name).is_some(),
+        VariableType::Generic(..) | VariableType::Array(_) => true,
+        _ => false,
+    }
+}
+
+/// new X(message, cause), an exception only keeps its message, a cause alone becomes the
+/// message like in Java
+fn convert_new_exception_expr(
+    context: &Context,
+    expr: &Expr,
+    class: &str,
+    args: &[Expr],
+) -> Result<String, ParseError> {
+    let is_cause = |arg: &Expr| matches!(arg.r#type(), VariableType::Class(name) if is_exception(context, &name));
+    let message = match args {
+        [] => None,
+        [cause] if is_cause(cause) => Some(format!(
+            "{}.to_string()",
+            operand_code(context, cause, ATOM)?
+        )),
+        [message] if message.r#type().is_string() => {
+            Some(convert_expr_to(context, message, &VariableType::string())?)
+        }
+        [message, cause] if message.r#type().is_string() && is_cause(cause) => {
+            Some(convert_expr_to(context, message, &VariableType::string())?)
+        }
+        _ => {
+            return Err(ParseError::new(
+                expr.span,
+                "Invalid new: exceptions only take a message and a cause",
+            ))
+        }
+    };
+    Ok(convert_new_exception(context, class, message.as_deref()))
+}
+
+/// Every inner array is a new handle, so they can't be copied with vec!
+///
+/// # Example
+///
+/// new int[2][3] -> Rc::new(RefCell::new((0..2).map(|_| Rc::new(RefCell::new(vec![0; 3]))).collect::<Vec<_>>()))
+fn convert_new_array(
+    context: &Context,
+    r#type: &VariableType,
+    dimensions: &[Expr],
+) -> Result<String, ParseError> {
+    let mut element = r#type;
+    for _ in dimensions {
+        if let VariableType::Array(inner) = element {
+            element = inner;
+        }
+    }
+    let mut code = match element {
+        // Dimensions without size, e.g. the second one of new int[2][]
+        VariableType::Array(_) => convert_array("Vec::new()"),
+        VariableType::DataType(data_type) => default_value(*data_type).to_string(),
+        _ => "Default::default()".to_string(),
+    };
+    let mut is_array = matches!(element, VariableType::Array(_));
+    for dimension in dimensions.iter().rev() {
+        let elements = match is_array {
+            true => format!(
+                "(0..{}).map(|_| {}).collect::<Vec<_>>()",
+                operand_code(context, dimension, ASSIGN + 1)?,
+                code
+            ),
+            false => format!("vec![{}; {}]", code, convert_index(context, dimension)?),
+        };
+        code = convert_array(&elements);
+        is_array = true;
+    }
+    Ok(code)
+}
+
+/// A new array with the elements of a Vec
+pub fn convert_array(elements: &str) -> String {
+    format!("std::rc::Rc::new(std::cell::RefCell::new({}))", elements)
+}
+
+/// The value of fields and array elements which are not set yet
+///
+/// Java uses null for objects, fields which can be null get None, other objects their Rust
+/// default value
+pub fn convert_default_value(context: &Context, r#type: &VariableType) -> String {
+    match r#type {
+        VariableType::Nullable(_) => "None".to_string(),
+        VariableType::DataType(data_type) => default_value(*data_type).to_string(),
+        r#type if r#type.is_string() => "String::new()".to_string(),
+        VariableType::Array(_) => convert_array("Vec::new()"),
+        _ => {
+            require_bound(context, r#type, "Default");
+            "Default::default()".to_string()
+        }
+    }
+}
+
+/// The value of array elements which are not set yet
+fn default_value(data_type: DataType) -> &'static str {
+    match data_type {
+        DataType::LONG => "0i64",
+        DataType::FLOAT => "0.0f32",
+        DataType::DOUBLE => "0.0",
+        DataType::CHAR => "'\\0'",
+        DataType::BOOLEAN => "false",
+        _ => "0",
+    }
+}
+
+fn convert_array_init(
+    context: &Context,
+    r#type: &VariableType,
+    values: &[Expr],
+) -> Result<String, ParseError> {
+    let element = match r#type {
+        VariableType::Array(element) => element,
+        _ => &VariableType::Inferred,
+    };
+    let values: Result<Vec<String>, ParseError> = values
+        .iter()
+        .map(|value| convert_expr_to(context, value, element))
+        .collect();
+    Ok(convert_array(&format!("vec![{}]", values?.join(", "))))
+}
+
+/// Converts a string concatenation into the arguments of format!
+///
+/// # Example
+///
+/// "a" + b + 1.5 -> "a{b}{}", double_to_string(1.5)
+pub fn convert_format_args(context: &Context, expr: &Expr) -> Result<String, ParseError> {
+    let mut parts = Vec::new();
+    collect_concat_parts(expr, &mut parts);
+
+    let mut format = String::new();
+    let mut args = Vec::new();
+    for part in parts {
+        require_bound(context, &part.r#type(), "std::fmt::Display");
+        // Java prints 1.0 and not 1 for doubles
+        let is_float = matches!(
+            part.r#type(),
+            VariableType::DataType(DataType::FLOAT | DataType::DOUBLE)
+        );
+        match &part.kind {
+            ExprKind::Literal(Literal::String(value)) => {
+                format.push_str(&value.replace('{', "{{").replace('}', "}}"))
+            }
+            ExprKind::Literal(Literal::Char(value)) => match value {
+                '{' => format.push_str("{{"),
+                '}' => format.push_str("}}"),
+                value => format.push(*value),
+            },
+            ExprKind::Literal(Literal::Bool(value)) => format.push_str(&value.to_string()),
+            ExprKind::Literal(Literal::Null) => format.push_str("null"),
+            _ if part.is_nullable() => {
+                format.push_str("{}");
+                args.push(convert_null_string(context, part)?);
+            }
+            ExprKind::Variable {
+                name,
+                scope: VariableScope::Local,
+                ..
+            } if !is_float && !convert_ident(context, name).starts_with("r#") => {
+                format.push('{');
+                format.push_str(&convert_ident(context, name));
+                format.push('}');
+            }
+            _ if is_float => {
+                format.push_str("{}");
+                args.push(convert_double_to_string(
+                    context,
+                    &convert_expr(context, part)?,
+                ));
+            }
+            _ => {
+                format.push_str("{}");
+                args.push(convert_expr(context, part)?);
+            }
+        }
+    }
+    let mut code = convert_string_literal(&format);
+    for arg in args {
+        code.push_str(", ");
+        code.push_str(&arg);
+    }
+    Ok(code)
+}
+
+/// Flattens "a" + b + c, but not 1 + 2 + "a" where 1 + 2 is a number
+fn collect_concat_parts<'a>(expr: &'a Expr, parts: &mut Vec<&'a Expr>) {
+    match &expr.kind {
+        ExprKind::Binary(BinaryOp::Add, left, right) if expr.r#type().is_string() => {
+            collect_concat_parts(left, parts);
+            parts.push(right);
+        }
+        _ => parts.push(expr),
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    lexer::{is_combining_mark, is_connector_punctuation, is_identifier_ignorable},
+    parser::context::Context,
+};
+
+/// Strict and reserved Rust keywords which are normal identifiers in Java
+const RUST_KEYWORDS: [&str; 38] = [
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "crate",
+    "dyn",
+    "extern",
+    "fn",
+    "gen",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "pub",
+    "ref",
+    "self",
+    "Self",
+    "struct",
+    "super",
+    "trait",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "yield",
+    "macro_rules",
+    "union",
+];
+
+/// Keywords which can't be used as raw identifiers
+const NON_RAW_KEYWORDS: [&str; 4] = ["self", "Self", "super", "crate"];
+
+/// The names of the output, see Context
+#[derive(Default)]
+pub struct Identifiers {
+    /// Every identifier of the input, a new Rust name may never be one of them
+    used: HashSet<String>,
+    /// Java names which needed a new Rust name, so the same name always gets the same Rust name
+    renamed: HashMap<String, String>,
+    /// The Rust names of static variables by their class and name
+    statics: HashMap<(String, String), String>,
+}
+
+/// Registers all identifiers of the input, has to be called before `convert_ident`,
+/// otherwise a new name could collide with an identifier which comes later in the input
+pub fn reserve_identifiers<'a>(context: &Context, names: impl IntoIterator<Item = &'a str>) {
+    let mut identifiers = context.identifiers.borrow_mut();
+    for name in names {
+        identifiers.used.insert(name.to_string());
+    }
+}
+
+/// Converts a Java identifier into a Rust identifier
+///
+/// Chars which are not allowed in Rust and Rust keywords get a new name,
+/// which never collides with another identifier of the input
+///
+/// # Example
+///
+/// yield -> r#yield
+///
+/// self -> self_
+///
+/// Outer$Inner -> Outer_dollar_Inner
+pub fn convert_ident(context: &Context, name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) && !NON_RAW_KEYWORDS.contains(&name) {
+        return format!("r#{}", name);
+    }
+    let is_rust_ident = !NON_RAW_KEYWORDS.contains(&name)
+        && name.starts_with(is_rust_ident_start)
+        && name.chars().all(is_rust_ident_char);
+    if is_rust_ident {
+        return name.to_string();
+    }
+
+    let mut identifiers = context.identifiers.borrow_mut();
+    if let Some(renamed) = identifiers.renamed.get(name) {
+        return renamed.clone();
+    }
+    let base = mangle(name);
+    let mut renamed = base.clone();
+    let mut counter = 1;
+    while identifiers.used.contains(&renamed) || RUST_KEYWORDS.contains(&renamed.as_str()) {
+        renamed = format!("{}{}", base, counter);
+        counter += 1;
+    }
+    identifiers.used.insert(renamed.clone());
+    identifiers
+        .renamed
+        .insert(name.to_string(), renamed.clone());
+    renamed
+}
+
+/// Creates the name of a variable which is not part of the input, it never collides with an
+/// identifier of the input or another generated name
+///
+/// # Example
+///
+/// selector -> selector1, when selector is already used
+pub fn generate_ident(context: &Context, base: &str) -> String {
+    let mut identifiers = context.identifiers.borrow_mut();
+    let mut name = base.to_string();
+    let mut counter = 1;
+    while identifiers.used.contains(&name) {
+        name = format!("{}{}", base, counter);
+        counter += 1;
+    }
+    identifiers.used.insert(name.clone());
+    name
+}
+
+/// The name of a static variable of a class, the first call generates a new name from base, so
+/// two statics never get the same name, e.g. A.x and A.X or A_B.c and A.b_c
+pub fn generate_static_ident(context: &Context, class: &str, name: &str, base: &str) -> String {
+    let key = (class.to_string(), name.to_string());
+    if let Some(name) = context.identifiers.borrow().statics.get(&key).cloned() {
+        return name;
+    }
+    let generated = generate_ident(context, base);
+    context
+        .identifiers
+        .borrow_mut()
+        .statics
+        .insert(key, generated.clone());
+    generated
+}
+
+/// Replaces every char which is not allowed in Rust identifiers
+fn mangle(name: &str) -> String {
+    let mut mangled = String::new();
+    for c in name.chars() {
+        match c {
+            '$' => mangled.push_str("_dollar_"),
+            // Ignorable chars have no meaning, so they are just dropped
+            c if is_identifier_ignorable(c) => {}
+            c if is_rust_ident_char(c) => mangled.push(c),
+            c => mangled.push_str(&format!("_u{:x}_", c as u32)),
+        }
+    }
+    if NON_RAW_KEYWORDS.contains(&name) {
+        mangled.push('_');
+    }
+    // e.g. ‿a is a valid Java identifier, but ‿ can't start a Rust identifier
+    if !mangled.starts_with(is_rust_ident_start) {
+        mangled.insert(0, '_');
+    }
+    mangled
+}
+
+/// Rust identifiers start with a XID_Start char or _, this does not cover every detail
+fn is_rust_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+/// Rust identifiers continue with XID_Continue chars, this does not cover every detail
+fn is_rust_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || is_connector_punctuation(c) || is_combining_mark(c)
+}
@@ -0,0 +1,187 @@
+use crate::parser::{
+    context::Context,
+    nodes::{
+        expr::{Lambda, LambdaBody},
+        variable::VariableType,
+        MethodReturnType,
+    },
+    ParseError,
+};
+
+use super::{
+    data_type::{convert_variable_type, is_interface},
+    exception::with_declared_exceptions,
+    expr::{convert_expr_statement, convert_expr_to, receiver, with_receiver},
+    ident::convert_ident,
+    statement::convert_statements,
+};
+
+/// Checks if a lambda implements the interface, so it needs an impl for closures
+pub fn is_implemented_by_lambdas(context: &Context, name: &str) -> bool {
+    context.closure_interfaces.borrow().contains(name)
+}
+
+/// Converts a lambda into a closure, which is shared like the other objects. Captured variables
+/// are moved into the closure, the ones which are no primitives are cloned first, because Java
+/// still uses them after the lambda. A clone of a shared object or an array is another handle of it
+///
+/// The current object is cloned into `this`, when the body uses it
+///
+/// # Example
+///
+/// Function<Integer, Integer> f = x -> x + offset;
+///
+/// ->
+///
+/// let f = std::rc::Rc::new(move |x: i32| x + offset) as std::rc::Rc<dyn Fn(i32) -> i32>;
+pub fn convert_lambda(context: &Context, lambda: &Lambda) -> Result<String, ParseError> {
+    let closure = convert_closure(context, lambda)?;
+    let Some(interface) = lambda.r#type.class_name() else {
+        return Ok(closure);
+    };
+    if is_interface(context, interface) {
+        context
+            .closure_interfaces
+            .borrow_mut()
+            .insert(interface.to_string());
+    }
+    Ok(format!(
+        "std::rc::Rc::new({}) as {}",
+        closure,
+        convert_variable_type(context, &lambda.r#type)
+    ))
+}
+
+/// Converts a lambda into a closure which is not shared, e.g. for an argument of type impl Fn
+///
+/// A body which changes a captured variable needs a FnMut closure, it is put into a RefCell,
+/// so the closure around it is still a Fn like the ones of the other lambdas
+///
+/// # Example
+///
+/// () -> count[0]++
+///
+/// ->
+///
+/// {
+/// let mut count = count.clone();
+/// let closure = std::cell::RefCell::new(move || { count[0] += 1; });
+/// move || (closure.borrow_mut())()
+/// }
+pub fn convert_closure(context: &Context, lambda: &Lambda) -> Result<String, ParseError> {
+    let params: Vec<String> = lambda
+        .params
+        .iter()
+        .map(|param| {
+            let name = format!(
+                "{}{}",
+                if param.mutable { "mut " } else { "" },
+                convert_ident(context, &param.name)
+            );
+            match &param.r#type {
+                VariableType::Inferred => name,
+                r#type => format!("{}: {}", name, convert_variable_type(context, r#type)),
+            }
+        })
+        .collect();
+
+    let changed = changed_captures(context, lambda);
+    let mut copies = String::new();
+    if lambda.captures_self {
+        copies.push_str(&format!("let this = {}.clone();\n", receiver(context)));
+    }
+    for capture in &lambda.captures {
+        if !matches!(capture.r#type, VariableType::DataType(_)) {
+            copies.push_str(&format!(
+                "let {}{1} = {1}.clone();\n",
+                if changed.contains(&capture.name.as_str()) {
+                    "mut "
+                } else {
+                    ""
+                },
+                convert_ident(context, &capture.name)
+            ));
+        }
+    }
+    // Functional interfaces can't throw checked exceptions
+    let body = with_declared_exceptions(context, false, || match lambda.captures_self {
+        true => with_receiver(context, "this", || convert_body(context, lambda)),
+        false => convert_body(context, lambda),
+    })?;
+    let is_move = lambda.captures_self || !lambda.captures.is_empty();
+    let closure = format!(
+        "{}|{}| {}",
+        if is_move { "move " } else { "" },
+        params.join(", "),
+        body
+    );
+    if !changed.is_empty() {
+        let names: Vec<String> = lambda
+            .params
+            .iter()
+            .map(|param| convert_ident(context, &param.name))
+            .collect();
+        // The parameters of the outer closure are only passed on, so they are not mut
+        let outer_params: Vec<&str> = params
+            .iter()
+            .map(|param| param.strip_prefix("mut ").unwrap_or(param))
+            .collect();
+        copies.push_str(&format!(
+            "let closure = std::cell::RefCell::new({});\n",
+            closure
+        ));
+        return Ok(format!(
+            "{{\n{}move |{}| (closure.borrow_mut())({})\n}}",
+            copies,
+            outer_params.join(", "),
+            names.join(", ")
+        ));
+    }
+    Ok(match copies.is_empty() {
+        true => closure,
+        false => format!("{{\n{}{}\n}}", copies, closure),
+    })
+}
+
+/// The captured variables which the body changes a part of, e.g. an element of an array
+fn changed_captures<'a>(context: &Context, lambda: &'a Lambda) -> Vec<&'a str> {
+    let assigned = match &lambda.body {
+        LambdaBody::Expr(body) => body.assigned_variables(context),
+        LambdaBody::Block(block) => block
+            .statements
+            .iter()
+            .flat_map(|statement| statement.assigned_variables(context))
+            .collect(),
+    };
+    lambda
+        .captures
+        .iter()
+        .map(|capture| capture.name.as_str())
+        .filter(|name| assigned.contains(name))
+        .collect()
+}
+
+/// The body of the closure, a block body gets the return type, so its returns are checked
+fn convert_body(context: &Context, lambda: &Lambda) -> Result<String, ParseError> {
+    match (&lambda.body, &lambda.return_type) {
+        (LambdaBody::Expr(body), MethodReturnType::TYPE(r#type)) => {
+            convert_expr_to(context, body, r#type)
+        }
+        // The value of the expression is dropped, like the one of an expression statement
+        (LambdaBody::Expr(body), MethodReturnType::VOID) => {
+            Ok(format!("{{ {}; }}", convert_expr_statement(context, body)?))
+        }
+        (LambdaBody::Block(block), return_type) => {
+            let return_type = match return_type {
+                MethodReturnType::TYPE(VariableType::Inferred) | MethodReturnType::VOID => {
+                    String::new()
+                }
+                MethodReturnType::TYPE(r#type) => {
+                    format!("-> {} ", convert_variable_type(context, r#type))
+                }
+            };
+            let statements = convert_statements(context, block, &lambda.return_type)?;
+            Ok(format!("{}{{\n{}\n}}", return_type, statements.join("\n")))
+        }
+    }
+}
@@ -1,23 +1,158 @@
 use std::borrow::Cow;
 
-use crate::parser::nodes::method::NodeMethod;
+use crate::{
+    java_std::function::get_functional_method,
+    parser::{
+        context::Context,
+        nodes::{
+            method::NodeMethod,
+            variable::{TypeParam, VariableType, WildcardBound},
+            MethodReturnType,
+        },
+    },
+    token::Visibility,
+};
 
-use super::data_type::convert_data_type;
+use super::{
+    annotation::{convert_annotations, convert_argument_comment},
+    data_type::{
+        convert_bound, convert_fn_trait, convert_type_params, convert_variable_type,
+        declare_wildcard_param,
+    },
+    exception::exception_type,
+    ident::{convert_ident, generate_ident},
+};
 
-pub fn convert_method(method: &NodeMethod) -> Cow<'static, str> {
-    let visibility = match method.visibility {
-        crate::token::Visibility::PUBLIC => "pub",
-        crate::token::Visibility::NONE => "",
-        crate::token::Visibility::PRIVATE => "",
-    };
-    let return_type = match &method.return_type {
-        crate::parser::nodes::MethodReturnType::VOID => "",
-        crate::parser::nodes::MethodReturnType::DATATYPE(data_type) => {
-            &format!("-> {}", convert_data_type(data_type))
+pub fn convert_visibility(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::PUBLIC => "pub",
+        Visibility::NONE => "",
+        Visibility::PRIVATE => "",
+        Visibility::PROTECTED => "pub(crate)",
+    }
+}
+
+/// Converts the header of a method, instance methods take `&self` or `&mut self`
+///
+/// # Example
+///
+/// public int add(int a, int b) -> pub fn add(&self, a: i32, b: i32) -> i32
+///
+/// int read() throws IOException -> fn read(&self) -> Result<i32, Exception>
+///
+/// <T> T first(T[] values) -> fn first<T: Clone>(values: std::rc::Rc<std::cell::RefCell<Vec<T>>>) -> T
+///
+/// static int twice(Function<Integer, Integer> f, int x) -> fn twice(f: impl Fn(i32) -> i32, x: i32) -> i32,
+/// when the body only calls f
+pub fn convert_method(context: &Context, method: &NodeMethod) -> Cow<'static, str> {
+    let visibility = convert_visibility(&method.visibility);
+    let return_type = match (&method.return_type, method.throws.is_empty()) {
+        (MethodReturnType::VOID, true) => String::new(),
+        (MethodReturnType::TYPE(r#type), true) => {
+            format!("-> {}", convert_variable_type(context, r#type))
         }
-        crate::parser::nodes::MethodReturnType::CLASS(class) => todo!(),
+        (MethodReturnType::VOID, false) => format!("-> Result<(), {}>", exception_type(context)),
+        (MethodReturnType::TYPE(r#type), false) => format!(
+            "-> Result<{}, {}>",
+            convert_variable_type(context, r#type),
+            exception_type(context)
+        ),
     };
-    let name = method.name.clone();
-    // TODO: args
-    format!("{visibility} fn {name}() {return_type}").into()
+    let rust_name = method.overload.as_ref().unwrap_or(&method.name);
+    let name = convert_ident(context, rust_name);
+    // The code of every argument with the comment behind it
+    let mut args = Vec::new();
+    match (method.r#static, method.modifies_self) {
+        (true, _) => {}
+        (false, true) => args.push(("&mut self".to_string(), String::new())),
+        (false, false) => args.push(("&self".to_string(), String::new())),
+    }
+    let mut type_params = method.type_params.clone();
+    for (i, arg) in method.args.iter().enumerate() {
+        let r#type = match get_functional_method(context, &arg.r#type) {
+            Some(function) if method.closure_params.get(i) == Some(&true) => format!(
+                "impl {}",
+                convert_fn_trait(context, &function.params, &function.return_type)
+            ),
+            _ => convert_variable_type(
+                context,
+                &replace_wildcards(context, &arg.r#type, &mut type_params),
+            ),
+        };
+        let code = format!(
+            "{}{}{}: {}",
+            convert_annotations(&arg.annotations, true),
+            if arg.mutable { "mut " } else { "" },
+            convert_ident(context, &arg.name),
+            r#type
+        );
+        args.push((code, convert_argument_comment(&arg.annotations)));
+    }
+    // A comment ends the line, so it is placed behind the comma of its argument
+    let mut arg_list = String::new();
+    for (i, (code, comment)) in args.iter().enumerate() {
+        arg_list.push_str(code);
+        let is_last = i + 1 == args.len();
+        if !is_last {
+            arg_list.push(',');
+        }
+        match comment.is_empty() {
+            true if !is_last => arg_list.push(' '),
+            true => {}
+            false => arg_list.push_str(&format!(" {}\n", comment)),
+        }
+    }
+    for param in &type_params[method.type_params.len()..] {
+        declare_wildcard_param(context, rust_name, &param.name);
+    }
+    let type_params = convert_type_params(context, Some(rust_name), &type_params, &[]);
+    format!("{visibility} fn {name}{type_params}({arg_list}) {return_type}").into()
+}
+
+/// Wildcards in the type of an argument become new type parameters of the method, so it takes
+/// every matching type like in Java
+///
+/// Rust has no lower bounds, so ? super T is replaced by T. A bound which can't be a trait, like
+/// Integer or a class, is the only type which the argument can have in Rust, so it replaces
+/// the wildcard too
+///
+/// # Example
+///
+/// Box<? extends Shape> box -> box: Box<W>, the method gets <W: Shape>
+///
+/// Box<? extends Integer> box -> box: Box<i32>
+fn replace_wildcards(
+    context: &Context,
+    r#type: &VariableType,
+    type_params: &mut Vec<TypeParam>,
+) -> VariableType {
+    match r#type {
+        VariableType::Wildcard(WildcardBound::Super(bound)) => *bound.clone(),
+        VariableType::Wildcard(WildcardBound::Extends(bound))
+            if convert_bound(context, bound).is_none() =>
+        {
+            *bound.clone()
+        }
+        VariableType::Wildcard(bound) => {
+            let name = generate_ident(context, "W");
+            type_params.push(TypeParam {
+                name: name.clone(),
+                bounds: match bound {
+                    WildcardBound::Extends(bound) => vec![*bound.clone()],
+                    _ => Vec::new(),
+                },
+            });
+            VariableType::Class(name)
+        }
+        VariableType::Generic(name, args) => VariableType::Generic(
+            name.clone(),
+            args.iter()
+                .map(|arg| replace_wildcards(context, arg, type_params))
+                .collect(),
+        ),
+        VariableType::Array(element) => {
+            VariableType::Array(Box::new(replace_wildcards(context, element, type_params)))
+        }
+        _ => r#type.clone(),
+    }
 }
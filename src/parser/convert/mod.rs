@@ -1,2 +1,12 @@
-mod data_type;
+pub mod annotation;
+pub mod class;
+pub mod comment;
+pub mod compilation_unit;
+pub mod data_type;
+pub mod exception;
+pub mod expr;
+pub mod ident;
+pub mod lambda;
 pub mod method;
+pub mod statement;
+pub mod string;
@@ -0,0 +1,1475 @@
+use std::borrow::Cow;
+
+use crate::{
+    parser::{
+        context::Context,
+        nodes::{
+            expr::{BinaryOp, Expr, ExprKind, Literal, Pattern, UnaryOp, VariableScope},
+            statement::{Block, CaseLabel, Statement, StatementKind, Switch, Try},
+            variable::{NodeVariable, VariableType},
+            MethodReturnType, NodeComments,
+        },
+        ParseError,
+    },
+    token::{DataType, Trivia},
+};
+
+use super::{
+    comment::{convert_leading_comments, convert_trailing_comments, convert_with_comments},
+    data_type::require_bound,
+    exception::{
+        check_caught, convert_throw, convert_throw_statement, declares_exceptions, exception_type,
+        raised_count, with_try_label,
+    },
+    expr::{
+        convert_atom, convert_expr, convert_expr_statement, convert_expr_to, convert_pattern,
+        flatten, reads_borrowed_cell, release_borrows,
+    },
+    ident::{convert_ident, generate_ident},
+};
+
+/// Converts the statements of a method body, every statement becomes one entry
+///
+/// # Example
+///
+/// do { i++; } while (i < 10); -> loop { i += 1; if !(i < 10) { break; } }
+pub fn convert_block(
+    context: &Context,
+    block: &Block,
+    return_type: &MethodReturnType,
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    context.generated_labels.set(0);
+    let mut final_code = convert_statements(context, block, return_type)?;
+    if declares_exceptions(context) && block.completes_normally() {
+        final_code.push("Ok(())".into());
+    }
+    Ok(final_code)
+}
+
+/// Like convert_block, but the generated labels go on with the next number, so the body of a
+/// lambda gets other labels than the method around it
+pub fn convert_statements(
+    context: &Context,
+    block: &Block,
+    return_type: &MethodReturnType,
+) -> Result<Vec<Cow<'static, str>>, ParseError> {
+    let mut converter = StatementConverter {
+        context,
+        return_type,
+        jumps: Vec::new(),
+    };
+    let mut final_code = Vec::new();
+    for statement in &block.statements {
+        let code = converter.convert(statement)?;
+        if !code.is_empty() {
+            final_code.push(code.into());
+        }
+    }
+    // Comments before the } are still part of the method
+    let comments = convert_leading_comments(&block.end_comments, false);
+    if !comments.is_empty() {
+        final_code.push(comments.trim_end().to_string().into());
+    }
+    Ok(final_code)
+}
+
+/// Converts a switch expression into a match, the values are converted to r#type
+///
+/// # Example
+///
+/// switch (day) { case 6, 7 -> true; default -> false; } -> match day { 6 | 7 => true, _ => false }
+pub fn convert_switch_expression(
+    context: &Context,
+    switch: &Switch,
+    r#type: &VariableType,
+) -> Result<String, ParseError> {
+    // A switch expression can't be left with return, break or continue
+    let mut converter = StatementConverter {
+        context,
+        return_type: &MethodReturnType::VOID,
+        jumps: Vec::new(),
+    };
+    converter.convert_switch(switch, Some(r#type))
+}
+
+/// Creates a new label like 'loop1
+fn generate_label(context: &Context, name: &str) -> String {
+    let number = context.generated_labels.get() + 1;
+    context.generated_labels.set(number);
+    format!("'{}{}", name, number)
+}
+
+/// A loop, labeled block, switch or try block which can be left with break
+struct Jump {
+    java_label: Option<String>,
+    /// The Rust label, only emitted when a jump needs it
+    label: String,
+    /// Label of the block around the body of loops which have to run code before the next
+    /// iteration, continue leaves this block
+    body_label: Option<String>,
+    kind: JumpKind,
+    used: bool,
+}
+
+#[derive(PartialEq)]
+enum JumpKind {
+    Loop,
+    Block,
+    /// Holds the type of the value for switch expressions
+    Switch(Option<VariableType>),
+    /// The block of a try statement or of its catch, holds the code of the finally block which
+    /// runs when the block is left
+    Try(Option<String>),
+}
+
+impl Jump {
+    fn prefix(&self) -> String {
+        match self.used {
+            true => format!("{}: ", self.label),
+            false => String::new(),
+        }
+    }
+}
+
+struct StatementConverter<'a> {
+    context: &'a Context,
+    return_type: &'a MethodReturnType,
+    /// The loops, labeled blocks and switches around the current statement
+    jumps: Vec<Jump>,
+}
+
+impl StatementConverter<'_> {
+    fn convert(&mut self, statement: &Statement) -> Result<String, ParseError> {
+        let code = self.convert_kind(statement)?;
+        Ok(code + &convert_trailing_comments(statement.close_comments()))
+    }
+
+    fn convert_kind(&mut self, statement: &Statement) -> Result<String, ParseError> {
+        let context = self.context;
+        let code = match &statement.kind {
+            StatementKind::Variable(variable) => {
+                return Ok(variable.to_code(context)?.into_owned())
+            }
+            StatementKind::Expr(expr) => format!("{};", convert_expr_statement(context, expr)?),
+            StatementKind::Return(expr) => self.convert_return(expr.as_ref())?,
+            StatementKind::Throw(expr) => {
+                convert_throw_statement(context, expr, &convert_expr(context, expr)?)
+            }
+            StatementKind::Break(label) => self.convert_break(label.as_deref())?,
+            StatementKind::Continue(label) => self.convert_continue(label.as_deref())?,
+            StatementKind::Yield(expr) => self.convert_yield(expr)?,
+            StatementKind::Switch(switch) => {
+                let code = self.convert_switch(switch, None)?;
+                return Ok(wrap_header_comments(&statement.comments, &code));
+            }
+            StatementKind::Empty => String::new(),
+            StatementKind::Block(_) => {
+                // The comments before the { are placed above the block
+                let (mut trailing, inner) = self.convert_contents(statement)?;
+                let comments = NodeComments {
+                    leading: trailing.drain(..statement.comments.leading.len()).collect(),
+                    trailing: Vec::new(),
+                };
+                return Ok(wrap(&comments, "", &trailing, &inner));
+            }
+            StatementKind::If { .. } => return self.convert_if(statement),
+            StatementKind::Labeled(label, inner) => {
+                return self.convert_labeled(&statement.comments, label, inner)
+            }
+            StatementKind::Try(r#try) => return self.convert_try(&statement.comments, r#try),
+            _ => return self.convert_loop(None, &statement.comments, statement),
+        };
+        Ok(convert_with_comments(&statement.comments, &code, false))
+    }
+
+    /// The code inside the braces of a body and the comments which belong behind the {
+    fn convert_contents(&mut self, body: &Statement) -> Result<(Vec<Trivia>, String), ParseError> {
+        let StatementKind::Block(block) = &body.kind else {
+            return Ok((Vec::new(), self.convert(body)?));
+        };
+        let trailing = body
+            .comments
+            .leading
+            .iter()
+            .chain(&block.open_comments)
+            .cloned()
+            .collect();
+        let mut lines = Vec::new();
+        for statement in &block.statements {
+            let code = self.convert(statement)?;
+            if !code.is_empty() {
+                lines.push(code);
+            }
+        }
+        let end_comments = convert_leading_comments(&block.end_comments, false);
+        if !end_comments.is_empty() {
+            lines.push(end_comments.trim_end().to_string());
+        }
+        Ok((trailing, lines.join("\n")))
+    }
+
+    /// if (a) {} else if (b) {} else {} -> if a {} else if b {} else {}
+    fn convert_if(&mut self, statement: &Statement) -> Result<String, ParseError> {
+        let context = self.context;
+        let StatementKind::If {
+            condition,
+            then,
+            otherwise,
+        } = &statement.kind
+        else {
+            unreachable!()
+        };
+        if !condition.bindings_when_true().is_empty() {
+            return self.convert_pattern_if(statement);
+        }
+        if !condition.bindings_when_false().is_empty() {
+            if let Some(code) = self.convert_negated_pattern_if(statement)? {
+                return Ok(code);
+            }
+        }
+        let (trailing, inner) = self.convert_contents(then)?;
+        let header = format!("if {}", convert_expr(context, condition)?);
+        let mut final_code = wrap(&statement.comments, &header, &trailing, &inner);
+        if let Some(otherwise) = otherwise {
+            final_code.push_str(&self.convert_else(otherwise)?);
+        }
+        Ok(final_code)
+    }
+
+    /// An if whose condition declares pattern variables becomes an if let for every pattern,
+    /// with an else the ifs are put into a labeled block, which is left when the then branch
+    /// is done
+    ///
+    /// # Example
+    ///
+    /// if (o instanceof Point p && p.x > 0) -> if let Some(p) = o.downcast_ref::<Point>() { if p.x > 0 {
+    fn convert_pattern_if(&mut self, statement: &Statement) -> Result<String, ParseError> {
+        let context = self.context;
+        let StatementKind::If {
+            condition,
+            then,
+            otherwise,
+        } = &statement.kind
+        else {
+            unreachable!()
+        };
+        let headers = condition_headers(context, &flatten(condition, BinaryOp::And))?;
+        let (first, nested) = headers.split_first().unwrap();
+        let Some(otherwise) = otherwise.as_deref().filter(|_| !nested.is_empty()) else {
+            let (trailing, inner) = self.convert_contents(then)?;
+            let mut final_code = wrap(&statement.comments, first, &trailing, &nest(nested, inner));
+            if let Some(otherwise) = otherwise {
+                final_code.push_str(&self.convert_else(otherwise)?);
+            }
+            return Ok(final_code);
+        };
+
+        let label = generate_label(context, "if");
+        self.jumps.push(Jump {
+            java_label: None,
+            label: label.clone(),
+            body_label: None,
+            kind: JumpKind::Block,
+            used: true,
+        });
+        let contents = self.convert_pattern_if_else(&label, then, otherwise);
+        self.jumps.pop();
+        let (trailing, then_code, else_code) = contents?;
+        let inner = format!(
+            "{}
+{}",
+            nest(&headers, then_code),
+            else_code
+        );
+        Ok(wrap(
+            &statement.comments,
+            &format!("{}:", label),
+            &trailing,
+            &inner,
+        ))
+    }
+
+    /// The then branch which leaves the labeled block around the ifs and the else branch
+    /// after them
+    fn convert_pattern_if_else(
+        &mut self,
+        label: &str,
+        then: &Statement,
+        otherwise: &Statement,
+    ) -> Result<(Vec<Trivia>, String, String), ParseError> {
+        let (trailing, mut then_code) = self.convert_contents(then)?;
+        if then.completes_normally() {
+            then_code.push_str(&format!("\nbreak {};", label));
+        }
+        let else_code = match &otherwise.kind {
+            StatementKind::If { .. } => self.convert_if(otherwise)?,
+            _ => {
+                let (else_trailing, else_code) = self.convert_contents(otherwise)?;
+                let comments = convert_trailing_comments(&else_trailing);
+                format!("{}{}", comments.trim_start(), else_code)
+            }
+        };
+        Ok((trailing, then_code, else_code))
+    }
+
+    /// else if or else, including the leading space
+    fn convert_else(&mut self, otherwise: &Statement) -> Result<String, ParseError> {
+        match &otherwise.kind {
+            StatementKind::If { .. } => Ok(format!(" else {}", self.convert_if(otherwise)?)),
+            _ => {
+                let (trailing, inner) = self.convert_contents(otherwise)?;
+                Ok(wrap(&NodeComments::default(), " else", &trailing, &inner))
+            }
+        }
+    }
+
+    /// An if whose negated condition declares pattern variables, returns None when the
+    /// variables are not visible anywhere
+    ///
+    /// # Example
+    ///
+    /// if (!(o instanceof String s)) return; -> let Some(s) = o.downcast_ref::<String>() else { return; };
+    fn convert_negated_pattern_if(
+        &mut self,
+        statement: &Statement,
+    ) -> Result<Option<String>, ParseError> {
+        let context = self.context;
+        let StatementKind::If {
+            condition,
+            then,
+            otherwise,
+        } = &statement.kind
+        else {
+            unreachable!()
+        };
+        if otherwise.is_none() && then.completes_normally() {
+            return Ok(None);
+        }
+        let Some((operand, pattern)) = negated_pattern(condition) else {
+            return Err(ParseError::new(
+                condition.span,
+                "Invalid if: this negated pattern is not supported yet",
+            ));
+        };
+        let (binding, downcast) = convert_pattern(context, operand, pattern)?;
+        let (trailing, inner) = self.convert_contents(then)?;
+        let Some(otherwise) = otherwise else {
+            let header = format!("let Some({}) = {} else", binding, downcast);
+            return Ok(Some(
+                wrap(&statement.comments, &header, &trailing, &inner) + ";",
+            ));
+        };
+        // The branches are swapped, so the variables are declared in the else branch
+        let (else_trailing, else_inner) = self.convert_contents(otherwise)?;
+        let header = format!("if let Some({}) = {}", binding, downcast);
+        let mut final_code = wrap(&statement.comments, &header, &else_trailing, &else_inner);
+        final_code.push_str(&wrap(&NodeComments::default(), " else", &trailing, &inner));
+        Ok(Some(final_code))
+    }
+
+    /// label: statement, a labeled statement which is no loop becomes a labeled block
+    fn convert_labeled(
+        &mut self,
+        outer: &NodeComments,
+        label: &str,
+        statement: &Statement,
+    ) -> Result<String, ParseError> {
+        let comments = NodeComments {
+            leading: [&outer.leading[..], &statement.comments.leading].concat(),
+            trailing: [&outer.trailing[..], &statement.comments.trailing].concat(),
+        };
+        match &statement.kind {
+            StatementKind::While { .. }
+            | StatementKind::DoWhile { .. }
+            | StatementKind::For { .. }
+            | StatementKind::ForEach { .. } => self.convert_loop(Some(label), &comments, statement),
+            _ => {
+                self.jumps.push(Jump {
+                    java_label: Some(label.to_string()),
+                    label: convert_label(label),
+                    body_label: None,
+                    kind: JumpKind::Block,
+                    used: false,
+                });
+                let contents = self.convert_contents(statement);
+                let jump = self.jumps.pop().unwrap();
+                let (trailing, inner) = contents?;
+                let header = jump.prefix();
+                Ok(wrap(outer, header.trim_end(), &trailing, &inner))
+            }
+        }
+    }
+
+    /// Starts a loop, needs_body_block is true for loops which run code after the body,
+    /// a continue has to jump there
+    fn push_loop(&mut self, java_label: Option<&str>, body: &Statement, needs_body_block: bool) {
+        let context = self.context;
+        let label = match java_label {
+            Some(label) => convert_label(label),
+            None => generate_label(context, "loop"),
+        };
+        let body_label = match needs_body_block && body.continues_to(java_label) {
+            true => Some(format!("{}_body", label)),
+            false => None,
+        };
+        self.jumps.push(Jump {
+            java_label: java_label.map(str::to_string),
+            label,
+            body_label,
+            kind: JumpKind::Loop,
+            used: false,
+        });
+    }
+
+    /// Converts the body of the current loop, also puts it into the block for continue
+    fn convert_loop_body(&mut self, body: &Statement) -> Result<(Vec<Trivia>, String), ParseError> {
+        let (trailing, inner) = self.convert_contents(body)?;
+        match &self.jumps.last().unwrap().body_label {
+            Some(body_label) => Ok((trailing, format!("{}: {{\n{}\n}}", body_label, inner))),
+            None => Ok((trailing, inner)),
+        }
+    }
+
+    fn convert_loop(
+        &mut self,
+        java_label: Option<&str>,
+        comments: &NodeComments,
+        statement: &Statement,
+    ) -> Result<String, ParseError> {
+        let context = self.context;
+        match &statement.kind {
+            StatementKind::While { condition, body } => {
+                self.push_loop(java_label, body, false);
+                let contents = self.convert_loop_body(body);
+                let jump = self.jumps.pop().unwrap();
+                let (trailing, inner) = contents?;
+                if condition.bindings_when_true().is_empty() {
+                    let header = format!(
+                        "{}while {}",
+                        jump.prefix(),
+                        convert_expr(context, condition)?
+                    );
+                    return Ok(wrap(comments, &header, &trailing, &inner));
+                }
+                // while (o instanceof Node n && rest) -> while let Some(n) = ... { if !(rest) { break; } }
+                let terms = flatten(condition, BinaryOp::And);
+                let (first, rest) = terms.split_first().unwrap();
+                let (ExprKind::InstanceOf(operand, pattern), false) =
+                    (&first.kind, rest.iter().any(|term| has_bindings(term)))
+                else {
+                    return Err(ParseError::new(
+                        condition.span,
+                        "Invalid while: only the first condition can declare variables",
+                    ));
+                };
+                let (binding, downcast) = convert_pattern(context, operand, pattern)?;
+                let header = format!(
+                    "{}while let Some({}) = {}",
+                    jump.prefix(),
+                    binding,
+                    downcast
+                );
+                let inner = match rest.is_empty() {
+                    true => inner,
+                    false => {
+                        let rest = Expr {
+                            kind: ExprKind::Unary(UnaryOp::Not, Box::new(conjunction(rest))),
+                            span: condition.span,
+                        };
+                        format!(
+                            "if {} {{\nbreak;\n}}\n{}",
+                            convert_expr(context, &rest)?,
+                            inner
+                        )
+                    }
+                };
+                Ok(wrap(comments, &header, &trailing, &inner))
+            }
+            // do { body } while (condition); -> loop { body if !(condition) { break; } }
+            StatementKind::DoWhile { body, condition } => {
+                self.push_loop(java_label, body, true);
+                let contents = self.convert_loop_body(body);
+                let jump = self.jumps.pop().unwrap();
+                let (trailing, inner) = contents?;
+                let inner = format!(
+                    "{}\nif !({}) {{\nbreak;\n}}",
+                    inner,
+                    convert_expr(context, condition)?
+                );
+                let header = format!("{}loop", jump.prefix());
+                Ok(wrap(comments, &header, &trailing, &inner))
+            }
+            // Like in Java the elements are read when the loop gets to them, so the body can
+            // change the array
+            StatementKind::ForEach {
+                variable,
+                iterable,
+                body,
+            } => {
+                self.push_loop(java_label, body, false);
+                let contents = self.convert_loop_body(body);
+                let jump = self.jumps.pop().unwrap();
+                let (trailing, inner) = contents?;
+                let mutability = if variable.mutable { "mut " } else { "" };
+                let element = match &variable.r#type {
+                    VariableType::DataType(_) => "array.borrow()[i]",
+                    r#type => {
+                        require_bound(context, r#type, "Clone");
+                        "array.borrow()[i].clone()"
+                    }
+                };
+                let iterable = format!(
+                    "{{\nlet array = {};\nlet length = array.borrow().len();\n(0..length).map(move |i| {})\n}}",
+                    convert_expr_to(context, iterable, &iterable.r#type())?,
+                    element
+                );
+                let header = format!(
+                    "{}for {}{} in {}",
+                    jump.prefix(),
+                    mutability,
+                    convert_ident(context, &variable.name),
+                    iterable
+                );
+                Ok(wrap(comments, &header, &trailing, &inner))
+            }
+            StatementKind::For {
+                init,
+                condition,
+                update,
+                body,
+            } => {
+                if let Some(range) = convert_range(context, init, condition.as_ref(), update, body)?
+                {
+                    self.push_loop(java_label, body, false);
+                    let contents = self.convert_loop_body(body);
+                    let jump = self.jumps.pop().unwrap();
+                    let (trailing, inner) = contents?;
+                    let header = format!("{}{}", jump.prefix(), range);
+                    return Ok(wrap(comments, &header, &trailing, &inner));
+                }
+
+                // for (init; condition; update) body -> { init while condition { body update } }
+                self.push_loop(java_label, body, !update.is_empty());
+                let contents = self.convert_loop_body(body);
+                let jump = self.jumps.pop().unwrap();
+                let (trailing, mut inner) = contents?;
+                for expr in update {
+                    inner.push_str(&format!("\n{};", convert_expr_statement(context, expr)?));
+                }
+                let header = match condition {
+                    Some(condition) => {
+                        format!(
+                            "{}while {}",
+                            jump.prefix(),
+                            convert_expr(context, condition)?
+                        )
+                    }
+                    None => format!("{}loop", jump.prefix()),
+                };
+                if init.is_empty() {
+                    return Ok(wrap(comments, &header, &trailing, &inner));
+                }
+                // The variables of the header are only visible in the loop
+                let mut final_code = convert_leading_comments(&comments.leading, false);
+                final_code.push_str("{\n");
+                for statement in init {
+                    final_code.push_str(&self.convert(statement)?);
+                    final_code.push('\n');
+                }
+                let comments = NodeComments {
+                    leading: Vec::new(),
+                    trailing: comments.trailing.clone(),
+                };
+                final_code.push_str(&wrap(&comments, &header, &trailing, &inner));
+                final_code.push_str("\n}");
+                Ok(final_code)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Converts a switch into a match, r#type is Some for switch expressions
+    ///
+    /// Cases which fall through get the statements of the following cases too
+    fn convert_switch(
+        &mut self,
+        switch: &Switch,
+        r#type: Option<&VariableType>,
+    ) -> Result<String, ParseError> {
+        let context = self.context;
+        if switch.has_patterns() {
+            return self.convert_pattern_switch(switch, r#type);
+        }
+        let selector = &switch.selector;
+        let selector = match (
+            selector.r#type().is_string(),
+            reads_borrowed_cell(context, selector),
+        ) {
+            (true, true) => format!(
+                "{}.as_str()",
+                release_borrows(
+                    context,
+                    "selector",
+                    convert_expr_to(context, selector, &selector.r#type())?,
+                    &[selector]
+                )
+            ),
+            (true, false) => format!("{}.as_str()", convert_atom(context, selector)?),
+            (false, _) => release_borrows(
+                context,
+                "selector",
+                convert_expr(context, selector)?,
+                &[selector],
+            ),
+        };
+        self.jumps.push(Jump {
+            java_label: None,
+            label: generate_label(context, "switch"),
+            body_label: None,
+            kind: JumpKind::Switch(r#type.cloned()),
+            used: false,
+        });
+        let arms = self.convert_arms(switch, r#type);
+        let jump = self.jumps.pop().unwrap();
+        let mut final_code = format!("match {} {{\n", selector);
+        final_code.push_str(&arms?);
+        final_code.push_str(&convert_leading_comments(&switch.end_comments, false));
+        final_code.push('}');
+        if jump.used {
+            final_code = format!("{}: {{\n{}\n}}", jump.label, final_code);
+        }
+        Ok(final_code)
+    }
+
+    fn convert_arms(
+        &mut self,
+        switch: &Switch,
+        r#type: Option<&VariableType>,
+    ) -> Result<String, ParseError> {
+        let context = self.context;
+        let mut arms = Vec::new();
+        let mut default_arm = None;
+        let mut labels = Vec::new();
+        let mut comments = Vec::new();
+        for (i, case) in switch.cases.iter().enumerate() {
+            labels.extend(&case.labels);
+            comments.extend(case.comments.leading.iter().chain(&case.comments.trailing));
+            // case 1: case 2: body -> 1 | 2 => body
+            if !switch.arrow && case.body.is_empty() && i + 1 < switch.cases.len() {
+                continue;
+            }
+            let mut body = Vec::new();
+            for next in &switch.cases[i..] {
+                body.extend(next.body.iter().cloned());
+                if switch.arrow || body.last().is_some_and(|last| !last.completes_normally()) {
+                    break;
+                }
+            }
+            // The break at the end of a case is the end of the arm
+            if matches!(
+                body.last(),
+                Some(Statement {
+                    kind: StatementKind::Break(None),
+                    ..
+                })
+            ) {
+                body.pop();
+            }
+            let pattern = match labels
+                .iter()
+                .any(|label| matches!(label, CaseLabel::Default))
+            {
+                true => "_".to_string(),
+                false => labels
+                    .iter()
+                    .map(|label| match label {
+                        CaseLabel::Value(value) => convert_case_label(context, value),
+                        CaseLabel::Pattern(_) | CaseLabel::Default => unreachable!(),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(" | "),
+            };
+            let comments: Vec<Trivia> =
+                std::mem::take(&mut comments).into_iter().cloned().collect();
+            let arm = format!(
+                "{}{} => {}",
+                convert_leading_comments(&comments, false),
+                pattern,
+                self.convert_arm(&body, r#type)?
+            );
+            labels.clear();
+            match pattern.as_str() {
+                // Rust takes the first matching arm, so the default has to be the last one
+                "_" => default_arm = Some(arm),
+                _ => arms.push(arm),
+            }
+        }
+        // Java only needs a default for switch expressions over values which aren't enums
+        let default_arm = default_arm.unwrap_or_else(|| match r#type {
+            Some(_) => "_ => unreachable!(),".to_string(),
+            None => "_ => {}".to_string(),
+        });
+        arms.push(default_arm);
+        Ok(arms.join("\n") + "\n")
+    }
+
+    /// The code of one arm, the last yield of a switch expression becomes its value
+    fn convert_arm(
+        &mut self,
+        body: &[Statement],
+        r#type: Option<&VariableType>,
+    ) -> Result<String, ParseError> {
+        let context = self.context;
+        let statements = match body {
+            [Statement {
+                kind: StatementKind::Block(block),
+                ..
+            }] => &block.statements[..],
+            _ => body,
+        };
+        let (statements, value) = match (statements, r#type) {
+            (
+                [rest @ .., Statement {
+                    kind: StatementKind::Yield(value),
+                    comments,
+                }],
+                Some(r#type),
+            ) => (
+                rest,
+                Some(convert_with_comments(
+                    comments,
+                    &convert_expr_to(context, value, r#type)?,
+                    false,
+                )),
+            ),
+            _ => (statements, None),
+        };
+        let mut lines = Vec::new();
+        for statement in statements {
+            let code = self.convert(statement)?;
+            if !code.is_empty() {
+                lines.push(code);
+            }
+        }
+        if let [Statement {
+            kind: StatementKind::Block(block),
+            ..
+        }] = body
+        {
+            let end_comments = convert_leading_comments(&block.end_comments, false);
+            if !end_comments.is_empty() {
+                lines.push(end_comments.trim_end().to_string());
+            }
+        }
+        match value {
+            // 1 => "one",
+            Some(value) if lines.is_empty() && !value.contains("//") => Ok(format!("{},", value)),
+            Some(value) => Ok(format!("{{\n{}\n{}\n}}", lines.join("\n"), value)),
+            None => Ok(format!("{{\n{}\n}}", lines.join("\n"))),
+        }
+    }
+
+    /// A switch over patterns becomes a labeled block with an if let for every case, the first
+    /// matching case leaves the block, the default case is placed at its end
+    ///
+    /// # Example
+    ///
+    /// switch (o) { case Integer i when i > 0 -> a(); default -> b(); }
+    /// -> 'switch1: { if let Some(i) = ... { if i > 0 { a(); break 'switch1; } } b(); }
+    fn convert_pattern_switch(
+        &mut self,
+        switch: &Switch,
+        r#type: Option<&VariableType>,
+    ) -> Result<String, ParseError> {
+        let context = self.context;
+        let label = generate_label(context, "switch");
+        self.jumps.push(Jump {
+            java_label: None,
+            label: label.clone(),
+            body_label: None,
+            kind: JumpKind::Switch(r#type.cloned()),
+            used: true,
+        });
+        let cases = self.convert_pattern_cases(switch, &label, r#type);
+        self.jumps.pop();
+        let mut final_code = format!("{}: {{\n", label);
+        final_code.push_str(&cases?);
+        final_code.push_str(&convert_leading_comments(&switch.end_comments, false));
+        final_code.push('}');
+        Ok(final_code)
+    }
+
+    fn convert_pattern_cases(
+        &mut self,
+        switch: &Switch,
+        label: &str,
+        r#type: Option<&VariableType>,
+    ) -> Result<String, ParseError> {
+        let context = self.context;
+        let mut lines = Vec::new();
+        // The selector is only evaluated once
+        let selector = match switch.selector.kind {
+            ExprKind::Variable { .. } | ExprKind::FieldAccess(..) => switch.selector.clone(),
+            _ => {
+                let name = generate_ident(context, "selector");
+                lines.push(format!(
+                    "let {} = {};",
+                    name,
+                    convert_expr(context, &switch.selector)?
+                ));
+                Expr {
+                    kind: ExprKind::Variable {
+                        name,
+                        r#type: switch.selector.r#type(),
+                        scope: VariableScope::Local,
+                    },
+                    span: switch.selector.span,
+                }
+            }
+        };
+        let mut default_case = None;
+        for (i, case) in switch.cases.iter().enumerate() {
+            let comments: Vec<Trivia> = case
+                .comments
+                .leading
+                .iter()
+                .chain(&case.comments.trailing)
+                .cloned()
+                .collect();
+            let comments = convert_leading_comments(&comments, false);
+            let falls_through = case.body.last().is_none_or(Statement::completes_normally);
+            if !switch.arrow && falls_through && i + 1 < switch.cases.len() {
+                return Err(ParseError::new(
+                    switch.selector.span,
+                    "Invalid switch: cases with patterns can't fall through",
+                ));
+            }
+            let mut pattern = None;
+            for case_label in &case.labels {
+                match case_label {
+                    CaseLabel::Pattern(case_pattern) if pattern.is_none() => {
+                        pattern = Some(case_pattern)
+                    }
+                    CaseLabel::Pattern(_) => {
+                        return Err(ParseError::new(
+                            switch.selector.span,
+                            "Invalid case: only one pattern per case is supported",
+                        ))
+                    }
+                    // Objects are never null in Rust
+                    CaseLabel::Value(Expr {
+                        kind: ExprKind::Literal(Literal::Null),
+                        ..
+                    }) => {}
+                    CaseLabel::Value(value) => {
+                        return Err(ParseError::new(
+                            value.span,
+                            "Invalid case: constants can't be mixed with patterns yet",
+                        ))
+                    }
+                    CaseLabel::Default => {}
+                }
+            }
+            let is_default = case
+                .labels
+                .iter()
+                .any(|case_label| matches!(case_label, CaseLabel::Default));
+            let body = match &case.body[..] {
+                [rest @ .., Statement {
+                    kind: StatementKind::Break(None),
+                    ..
+                }] => rest,
+                body => body,
+            };
+            if is_default {
+                default_case = Some((comments, body));
+                continue;
+            }
+            let Some(pattern) = pattern else {
+                // case null ->
+                continue;
+            };
+            let check = Expr {
+                kind: ExprKind::InstanceOf(Box::new(selector.clone()), pattern.clone()),
+                span: switch.selector.span,
+            };
+            let mut terms = vec![&check];
+            if let Some(guard) = &case.guard {
+                terms.extend(flatten(guard, BinaryOp::And));
+            }
+            let headers = condition_headers(context, &terms)?;
+            let mut inner = self.convert_case_body(body, None)?;
+            if body.last().is_none_or(Statement::completes_normally) {
+                inner.push_str(&format!("\nbreak {};", label));
+            }
+            lines.push(format!("{}{}", comments, nest(&headers, inner)));
+        }
+        match default_case {
+            Some((comments, body)) => lines.push(format!(
+                "{}{}",
+                comments,
+                self.convert_case_body(body, r#type)?
+            )),
+            // Java checks that a switch expression covers every value
+            None if r#type.is_some() => lines.push("unreachable!()".to_string()),
+            None => {}
+        }
+        Ok(lines.join("\n") + "\n")
+    }
+
+    /// The statements of a case of a pattern switch, r#type is Some when the last yield
+    /// becomes the value of the block
+    fn convert_case_body(
+        &mut self,
+        body: &[Statement],
+        r#type: Option<&VariableType>,
+    ) -> Result<String, ParseError> {
+        let context = self.context;
+        let (statements, end_comments) = match body {
+            [Statement {
+                kind: StatementKind::Block(block),
+                ..
+            }] => (&block.statements[..], &block.end_comments[..]),
+            _ => (body, &[][..]),
+        };
+        let (statements, value) = match (statements, r#type) {
+            (
+                [rest @ .., Statement {
+                    kind: StatementKind::Yield(value),
+                    comments,
+                }],
+                Some(r#type),
+            ) => (
+                rest,
+                Some(convert_with_comments(
+                    comments,
+                    &convert_expr_to(context, value, r#type)?,
+                    false,
+                )),
+            ),
+            _ => (statements, None),
+        };
+        let mut lines = Vec::new();
+        for statement in statements {
+            let code = self.convert(statement)?;
+            if !code.is_empty() {
+                lines.push(code);
+            }
+        }
+        let end_comments = convert_leading_comments(end_comments, false);
+        if !end_comments.is_empty() {
+            lines.push(end_comments.trim_end().to_string());
+        }
+        lines.extend(value);
+        Ok(lines.join("\n"))
+    }
+
+    /// return value;, a method which declares exceptions returns Ok, the finally blocks of the
+    /// try statements around run after the value is computed
+    fn convert_return(&mut self, value: Option<&Expr>) -> Result<String, ParseError> {
+        let context = self.context;
+        let value = match (value, self.return_type) {
+            (None, _) => None,
+            (Some(expr), MethodReturnType::TYPE(r#type)) => {
+                Some(convert_expr_to(context, expr, r#type)?)
+            }
+            (Some(expr), MethodReturnType::VOID) => Some(convert_expr(context, expr)?),
+        };
+        let value = match declares_exceptions(context) {
+            true => Some(format!("Ok({})", value.as_deref().unwrap_or("()"))),
+            false => value,
+        };
+        let finally = self.finally_blocks(0);
+        Ok(match (value, finally.is_empty()) {
+            (None, _) => self.run_finally(0, "return;".to_string()),
+            (Some(value), true) => format!("return {};", value),
+            (Some(value), false) => {
+                let ident = generate_ident(context, "value");
+                format!(
+                    "{{\nlet {} = {};\n{}\nreturn {};\n}}",
+                    ident, value, finally, ident
+                )
+            }
+        })
+    }
+
+    /// The body of a try statement becomes a labeled block which is left with the thrown
+    /// exception, the catch blocks match it. A finally block runs after the try statement and
+    /// before every jump out of it
+    ///
+    /// # Example
+    ///
+    /// try { read(); } catch (IOException e) { }
+    ///
+    /// ->
+    ///
+    /// let result: Result<(), Exception> = 'try1: { ... Ok(()) };
+    /// match result { Err(e) if e.is_instance_of("IOException") => {} ... }
+    fn convert_try(&mut self, comments: &NodeComments, r#try: &Try) -> Result<String, ParseError> {
+        let context = self.context;
+        // The finally block is converted first, so the jumps out of the try block can run it
+        let finally = match &r#try.finally {
+            Some(finally) => Some(self.convert_contents(finally)?),
+            None => None,
+        };
+        let finally_code = finally.as_ref().map(|(_, inner)| inner.clone());
+        let result = generate_ident(context, "result");
+        let result_type = format!("Result<(), {}>", exception_type(context));
+        let label = generate_label(context, "try");
+        let raised = raised_count(context);
+        let (trailing, inner) = self.convert_try_block(&label, &r#try.body, &finally_code)?;
+        for catch in &r#try.catches {
+            check_caught(context, raised, &catch.classes);
+        }
+        let header = format!("let {}: {} = {}:", result, result_type, label);
+        let mut lines = vec![wrap(comments, &header, &trailing, &inner) + ";"];
+
+        let mut arms = Vec::new();
+        for catch in &r#try.catches {
+            let ident = convert_ident(context, &catch.name);
+            let condition: Vec<String> = catch
+                .classes
+                .iter()
+                .map(|(class, _)| format!("{}.is_instance_of({:?})", ident, class))
+                .collect();
+            let header = format!("Err({}) if {} =>", ident, condition.join(" || "));
+            let arm = match &finally_code {
+                Some(_) => {
+                    let label = generate_label(context, "catch");
+                    let (trailing, inner) =
+                        self.convert_try_block(&label, &catch.body, &finally_code)?;
+                    let header = format!("{} {}:", header, label);
+                    wrap(&catch.comments, &header, &trailing, &inner)
+                }
+                None => {
+                    let (trailing, inner) = self.convert_contents(&catch.body)?;
+                    wrap(&catch.comments, &header, &trailing, &inner)
+                }
+            };
+            arms.push(arm);
+        }
+
+        let Some((finally_trailing, finally_inner)) = finally else {
+            let ok = match r#try.body.completes_normally() {
+                true => "{}",
+                false => "unreachable!()",
+            };
+            lines.push(format!(
+                "match {} {{\n{}\nErr(error) => {{\n{}\n}}\nOk(()) => {},\n}}",
+                result,
+                arms.join("\n"),
+                convert_throw(context, "error"),
+                ok
+            ));
+            return Ok(lines.join("\n"));
+        };
+        if !arms.is_empty() {
+            lines.push(format!(
+                "let {0}: {1} = match {0} {{\n{2}\n{0} => {0},\n}};",
+                result,
+                result_type,
+                arms.join("\n")
+            ));
+        }
+        lines.push(wrap(
+            &NodeComments::default(),
+            "",
+            &finally_trailing,
+            &finally_inner,
+        ));
+        lines.push(match r#try.completes_normally() {
+            true => format!(
+                "if let Err(error) = {} {{\n{}\n}}",
+                result,
+                convert_throw(context, "error")
+            ),
+            false => format!(
+                "let Err(error) = {} else {{\nunreachable!()\n}};\n{}",
+                result,
+                convert_throw(context, "error")
+            ),
+        });
+        Ok(lines.join("\n"))
+    }
+
+    /// The block of a try statement or of a catch, its value is Ok when it completes
+    fn convert_try_block(
+        &mut self,
+        label: &str,
+        body: &Statement,
+        finally: &Option<String>,
+    ) -> Result<(Vec<Trivia>, String), ParseError> {
+        let context = self.context;
+        self.jumps.push(Jump {
+            java_label: None,
+            label: label.to_string(),
+            body_label: None,
+            kind: JumpKind::Try(finally.clone()),
+            used: true,
+        });
+        let contents = with_try_label(context, label, || self.convert_contents(body));
+        self.jumps.pop();
+        let (trailing, mut inner) = contents?;
+        if body.completes_normally() {
+            inner.push_str("\nOk(())");
+        }
+        Ok((trailing, inner))
+    }
+
+    /// Runs the finally blocks which a jump out of jumps[start..] leaves before the jump
+    fn run_finally(&self, start: usize, code: String) -> String {
+        let finally = self.finally_blocks(start);
+        match finally.is_empty() {
+            true => code,
+            false => format!("{}\n{}", finally, code),
+        }
+    }
+
+    /// The finally blocks of the try statements in jumps[start..], the innermost first
+    fn finally_blocks(&self, start: usize) -> String {
+        let blocks: Vec<String> = self.jumps[start..]
+            .iter()
+            .rev()
+            .filter_map(|jump| match &jump.kind {
+                JumpKind::Try(Some(finally)) => Some(format!("{{\n{}\n}}", finally)),
+                _ => None,
+            })
+            .collect();
+        blocks.join("\n")
+    }
+
+    /// yield value; which is not at the end of a case leaves the block around the match
+    fn convert_yield(&mut self, value: &Expr) -> Result<String, ParseError> {
+        let context = self.context;
+        for (index, jump) in self.jumps.iter_mut().enumerate().rev() {
+            if let JumpKind::Switch(Some(r#type)) = &jump.kind {
+                jump.used = true;
+                let code = format!(
+                    "break {} {};",
+                    jump.label,
+                    convert_expr_to(context, value, r#type)?
+                );
+                return Ok(self.run_finally(index + 1, code));
+            }
+        }
+        Err(ParseError::new(
+            value.span,
+            "Invalid yield: yield outside of a switch expression",
+        ))
+    }
+
+    /// An unlabeled break inside a labeled block needs the label of its loop in Rust
+    fn convert_break(&mut self, label: Option<&str>) -> Result<String, ParseError> {
+        if let Some(label) = label {
+            let Some((index, jump)) = self
+                .jumps
+                .iter_mut()
+                .enumerate()
+                .rev()
+                .find(|(_, jump)| jump.java_label.as_deref() == Some(label))
+            else {
+                unreachable!("the parser checks the labels of break");
+            };
+            jump.used = true;
+            let code = format!("break {};", jump.label);
+            return Ok(self.run_finally(index + 1, code));
+        }
+        let mut in_block = false;
+        for (index, jump) in self.jumps.iter_mut().enumerate().rev() {
+            let code = match jump.kind {
+                JumpKind::Block | JumpKind::Try(_) => {
+                    in_block = true;
+                    continue;
+                }
+                JumpKind::Loop if !in_block && jump.body_label.is_none() => "break;".to_string(),
+                // A match is no loop, so it is left through the block around it
+                JumpKind::Loop | JumpKind::Switch(_) => {
+                    jump.used = true;
+                    format!("break {};", jump.label)
+                }
+            };
+            return Ok(self.run_finally(index + 1, code));
+        }
+        unreachable!("the parser checks that break has a statement which it leaves")
+    }
+
+    /// A continue of a loop with a body block leaves this block
+    fn convert_continue(&mut self, label: Option<&str>) -> Result<String, ParseError> {
+        let mut in_block = false;
+        for (index, jump) in self.jumps.iter_mut().enumerate().rev() {
+            if jump.kind != JumpKind::Loop {
+                in_block = true;
+                continue;
+            }
+            if label.is_some() && jump.java_label.as_deref() != label {
+                continue;
+            }
+            let code = match &jump.body_label {
+                Some(body_label) => format!("break {};", body_label),
+                None if label.is_none() && !in_block => "continue;".to_string(),
+                None => {
+                    jump.used = true;
+                    format!("continue {};", jump.label)
+                }
+            };
+            return Ok(self.run_finally(index + 1, code));
+        }
+        unreachable!("the parser checks that continue has a loop")
+    }
+}
+
+/// Puts the code into braces behind the header, the trailing comments are placed behind the {
+fn wrap(comments: &NodeComments, header: &str, trailing: &[Trivia], inner: &str) -> String {
+    let trailing = [&comments.trailing[..], trailing].concat();
+    let separator = if header.is_empty() { "" } else { " " };
+    format!(
+        "{}{}{}{{{}\n{}\n}}",
+        convert_leading_comments(&comments.leading, false),
+        header,
+        separator,
+        convert_trailing_comments(&trailing),
+        inner
+    )
+}
+
+/// Puts the leading comments above the code and the trailing ones behind its first line
+fn wrap_header_comments(comments: &NodeComments, code: &str) -> String {
+    let (first, rest) = code.split_once('\n').unwrap_or((code, ""));
+    format!(
+        "{}{}{}\n{}",
+        convert_leading_comments(&comments.leading, false),
+        first,
+        convert_trailing_comments(&comments.trailing),
+        rest
+    )
+}
+
+/// The headers of the nested ifs for a chain of && operands, every pattern which declares
+/// variables gets its own if let
+///
+/// # Example
+///
+/// a && o instanceof String s && b -> ["if a", "if let Some(s) = ...", "if b"]
+fn condition_headers(context: &Context, terms: &[&Expr]) -> Result<Vec<String>, ParseError> {
+    let mut headers = Vec::new();
+    let mut plain = Vec::new();
+    for term in terms {
+        let ExprKind::InstanceOf(operand, pattern) = &term.kind else {
+            plain.push(*term);
+            continue;
+        };
+        if pattern.variables().is_empty() {
+            plain.push(*term);
+            continue;
+        }
+        if !plain.is_empty() {
+            headers.push(format!(
+                "if {}",
+                convert_expr(context, &conjunction(&plain))?
+            ));
+            plain.clear();
+        }
+        let (binding, downcast) = convert_pattern(context, operand, pattern)?;
+        headers.push(format!("if let Some({}) = {}", binding, downcast));
+    }
+    if !plain.is_empty() {
+        headers.push(format!(
+            "if {}",
+            convert_expr(context, &conjunction(&plain))?
+        ));
+    }
+    Ok(headers)
+}
+
+/// Puts the code into nested blocks behind the headers
+fn nest(headers: &[String], inner: String) -> String {
+    headers.iter().rev().fold(inner, |inner, header| {
+        format!("{} {{\n{}\n}}", header, inner)
+    })
+}
+
+/// Joins the operands with &&
+fn conjunction(terms: &[&Expr]) -> Expr {
+    let (first, rest) = terms.split_first().unwrap();
+    rest.iter().fold((*first).clone(), |left, right| Expr {
+        span: left.span.to(right.span),
+        kind: ExprKind::Binary(BinaryOp::And, Box::new(left), Box::new((*right).clone())),
+    })
+}
+
+fn has_bindings(term: &Expr) -> bool {
+    matches!(&term.kind, ExprKind::InstanceOf(_, pattern) if !pattern.variables().is_empty())
+}
+
+/// The operand and pattern of !(value instanceof Type name)
+fn negated_pattern(condition: &Expr) -> Option<(&Expr, &Pattern)> {
+    let ExprKind::Unary(UnaryOp::Not, inner) = &condition.kind else {
+        return None;
+    };
+    match &inner.kind {
+        ExprKind::InstanceOf(operand, pattern) => Some((operand, pattern)),
+        _ => None,
+    }
+}
+
+/// Case labels have to be constants, so they are valid Rust patterns
+fn convert_case_label(context: &Context, label: &Expr) -> Result<String, ParseError> {
+    match &label.kind {
+        ExprKind::Literal(_)
+        | ExprKind::Variable {
+            scope: VariableScope::Const(_),
+            ..
+        } => convert_expr(context, label),
+        ExprKind::Unary(UnaryOp::Minus, value) if matches!(value.kind, ExprKind::Literal(_)) => {
+            convert_expr(context, label)
+        }
+        _ => Err(ParseError::new(
+            label.span,
+            "Invalid case: only literals and constants are supported",
+        )),
+    }
+}
+
+fn convert_label(label: &str) -> String {
+    format!("'{}", label)
+}
+
+/// Converts a counting loop into a Rust range loop, when the body doesn't change the counter
+/// and the bound
+///
+/// # Example
+///
+/// for (int i = 0; i < n; i++) -> for i in 0..n
+///
+/// for (int i = n; i >= 0; i--) -> for i in (0..=n).rev()
+fn convert_range(
+    context: &Context,
+    init: &[Statement],
+    condition: Option<&Expr>,
+    update: &[Expr],
+    body: &Statement,
+) -> Result<Option<String>, ParseError> {
+    let [Statement {
+        kind:
+            StatementKind::Variable(NodeVariable {
+                name,
+                r#type:
+                    r#type @ VariableType::DataType(
+                        DataType::INT | DataType::LONG | DataType::SHORT | DataType::BYTE,
+                    ),
+                value: Some(start),
+                ..
+            }),
+        ..
+    }] = init
+    else {
+        return Ok(None);
+    };
+    let Some(Expr {
+        kind: ExprKind::Binary(op, counter, end),
+        ..
+    }) = condition
+    else {
+        return Ok(None);
+    };
+    let [step] = update else {
+        return Ok(None);
+    };
+    let assigned = body.assigned_variables(context);
+    if !is_variable(counter, name)
+        || assigned.contains(&name.as_str())
+        || !is_stable(end, &assigned)
+    {
+        return Ok(None);
+    }
+
+    let ascending = match &step.kind {
+        ExprKind::Unary(UnaryOp::PreIncrement | UnaryOp::PostIncrement, target)
+            if is_variable(target, name) =>
+        {
+            true
+        }
+        ExprKind::Unary(UnaryOp::PreDecrement | UnaryOp::PostDecrement, target)
+            if is_variable(target, name) =>
+        {
+            false
+        }
+        ExprKind::Assign(Some(op @ (BinaryOp::Add | BinaryOp::Sub)), target, value)
+            if is_variable(target, name) && is_one(value) =>
+        {
+            *op == BinaryOp::Add
+        }
+        _ => return Ok(None),
+    };
+    let from = convert_expr_to(context, start, r#type)?;
+    let to = convert_expr_to(context, end, r#type)?;
+    let range = match (op, ascending) {
+        (BinaryOp::Less, true) => format!("{}..{}", from, to),
+        (BinaryOp::LessEqual, true) => format!("{}..={}", from, to),
+        (BinaryOp::Greater, false) => format!("({} + 1..={}).rev()", to, from),
+        (BinaryOp::GreaterEqual, false) => format!("({}..={}).rev()", to, from),
+        _ => return Ok(None),
+    };
+    Ok(Some(format!(
+        "for {} in {}",
+        convert_ident(context, name),
+        release_borrows(context, "range", range, &[start, end])
+    )))
+}
+
+fn is_variable(expr: &Expr, name: &str) -> bool {
+    matches!(
+        &expr.kind,
+        ExprKind::Variable {
+            name: variable,
+            scope: VariableScope::Local,
+            ..
+        } if variable == name
+    )
+}
+
+fn is_one(expr: &Expr) -> bool {
+    matches!(
+        &expr.kind,
+        ExprKind::Literal(Literal::Number(_, value)) if value == "1"
+    )
+}
+
+/// Checks if the value of the expression can't change in the loop, assigned are the local
+/// variables which get a new value in the loop
+fn is_stable(expr: &Expr, assigned: &[&str]) -> bool {
+    match &expr.kind {
+        ExprKind::Literal(_) => true,
+        ExprKind::Variable { name, scope, .. } => match scope {
+            VariableScope::Local => !assigned.contains(&name.as_str()),
+            VariableScope::Const(_) => true,
+            _ => false,
+        },
+        ExprKind::FieldAccess(target, name, _) => {
+            name == "length"
+                && matches!(target.r#type(), VariableType::Array(_))
+                && is_stable(target, assigned)
+        }
+        // Java Strings can't be changed
+        ExprKind::MethodCall {
+            target: Some(target),
+            name,
+            args,
+            ..
+        } => {
+            name == "length"
+                && args.is_empty()
+                && target.r#type().is_string()
+                && is_stable(target, assigned)
+        }
+        ExprKind::Unary(UnaryOp::Plus | UnaryOp::Minus, value) => is_stable(value, assigned),
+        ExprKind::Binary(op, left, right) => {
+            matches!(
+                op,
+                BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem
+            ) && is_stable(left, assigned)
+                && is_stable(right, assigned)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,104 @@
+use crate::parser::context::Context;
+
+use super::ident::generate_ident;
+
+/// Converts a decoded Java string into an escaped Rust string literal, including the quotes
+///
+/// Strings with multiple lines (e.g. from text blocks) become raw string literals,
+/// as long as they don't contain chars which need to be escaped
+pub fn convert_string_literal(value: &str) -> String {
+    let is_multi_line = value.trim_end_matches('\n').contains('\n');
+    let is_raw_safe = value
+        .chars()
+        .all(|c| c == '\n' || c == '\t' || !c.is_control());
+    if is_multi_line && is_raw_safe {
+        return convert_raw_string_literal(value);
+    }
+    // The Debug output of a str is always a valid Rust string literal
+    format!("{:?}", value)
+}
+
+/// Uses as many # as needed, so the string itself can't end the literal
+///
+/// # Example
+///
+/// say "hi" -> r#"say "hi""#
+fn convert_raw_string_literal(value: &str) -> String {
+    let mut hashes = 0;
+    for (i, _) in value.match_indices('"') {
+        let following = value[i + 1..].chars().take_while(|c| *c == '#').count();
+        hashes = hashes.max(following + 1);
+    }
+    let hashes = "#".repeat(hashes);
+    format!("r{hashes}\"{value}\"{hashes}")
+}
+
+/// Same as `convert_string_literal` but also escapes `{` and `}`, so the literal can be used as format string
+///
+/// # Example
+///
+/// "Set {a}" -> "Set {{a}}"
+pub fn convert_format_string(value: &str) -> String {
+    convert_string_literal(value)
+        .replace('{', "{{")
+        .replace('}', "}}")
+}
+
+/// Converts a decoded Java char into an escaped Rust char literal, including the quotes
+pub fn convert_char_literal(value: char) -> String {
+    // The Debug output of a char is always a valid Rust char literal
+    format!("{:?}", value)
+}
+
+/// Converts a float or double into a String like Double.toString, Rust prints 1e20 where Java
+/// prints 1.0E20, the function is generated when it is used
+///
+/// # Example
+///
+/// 1e20 -> double_to_string(1e20)
+pub fn convert_double_to_string(context: &Context, code: &str) -> String {
+    let function = context
+        .double_to_string
+        .borrow_mut()
+        .get_or_insert_with(|| generate_ident(context, "double_to_string"))
+        .clone();
+    format!("{}({})", function, code)
+}
+
+/// The function of `convert_double_to_string`, None if no float or double is printed
+///
+/// Java uses the shortest digits which still read as the same value, like Rust does, but
+/// prints values below 10^-3 and from 10^7 on with an exponent
+pub fn convert_double_to_string_fn(context: &Context) -> Option<String> {
+    let function = context.double_to_string.borrow().clone()?;
+    Some(format!(
+        "fn {function}<T: std::fmt::LowerExp>(value: T) -> String {{
+let scientific = format!(\"{{:e}}\", value);
+let (sign, scientific) = match scientific.strip_prefix('-') {{
+Some(scientific) => (\"-\", scientific),
+None => (\"\", scientific.as_str()),
+}};
+let Some((mantissa, exponent)) = scientific.split_once('e') else {{
+return match scientific {{
+\"inf\" => format!(\"{{}}Infinity\", sign),
+_ => \"NaN\".to_string(),
+}};
+}};
+let digits = mantissa.replace('.', \"\");
+let exponent: i32 = exponent.parse().unwrap();
+if digits == \"0\" {{
+return format!(\"{{}}0.0\", sign);
+}}
+if !(-3..7).contains(&exponent) {{
+let fraction = if digits.len() > 1 {{ &digits[1..] }} else {{ \"0\" }};
+return format!(\"{{}}{{}}.{{}}E{{}}\", sign, &digits[..1], fraction, exponent);
+}}
+if exponent < 0 {{
+return format!(\"{{}}0.{{}}{{}}\", sign, \"0\".repeat((-exponent - 1) as usize), digits);
+}}
+let point = exponent as usize + 1;
+let digits = format!(\"{{:0<1$}}\", digits, point + 1);
+format!(\"{{}}{{}}.{{}}\", sign, &digits[..point], &digits[point..])
+}}\n\n"
+    ))
+}
@@ -1,87 +1,325 @@
-use nodes::{class::NodeClass, method::NodeMethod, variable::NodeVariable};
+use context::Context;
+use convert::data_type::resolve_class;
+use nodes::{
+    annotation::NodeAnnotation,
+    class::{ClassKind, SuperType},
+    compilation_unit::CompilationUnit,
+    expr::VariableScope,
+    method::MethodArgument,
+    variable::{NodeVariable, VariableType},
+    MethodReturnType, NodeComments,
+};
 
-use crate::token::{Token, TokenType, Visibility};
+use crate::{
+    source_map::{SourceMap, Span},
+    token::{Token, Trivia, Visibility},
+};
+pub mod context;
 pub mod convert;
 pub mod nodes;
 
-pub enum Node {
-    Class(NodeClass),
-    Method(NodeMethod),
-}
-
-#[derive(Default)]
+/// Everything a method body can see of its class
+#[derive(Clone, Default)]
 pub struct ClassContext {
+    /// Name of the class
+    name: String,
     variables: Vec<ClassVariable>,
-    methods: Vec<NodeMethod>,
+    /// All methods of the class, also the ones after the current method
+    methods: Vec<ClassMethod>,
+    /// All classes and interfaces of the file
+    classes: Vec<ClassType>,
+}
+
+impl ClassContext {
+    /// Static final fields with a constant value become Rust constants, other static fields
+    /// become static variables
+    fn scope_of(&self, variable: &ClassVariable) -> VariableScope {
+        match variable.is_static {
+            true if variable.is_const() => VariableScope::Const(self.name.clone()),
+            true => VariableScope::Static(self.name.clone()),
+            false => VariableScope::Field,
+        }
+    }
+
+    fn get_method(&self, name: &str) -> Option<&ClassMethod> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+
+    /// The constructors of the current class
+    fn constructors(&self, context: &Context) -> &[ClassMethod] {
+        self.get_class(context, &self.name)
+            .map_or(&[], |class| class.constructors.as_slice())
+    }
+
+    /// A class of the input, a simple name is looked up in the classes around the current code
+    fn get_class(&self, context: &Context, name: &str) -> Option<&ClassType> {
+        let rust_name = resolve_class(context, name)?;
+        self.classes
+            .iter()
+            .find(|class| class.rust_name() == rust_name)
+    }
+
+    /// The instance methods of the interfaces of the file which the super types extend or
+    /// implement, directly or through other interfaces, with their type arguments filled in
+    fn inherited_methods(&self, context: &Context, super_types: &[SuperType]) -> Vec<ClassMethod> {
+        let mut methods = Vec::new();
+        for super_type in super_types {
+            let Some(class) = self
+                .get_class(context, &super_type.name)
+                .filter(|class| class.kind == ClassKind::Interface)
+            else {
+                continue;
+            };
+            let args: Vec<VariableType> = match super_type.args.len() == class.type_params.len() {
+                true => super_type.args.iter().map(VariableType::unboxed).collect(),
+                false => vec![VariableType::Inferred; class.type_params.len()],
+            };
+            let substitute = |r#type: &VariableType| r#type.substitute(&class.type_params, &args);
+            methods.extend(
+                class
+                    .methods
+                    .iter()
+                    .filter(|method| !method.is_static)
+                    .map(|method| ClassMethod {
+                        return_type: match &method.return_type {
+                            MethodReturnType::TYPE(r#type) => {
+                                MethodReturnType::TYPE(substitute(r#type))
+                            }
+                            MethodReturnType::VOID => MethodReturnType::VOID,
+                        },
+                        params: method.params.iter().map(substitute).collect(),
+                        ..method.clone()
+                    }),
+            );
+            let super_types: Vec<SuperType> = class
+                .interfaces
+                .iter()
+                .map(|interface| SuperType {
+                    name: interface.name.clone(),
+                    args: interface.args.iter().map(substitute).collect(),
+                })
+                .collect();
+            methods.extend(self.inherited_methods(context, &super_types));
+        }
+        methods
+    }
+
+    /// The components of a record of the file, with the type arguments of r#type filled in
+    fn record_components(
+        &self,
+        context: &Context,
+        r#type: &VariableType,
+    ) -> Option<Vec<MethodArgument>> {
+        let (name, args) = match r#type {
+            VariableType::Class(name) => (name, &[][..]),
+            VariableType::Generic(name, args) => (name, &args[..]),
+            _ => return None,
+        };
+        let class = self.get_class(context, name)?;
+        if class.kind != ClassKind::Record {
+            return None;
+        }
+        // A raw record type gives no type arguments, its components are inferred
+        let args = match args.len() == class.type_params.len() {
+            true => args.iter().map(VariableType::unboxed).collect(),
+            false => vec![VariableType::Inferred; class.type_params.len()],
+        };
+        let components = class
+            .components
+            .iter()
+            .map(|component| MethodArgument {
+                r#type: component.r#type.substitute(&class.type_params, &args),
+                ..component.clone()
+            })
+            .collect();
+        Some(components)
+    }
+
+    /// The declared type of a field of a class of the file, Inferred when it is not known
+    fn field_type(&self, context: &Context, class: &str, name: &str) -> VariableType {
+        self.get_class(context, class)
+            .and_then(|class| class.fields.iter().find(|field| field.name == name))
+            .map_or(VariableType::Inferred, |field| field.r#type.clone())
+    }
+
+    /// A static field of a class of the file, accessed through the class, e.g. Counter.count
+    fn get_static_field(
+        &self,
+        context: &Context,
+        class: &str,
+        name: &str,
+    ) -> Option<(NodeVariable, VariableScope)> {
+        let class = self.get_class(context, class)?;
+        let field = class
+            .fields
+            .iter()
+            .find(|field| field.is_static && field.name == name)?;
+        let variable = NodeVariable {
+            name: name.to_string(),
+            r#type: field.r#type.clone(),
+            value: None,
+            initialized: true,
+            mutable: false,
+            comments: NodeComments::default(),
+        };
+        let scope = match field.is_const {
+            true => VariableScope::Const(class.name.clone()),
+            false => VariableScope::Static(class.name.clone()),
+        };
+        Some((variable, scope))
+    }
+
+    /// A constant of an enum of the file, it is used like a constant of the enum type
+    fn get_enum_constant(
+        &self,
+        context: &Context,
+        class: &str,
+        name: &str,
+    ) -> Option<(NodeVariable, VariableScope)> {
+        let class = self.get_class(context, class)?;
+        if !class.constants.iter().any(|constant| constant == name) {
+            return None;
+        }
+        let variable = NodeVariable {
+            name: name.to_string(),
+            r#type: VariableType::Class(class.name.clone()),
+            value: None,
+            initialized: true,
+            mutable: false,
+            comments: NodeComments::default(),
+        };
+        Some((variable, VariableScope::Const(class.name.clone())))
+    }
 }
 
+/// A class, interface or enum of the file, with the signatures of its methods
+#[derive(Clone)]
+pub struct ClassType {
+    name: String,
+    /// The Rust name of the class around a nested class
+    outer: Option<String>,
+    kind: ClassKind,
+    /// The names of the type parameters, the types of the methods use them
+    type_params: Vec<String>,
+    /// The simple name of the class which a class extends
+    super_class: Option<String>,
+    /// The implemented interfaces, for an interface the ones it extends
+    interfaces: Vec<SuperType>,
+    /// The constants of an enum
+    constants: Vec<String>,
+    methods: Vec<ClassMethod>,
+    /// The constructors of a class, they are called new
+    constructors: Vec<ClassMethod>,
+    fields: Vec<ClassField>,
+    /// The components of a record, empty for other kinds
+    components: Vec<MethodArgument>,
+}
+
+impl ClassType {
+    /// Nested classes get the Rust name of their outer class as prefix
+    fn rust_name(&self) -> String {
+        format!("{}{}", self.outer.as_deref().unwrap_or_default(), self.name)
+    }
+}
+
+/// A field of a class, it is known before the method bodies are parsed
+#[derive(Clone)]
+pub struct ClassField {
+    name: String,
+    r#type: VariableType,
+    is_static: bool,
+    /// Static, final and with a constant value, so it is a Rust constant
+    is_const: bool,
+}
+
+#[derive(Clone)]
 pub struct ClassVariable {
     visibility: Visibility,
     is_static: bool,
+    is_final: bool,
+    annotations: Vec<NodeAnnotation>,
     variable: NodeVariable,
 }
 
-/// Usally parses all tokens of one file
-pub fn parse_tokens(tokens: &Vec<Token>) -> Result<Vec<Node>, String> {
-    let mut tokens = tokens.iter().peekable();
-    let mut nodes = Vec::new();
-
-    let mut class_context = ClassContext::default();
-
-    let mut is_static = false;
-    let mut current_visibility = Visibility::NONE;
-
-    while let Some(token) = tokens.next() {
-        let tokens_clone = tokens.clone();
-        if let Ok(variable) = NodeVariable::parse(&mut tokens, &class_context, &[]) {
-            dbg!("aa");
-            class_context.variables.push(ClassVariable {
-                visibility: current_visibility.clone(),
-                is_static,
-                variable,
-            });
-        } else {
-            tokens = tokens_clone;
-        }
+impl ClassVariable {
+    /// final primitives and Strings with a value can be a Rust const
+    fn is_const(&self) -> bool {
+        let is_const_type = match &self.variable.r#type {
+            VariableType::DataType(_) => true,
+            r#type => r#type.is_string(),
+        };
+        self.is_final && is_const_type && self.variable.value.is_some()
+    }
+}
 
-        let token_type = &token.token_type;
-        match token_type {
-            TokenType::RETURN => todo!(), // Handle return statement (optional)
-            TokenType::INTLIT => todo!(), // Handle integer literals outside of exit (optional)
-            TokenType::SEMICOLON => {}    // Currently ignored, consider handling semicolons
-            TokenType::DATATYPE(datatyp) => {
-                let method = NodeMethod::parse(
-                    &mut tokens,
-                    &class_context,
-                    current_visibility.clone(),
-                    is_static,
-                    nodes::MethodReturnType::DATATYPE(*datatyp),
-                )
-                .unwrap();
-                nodes.push(Node::Method(method));
-            }
-            TokenType::CLASS => {
-                let class = NodeClass::parse(&mut tokens)?;
-                nodes.push(Node::Class(class));
-            }
-            TokenType::VOID => {
-                let method = NodeMethod::parse(
-                    &mut tokens,
-                    &class_context,
-                    current_visibility.clone(),
-                    is_static,
-                    nodes::MethodReturnType::VOID,
-                )
-                .unwrap();
-                nodes.push(Node::Method(method));
-            }
-            TokenType::VISIBILITY(visibility) => current_visibility = visibility.to_owned(),
-            TokenType::STATIC => {
-                is_static = true;
-            }
-            _ => {}
+/// The signature of a method, it is known before the method bodies are parsed
+#[derive(Clone)]
+pub struct ClassMethod {
+    name: String,
+    is_static: bool,
+    /// The names of the type parameters of a generic method
+    type_params: Vec<String>,
+    return_type: MethodReturnType,
+    /// The types of the arguments
+    params: Vec<VariableType>,
+    /// Has no body, a lambda can implement an interface with exactly one of them
+    is_abstract: bool,
+    /// Declares exceptions, so it returns a Result
+    throws: bool,
+    /// The Rust name of an overload which is declared after another method with the same
+    /// name, e.g. add_double_double
+    overload: Option<String>,
+    /// Which arguments take any closure as impl Fn, see scan_closure_params
+    closure_params: Vec<bool>,
+}
+
+/// A syntax error or code which can't be converted, a file can have multiple of them
+#[derive(Clone, Debug)]
+pub struct ParseError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
         }
     }
 
-    Ok(nodes) // Parsing successful
+    /// The input ended before a node was complete, the error is placed at the last token
+    pub fn end_of_input(context: &Context, message: impl Into<String>) -> Self {
+        let span = context.end_span.get().expect("end of input without tokens");
+        Self::new(span, message)
+    }
+
+    /// The error with the location of its span, e.g. Main.java:3:9-12: Unknown identifier x
+    pub fn display(&self, source_map: &SourceMap) -> String {
+        format!("{}: {}", source_map.location(self.span), self.message)
+    }
+}
+
+/// Keeps the errors of the skipped statements of a lambda body or switch expression, the
+/// expression itself is still parsed
+pub fn report_nested_errors(context: &Context, errors: Vec<ParseError>) {
+    context.nested_errors.borrow_mut().extend(errors);
+}
+
+/// Usally parses all tokens of one file, also returns all syntax errors of the file
+///
+/// The statements and members with errors are skipped, so the unit holds everything else
+pub fn parse_tokens(
+    context: &Context,
+    tokens: &[Token],
+    end_comments: Vec<Trivia>,
+) -> (CompilationUnit, Vec<ParseError>) {
+    context.end_span.set(tokens.last().map(|token| token.span));
+    let mut tokens = tokens.iter().peekable();
+    let mut errors = Vec::new();
+    let mut unit = CompilationUnit::parse(context, &mut tokens, &mut errors);
+    unit.end_comments.extend(end_comments);
+    errors.extend(context.nested_errors.take());
+    errors.sort_by_key(|error| error.span.start);
+    (unit, errors)
 }
@@ -0,0 +1,266 @@
+use std::{iter::Peekable, slice::Iter};
+
+use crate::{
+    parser::{context::Context, ParseError},
+    source_map::Span,
+    token::{Token, TokenType},
+};
+
+use super::{consumed_tokens, parse_qualified_name};
+
+/// @Override, @SuppressWarnings("unused") or @Deprecated(since = "9", forRemoval = true)
+#[derive(Clone, Debug)]
+pub struct NodeAnnotation {
+    pub name: String,
+    /// The elements with their values, the single value of @SuppressWarnings("unused") is
+    /// the element value
+    pub elements: Vec<(String, AnnotationValue)>,
+    /// The source text in one line, unknown annotations are kept as comment
+    pub text: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum AnnotationValue {
+    /// "unused"
+    String(String),
+    /// {"unused", "unchecked"}
+    Array(Vec<AnnotationValue>),
+    /// Every other value, e.g. true, Main.class or another annotation
+    Other(String),
+}
+
+impl NodeAnnotation {
+    /// Parses all annotations in front of a declaration, starting at the first @
+    ///
+    /// @interface starts the declaration of an annotation, so it is no annotation
+    pub fn parse_list(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+    ) -> Result<Vec<Self>, ParseError> {
+        let mut annotations = Vec::new();
+        while tokens
+            .peek()
+            .is_some_and(|token| token.token_type == TokenType::AT)
+            && tokens
+                .clone()
+                .nth(1)
+                .is_some_and(|token| token.token_type != TokenType::INTERFACE)
+        {
+            annotations.push(Self::parse(context, tokens)?);
+        }
+        Ok(annotations)
+    }
+
+    /// Parses one annotation, starting at its @
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+    ) -> Result<Self, ParseError> {
+        let start = tokens.clone();
+        let at = match tokens.next() {
+            Some(token) if token.token_type == TokenType::AT => token,
+            Some(token) => {
+                return Err(ParseError::new(
+                    token.span,
+                    "Invalid annotation: expected @",
+                ))
+            }
+            None => {
+                return Err(ParseError::end_of_input(
+                    context,
+                    "Invalid annotation: Unexpected end of input",
+                ))
+            }
+        };
+        let name = parse_qualified_name(context, tokens)?;
+        let mut elements = Vec::new();
+        if tokens
+            .next_if(|token| token.token_type == TokenType::OPEN_BRACE)
+            .is_some()
+        {
+            // @A(x) is the short form of @A(value = x)
+            let is_single_value = !tokens
+                .clone()
+                .nth(1)
+                .is_some_and(|token| token.token_type == TokenType::EQUAL);
+            while tokens
+                .peek()
+                .is_some_and(|token| token.token_type != TokenType::CLOSE_BRACE)
+            {
+                let name = match is_single_value {
+                    true => "value".to_string(),
+                    false => {
+                        let name = parse_qualified_name(context, tokens)?;
+                        match tokens.next() {
+                            Some(token) if token.token_type == TokenType::EQUAL => name,
+                            Some(token) => {
+                                return Err(ParseError::new(
+                                    token.span,
+                                    "Invalid annotation: expected =",
+                                ))
+                            }
+                            None => {
+                                return Err(ParseError::end_of_input(
+                                    context,
+                                    "Invalid annotation: expected =",
+                                ))
+                            }
+                        }
+                    }
+                };
+                elements.push((name, AnnotationValue::parse(context, tokens)?));
+                if tokens
+                    .next_if(|token| token.token_type == TokenType::COMMA)
+                    .is_none()
+                {
+                    break;
+                }
+            }
+            match tokens.next() {
+                Some(token) if token.token_type == TokenType::CLOSE_BRACE => {}
+                Some(token) => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid annotation: expected )",
+                    ))
+                }
+                None => {
+                    return Err(ParseError::end_of_input(
+                        context,
+                        format!("Invalid annotation @{}: expected )", name),
+                    ))
+                }
+            }
+        }
+        let end = consumed_tokens(&start, tokens)
+            .last()
+            .map_or(at.span, |token| token.span);
+        let span = at.span.to(end);
+        let text = context
+            .source_map
+            .text(span)
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(Self {
+            name,
+            elements,
+            text,
+            span,
+        })
+    }
+
+    /// Checks the name, the package is optional, e.g. Override or java.lang.Override
+    pub fn is(&self, name: &str) -> bool {
+        self.name.rsplit('.').next() == Some(name)
+    }
+
+    /// The strings of an element, a single string is used like an array with one element
+    ///
+    /// # Example
+    ///
+    /// @SuppressWarnings({"unused", "unchecked"}) -> ["unused", "unchecked"]
+    pub fn strings(&self, element: &str) -> Vec<&str> {
+        let mut strings = Vec::new();
+        for (name, value) in &self.elements {
+            if name == element {
+                value.collect_strings(&mut strings);
+            }
+        }
+        strings
+    }
+
+    /// The value of an element, None if the annotation has no such element
+    pub fn value(&self, element: &str) -> Option<&AnnotationValue> {
+        self.elements
+            .iter()
+            .find(|(name, _)| name == element)
+            .map(|(_, value)| value)
+    }
+}
+
+impl AnnotationValue {
+    /// Parses the value of an element, a value which is no string or array is only skipped
+    fn parse(context: &Context, tokens: &mut Peekable<Iter<Token>>) -> Result<Self, ParseError> {
+        let Some(token) = tokens.peek().copied() else {
+            return Err(ParseError::end_of_input(
+                context,
+                "Invalid annotation: expected value",
+            ));
+        };
+        match &token.token_type {
+            TokenType::STRING_LITERAL(_)
+                if tokens.clone().nth(1).is_some_and(|next| {
+                    matches!(
+                        next.token_type,
+                        TokenType::COMMA | TokenType::CLOSE_BRACE | TokenType::CLOSE_BRACKET
+                    )
+                }) =>
+            {
+                tokens.next();
+                Ok(Self::String(token.value.clone().unwrap()))
+            }
+            TokenType::OPEN_BRACKET => {
+                tokens.next();
+                let mut values = Vec::new();
+                while tokens
+                    .peek()
+                    .is_some_and(|token| token.token_type != TokenType::CLOSE_BRACKET)
+                {
+                    values.push(Self::parse(context, tokens)?);
+                    if tokens
+                        .next_if(|token| token.token_type == TokenType::COMMA)
+                        .is_none()
+                    {
+                        break;
+                    }
+                }
+                match tokens.next() {
+                    Some(token) if token.token_type == TokenType::CLOSE_BRACKET => {
+                        Ok(Self::Array(values))
+                    }
+                    Some(token) => Err(ParseError::new(
+                        token.span,
+                        "Invalid annotation: expected }",
+                    )),
+                    None => Err(ParseError::end_of_input(
+                        context,
+                        "Invalid annotation: expected }",
+                    )),
+                }
+            }
+            TokenType::AT => Ok(Self::Other(NodeAnnotation::parse(context, tokens)?.text)),
+            _ => {
+                // Constant expressions end at the , or the closing bracket of the element
+                let mut depth = 0;
+                let mut end = token.span;
+                while let Some(token) = tokens.peek() {
+                    match token.token_type {
+                        TokenType::OPEN_BRACE => depth += 1,
+                        TokenType::CLOSE_BRACE | TokenType::CLOSE_BRACKET if depth == 0 => break,
+                        TokenType::COMMA if depth == 0 => break,
+                        TokenType::CLOSE_BRACE => depth -= 1,
+                        _ => {}
+                    }
+                    end = token.span;
+                    tokens.next();
+                }
+                Ok(Self::Other(context.source_map.text(token.span.to(end))))
+            }
+        }
+    }
+
+    fn collect_strings<'a>(&'a self, strings: &mut Vec<&'a str>) {
+        match self {
+            Self::String(value) => strings.push(value),
+            Self::Array(values) => {
+                for value in values {
+                    value.collect_strings(strings);
+                }
+            }
+            Self::Other(_) => {}
+        }
+    }
+}
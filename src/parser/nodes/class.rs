@@ -1,28 +1,1579 @@
-use std::{iter::Peekable, slice::Iter};
+use std::{collections::HashSet, iter::Peekable, slice::Iter};
 
-use crate::token::{Token, TokenType};
+use crate::{
+    java_std::function::get_functional_method,
+    parser::{
+        context::Context,
+        convert::{
+            data_type::{resolve_class, with_class_scope, with_type_params},
+            exception::is_exception,
+        },
+        ClassContext, ClassField, ClassMethod, ClassType, ClassVariable, ParseError,
+    },
+    source_map::Span,
+    token::{DataType, Token, TokenType, Trivia, Visibility},
+};
+
+use super::{
+    annotation::NodeAnnotation,
+    consumed_tokens,
+    expr::Expr,
+    method::{MethodArgument, NodeMethod},
+    parse_qualified_name, skip_to_boundary,
+    variable::{NodeVariable, TypeParam, VariableType},
+    MethodReturnType, NodeComments,
+};
 
 pub struct NodeClass {
     pub name: String,
+    /// The span of the name, errors of the whole class are placed there
+    pub span: Span,
+    pub kind: ClassKind,
+    pub visibility: Visibility,
+    pub annotations: Vec<NodeAnnotation>,
+    /// The type parameters, e.g. T of interface Comparable<T>
+    pub type_params: Vec<TypeParam>,
+    /// The class which a class extends, it is only used to check @Override
+    pub super_class: Option<SuperType>,
+    /// The implemented interfaces, for an interface the ones it extends
+    pub interfaces: Vec<SuperType>,
+    /// The constants of an enum in the order of the source, empty for other kinds
+    pub constants: Vec<EnumConstant>,
+    /// The components of a record, empty for other kinds
+    pub components: Vec<MethodArgument>,
+    /// Fields, constructors, methods, initializers and nested classes in the order of the source
+    pub members: Vec<ClassMember>,
+    pub comments: NodeComments,
+    /// Comments before the closing }
+    pub end_comments: Vec<Trivia>,
+    /// Comments behind the closing }
+    pub close_comments: Vec<Trivia>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ClassKind {
+    Class,
+    /// Becomes a trait
+    Interface,
+    /// Becomes a Rust enum, its fields belong to a struct which every constant has
+    Enum,
+    /// Becomes a struct with a field and an accessor for every component
+    Record,
+}
+
+/// A constant of an enum with the arguments of its constructor and the members of its own body
+///
+/// # Example
+///
+/// PLUS("+") { int apply(int a, int b) { return a + b; } }
+pub struct EnumConstant {
+    pub name: String,
+    pub args: Vec<Expr>,
+    pub members: Vec<ClassMember>,
+    pub comments: NodeComments,
+}
+
+/// A type in an extends or implements clause
+///
+/// # Example
+///
+/// Comparable<Point> -> SuperType { name: Comparable, args: [Point] }
+#[derive(Clone)]
+pub struct SuperType {
+    pub name: String,
+    pub args: Vec<VariableType>,
+}
+
+/// The modifiers before a member which are converted
+struct Modifiers {
+    visibility: Visibility,
+    is_static: bool,
+    is_final: bool,
+    annotations: Vec<NodeAnnotation>,
+}
+
+pub enum ClassMember {
+    Variable(ClassVariable),
+    Constructor(NodeMethod),
+    /// The constructor of a record without arguments, its body runs before the fields are set
+    ///
+    /// # Example
+    ///
+    /// Point { if (x < 0) throw new IllegalArgumentException(); }
+    CompactConstructor(NodeMethod),
+    Method(NodeMethod),
+    /// { ... } or static { ... }
+    Initializer(NodeMethod),
+    Class(NodeClass),
 }
 
 impl NodeClass {
-    pub fn parse(tokens: &mut Peekable<Iter<Token>>) -> Result<Self, String> {
-        if let Some(token) = tokens.next() {
+    /// Parses everything after the class, interface, enum or record keyword, including the body
+    ///
+    /// classes are all classes and interfaces of the file, the errors of skipped members are added
+    /// to errors
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        kind: ClassKind,
+        visibility: Visibility,
+        classes: &[ClassType],
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let name = match tokens.next() {
+            Some(token) if token.token_type == TokenType::UNKNOWN => token,
+            Some(token) => {
+                return Err(ParseError::new(
+                    token.span,
+                    "Invalid Class expression: expected class name",
+                ))
+            }
+            None => {
+                return Err(ParseError::end_of_input(
+                    context,
+                    "Unexpected end of input while parsing expression",
+                ))
+            }
+        };
+        let simple_name = name.value.as_deref().unwrap();
+        let rust_name = resolve_class(context, simple_name).unwrap_or_else(|| simple_name.into());
+        with_class_scope(context, &rust_name, || {
+            Self::parse_declaration(context, tokens, name, kind, visibility, classes, errors)
+        })
+    }
+
+    /// Parses everything behind the name of the class, its nested classes are visible
+    fn parse_declaration(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        name: &Token,
+        kind: ClassKind,
+        visibility: Visibility,
+        classes: &[ClassType],
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let (name, span) = (name.value.clone().unwrap(), name.span);
+        let type_params = TypeParam::parse_list(tokens)?;
+        let components = match kind {
+            ClassKind::Record => NodeMethod::parse_arguments(context, tokens)?,
+            _ => Vec::new(),
+        };
+        let mut super_class = None;
+        let mut interfaces = Vec::new();
+        loop {
+            let Some(token) = tokens.next() else {
+                return Err(ParseError::end_of_input(
+                    context,
+                    format!("Invalid class {}: expected {{", name),
+                ));
+            };
             match token.token_type {
-                TokenType::UNKNOWN => {
-                    let name = token.value.clone().unwrap();
-                    Ok(Self { name })
+                TokenType::OPEN_BRACKET => break,
+                TokenType::IMPLEMENTS => interfaces = Self::parse_super_types(context, tokens)?,
+                TokenType::EXTENDS if kind == ClassKind::Interface => {
+                    interfaces = Self::parse_super_types(context, tokens)?
+                }
+                // Super classes are not converted yet
+                TokenType::EXTENDS => {
+                    super_class = Self::parse_super_types(context, tokens)?.into_iter().next()
+                }
+                // The permitted subclasses are not converted either
+                TokenType::UNKNOWN if token.is_contextual_keyword("permits") => {
+                    Self::parse_super_types(context, tokens)?;
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        token.span,
+                        format!("Invalid class {}: expected {{", name),
+                    ))
+                }
+            }
+        }
+        // Exceptions are variants of one enum, which is generated from their uses
+        if kind == ClassKind::Class && is_exception(context, &name) {
+            Self::parse_exception_body(context, tokens, &name)?;
+            return Ok(Self {
+                name,
+                span,
+                kind,
+                visibility,
+                annotations: Vec::new(),
+                type_params,
+                super_class,
+                interfaces,
+                constants: Vec::new(),
+                components,
+                members: Vec::new(),
+                comments: NodeComments::default(),
+                end_comments: Vec::new(),
+                close_comments: Vec::new(),
+            });
+        }
+        let rust_name = resolve_class(context, &name);
+        let methods = match classes
+            .iter()
+            .find(|class| Some(class.rust_name()) == rust_name)
+        {
+            Some(class) => class.methods.clone(),
+            None => {
+                scan_body(
+                    context,
+                    tokens.clone(),
+                    &name,
+                    kind,
+                    &components,
+                    &HashSet::new(),
+                )
+                .1
+            }
+        };
+        let mut class_context = ClassContext {
+            name: name.clone(),
+            // The components are the only fields of a record
+            variables: components.iter().map(component_field).collect(),
+            methods,
+            classes: classes.to_vec(),
+        };
+        // Inherited default methods are called on the current object like its own methods
+        let inherited: Vec<ClassMethod> = class_context
+            .inherited_methods(context, &interfaces)
+            .into_iter()
+            .filter(|method| class_context.get_method(&method.name).is_none())
+            .collect();
+        class_context.methods.extend(inherited);
+        // The constants of an enum are parsed after its members, so their bodies and arguments
+        // can use the fields and methods of the enum
+        let constants_start = tokens.clone();
+        if kind == ClassKind::Enum {
+            skip_enum_constants(tokens);
+        }
+        let body_start = tokens.clone();
+        let (members, end_comments) = with_type_params(context, None, &type_params, || {
+            Self::parse_body(context, tokens, kind, &mut class_context, errors)
+        });
+        let close_comments = close_comments(&body_start, tokens);
+        let constants = match kind {
+            ClassKind::Enum => Self::parse_enum_constants(
+                context,
+                &mut constants_start.clone(),
+                &class_context,
+                errors,
+            )?,
+            _ => Vec::new(),
+        };
+        Ok(Self {
+            name,
+            span,
+            kind,
+            visibility,
+            annotations: Vec::new(),
+            type_params,
+            super_class,
+            interfaces,
+            constants,
+            components,
+            members,
+            comments: NodeComments::default(),
+            end_comments,
+            close_comments,
+        })
+    }
+
+    /// RED, GREEN("green") { ... };, starting behind the { of the enum
+    ///
+    /// The ; is only needed when the enum has members, it is not consumed
+    fn parse_enum_constants(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Vec<EnumConstant>, ParseError> {
+        let mut constants = Vec::new();
+        loop {
+            let start = tokens.clone();
+            let name = match tokens.peek() {
+                Some(token) if token.token_type == TokenType::UNKNOWN => {
+                    tokens.next().unwrap().value.clone().unwrap()
+                }
+                Some(token)
+                    if matches!(
+                        token.token_type,
+                        TokenType::SEMICOLON | TokenType::CLOSE_BRACKET
+                    ) =>
+                {
+                    return Ok(constants)
+                }
+                Some(token) => {
+                    return Err(ParseError::new(
+                        token.span,
+                        format!("Invalid enum {}: expected constant", class_context.name),
+                    ))
+                }
+                None => {
+                    return Err(ParseError::end_of_input(
+                        context,
+                        "Invalid enum: Unexpected end of input",
+                    ))
+                }
+            };
+            let mut args = Vec::new();
+            if let Some(open) = tokens.next_if(|token| token.token_type == TokenType::OPEN_BRACE) {
+                if tokens
+                    .next_if(|token| token.token_type == TokenType::CLOSE_BRACE)
+                    .is_none()
+                {
+                    loop {
+                        args.push(Expr::parse(context, tokens, class_context, &[])?);
+                        match tokens.next() {
+                            Some(token) if token.token_type == TokenType::COMMA => {}
+                            Some(token) if token.token_type == TokenType::CLOSE_BRACE => break,
+                            _ => {
+                                return Err(ParseError::new(
+                                    open.span,
+                                    format!(
+                                        "Invalid enum constant {}: expected , or ) after argument",
+                                        name
+                                    ),
+                                ))
+                            }
+                        }
+                    }
+                }
+            }
+            let mut comments = NodeComments::from_tokens(consumed_tokens(&start, tokens));
+            let mut members = Vec::new();
+            if tokens
+                .next_if(|token| token.token_type == TokenType::OPEN_BRACKET)
+                .is_some()
+            {
+                // The body is an anonymous class, it sees the members of the enum
+                let body_start = tokens.clone();
+                let (body, end_comments) = Self::parse_body(
+                    context,
+                    tokens,
+                    ClassKind::Class,
+                    &mut class_context.clone(),
+                    errors,
+                );
+                members = body;
+                comments.trailing.extend(end_comments);
+                comments
+                    .trailing
+                    .extend(close_comments(&body_start, tokens));
+            }
+            match tokens.peek() {
+                Some(token) if token.token_type == TokenType::COMMA => {
+                    comments
+                        .trailing
+                        .extend(token.leading_trivia.iter().cloned());
+                    comments
+                        .trailing
+                        .extend(token.trailing_trivia.iter().cloned());
+                    tokens.next();
+                }
+                Some(token)
+                    if matches!(
+                        token.token_type,
+                        TokenType::SEMICOLON | TokenType::CLOSE_BRACKET
+                    ) => {}
+                Some(token) => {
+                    return Err(ParseError::new(
+                        token.span,
+                        format!("Invalid enum constant {}: expected , or ;", name),
+                    ))
+                }
+                None => {
+                    return Err(ParseError::end_of_input(
+                        context,
+                        "Invalid enum: Unexpected end of input",
+                    ))
+                }
+            }
+            constants.push(EnumConstant {
+                name,
+                args,
+                members,
+                comments,
+            });
+        }
+    }
+
+    /// The body of an exception class until the closing }, the variants of the exception enum
+    /// only hold the message, so the constructors can only pass their arguments to super
+    ///
+    /// # Example
+    ///
+    /// EmptyException(String message) { super(message); }
+    fn parse_exception_body(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        name: &str,
+    ) -> Result<(), ParseError> {
+        loop {
+            let Some(token) = tokens.peek().copied() else {
+                return Err(ParseError::end_of_input(
+                    context,
+                    format!(
+                        "Invalid class {}: Unexpected end of input, expected }}",
+                        name
+                    ),
+                ));
+            };
+            let is_valid = match &token.token_type {
+                TokenType::CLOSE_BRACKET => {
+                    tokens.next();
+                    return Ok(());
+                }
+                TokenType::AT => NodeAnnotation::parse(context, tokens).is_ok(),
+                TokenType::VISIBILITY(_) | TokenType::STATIC | TokenType::FINAL => {
+                    tokens.next();
+                    true
+                }
+                // The serialization of Java is not converted
+                TokenType::DATATYPE(DataType::LONG)
+                    if tokens
+                        .clone()
+                        .nth(1)
+                        .is_some_and(|next| next.value.as_deref() == Some("serialVersionUID")) =>
+                {
+                    while tokens
+                        .next()
+                        .is_some_and(|token| token.token_type != TokenType::SEMICOLON)
+                    {
+                    }
+                    true
+                }
+                TokenType::UNKNOWN if token.value.as_deref() == Some(name) => {
+                    tokens.next();
+                    Self::parse_exception_constructor(context, tokens)
+                }
+                _ => false,
+            };
+            if !is_valid {
+                return Err(ParseError::new(token.span, format!("Invalid class {}: exceptions can only pass their message and cause to super", name)));
+            }
+        }
+    }
+
+    /// (String message) { super(message); }, the arguments have to be passed to super in the
+    /// same order
+    fn parse_exception_constructor(context: &Context, tokens: &mut Peekable<Iter<Token>>) -> bool {
+        let Ok(args) = NodeMethod::parse_arguments(context, tokens) else {
+            return false;
+        };
+        let types_valid = args.iter().all(|arg| match &arg.r#type {
+            VariableType::Class(class) => arg.r#type.is_string() || is_exception(context, class),
+            _ => false,
+        });
+        // super() can be left out when there are no arguments
+        let calls_super = tokens
+            .clone()
+            .nth(1)
+            .is_some_and(|token| token.token_type == TokenType::SUPER);
+        let mut expected = vec![(TokenType::OPEN_BRACKET, None)];
+        if calls_super || !args.is_empty() {
+            expected.extend([(TokenType::SUPER, None), (TokenType::OPEN_BRACE, None)]);
+            for (index, arg) in args.iter().enumerate() {
+                if index > 0 {
+                    expected.push((TokenType::COMMA, None));
+                }
+                expected.push((TokenType::UNKNOWN, Some(arg.name.as_str())));
+            }
+            expected.extend([(TokenType::CLOSE_BRACE, None), (TokenType::SEMICOLON, None)]);
+        }
+        expected.push((TokenType::CLOSE_BRACKET, None));
+        types_valid
+            && expected.into_iter().all(|(token_type, value)| {
+                tokens
+                    .next_if(|token| {
+                        token.token_type == token_type
+                            && value.is_none_or(|value| token.value.as_deref() == Some(value))
+                    })
+                    .is_some()
+            })
+    }
+
+    /// A, B<C>, the types behind implements, extends or permits
+    pub fn parse_super_types(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+    ) -> Result<Vec<SuperType>, ParseError> {
+        let mut types = Vec::new();
+        loop {
+            let name = parse_qualified_name(context, tokens)?;
+            let args = match tokens.peek().copied() {
+                Some(open) if open.token_type == TokenType::LESS => {
+                    VariableType::parse_type_args(tokens).ok_or_else(|| {
+                        ParseError::new(open.span, "Invalid type arguments: expected types and >")
+                    })?
+                }
+                _ => Vec::new(),
+            };
+            types.push(SuperType { name, args });
+            if tokens
+                .next_if(|token| token.token_type == TokenType::COMMA)
+                .is_none()
+            {
+                return Ok(types);
+            }
+        }
+    }
+
+    /// Parses all members until the closing } of the class, returns them with the comments
+    /// before the }
+    ///
+    /// Every class has its own context, so methods only see the fields of their own class.
+    /// A member with a syntax error is skipped, so the following members are still parsed
+    fn parse_body(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        kind: ClassKind,
+        class_context: &mut ClassContext,
+        errors: &mut Vec<ParseError>,
+    ) -> (Vec<ClassMember>, Vec<Trivia>) {
+        let mut members = Vec::new();
+
+        let mut is_static = false;
+        let mut is_final = false;
+        let mut current_visibility = Visibility::NONE;
+        let mut annotations = Vec::new();
+        // Comments of modifiers and other tokens before the next member
+        let mut pending_comments = Vec::new();
+
+        loop {
+            let Some(token) = tokens.peek().copied() else {
+                errors.push(ParseError::end_of_input(
+                    context,
+                    "Invalid class: Unexpected end of input, expected }",
+                ));
+                return (members, pending_comments);
+            };
+            if token.token_type == TokenType::AT {
+                let start = tokens.clone();
+                match NodeAnnotation::parse(context, tokens) {
+                    Ok(annotation) => annotations.push(annotation),
+                    Err(error) => {
+                        errors.push(error);
+                        annotations.clear();
+                        *tokens = start.clone();
+                        skip_to_boundary(tokens);
+                    }
+                }
+                for token in consumed_tokens(&start, tokens) {
+                    pending_comments.extend(token.leading_trivia.iter().cloned());
+                    pending_comments.extend(token.trailing_trivia.iter().cloned());
+                }
+                continue;
+            }
+            let is_modifier = match &token.token_type {
+                TokenType::VISIBILITY(visibility) => {
+                    current_visibility = visibility.to_owned();
+                    true
+                }
+                TokenType::STATIC => {
+                    is_static = true;
+                    true
+                }
+                TokenType::FINAL => {
+                    is_final = true;
+                    true
+                }
+                // Modifiers which have no meaning in Rust and stray semicolons
+                TokenType::ABSTRACT
+                | TokenType::SYNCHRONIZED
+                | TokenType::NATIVE
+                | TokenType::TRANSIENT
+                | TokenType::VOLATILE
+                | TokenType::STRICTFP
+                | TokenType::DEFAULT
+                | TokenType::SEMICOLON => true,
+                // Rust has no sealed classes, so these modifiers are just skipped
+                TokenType::UNKNOWN if token.is_contextual_keyword("sealed") => true,
+                TokenType::UNKNOWN if token.is_contextual_keyword("non-sealed") => {
+                    // non-sealed is lexed as non - sealed
+                    let mut lookahead = tokens.clone();
+                    lookahead.next();
+                    lookahead.next_if(|token| token.token_type == TokenType::MINUS);
+                    lookahead.next_if(|token| token.is_contextual_keyword("sealed"));
+                    *tokens = lookahead;
+                    pending_comments.extend(token.leading_trivia.iter().cloned());
+                    continue;
+                }
+                _ => false,
+            };
+            if is_modifier {
+                tokens.next();
+                pending_comments.extend(token.leading_trivia.iter().cloned());
+                pending_comments.extend(token.trailing_trivia.iter().cloned());
+                continue;
+            }
+
+            let mut comments = std::mem::take(&mut pending_comments);
+            if token.token_type == TokenType::CLOSE_BRACKET {
+                tokens.next();
+                comments.extend(token.leading_trivia.iter().cloned());
+                return (members, comments);
+            }
+            let mut modifiers = Modifiers {
+                visibility: std::mem::take(&mut current_visibility),
+                is_static: std::mem::take(&mut is_static),
+                is_final: std::mem::take(&mut is_final),
+                annotations: std::mem::take(&mut annotations),
+            };
+            // The members of an interface are public
+            if kind == ClassKind::Interface && modifiers.visibility == Visibility::NONE {
+                modifiers.visibility = Visibility::PUBLIC;
+            }
+            let start = tokens.clone();
+            match Self::parse_member(
+                context,
+                tokens,
+                kind,
+                class_context,
+                modifiers,
+                &mut comments,
+                errors,
+            ) {
+                Ok(member) => members.push(Self::with_leading_comments(member, comments)),
+                Err(error) => {
+                    errors.push(error);
+                    *tokens = start;
+                    skip_to_boundary(tokens);
+                }
+            }
+        }
+    }
+
+    /// Parses one field, constructor, method, initializer or nested class, starting behind its
+    /// modifiers
+    fn parse_member(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        kind: ClassKind,
+        class_context: &mut ClassContext,
+        modifiers: Modifiers,
+        comments: &mut Vec<Trivia>,
+        errors: &mut Vec<ParseError>,
+    ) -> Result<ClassMember, ParseError> {
+        let token = tokens.peek().copied().unwrap();
+        if let Some(kind) = declaration_kind(tokens.clone()) {
+            tokens.next();
+            comments.extend(token.leading_trivia.iter().cloned());
+            comments.extend(token.trailing_trivia.iter().cloned());
+            return Ok(ClassMember::Class(NodeClass {
+                annotations: modifiers.annotations,
+                ..NodeClass::parse(
+                    context,
+                    tokens,
+                    kind,
+                    modifiers.visibility,
+                    &class_context.classes,
+                    errors,
+                )?
+            }));
+        }
+        let mut member = match &token.token_type {
+            TokenType::UNKNOWN
+                if kind == ClassKind::Record
+                    && token.value.as_ref() == Some(&class_context.name)
+                    && tokens
+                        .clone()
+                        .nth(1)
+                        .is_some_and(|next| next.token_type == TokenType::OPEN_BRACKET) =>
+            {
+                // The arguments are the components, which are the fields of the record
+                let components: Vec<MethodArgument> = class_context
+                    .variables
+                    .iter()
+                    .filter(|field| !field.is_static)
+                    .map(|field| MethodArgument {
+                        r#type: field.variable.r#type.clone(),
+                        name: field.variable.name.clone(),
+                        mutable: false,
+                        annotations: Vec::new(),
+                    })
+                    .collect();
+                ClassMember::CompactConstructor(NodeMethod::parse_compact_constructor(
+                    context,
+                    tokens,
+                    class_context,
+                    modifiers.visibility,
+                    &components,
+                    errors,
+                )?)
+            }
+            TokenType::OPEN_BRACKET => ClassMember::Initializer(NodeMethod::parse_initializer(
+                context,
+                tokens,
+                class_context,
+                modifiers.is_static,
+                errors,
+            )?),
+            TokenType::UNKNOWN
+                if token.value.as_ref() == Some(&class_context.name)
+                    && tokens
+                        .clone()
+                        .nth(1)
+                        .is_some_and(|next| next.token_type == TokenType::OPEN_BRACE) =>
+            {
+                ClassMember::Constructor(NodeMethod::parse_constructor(
+                    context,
+                    tokens,
+                    class_context,
+                    modifiers.visibility,
+                    errors,
+                )?)
+            }
+            // Generic methods start with their type parameters
+            TokenType::LESS => {
+                let start = tokens.clone();
+                let type_params = TypeParam::parse_list(tokens)?;
+                for token in consumed_tokens(&start, tokens) {
+                    comments.extend(token.leading_trivia.iter().cloned());
+                    comments.extend(token.trailing_trivia.iter().cloned());
+                }
+                let return_type = Self::parse_return_type(context, tokens, comments)?;
+                ClassMember::Method(NodeMethod::parse(
+                    context,
+                    tokens,
+                    class_context,
+                    modifiers.visibility,
+                    modifiers.is_static,
+                    return_type,
+                    type_params,
+                    errors,
+                )?)
+            }
+            _ => {
+                let tokens_clone = tokens.clone();
+                let variable = match NodeVariable::parse(context, tokens, class_context, &[]) {
+                    Ok(variable) => Some(variable),
+                    // Only a method has a ( after the name
+                    Err(_) if Self::is_method(tokens_clone.clone()) => None,
+                    // A field whose value has a syntax error is kept without it, see parse_declared
+                    Err(error) => match NodeVariable::parse_declared(&mut tokens_clone.clone()) {
+                        Some(variable) => {
+                            errors.push(error);
+                            *tokens = tokens_clone.clone();
+                            skip_to_boundary(tokens);
+                            Some(variable)
+                        }
+                        None => return Err(error),
+                    },
+                };
+                match variable {
+                    Some(mut variable) => {
+                        // The scan found the fields which can be null
+                        let r#type =
+                            class_context.field_type(context, &class_context.name, &variable.name);
+                        if r#type.is_nullable() {
+                            variable.r#type = r#type;
+                        }
+                        // The fields of an interface are constants
+                        let is_constant = kind == ClassKind::Interface;
+                        let variable = ClassVariable {
+                            visibility: modifiers.visibility,
+                            is_static: modifiers.is_static || is_constant,
+                            is_final: modifiers.is_final || is_constant,
+                            annotations: Vec::new(),
+                            variable,
+                        };
+                        class_context.variables.push(variable.clone());
+                        ClassMember::Variable(variable)
+                    }
+                    None => {
+                        *tokens = tokens_clone;
+                        let return_type = Self::parse_return_type(context, tokens, comments)?;
+                        ClassMember::Method(NodeMethod::parse(
+                            context,
+                            tokens,
+                            class_context,
+                            modifiers.visibility,
+                            modifiers.is_static,
+                            return_type,
+                            Vec::new(),
+                            errors,
+                        )?)
+                    }
+                }
+            }
+        };
+        match &mut member {
+            ClassMember::Variable(variable) => variable.annotations = modifiers.annotations,
+            ClassMember::Constructor(method)
+            | ClassMember::CompactConstructor(method)
+            | ClassMember::Method(method)
+            | ClassMember::Initializer(method) => method.annotations = modifiers.annotations,
+            ClassMember::Class(class) => class.annotations = modifiers.annotations,
+        }
+        Ok(member)
+    }
+
+    /// Parses the return type of a method, the comments of the type are added to comments
+    fn parse_return_type(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        comments: &mut Vec<Trivia>,
+    ) -> Result<MethodReturnType, ParseError> {
+        let start = tokens.clone();
+        let return_type = match tokens.next_if(|token| token.token_type == TokenType::VOID) {
+            Some(_) => MethodReturnType::VOID,
+            None => match VariableType::parse(tokens) {
+                Some(r#type) => MethodReturnType::TYPE(r#type),
+                None => {
+                    return Err(match tokens.peek() {
+                        Some(token) => {
+                            ParseError::new(token.span, "Invalid class: expected member")
+                        }
+                        None => ParseError::end_of_input(
+                            context,
+                            "Invalid class: Unexpected end of input",
+                        ),
+                    })
+                }
+            },
+        };
+        for token in consumed_tokens(&start, tokens) {
+            comments.extend(token.leading_trivia.iter().cloned());
+            comments.extend(token.trailing_trivia.iter().cloned());
+        }
+        Ok(return_type)
+    }
+
+    fn is_method(mut tokens: Peekable<Iter<Token>>) -> bool {
+        let has_type = tokens
+            .next_if(|token| token.token_type == TokenType::VOID)
+            .is_some()
+            || VariableType::parse(&mut tokens).is_some();
+        has_type
+            && tokens
+                .next_if(|token| token.token_type == TokenType::UNKNOWN)
+                .is_some()
+            && tokens
+                .peek()
+                .is_some_and(|token| token.token_type == TokenType::OPEN_BRACE)
+    }
+
+    fn with_leading_comments(mut member: ClassMember, comments: Vec<Trivia>) -> ClassMember {
+        let member_comments = match &mut member {
+            ClassMember::Variable(variable) => &mut variable.variable.comments,
+            ClassMember::Constructor(method)
+            | ClassMember::CompactConstructor(method)
+            | ClassMember::Method(method)
+            | ClassMember::Initializer(method) => &mut method.comments,
+            ClassMember::Class(class) => &mut class.comments,
+        };
+        member_comments.leading.splice(0..0, comments);
+        member
+    }
+}
+
+/// Collects the names of the enum constants and the signatures of the methods of a class body,
+/// tokens has to be behind the { of the class
+///
+/// Enums also get the methods which every enum has, e.g. ordinal()
+pub fn scan_body(
+    context: &Context,
+    mut tokens: Peekable<Iter<Token>>,
+    class_name: &str,
+    kind: ClassKind,
+    components: &[MethodArgument],
+    null_checks: &HashSet<String>,
+) -> (Vec<String>, Vec<ClassMethod>) {
+    match kind {
+        ClassKind::Class => {
+            return (
+                Vec::new(),
+                scan_methods(context, tokens, class_name, null_checks),
+            )
+        }
+        // The methods of a trait keep the shared closures, so it can be a trait object
+        ClassKind::Interface => {
+            let mut methods = scan_methods(context, tokens, class_name, null_checks);
+            for method in &mut methods {
+                method.closure_params.clear();
+            }
+            return (Vec::new(), methods);
+        }
+        ClassKind::Record => {
+            let mut methods = scan_methods(context, tokens, class_name, null_checks);
+            // Every component has an accessor, unless the record declares it itself
+            let mut built_ins: Vec<(&str, VariableType, Vec<VariableType>)> = components
+                .iter()
+                .map(|component| (component.name.as_str(), component.r#type.clone(), vec![]))
+                .collect();
+            built_ins.push((
+                "equals",
+                VariableType::DataType(DataType::BOOLEAN),
+                vec![VariableType::Class(class_name.to_string())],
+            ));
+            built_ins.push(("toString", VariableType::string(), vec![]));
+            built_ins.push(("hashCode", VariableType::DataType(DataType::INT), vec![]));
+            for (name, return_type, params) in built_ins {
+                if !methods.iter().any(|method| method.name == name) {
+                    methods.push(ClassMethod {
+                        name: name.to_string(),
+                        is_static: false,
+                        type_params: Vec::new(),
+                        return_type: MethodReturnType::TYPE(return_type),
+                        params,
+                        is_abstract: false,
+                        throws: false,
+                        overload: None,
+                        closure_params: Vec::new(),
+                    });
+                }
+            }
+            return (Vec::new(), methods);
+        }
+        ClassKind::Enum => {}
+    }
+    let mut constants = Vec::new();
+    let mut depth = 0;
+    let mut expects_name = true;
+    for token in tokens.clone() {
+        match token.token_type {
+            TokenType::OPEN_BRACE | TokenType::OPEN_BRACKET => depth += 1,
+            TokenType::CLOSE_BRACE | TokenType::CLOSE_BRACKET if depth == 0 => break,
+            TokenType::CLOSE_BRACE | TokenType::CLOSE_BRACKET => depth -= 1,
+            TokenType::SEMICOLON if depth == 0 => break,
+            TokenType::COMMA if depth == 0 => expects_name = true,
+            TokenType::UNKNOWN if depth == 0 && expects_name => {
+                constants.push(token.value.clone().unwrap());
+                expects_name = false;
+            }
+            _ => {}
+        }
+    }
+    skip_enum_constants(&mut tokens);
+    let mut methods = scan_methods(context, tokens, class_name, null_checks);
+    let this = VariableType::Class(class_name.to_string());
+    let built_ins = [
+        (
+            "values",
+            true,
+            VariableType::Array(Box::new(this.clone())),
+            vec![],
+        ),
+        ("valueOf", true, this.clone(), vec![VariableType::string()]),
+        (
+            "ordinal",
+            false,
+            VariableType::DataType(DataType::INT),
+            vec![],
+        ),
+        ("name", false, VariableType::string(), vec![]),
+        (
+            "compareTo",
+            false,
+            VariableType::DataType(DataType::INT),
+            vec![this],
+        ),
+    ];
+    for (name, is_static, return_type, params) in built_ins {
+        methods.push(ClassMethod {
+            name: name.to_string(),
+            is_static,
+            type_params: Vec::new(),
+            return_type: MethodReturnType::TYPE(return_type),
+            params,
+            is_abstract: false,
+            throws: false,
+            overload: None,
+            closure_params: Vec::new(),
+        });
+    }
+    (constants, methods)
+}
+
+/// The kind of the type which is declared at the start of tokens, None if there is no
+/// declaration
+///
+/// record is no keyword, so it is only a declaration when a name and ( or < follow
+pub fn declaration_kind(mut tokens: Peekable<Iter<Token>>) -> Option<ClassKind> {
+    let token = tokens.next()?;
+    match token.token_type {
+        TokenType::CLASS => Some(ClassKind::Class),
+        TokenType::INTERFACE => Some(ClassKind::Interface),
+        TokenType::ENUM => Some(ClassKind::Enum),
+        _ if token.is_contextual_keyword("record")
+            && tokens
+                .next()
+                .is_some_and(|token| token.token_type == TokenType::UNKNOWN)
+            && tokens.peek().is_some_and(|token| {
+                matches!(token.token_type, TokenType::OPEN_BRACE | TokenType::LESS)
+            }) =>
+        {
+            Some(ClassKind::Record)
+        }
+        _ => None,
+    }
+}
+
+/// The field of a record component, it is private and can't be changed
+pub fn component_field(component: &MethodArgument) -> ClassVariable {
+    ClassVariable {
+        visibility: Visibility::NONE,
+        is_static: false,
+        is_final: true,
+        annotations: Vec::new(),
+        variable: NodeVariable {
+            name: component.name.clone(),
+            r#type: component.r#type.clone(),
+            value: None,
+            initialized: true,
+            mutable: false,
+            comments: NodeComments::default(),
+        },
+    }
+}
+
+/// Skips the constants of an enum, stops behind the ; which ends them or before the } of the
+/// enum
+fn skip_enum_constants(tokens: &mut Peekable<Iter<Token>>) {
+    let mut depth = 0;
+    while let Some(token) = tokens.peek() {
+        match token.token_type {
+            TokenType::OPEN_BRACE | TokenType::OPEN_BRACKET => depth += 1,
+            TokenType::CLOSE_BRACE | TokenType::CLOSE_BRACKET if depth == 0 => return,
+            TokenType::CLOSE_BRACE | TokenType::CLOSE_BRACKET => depth -= 1,
+            TokenType::SEMICOLON if depth == 0 => {
+                tokens.next();
+                return;
+            }
+            _ => {}
+        }
+        tokens.next();
+    }
+}
+
+/// Collects the signatures of all methods of a class body, so calls can be resolved before
+/// the called method is parsed
+fn scan_methods(
+    context: &Context,
+    tokens: Peekable<Iter<Token>>,
+    class_name: &str,
+    null_checks: &HashSet<String>,
+) -> Vec<ClassMethod> {
+    scan_members(context, tokens, class_name, null_checks).0
+}
+
+/// The fields of a class body, so they can be used before their declaration, tokens start
+/// behind the {
+pub fn scan_fields(
+    context: &Context,
+    tokens: Peekable<Iter<Token>>,
+    class_name: &str,
+    null_checks: &HashSet<String>,
+) -> Vec<ClassField> {
+    scan_members(context, tokens, class_name, null_checks).1
+}
+
+/// The signatures of the constructors of a class body, they are called new, tokens start
+/// behind the {
+pub fn scan_constructors(
+    context: &Context,
+    tokens: Peekable<Iter<Token>>,
+    class_name: &str,
+    null_checks: &HashSet<String>,
+) -> Vec<ClassMethod> {
+    scan_members(context, tokens, class_name, null_checks).2
+}
+
+/// The methods and fields of a class body
+fn scan_members(
+    context: &Context,
+    mut tokens: Peekable<Iter<Token>>,
+    class_name: &str,
+    null_checks: &HashSet<String>,
+) -> (Vec<ClassMethod>, Vec<ClassField>, Vec<ClassMethod>) {
+    let mut methods = Vec::new();
+    let mut fields = Vec::new();
+    let mut constructors = Vec::new();
+    // The fields without a value, the arguments and the body of every method and constructor
+    let mut unset_fields = Vec::new();
+    let mut method_bodies = Vec::new();
+    let mut constructor_bodies = Vec::new();
+    let mut depth = 0;
+    let mut is_static = false;
+    let mut is_final = false;
+    // The first token after the modifiers, None for constructors
+    let mut member_start: Option<Peekable<Iter<Token>>> = None;
+    // Calls in field values are no methods
+    let mut in_value = false;
+    while let Some(token) = tokens.peek().copied() {
+        let start = tokens.clone();
+        tokens.next();
+        match &token.token_type {
+            TokenType::OPEN_BRACKET | TokenType::OPEN_BRACE => depth += 1,
+            TokenType::CLOSE_BRACKET | TokenType::CLOSE_BRACE if depth == 0 => break,
+            TokenType::CLOSE_BRACKET | TokenType::CLOSE_BRACE => {
+                depth -= 1;
+                // The } of an array initializer doesn't end the field
+                if depth == 0 && token.token_type == TokenType::CLOSE_BRACKET && !in_value {
+                    (member_start, is_static, is_final, in_value) = (None, false, false, false);
+                }
+            }
+            _ if depth > 0 => {}
+            TokenType::SEMICOLON => {
+                // Methods take member_start, so only fields are left
+                if let Some(start) = member_start.take() {
+                    for (field, has_value) in scan_field_names(start, is_static, is_final) {
+                        if !has_value {
+                            unset_fields.push(field.name.clone());
+                        }
+                        fields.push(field);
+                    }
+                }
+                (is_static, is_final, in_value) = (false, false, false)
+            }
+            TokenType::EQUAL => in_value = true,
+            TokenType::STATIC => is_static = true,
+            TokenType::FINAL => is_final = true,
+            TokenType::VISIBILITY(_) | TokenType::ABSTRACT => {}
+            // The arguments of an annotation are no method
+            TokenType::AT => {
+                let mut annotation = start.clone();
+                if NodeAnnotation::parse(context, &mut annotation).is_ok() {
+                    tokens = annotation;
+                }
+            }
+            TokenType::UNKNOWN
+                if !in_value
+                    && tokens
+                        .peek()
+                        .is_some_and(|next| next.token_type == TokenType::OPEN_BRACE) =>
+            {
+                let name = token.value.clone().unwrap();
+                let mut type_params = Vec::new();
+                let return_type = match member_start.take() {
+                    // Constructors have no return type
+                    None if name == class_name => None,
+                    None => Some(MethodReturnType::TYPE(VariableType::Inferred)),
+                    Some(mut start) => {
+                        type_params = TypeParam::parse_list(&mut start).unwrap_or_default();
+                        match start.peek() {
+                            Some(token) if token.token_type == TokenType::VOID => {
+                                Some(MethodReturnType::VOID)
+                            }
+                            _ => Some(MethodReturnType::TYPE(
+                                VariableType::parse(&mut start).unwrap_or(VariableType::Inferred),
+                            )),
+                        }
+                    }
+                };
+                // Errors are reported when the method itself is parsed
+                let mut header = tokens.clone();
+                let args = NodeMethod::parse_arguments(context, &mut header).unwrap_or_default();
+                // The exceptions of the file may not be declared yet, so the clause is skipped
+                let throws = header
+                    .peek()
+                    .is_some_and(|token| token.token_type == TokenType::THROWS);
+                let _ = NodeMethod::parse_throws(context, &mut header);
+                let body = (args.clone(), header.clone());
+                let closure_params = match is_static && return_type.is_some() {
+                    true => scan_closure_params(context, header, &args),
+                    false => Vec::new(),
+                };
+                let params = args.into_iter().map(|arg| arg.r#type).collect();
+                let method = ClassMethod {
+                    name,
+                    is_static,
+                    type_params: type_params.into_iter().map(|param| param.name).collect(),
+                    return_type: return_type.clone().unwrap_or(MethodReturnType::VOID),
+                    params,
+                    is_abstract: !has_body(tokens.clone()),
+                    throws,
+                    overload: None,
+                    closure_params,
+                };
+                match return_type {
+                    Some(_) => {
+                        methods.push(method);
+                        method_bodies.push(body);
+                    }
+                    None => {
+                        constructors.push(ClassMethod {
+                            name: "new".to_string(),
+                            is_static: true,
+                            ..method
+                        });
+                        constructor_bodies.push(body);
+                    }
+                }
+            }
+            _ if member_start.is_none() => member_start = Some(start),
+            _ => {}
+        }
+    }
+    // A field can be null when it starts with null, when a constructor doesn't set it or when
+    // the input checks a field with its name for null
+    for field in &mut fields {
+        let is_set = !field.is_static
+            && !constructor_bodies.is_empty()
+            && constructor_bodies
+                .iter()
+                .all(|(_, body)| sets_field(body.clone(), &field.name));
+        let is_unset = unset_fields.contains(&field.name) && !is_set;
+        let is_null = match &field.r#type {
+            VariableType::Nullable(_) => true,
+            _ => is_unset || null_checks.contains(&field.name),
+        };
+        if is_null && !field.is_const {
+            field.r#type = field.r#type.clone().nullable();
+        }
+    }
+    for (method, (args, body)) in methods
+        .iter_mut()
+        .zip(&method_bodies)
+        .chain(constructors.iter_mut().zip(&constructor_bodies))
+    {
+        scan_nullable(method, args, body.clone(), &fields);
+    }
+    name_overloads(&mut methods);
+    name_overloads(&mut constructors);
+    (methods, fields, constructors)
+}
+
+/// The names which the input compares with null or sets to null, a field with such a name can
+/// be null
+///
+/// # Example
+///
+/// node.next != null -> next
+pub fn scan_null_checks(tokens: Peekable<Iter<Token>>) -> HashSet<String> {
+    let tokens: Vec<&Token> = tokens.collect();
+    let mut names = HashSet::new();
+    for window in tokens.windows(3) {
+        let name = match (
+            &window[0].token_type,
+            &window[1].token_type,
+            &window[2].token_type,
+        ) {
+            (
+                TokenType::UNKNOWN,
+                TokenType::EQUAL_EQUAL | TokenType::NOT_EQUAL | TokenType::EQUAL,
+                TokenType::NULL,
+            ) => window[0],
+            (
+                TokenType::NULL,
+                TokenType::EQUAL_EQUAL | TokenType::NOT_EQUAL,
+                TokenType::UNKNOWN,
+            ) => window[2],
+            _ => continue,
+        };
+        names.insert(name.value.clone().unwrap());
+    }
+    names
+}
+
+/// The tokens of a method body, without the { and }, body starts at the {. Nothing for
+/// abstract methods
+fn body_tokens(mut body: Peekable<Iter<'_, Token>>) -> Vec<&Token> {
+    if body
+        .next_if(|token| token.token_type == TokenType::OPEN_BRACKET)
+        .is_none()
+    {
+        return Vec::new();
+    }
+    let mut depth = 0;
+    body.take_while(|token| {
+        match token.token_type {
+            TokenType::OPEN_BRACKET => depth += 1,
+            TokenType::CLOSE_BRACKET if depth == 0 => return false,
+            TokenType::CLOSE_BRACKET => depth -= 1,
+            _ => {}
+        }
+        true
+    })
+    .collect()
+}
+
+/// Checks if the constructor whose body starts at body always sets the field to a value which
+/// isn't null, only the statements of the body itself count. A constructor which calls another
+/// one with this(...) leaves the field to it
+fn sets_field(body: Peekable<Iter<Token>>, name: &str) -> bool {
+    let tokens = body_tokens(body);
+    let mut depth = 0;
+    let mut statement_start = true;
+    for (i, token) in tokens.iter().enumerate() {
+        let rest = &tokens[i..];
+        let is_name = |token: &Token| token.value.as_deref() == Some(name);
+        let value = match rest {
+            [this, dot, field, equal, value, ..]
+                if this.token_type == TokenType::THIS
+                    && dot.token_type == TokenType::DOT
+                    && is_name(field)
+                    && equal.token_type == TokenType::EQUAL =>
+            {
+                Some(value)
+            }
+            [field, equal, value, ..] if is_name(field) && equal.token_type == TokenType::EQUAL => {
+                Some(value)
+            }
+            [this, open, ..]
+                if i == 0
+                    && this.token_type == TokenType::THIS
+                    && open.token_type == TokenType::OPEN_BRACE =>
+            {
+                return true
+            }
+            _ => None,
+        };
+        if let Some(value) = value.filter(|_| depth == 0 && statement_start) {
+            if value.token_type != TokenType::NULL {
+                return true;
+            }
+        }
+        match token.token_type {
+            TokenType::OPEN_BRACKET | TokenType::OPEN_BRACE => depth += 1,
+            TokenType::CLOSE_BRACKET | TokenType::CLOSE_BRACE => depth -= 1,
+            _ => {}
+        }
+        statement_start = matches!(
+            token.token_type,
+            TokenType::SEMICOLON | TokenType::OPEN_BRACKET | TokenType::CLOSE_BRACKET
+        );
+    }
+    false
+}
+
+/// Marks the arguments and the return value of a method which can be null. An argument can be
+/// null when the body compares it with null or sets a field which can be null to it. A method
+/// can return null when it returns null, such a field or such an argument
+fn scan_nullable(
+    method: &mut ClassMethod,
+    args: &[MethodArgument],
+    body: Peekable<Iter<Token>>,
+    fields: &[ClassField],
+) {
+    let tokens = body_tokens(body);
+    let is_nullable_field = |name: &str| {
+        fields
+            .iter()
+            .any(|field| field.name == name && field.r#type.is_nullable())
+    };
+    for (param, arg) in method.params.iter_mut().zip(args) {
+        let is_null = tokens.windows(3).any(|window| {
+            let is_arg = |token: &Token| token.value.as_deref() == Some(&arg.name);
+            let is_check = |token: &Token| {
+                matches!(
+                    token.token_type,
+                    TokenType::EQUAL_EQUAL | TokenType::NOT_EQUAL
+                )
+            };
+            match window {
+                [name, check, null] | [null, check, name]
+                    if is_arg(name) && is_check(check) && null.token_type == TokenType::NULL =>
+                {
+                    true
+                }
+                [field, equal, value]
+                    if equal.token_type == TokenType::EQUAL
+                        && is_arg(value)
+                        && field.value.as_deref().is_some_and(is_nullable_field) =>
+                {
+                    true
+                }
+                _ => false,
+            }
+        });
+        if is_null {
+            *param = param.clone().nullable();
+        }
+    }
+    let MethodReturnType::TYPE(return_type) = &mut method.return_type else {
+        return;
+    };
+    let returns_null = tokens.windows(3).any(|window| match window {
+        [r#return, value, semicolon]
+            if r#return.token_type == TokenType::RETURN
+                && semicolon.token_type == TokenType::SEMICOLON =>
+        {
+            value.token_type == TokenType::NULL
+                || value.value.as_deref().is_some_and(|name| {
+                    is_nullable_field(name)
+                        || args
+                            .iter()
+                            .zip(&method.params)
+                            .any(|(arg, param)| arg.name == name && param.is_nullable())
+                })
+        }
+        _ => false,
+    });
+    if returns_null {
+        *return_type = return_type.clone().nullable();
+    }
+}
+
+/// Which arguments of a static method are closures of the Java library that the body only
+/// calls, they take any closure as impl Fn instead of a shared one. A lambda in the body
+/// could copy them, so then none of them is. Tokens start behind the arguments
+///
+/// # Example
+///
+/// static int twice(Function<Integer, Integer> f, int x) { return f.apply(f.apply(x)); }
+/// -> [true, false]
+fn scan_closure_params(
+    context: &Context,
+    tokens: Peekable<Iter<Token>>,
+    args: &[MethodArgument],
+) -> Vec<bool> {
+    let mut body = Vec::new();
+    let mut depth = 0;
+    for token in tokens.skip_while(|token| token.token_type != TokenType::OPEN_BRACKET) {
+        match token.token_type {
+            TokenType::OPEN_BRACKET => depth += 1,
+            TokenType::CLOSE_BRACKET if depth == 1 => break,
+            TokenType::CLOSE_BRACKET => depth -= 1,
+            TokenType::ARROW | TokenType::DOUBLE_COLON => return vec![false; args.len()],
+            _ => {}
+        }
+        body.push(token);
+    }
+    let value = |i: usize| {
+        body.get(i)
+            .and_then(|token: &&Token| token.value.as_deref())
+    };
+    let is_type = |i: usize, token_type: TokenType| {
+        body.get(i)
+            .is_some_and(|token| token.token_type == token_type)
+    };
+    args.iter()
+        .map(|arg| {
+            let Some(method) = get_functional_method(context, &arg.r#type) else {
+                return false;
+            };
+            // Every use is name.method(, a use behind a dot is a field of another object
+            (0..body.len())
+                .filter(|&i| value(i) == Some(arg.name.as_str()))
+                .filter(|&i| i == 0 || !is_type(i - 1, TokenType::DOT))
+                .all(|i| {
+                    is_type(i + 1, TokenType::DOT)
+                        && value(i + 2) == Some(method.name)
+                        && is_type(i + 3, TokenType::OPEN_BRACE)
+                })
+        })
+        .collect()
+}
+
+/// Rust has no overloading, so every method which has the name of an earlier one gets the
+/// types of its arguments as suffix
+///
+/// # Example
+///
+/// add(int a, int b) and add(double a, double b) -> add and add_double_double
+fn name_overloads(methods: &mut [ClassMethod]) {
+    for i in 1..methods.len() {
+        if methods[..i]
+            .iter()
+            .any(|other| other.name == methods[i].name)
+        {
+            methods[i].overload = Some(overload_name(&methods[i].name, &methods[i].params));
+        }
+    }
+}
+
+/// The name of an overload with these argument types, e.g. add_int_string
+pub fn overload_name(name: &str, params: &[VariableType]) -> String {
+    if params.is_empty() {
+        return format!("{}_empty", name);
+    }
+    let params: Vec<String> = params.iter().map(type_suffix).collect();
+    format!("{}_{}", name, params.join("_"))
+}
+
+/// int -> int, String[] -> string_array, java.util.List<String> -> list
+fn type_suffix(r#type: &VariableType) -> String {
+    match r#type {
+        VariableType::DataType(data_type) => format!("{:?}", data_type).to_lowercase(),
+        VariableType::Array(element) => format!("{}_array", type_suffix(element)),
+        VariableType::Class(name) | VariableType::Generic(name, _) => {
+            name.rsplit('.').next().unwrap_or(name).to_lowercase()
+        }
+        VariableType::Nullable(r#type) => type_suffix(r#type),
+        _ => "object".to_string(),
+    }
+}
+
+/// The fields of one declaration with whether they have a value, e.g. int a = 1, b;. A field
+/// whose value is null can be null
+fn scan_field_names(
+    mut tokens: Peekable<Iter<Token>>,
+    is_static: bool,
+    is_final: bool,
+) -> Vec<(ClassField, bool)> {
+    let Some(r#type) = VariableType::parse(&mut tokens) else {
+        return Vec::new();
+    };
+    // Like ClassVariable::is_const, the value is checked for every name
+    let is_const_type = matches!(r#type, VariableType::DataType(_)) || r#type.is_string();
+    let mut fields: Vec<(ClassField, bool)> = Vec::new();
+    let mut depth = 0;
+    let mut expects_name = true;
+    while let Some(token) = tokens.next() {
+        match token.token_type {
+            TokenType::OPEN_BRACE | TokenType::OPEN_BRACKET => depth += 1,
+            TokenType::CLOSE_BRACE | TokenType::CLOSE_BRACKET => depth -= 1,
+            TokenType::SEMICOLON if depth == 0 => break,
+            TokenType::COMMA if depth == 0 => expects_name = true,
+            TokenType::EQUAL if depth == 0 => {
+                let is_null = tokens
+                    .clone()
+                    .next()
+                    .is_some_and(|value| value.token_type == TokenType::NULL)
+                    && tokens.clone().nth(1).is_some_and(|next| {
+                        matches!(next.token_type, TokenType::SEMICOLON | TokenType::COMMA)
+                    });
+                if let Some((field, has_value)) = fields.last_mut() {
+                    field.is_const = is_static && is_final && is_const_type && !is_null;
+                    *has_value = true;
+                    if is_null {
+                        field.r#type = field.r#type.clone().nullable();
+                    }
                 }
-                _ => Err(format!(
-                    "{}, Invalid Class expression: expected string literal",
-                    token
-                )),
             }
-        } else {
-            Err(String::from(
-                "Unexpected end of input while parsing expression",
-            ))
+            TokenType::UNKNOWN if depth == 0 && expects_name => {
+                let field = ClassField {
+                    name: token.value.clone().unwrap(),
+                    r#type: r#type.clone(),
+                    is_static,
+                    is_const: false,
+                };
+                fields.push((field, false));
+                expects_name = false;
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
+/// Checks if the method whose arguments start at tokens has a body, abstract methods end
+/// with a ; instead
+fn has_body(tokens: Peekable<Iter<Token>>) -> bool {
+    let mut depth = 0;
+    for token in tokens {
+        match token.token_type {
+            TokenType::OPEN_BRACE => depth += 1,
+            TokenType::CLOSE_BRACE => depth -= 1,
+            TokenType::OPEN_BRACKET if depth == 0 => return true,
+            TokenType::SEMICOLON if depth == 0 => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// The comments behind the } which ends the body parsed from start, e.g. } // end of class
+fn close_comments<'a>(
+    start: &Peekable<Iter<'a, Token>>,
+    tokens: &Peekable<Iter<'a, Token>>,
+) -> Vec<Trivia> {
+    match consumed_tokens(start, tokens).last() {
+        Some(close) if close.token_type == TokenType::CLOSE_BRACKET => {
+            close.trailing_trivia.clone()
         }
+        _ => Vec::new(),
     }
 }
@@ -0,0 +1,320 @@
+use std::{iter::Peekable, slice::Iter};
+
+use crate::{
+    parser::{
+        context::Context,
+        convert::{
+            data_type::{declare_classes, declare_interfaces, declare_shared_classes},
+            exception::{declare_super_classes, is_exception},
+        },
+        ClassType, ParseError,
+    },
+    token::{Token, TokenType, Trivia, Visibility},
+};
+
+use super::{
+    annotation::NodeAnnotation,
+    class::{
+        declaration_kind, scan_body, scan_constructors, scan_fields, scan_null_checks, ClassKind,
+        NodeClass, SuperType,
+    },
+    consumed_tokens,
+    import::{declare_static_imports, NodeImport},
+    method::NodeMethod,
+    parse_qualified_name, skip_to_boundary,
+    variable::TypeParam,
+};
+
+/// Everything in one .java file
+///
+/// # Example
+///
+/// package com.example;
+///
+/// import java.util.List;
+///
+/// class Main {}
+#[derive(Default)]
+pub struct CompilationUnit {
+    /// None for the unnamed package
+    pub package: Option<String>,
+    pub imports: Vec<NodeImport>,
+    pub types: Vec<NodeClass>,
+    /// Comments before the package, e.g. a license header
+    pub comments: Vec<Trivia>,
+    /// Comments at the end of the file
+    pub end_comments: Vec<Trivia>,
+}
+
+impl CompilationUnit {
+    /// Parses all tokens of the file, parts with syntax errors are skipped and their errors are
+    /// added to errors, so the unit only has the parts which could be parsed
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        errors: &mut Vec<ParseError>,
+    ) -> Self {
+        let mut unit = Self::default();
+        // Comments of modifiers and other tokens before the next type
+        let mut pending_comments = Vec::new();
+
+        if let Some(token) = tokens.next_if(|token| token.token_type == TokenType::PACKAGE) {
+            unit.comments.extend(token.leading_trivia.iter().cloned());
+            let start = tokens.clone();
+            match Self::parse_package(context, tokens) {
+                Ok(package) => unit.package = Some(package),
+                Err(error) => {
+                    errors.push(error);
+                    skip_to_boundary(tokens);
+                }
+            }
+            // The package is not converted, so its comments are placed at the start
+            for token in consumed_tokens(&start, tokens) {
+                unit.comments.extend(token.leading_trivia.iter().cloned());
+                unit.comments.extend(token.trailing_trivia.iter().cloned());
+            }
+        }
+        while let Some(token) = tokens.next_if(|token| token.token_type == TokenType::IMPORT) {
+            pending_comments.extend(token.leading_trivia.iter().cloned());
+            match NodeImport::parse(context, tokens) {
+                Ok(import) => unit.imports.push(import),
+                Err(error) => {
+                    errors.push(error);
+                    skip_to_boundary(tokens);
+                }
+            }
+        }
+
+        declare_static_imports(context, &unit.imports);
+        let classes = Self::scan_classes(context, tokens.clone());
+        declare_classes(
+            context,
+            classes
+                .iter()
+                .map(|class| (class.name.clone(), class.outer.clone())),
+        );
+        declare_interfaces(
+            context,
+            classes
+                .iter()
+                .filter(|class| class.kind == ClassKind::Interface)
+                .map(|class| class.name.clone()),
+        );
+        declare_super_classes(
+            context,
+            classes
+                .iter()
+                .map(|class| (class.name.clone(), class.super_class.clone())),
+        );
+        // Exceptions are values of the exception enum
+        declare_shared_classes(
+            context,
+            classes
+                .iter()
+                .filter(|class| {
+                    class.fields.iter().any(|field| !field.is_static)
+                        && !is_exception(context, &class.name)
+                })
+                .map(ClassType::rust_name),
+        );
+        let mut current_visibility = Visibility::NONE;
+        let mut annotations = Vec::new();
+        loop {
+            let before = tokens.clone();
+            let Some(token) = tokens.next() else {
+                break;
+            };
+            pending_comments.extend(token.leading_trivia.iter().cloned());
+            pending_comments.extend(token.trailing_trivia.iter().cloned());
+            if token.token_type == TokenType::AT {
+                *tokens = before.clone();
+                match NodeAnnotation::parse(context, tokens) {
+                    Ok(annotation) => annotations.push(annotation),
+                    Err(error) => {
+                        errors.push(error);
+                        annotations.clear();
+                        skip_to_boundary(tokens);
+                    }
+                }
+                for token in consumed_tokens(&before, tokens).skip(1) {
+                    pending_comments.extend(token.leading_trivia.iter().cloned());
+                    pending_comments.extend(token.trailing_trivia.iter().cloned());
+                }
+                continue;
+            }
+            if let Some(kind) = declaration_kind(before) {
+                let start = tokens.clone();
+                let visibility = std::mem::take(&mut current_visibility);
+                match NodeClass::parse(context, tokens, kind, visibility, &classes, errors) {
+                    Ok(mut class) => {
+                        class.comments.leading = std::mem::take(&mut pending_comments);
+                        class.annotations = std::mem::take(&mut annotations);
+                        unit.types.push(class);
+                    }
+                    Err(error) => {
+                        errors.push(error);
+                        *tokens = start;
+                        skip_to_boundary(tokens);
+                    }
+                }
+                continue;
+            }
+            match &token.token_type {
+                TokenType::VISIBILITY(visibility) => current_visibility = visibility.to_owned(),
+                // Modifiers like final, abstract, sealed and non-sealed and stray semicolons
+                TokenType::ABSTRACT
+                | TokenType::FINAL
+                | TokenType::STRICTFP
+                | TokenType::SEMICOLON
+                | TokenType::MINUS => {}
+                TokenType::UNKNOWN
+                    if token.is_contextual_keyword("sealed")
+                        || token.is_contextual_keyword("non-sealed") => {}
+                _ => {
+                    errors.push(ParseError::new(
+                        token.span,
+                        "Invalid type declaration: expected class",
+                    ));
+                    // A stray } has no rest which could be skipped
+                    if token.token_type != TokenType::CLOSE_BRACKET {
+                        skip_to_boundary(tokens);
+                    }
+                }
+            }
+        }
+        unit.end_comments = pending_comments;
+        unit
+    }
+
+    /// package name;, starting behind the package keyword
+    fn parse_package(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+    ) -> Result<String, ParseError> {
+        let package = parse_qualified_name(context, tokens)?;
+        match tokens.next() {
+            Some(token) if token.token_type == TokenType::SEMICOLON => Ok(package),
+            Some(token) => Err(ParseError::new(token.span, "Invalid package: expected ;")),
+            None => Err(ParseError::end_of_input(
+                context,
+                "Invalid package: expected ;",
+            )),
+        }
+    }
+
+    /// All classes, interfaces, enums and records with their methods, also nested ones, so they
+    /// can be used before their declaration
+    fn scan_classes(context: &Context, mut tokens: Peekable<Iter<Token>>) -> Vec<ClassType> {
+        let null_checks = scan_null_checks(tokens.clone());
+        let mut classes = Vec::new();
+        // The Rust names of the classes around the current token with the depth of their body
+        let mut outer_classes: Vec<(String, usize)> = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            let Some(kind) = declaration_kind(tokens.clone()) else {
+                match tokens.next().map(|token| &token.token_type) {
+                    Some(TokenType::OPEN_BRACKET) => depth += 1,
+                    Some(TokenType::CLOSE_BRACKET) => {
+                        depth = depth.saturating_sub(1);
+                        outer_classes.retain(|(_, body_depth)| *body_depth <= depth);
+                    }
+                    Some(_) => {}
+                    None => return classes,
+                }
+                continue;
+            };
+            tokens.next();
+            let Some(name) = tokens.next_if(|token| token.token_type == TokenType::UNKNOWN) else {
+                continue;
+            };
+            let name = name.value.clone().unwrap();
+            let type_params = TypeParam::parse_list(&mut tokens.clone()).unwrap_or_default();
+            let super_class = match kind {
+                ClassKind::Class => Self::scan_super_class(context, tokens.clone()),
+                _ => None,
+            };
+            let interfaces = Self::scan_interfaces(context, tokens.clone(), kind);
+            // The methods start behind the { of the body
+            let mut body = tokens.clone();
+            while body
+                .next_if(|token| token.token_type != TokenType::OPEN_BRACKET)
+                .is_some()
+            {}
+            body.next();
+            // Errors are reported when the record itself is parsed
+            let components = match kind {
+                ClassKind::Record => {
+                    let mut header = tokens.clone();
+                    while header
+                        .next_if(|token| token.token_type != TokenType::OPEN_BRACE)
+                        .is_some()
+                    {}
+                    NodeMethod::parse_arguments(context, &mut header).unwrap_or_default()
+                }
+                _ => Vec::new(),
+            };
+            let (fields, constructors) = match kind {
+                ClassKind::Class => (
+                    scan_fields(context, body.clone(), &name, &null_checks),
+                    scan_constructors(context, body.clone(), &name, &null_checks),
+                ),
+                _ => (Vec::new(), Vec::new()),
+            };
+            let (constants, methods) =
+                scan_body(context, body, &name, kind, &components, &null_checks);
+            let outer = outer_classes.last().map(|(outer, _)| outer.clone());
+            classes.push(ClassType {
+                name,
+                outer,
+                kind,
+                type_params: type_params.into_iter().map(|param| param.name).collect(),
+                super_class,
+                interfaces,
+                constants,
+                methods,
+                constructors,
+                fields,
+                components,
+            });
+            outer_classes.push((classes.last().unwrap().rust_name(), depth + 1));
+        }
+    }
+
+    /// The types behind implements, for an interface the ones behind extends, tokens start
+    /// behind the name of the class
+    fn scan_interfaces(
+        context: &Context,
+        mut tokens: Peekable<Iter<Token>>,
+        kind: ClassKind,
+    ) -> Vec<SuperType> {
+        // The bounds of the type parameters use extends as well
+        if TypeParam::parse_list(&mut tokens).is_err() {
+            return Vec::new();
+        }
+        while let Some(token) = tokens.next_if(|token| token.token_type != TokenType::OPEN_BRACKET)
+        {
+            let is_interfaces = match token.token_type {
+                TokenType::IMPLEMENTS => true,
+                TokenType::EXTENDS => kind == ClassKind::Interface,
+                _ => false,
+            };
+            if is_interfaces {
+                return NodeClass::parse_super_types(context, &mut tokens).unwrap_or_default();
+            }
+        }
+        Vec::new()
+    }
+
+    /// The simple name of the class behind extends, tokens start behind the name of the class
+    fn scan_super_class(context: &Context, mut tokens: Peekable<Iter<Token>>) -> Option<String> {
+        // The bounds of the type parameters use extends as well
+        TypeParam::parse_list(&mut tokens).ok()?;
+        while tokens
+            .next_if(|token| token.token_type != TokenType::OPEN_BRACKET)?
+            .token_type
+            != TokenType::EXTENDS
+        {}
+        let name = parse_qualified_name(context, &mut tokens).ok()?;
+        Some(name.rsplit('.').next().unwrap_or(&name).to_string())
+    }
+}
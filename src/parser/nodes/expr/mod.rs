@@ -1,2 +1,607 @@
-pub mod bool;
-pub mod int;
+use crate::{
+    java_std::function::get_functional_method,
+    parser::{context::Context, convert::data_type::is_shared_type},
+    source_map::Span,
+    token::DataType,
+};
+
+use super::{
+    statement::{Block, Switch},
+    variable::{NodeVariable, VariableType},
+    MethodReturnType, NodeComments,
+};
+
+mod parse;
+
+pub use parse::is_assign_op;
+
+/// A Java expression, parsed with the Java operator precedence
+///
+/// # Example
+///
+/// 5 + 10 * 10 -> Binary(Add, 5, Binary(Mul, 10, 10))
+#[derive(Clone, Debug)]
+pub struct Expr {
+    pub kind: ExprKind,
+    /// Covers all tokens of the expression
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum ExprKind {
+    Literal(Literal),
+    /// A local or class variable, with the type it was declared with
+    Variable {
+        name: String,
+        r#type: VariableType,
+        scope: VariableScope,
+    },
+    /// A class used as value, e.g. System in System.out
+    Class(String),
+    This,
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /// condition ? then : otherwise
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// = and all compound assignments, the operator is None for =
+    ///
+    /// # Example
+    ///
+    /// i += 2 -> Assign(Some(Add), i, 2)
+    Assign(Option<BinaryOp>, Box<Expr>, Box<Expr>),
+    /// (int) value
+    Cast(VariableType, Box<Expr>),
+    /// value instanceof String or value instanceof Point p
+    InstanceOf(Box<Expr>, Pattern),
+    /// The target is None for methods which are not known, e.g. of a super class
+    MethodCall {
+        target: Option<Box<Expr>>,
+        name: String,
+        args: Vec<Expr>,
+        /// The return type, Inferred when the method is not known
+        r#type: VariableType,
+        /// The types of the arguments in the declaration, empty when the method is not known
+        params: Vec<VariableType>,
+        /// The called method declares exceptions, so it returns a Result
+        throws: bool,
+        /// The Rust name of an overloaded method of the file, see ClassMethod
+        overload: Option<String>,
+        /// Which arguments the method takes as impl Fn, see ClassMethod
+        closure_params: Vec<bool>,
+    },
+    /// object.field, with the declared type of the field, Inferred when it is not known
+    FieldAccess(Box<Expr>, String, VariableType),
+    /// array[index]
+    ArrayIndex(Box<Expr>, Box<Expr>),
+    /// new Class(args) or new Box<String>(args)
+    New {
+        /// The type of the new object
+        r#type: VariableType,
+        args: Vec<Expr>,
+        /// The types of the arguments of the constructor, empty when it is not known
+        params: Vec<VariableType>,
+        /// The Rust name of an overloaded constructor, e.g. new_int
+        overload: Option<String>,
+        /// The constructor declares exceptions, so it returns a Result
+        throws: bool,
+    },
+    /// new int[2][3], the type is the type of the whole array
+    NewArray(VariableType, Vec<Expr>),
+    /// {1, 2, 3} or new int[] {1, 2, 3}
+    ArrayInit(VariableType, Vec<Expr>),
+    /// switch (day) { case 1 -> "Monday"; default -> "Other"; }
+    Switch(Box<Switch>),
+    /// x -> x + 1 or a method reference like String::length
+    Lambda(Box<Lambda>),
+}
+
+/// A lambda, method references are lambdas which call the method with their arguments
+///
+/// # Example
+///
+/// String::length -> arg -> arg.length()
+#[derive(Clone, Debug)]
+pub struct Lambda {
+    pub params: Vec<NodeVariable>,
+    pub body: LambdaBody,
+    /// The functional interface which the lambda implements, Inferred when it is not known
+    pub r#type: VariableType,
+    /// The return type of the method of the functional interface
+    pub return_type: MethodReturnType,
+    /// The local variables of the enclosing method which the body uses
+    pub captures: Vec<NodeVariable>,
+    /// The body uses fields or methods of the current object
+    pub captures_self: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum LambdaBody {
+    Expr(Box<Expr>),
+    Block(Block),
+}
+
+/// The pattern of an instanceof or a case, it declares variables when it matches
+///
+/// # Example
+///
+/// Point(int x, var y) -> Record(Point, [x: Type(int, x), y: Type(int, y)])
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    /// A type with an optional variable, e.g. String s
+    Type(VariableType, Option<String>),
+    /// A record deconstruction with the patterns of its components
+    Record(VariableType, Vec<RecordComponent>),
+}
+
+/// A component of a record pattern, its value comes from the accessor of the component
+#[derive(Clone, Debug)]
+pub struct RecordComponent {
+    pub name: String,
+    /// The declared type of the component, the pattern can check for a more specific one
+    pub r#type: VariableType,
+    pub pattern: Pattern,
+}
+
+impl Pattern {
+    pub fn r#type(&self) -> &VariableType {
+        match self {
+            Pattern::Type(r#type, _) | Pattern::Record(r#type, _) => r#type,
+        }
+    }
+
+    /// The variables which are declared by this pattern, boxed types are used as primitives
+    pub fn variables(&self) -> Vec<NodeVariable> {
+        match self {
+            Pattern::Type(_, None) => Vec::new(),
+            Pattern::Type(r#type, Some(name)) => vec![NodeVariable {
+                name: name.clone(),
+                r#type: r#type.unboxed(),
+                value: None,
+                initialized: true,
+                mutable: false,
+                comments: NodeComments::default(),
+            }],
+            Pattern::Record(_, components) => components
+                .iter()
+                .flat_map(|component| component.pattern.variables())
+                .collect(),
+        }
+    }
+}
+
+/// Where a variable is declared
+#[derive(Clone, Debug, PartialEq)]
+pub enum VariableScope {
+    /// Local variables and arguments
+    Local,
+    /// Fields of the current object
+    Field,
+    /// Static fields, holds the class name
+    Static(String),
+    /// Static final fields with a constant value, holds the class name
+    Const(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    /// Type, raw Java literal
+    Number(DataType, String),
+    /// Decoded value
+    String(String),
+    Char(char),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnaryOp {
+    Plus,
+    Minus,
+    Not,
+    BitNot,
+    PreIncrement,
+    PreDecrement,
+    PostIncrement,
+    PostDecrement,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryOp {
+    Mul,
+    Div,
+    Rem,
+    Add,
+    Sub,
+    ShiftLeft,
+    ShiftRight,
+    UnsignedShiftRight,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    BitAnd,
+    BitXor,
+    BitOr,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    pub fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Less
+                | BinaryOp::Greater
+                | BinaryOp::LessEqual
+                | BinaryOp::GreaterEqual
+                | BinaryOp::Equal
+                | BinaryOp::NotEqual
+        )
+    }
+
+    pub fn is_shift(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::ShiftLeft | BinaryOp::ShiftRight | BinaryOp::UnsignedShiftRight
+        )
+    }
+}
+
+impl Expr {
+    /// The Java type of the expression, Inferred when we don't know it
+    ///
+    /// # Example
+    ///
+    /// 1 + 2L -> long
+    ///
+    /// "a" + 1 -> String
+    pub fn r#type(&self) -> VariableType {
+        match &self.kind {
+            ExprKind::Literal(literal) => match literal {
+                Literal::Number(data_type, _) => VariableType::DataType(*data_type),
+                Literal::String(_) => VariableType::string(),
+                Literal::Char(_) => VariableType::DataType(DataType::CHAR),
+                Literal::Bool(_) => VariableType::DataType(DataType::BOOLEAN),
+                Literal::Null => VariableType::Inferred,
+            },
+            ExprKind::Variable { r#type, .. } => r#type.non_null().clone(),
+            ExprKind::Class(name) => VariableType::Class(name.clone()),
+            ExprKind::This => VariableType::Inferred,
+            ExprKind::Unary(op, expr) => match op {
+                UnaryOp::Not => VariableType::DataType(DataType::BOOLEAN),
+                UnaryOp::Plus | UnaryOp::Minus | UnaryOp::BitNot => unary_promotion(expr.r#type()),
+                _ => expr.r#type(),
+            },
+            ExprKind::Binary(op, left, right) => {
+                let (left, right) = (left.r#type(), right.r#type());
+                match op {
+                    _ if op.is_comparison() => VariableType::DataType(DataType::BOOLEAN),
+                    BinaryOp::And | BinaryOp::Or => VariableType::DataType(DataType::BOOLEAN),
+                    BinaryOp::Add if left.is_string() || right.is_string() => {
+                        VariableType::string()
+                    }
+                    _ if op.is_shift() => unary_promotion(left),
+                    _ if left == VariableType::DataType(DataType::BOOLEAN) => left,
+                    _ => binary_promotion(left, right),
+                }
+            }
+            ExprKind::Ternary(_, then, otherwise) => {
+                let (then, otherwise) = (then.r#type(), otherwise.r#type());
+                match (&then, &otherwise) {
+                    (VariableType::DataType(_), VariableType::DataType(_)) if then != otherwise => {
+                        binary_promotion(then, otherwise)
+                    }
+                    (VariableType::Inferred, _) => otherwise,
+                    _ => then,
+                }
+            }
+            ExprKind::Assign(_, target, _) => target.r#type(),
+            ExprKind::Cast(r#type, _) => r#type.clone(),
+            ExprKind::InstanceOf(_, _) => VariableType::DataType(DataType::BOOLEAN),
+            ExprKind::MethodCall { r#type, .. } => r#type.non_null().clone(),
+            ExprKind::FieldAccess(target, name, r#type) => match target.r#type() {
+                VariableType::Array(_) if name == "length" => VariableType::DataType(DataType::INT),
+                _ => r#type.non_null().clone(),
+            },
+            ExprKind::ArrayIndex(array, _) => match array.r#type() {
+                VariableType::Array(element) => *element,
+                _ => VariableType::Inferred,
+            },
+            ExprKind::New { r#type, .. }
+            | ExprKind::NewArray(r#type, _)
+            | ExprKind::ArrayInit(r#type, _) => r#type.clone(),
+            ExprKind::Switch(switch) => switch.r#type(),
+            ExprKind::Lambda(lambda) => lambda.r#type.clone(),
+        }
+    }
+
+    /// Checks if the value can be null, so it is an Option. The type of a nullable variable,
+    /// field or method is Nullable, r#type is the type of the value
+    ///
+    /// # Example
+    ///
+    /// null, next when the field next has no value, c ? null : "a"
+    pub fn is_nullable(&self) -> bool {
+        match &self.kind {
+            ExprKind::Literal(Literal::Null) => true,
+            ExprKind::Variable { r#type, .. }
+            | ExprKind::MethodCall { r#type, .. }
+            | ExprKind::FieldAccess(_, _, r#type) => r#type.is_nullable(),
+            ExprKind::Ternary(_, then, otherwise) => then.is_nullable() || otherwise.is_nullable(),
+            _ => false,
+        }
+    }
+
+    /// The pattern variables which are visible where this condition is true
+    ///
+    /// # Example
+    ///
+    /// o instanceof String s && s != "" -> s is visible in the then branch of an if
+    pub fn bindings_when_true(&self) -> Vec<NodeVariable> {
+        match &self.kind {
+            ExprKind::InstanceOf(_, pattern) => pattern.variables(),
+            ExprKind::Binary(BinaryOp::And, left, right) => {
+                let mut bindings = left.bindings_when_true();
+                bindings.extend(right.bindings_when_true());
+                bindings
+            }
+            ExprKind::Unary(UnaryOp::Not, operand) => operand.bindings_when_false(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The pattern variables which are visible where this condition is false
+    ///
+    /// # Example
+    ///
+    /// !(o instanceof String s) -> s is visible after if (...) return;
+    pub fn bindings_when_false(&self) -> Vec<NodeVariable> {
+        match &self.kind {
+            ExprKind::Binary(BinaryOp::Or, left, right) => {
+                let mut bindings = left.bindings_when_false();
+                bindings.extend(right.bindings_when_false());
+                bindings
+            }
+            ExprKind::Unary(UnaryOp::Not, operand) => operand.bindings_when_true(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Names of the local variables which get a new value in this expression
+    ///
+    /// Calling a method of an object counts too, because the method can change the object
+    pub fn assigned_variables(&self, context: &Context) -> Vec<&str> {
+        let mut names = Vec::new();
+        for target in self.assign_targets(context) {
+            if let ExprKind::Variable {
+                name,
+                scope: VariableScope::Local,
+                ..
+            } = &target.root(context).kind
+            {
+                names.push(name.as_str());
+            }
+        }
+        names
+    }
+
+    /// Checks if this expression changes a field of the current object
+    pub fn modifies_self(&self, context: &Context) -> bool {
+        self.assign_targets(context).iter().any(|target| {
+            matches!(
+                target.root(context).kind,
+                ExprKind::This
+                    | ExprKind::Variable {
+                        scope: VariableScope::Field,
+                        ..
+                    }
+            )
+        })
+    }
+
+    /// Like modifies_self, but calls of methods of the current object are not included
+    pub fn modifies_fields(&self, context: &Context) -> bool {
+        self.assign_targets(context).iter().any(|target| {
+            !matches!(target.kind, ExprKind::This)
+                && matches!(
+                    target.root(context).kind,
+                    ExprKind::This
+                        | ExprKind::Variable {
+                            scope: VariableScope::Field,
+                            ..
+                        }
+                )
+        })
+    }
+
+    /// Names of the methods which are called on the current object
+    pub fn self_calls(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for target in self.calls() {
+            if let ExprKind::MethodCall {
+                target: Some(target),
+                name,
+                ..
+            } = &target.kind
+            {
+                if matches!(target.kind, ExprKind::This) {
+                    names.push(name.as_str());
+                }
+            }
+        }
+        names
+    }
+
+    /// All method calls in this expression
+    fn calls(&self) -> Vec<&Expr> {
+        let mut calls = Vec::new();
+        self.collect_calls(&mut calls);
+        calls
+    }
+
+    fn collect_calls<'a>(&'a self, calls: &mut Vec<&'a Expr>) {
+        match &self.kind {
+            ExprKind::MethodCall { .. } => calls.push(self),
+            ExprKind::Switch(switch) => {
+                for statement in switch.statements() {
+                    statement.for_each_expr(&mut |expr| expr.collect_calls(calls));
+                }
+            }
+            _ => {}
+        }
+        for child in self.children() {
+            child.collect_calls(calls);
+        }
+    }
+
+    /// All expressions which get a new value or whose methods are called
+    fn assign_targets(&self, context: &Context) -> Vec<&Expr> {
+        let mut targets = Vec::new();
+        self.collect_assign_targets(context, &mut targets);
+        targets
+    }
+
+    fn collect_assign_targets<'a>(&'a self, context: &Context, targets: &mut Vec<&'a Expr>) {
+        match &self.kind {
+            ExprKind::Assign(_, target, _)
+            | ExprKind::Unary(
+                UnaryOp::PreIncrement
+                | UnaryOp::PreDecrement
+                | UnaryOp::PostIncrement
+                | UnaryOp::PostDecrement,
+                target,
+            ) => targets.push(target),
+            // Strings can't be changed and methods of arrays don't change them
+            ExprKind::MethodCall {
+                target: Some(target),
+                ..
+            } if target.is_object(context) => targets.push(target),
+            // The cases are statements, so they are no children
+            ExprKind::Switch(switch) => {
+                for statement in switch.statements() {
+                    statement
+                        .for_each_expr(&mut |expr| expr.collect_assign_targets(context, targets));
+                }
+            }
+            _ => {}
+        }
+        for child in self.children() {
+            child.collect_assign_targets(context, targets);
+        }
+    }
+
+    /// Checks if methods of this value can change it
+    fn is_object(&self, context: &Context) -> bool {
+        match (&self.kind, self.r#type()) {
+            (ExprKind::This, _) => true,
+            // Static methods
+            (ExprKind::Class(_), _) => false,
+            // Closures can't be changed by calling them
+            (_, r#type) if get_functional_method(context, &r#type).is_some() => false,
+            // Shared objects only change their fields, which are cells
+            (_, r#type) if is_shared_type(context, &r#type) => false,
+            (_, VariableType::Class(_)) => !self.r#type().is_string(),
+            (_, VariableType::Generic(..)) => true,
+            _ => false,
+        }
+    }
+
+    /// The variable which holds the changed value
+    ///
+    /// # Example
+    ///
+    /// a[0].b -> a
+    ///
+    /// Fields of shared objects belong to the object and elements to the shared array, so p.x
+    /// and a[0] are their own root
+    fn root(&self, context: &Context) -> &Expr {
+        match &self.kind {
+            ExprKind::FieldAccess(inner, ..) if is_shared_type(context, &inner.r#type()) => self,
+            ExprKind::ArrayIndex(..) => self,
+            ExprKind::FieldAccess(inner, ..) => inner.root(context),
+            _ => self,
+        }
+    }
+
+    /// All direct sub expressions
+    pub fn children(&self) -> Vec<&Expr> {
+        match &self.kind {
+            ExprKind::Literal(_)
+            | ExprKind::Variable { .. }
+            | ExprKind::Class(_)
+            | ExprKind::This => {
+                vec![]
+            }
+            ExprKind::Unary(_, expr)
+            | ExprKind::Cast(_, expr)
+            | ExprKind::InstanceOf(expr, _)
+            | ExprKind::FieldAccess(expr, ..) => vec![expr],
+            ExprKind::Binary(_, left, right)
+            | ExprKind::Assign(_, left, right)
+            | ExprKind::ArrayIndex(left, right) => vec![left, right],
+            ExprKind::Ternary(condition, then, otherwise) => vec![condition, then, otherwise],
+            ExprKind::MethodCall { target, args, .. } => {
+                target.iter().map(|target| &**target).chain(args).collect()
+            }
+            ExprKind::New { args, .. }
+            | ExprKind::NewArray(_, args)
+            | ExprKind::ArrayInit(_, args) => args.iter().collect(),
+            ExprKind::Switch(switch) => vec![&switch.selector],
+            // The body runs when the lambda is called, not where it is declared
+            ExprKind::Lambda(_) => vec![],
+        }
+    }
+
+    /// Calls f for this expression and all expressions in it, also for the ones in the cases of
+    /// switch expressions and in the bodies of lambdas
+    pub fn for_each(&self, f: &mut impl FnMut(&Expr)) {
+        f(self);
+        match &self.kind {
+            ExprKind::Switch(switch) => {
+                for statement in switch.statements() {
+                    statement.for_each_expr(&mut |expr| expr.for_each(f));
+                }
+            }
+            ExprKind::Lambda(lambda) => match &lambda.body {
+                LambdaBody::Expr(body) => body.for_each(f),
+                LambdaBody::Block(block) => {
+                    for statement in &block.statements {
+                        statement.for_each_expr(&mut |expr| expr.for_each(f));
+                    }
+                }
+            },
+            _ => {}
+        }
+        for child in self.children() {
+            child.for_each(f);
+        }
+    }
+}
+
+/// byte, short and char become int in arithmetic, see JLS 5.6
+fn unary_promotion(r#type: VariableType) -> VariableType {
+    match r#type {
+        VariableType::DataType(DataType::BYTE | DataType::SHORT | DataType::CHAR) => {
+            VariableType::DataType(DataType::INT)
+        }
+        r#type => r#type,
+    }
+}
+
+/// The common type of two numeric operands, see JLS 5.6
+pub fn binary_promotion(left: VariableType, right: VariableType) -> VariableType {
+    let (VariableType::DataType(left), VariableType::DataType(right)) = (&left, &right) else {
+        return match left {
+            VariableType::Inferred => right,
+            _ => left,
+        };
+    };
+    for data_type in [DataType::DOUBLE, DataType::FLOAT, DataType::LONG] {
+        if *left == data_type || *right == data_type {
+            return VariableType::DataType(data_type);
+        }
+    }
+    VariableType::DataType(DataType::INT)
+}
@@ -0,0 +1,1675 @@
+use std::{iter::Peekable, slice::Iter};
+
+use crate::{
+    java_std::{exception::get_exception_method_type, function::get_functional_method},
+    parser::{
+        context::Context,
+        convert::{
+            data_type::{
+                convert_number_literal, get_type_param, is_comparable, require_param_bounds,
+                resolve_class,
+            },
+            exception::is_exception,
+            ident::generate_ident,
+        },
+        nodes::{
+            class::ClassKind,
+            consumed_tokens, get_variable_in_method,
+            import::static_import_class,
+            statement::{Block, Statement, StatementKind, Switch},
+            variable::{NodeVariable, VariableType, WildcardBound},
+            MethodReturnType, NodeComments,
+        },
+        report_nested_errors, ClassContext, ClassMethod, ParseError,
+    },
+    prelude::{get_prelude_class, get_string_method_type},
+    source_map::Span,
+    token::{DataType, Token, TokenType},
+};
+
+use super::{
+    BinaryOp, Expr, ExprKind, Lambda, LambdaBody, Literal, Pattern, RecordComponent, UnaryOp,
+    VariableScope,
+};
+
+/// Java precedence of binary operators, higher binds stronger
+fn binary_op(token_type: &TokenType) -> Option<(BinaryOp, u8)> {
+    let op = match token_type {
+        TokenType::OR => (BinaryOp::Or, 1),
+        TokenType::AND => (BinaryOp::And, 2),
+        TokenType::BIT_OR => (BinaryOp::BitOr, 3),
+        TokenType::BIT_XOR => (BinaryOp::BitXor, 4),
+        TokenType::BIT_AND => (BinaryOp::BitAnd, 5),
+        TokenType::EQUAL_EQUAL => (BinaryOp::Equal, 6),
+        TokenType::NOT_EQUAL => (BinaryOp::NotEqual, 6),
+        TokenType::LESS => (BinaryOp::Less, 7),
+        TokenType::GREATER => (BinaryOp::Greater, 7),
+        TokenType::LESS_EQUAL => (BinaryOp::LessEqual, 7),
+        TokenType::GREATER_EQUAL => (BinaryOp::GreaterEqual, 7),
+        TokenType::SHIFT_LEFT => (BinaryOp::ShiftLeft, 8),
+        TokenType::SHIFT_RIGHT => (BinaryOp::ShiftRight, 8),
+        TokenType::UNSIGNED_SHIFT_RIGHT => (BinaryOp::UnsignedShiftRight, 8),
+        TokenType::PLUS => (BinaryOp::Add, 9),
+        TokenType::MINUS => (BinaryOp::Sub, 9),
+        TokenType::STAR => (BinaryOp::Mul, 10),
+        TokenType::SLASH => (BinaryOp::Div, 10),
+        TokenType::PERCENT => (BinaryOp::Rem, 10),
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// instanceof has the same precedence as the relational operators
+const INSTANCEOF_PRECEDENCE: u8 = 7;
+
+/// Checks if the token is = or a compound assignment like +=
+pub fn is_assign_op(token_type: &TokenType) -> bool {
+    assign_op(token_type).is_some()
+}
+
+/// The operator of a compound assignment, None for =
+fn assign_op(token_type: &TokenType) -> Option<Option<BinaryOp>> {
+    let op = match token_type {
+        TokenType::EQUAL => None,
+        TokenType::PLUS_EQUAL => Some(BinaryOp::Add),
+        TokenType::MINUS_EQUAL => Some(BinaryOp::Sub),
+        TokenType::STAR_EQUAL => Some(BinaryOp::Mul),
+        TokenType::SLASH_EQUAL => Some(BinaryOp::Div),
+        TokenType::PERCENT_EQUAL => Some(BinaryOp::Rem),
+        TokenType::AND_EQUAL => Some(BinaryOp::BitAnd),
+        TokenType::OR_EQUAL => Some(BinaryOp::BitOr),
+        TokenType::XOR_EQUAL => Some(BinaryOp::BitXor),
+        TokenType::SHIFT_LEFT_EQUAL => Some(BinaryOp::ShiftLeft),
+        TokenType::SHIFT_RIGHT_EQUAL => Some(BinaryOp::ShiftRight),
+        TokenType::UNSIGNED_SHIFT_RIGHT_EQUAL => Some(BinaryOp::UnsignedShiftRight),
+        _ => return None,
+    };
+    Some(op)
+}
+
+impl Expr {
+    /// Parses a whole expression, the token after the expression (e.g. `;`) is not consumed
+    ///
+    /// Identifiers have to be variables which are visible here or known classes
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        method_vars: &[NodeVariable],
+    ) -> Result<Self, ParseError> {
+        ExprParser::new(context, tokens, class_context, method_vars).parse_assignment()
+    }
+
+    /// Parses an expression whose value is assigned to a variable, argument or return value
+    /// of this type, lambdas and method references implement it when it is a functional
+    /// interface
+    pub fn parse_to(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        method_vars: &[NodeVariable],
+        r#type: &VariableType,
+    ) -> Result<Self, ParseError> {
+        ExprParser::new(context, tokens, class_context, method_vars).parse_value(r#type)
+    }
+
+    /// Parses the constant or guard of a case, the -> behind it does not start a lambda
+    pub fn parse_case_label(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        method_vars: &[NodeVariable],
+    ) -> Result<Self, ParseError> {
+        let mut parser = ExprParser::new(context, tokens, class_context, method_vars);
+        parser.in_case_label = true;
+        parser.parse_assignment()
+    }
+}
+
+impl Pattern {
+    /// Parses a type pattern like `String s` or a record pattern like `Point(int x, var y)`,
+    /// nothing is consumed if there is no pattern
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+    ) -> Result<Option<Self>, ParseError> {
+        let mut lookahead = tokens.clone();
+        lookahead.next_if(|token| token.token_type == TokenType::FINAL);
+        let r#type = match lookahead.peek() {
+            Some(token) if token.is_contextual_keyword("var") => {
+                lookahead.next();
+                VariableType::Inferred
+            }
+            _ => match VariableType::parse(&mut lookahead) {
+                Some(r#type) => r#type,
+                None => return Ok(None),
+            },
+        };
+        let pattern = match lookahead.peek() {
+            Some(token) if token.token_type == TokenType::OPEN_BRACE => {
+                let open = lookahead.next().unwrap();
+                let mut patterns = Vec::new();
+                if lookahead
+                    .next_if(|token| token.token_type == TokenType::CLOSE_BRACE)
+                    .is_none()
+                {
+                    loop {
+                        match Pattern::parse(context, &mut lookahead, class_context)? {
+                            Some(pattern) => patterns.push(pattern),
+                            None => {
+                                return Err(ParseError::new(
+                                    open.span,
+                                    "Invalid pattern: expected a pattern for each component",
+                                ))
+                            }
+                        }
+                        match lookahead.next() {
+                            Some(token) if token.token_type == TokenType::COMMA => {}
+                            Some(token) if token.token_type == TokenType::CLOSE_BRACE => break,
+                            _ => {
+                                return Err(ParseError::new(
+                                    open.span,
+                                    "Invalid pattern: expected , or ) after component",
+                                ))
+                            }
+                        }
+                    }
+                }
+                let Some(declared) = class_context.record_components(context, &r#type) else {
+                    return Err(ParseError::new(
+                        open.span,
+                        "Invalid pattern: only records of the input can be deconstructed",
+                    ));
+                };
+                if declared.len() != patterns.len() {
+                    return Err(ParseError::new(
+                        open.span,
+                        format!(
+                            "Invalid pattern: expected {} components, found {}",
+                            declared.len(),
+                            patterns.len()
+                        ),
+                    ));
+                }
+                // var takes the type of the component
+                let components = declared
+                    .into_iter()
+                    .zip(patterns)
+                    .map(|(component, pattern)| RecordComponent {
+                        pattern: match pattern {
+                            Pattern::Type(VariableType::Inferred, name) => {
+                                Pattern::Type(component.r#type.clone(), name)
+                            }
+                            pattern => pattern,
+                        },
+                        name: component.name,
+                        r#type: component.r#type,
+                    })
+                    .collect();
+                Pattern::Record(r#type, components)
+            }
+            Some(token) if token.token_type == TokenType::UNKNOWN => {
+                let name = lookahead.next().unwrap().value.clone().unwrap();
+                // _ is an unnamed variable
+                Pattern::Type(r#type, Some(name).filter(|name| name != "_"))
+            }
+            _ => return Ok(None),
+        };
+        *tokens = lookahead;
+        Ok(Some(pattern))
+    }
+}
+
+struct ExprParser<'a, 'b> {
+    context: &'b Context,
+    tokens: &'b mut Peekable<Iter<'a, Token>>,
+    class_context: &'b ClassContext,
+    method_vars: &'b [NodeVariable],
+    /// Pattern variables of the conditions around the current operand
+    bindings: Vec<NodeVariable>,
+    /// The type which gets the value of the current expression, Inferred when it is not known
+    target: VariableType,
+    /// A -> ends the expression instead of starting a lambda
+    in_case_label: bool,
+}
+
+impl<'a, 'b> ExprParser<'a, 'b> {
+    fn new(
+        context: &'b Context,
+        tokens: &'b mut Peekable<Iter<'a, Token>>,
+        class_context: &'b ClassContext,
+        method_vars: &'b [NodeVariable],
+    ) -> Self {
+        ExprParser {
+            context,
+            tokens,
+            class_context,
+            method_vars,
+            bindings: Vec::new(),
+            target: VariableType::Inferred,
+            in_case_label: false,
+        }
+    }
+}
+
+impl<'a> ExprParser<'a, '_> {
+    fn next(&mut self) -> Result<&'a Token, ParseError> {
+        let context = self.context;
+        self.tokens.next().ok_or_else(|| {
+            ParseError::end_of_input(context, "Unexpected end of input while parsing expression")
+        })
+    }
+
+    fn peek_type(&mut self) -> Option<&'a TokenType> {
+        self.tokens.peek().map(|token| &token.token_type)
+    }
+
+    fn expect(&mut self, token_type: TokenType, expected: &str) -> Result<&'a Token, ParseError> {
+        let token = self.next()?;
+        if token.token_type != token_type {
+            return Err(ParseError::new(
+                token.span,
+                format!("Invalid expression: expected {}", expected),
+            ));
+        }
+        Ok(token)
+    }
+
+    fn parse_assignment(&mut self) -> Result<Expr, ParseError> {
+        self.parse_value(&VariableType::Inferred)
+    }
+
+    /// Parses an expression whose value gets the type target, which is Inferred when it is not
+    /// known
+    fn parse_value(&mut self, target: &VariableType) -> Result<Expr, ParseError> {
+        let target = target.non_null();
+        if let Some(lambda) = self.parse_lambda(target)? {
+            return Ok(lambda);
+        }
+        let outer = std::mem::replace(&mut self.target, target.clone());
+        let value = self.parse_assign();
+        self.target = outer;
+        value
+    }
+
+    /// a = b, the right side is parsed first, so a = b = c is a = (b = c)
+    fn parse_assign(&mut self) -> Result<Expr, ParseError> {
+        let target = self.parse_ternary()?;
+        let Some(op) = self.peek_type().and_then(assign_op) else {
+            return Ok(target);
+        };
+        let token = self.next()?;
+        if !matches!(
+            target.kind,
+            ExprKind::Variable { .. } | ExprKind::FieldAccess(..) | ExprKind::ArrayIndex(..)
+        ) {
+            return Err(ParseError::new(
+                target.span.to(token.span),
+                "Invalid expression: can not assign to this",
+            ));
+        }
+        let value = self.parse_value(&target.r#type())?;
+        let span = target.span.to(value.span);
+        Ok(Expr {
+            kind: ExprKind::Assign(op, Box::new(target), Box::new(value)),
+            span,
+        })
+    }
+
+    /// condition ? then : otherwise
+    fn parse_ternary(&mut self) -> Result<Expr, ParseError> {
+        let condition = self.parse_binary(1)?;
+        if self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::QUESTION)
+            .is_none()
+        {
+            return Ok(condition);
+        }
+        let then = self.parse_assignment()?;
+        self.expect(TokenType::COLON, ":")?;
+        let otherwise = self.parse_ternary()?;
+        let span = condition.span.to(otherwise.span);
+        Ok(Expr {
+            kind: ExprKind::Ternary(Box::new(condition), Box::new(then), Box::new(otherwise)),
+            span,
+        })
+    }
+
+    /// Precedence climbing, only operators which bind at least as strong as min_precedence are parsed
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expr, ParseError> {
+        let context = self.context;
+        let mut left = self.parse_unary()?;
+        loop {
+            let before_operator = self.tokens.clone();
+            let Some(token_type) = self.peek_type() else {
+                break;
+            };
+            if *token_type == TokenType::INSTANCEOF && INSTANCEOF_PRECEDENCE >= min_precedence {
+                let token = self.next()?;
+                let pattern = match Pattern::parse(context, self.tokens, self.class_context)? {
+                    Some(pattern) => pattern,
+                    // A type without a variable
+                    None => match VariableType::parse(self.tokens) {
+                        Some(r#type) => Pattern::Type(r#type, None),
+                        None => {
+                            return Err(ParseError::new(
+                                token.span,
+                                "Invalid expression: expected type after instanceof",
+                            ))
+                        }
+                    },
+                };
+                let end = consumed_tokens(&before_operator, self.tokens)
+                    .last()
+                    .unwrap();
+                let span = left.span.to(end.span);
+                left = Expr {
+                    kind: ExprKind::InstanceOf(Box::new(left), pattern),
+                    span,
+                };
+                continue;
+            }
+            let Some((op, precedence)) = binary_op(token_type) else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            self.next()?;
+            // The variables of a pattern on the left are visible on the right, when the right
+            // side is only evaluated after the pattern matched
+            let scope = self.bindings.len();
+            match op {
+                BinaryOp::And => self.bindings.extend(left.bindings_when_true()),
+                BinaryOp::Or => self.bindings.extend(left.bindings_when_false()),
+                _ => {}
+            }
+            // All binary operators are left associative
+            let right = self.parse_binary(precedence + 1);
+            self.bindings.truncate(scope);
+            let right = right?;
+            let span = left.span.to(right.span);
+            left = Expr {
+                kind: ExprKind::Binary(op, Box::new(left), Box::new(right)),
+                span,
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        let context = self.context;
+        let token = match self.tokens.peek() {
+            Some(token) => *token,
+            None => {
+                return Err(ParseError::end_of_input(
+                    context,
+                    "Unexpected end of input while parsing expression",
+                ))
+            }
+        };
+        let op = match token.token_type {
+            TokenType::PLUS => UnaryOp::Plus,
+            TokenType::MINUS => UnaryOp::Minus,
+            TokenType::NOT => UnaryOp::Not,
+            TokenType::BIT_NOT => UnaryOp::BitNot,
+            TokenType::INCREMENT => UnaryOp::PreIncrement,
+            TokenType::DECREMENT => UnaryOp::PreDecrement,
+            TokenType::OPEN_BRACE => {
+                if let Some(cast) = self.parse_cast()? {
+                    return Ok(cast);
+                }
+                return self.parse_postfix(false);
+            }
+            _ => return self.parse_postfix(false),
+        };
+        self.next()?;
+        // e.g. -2147483648, the literal is only in range when its negated
+        let expr = match (op, self.peek_type()) {
+            (UnaryOp::Minus, Some(TokenType::NUMBER_LITERAL(_))) => self.parse_postfix(true)?,
+            _ => self.parse_unary()?,
+        };
+        let span = token.span.to(expr.span);
+        Ok(Expr {
+            kind: ExprKind::Unary(op, Box::new(expr)),
+            span,
+        })
+    }
+
+    /// (int) value or (String) value, returns None when the brace is not a cast
+    fn parse_cast(&mut self) -> Result<Option<Expr>, ParseError> {
+        let mut lookahead = self.tokens.clone();
+        let open = lookahead.next().unwrap();
+        let is_primitive = matches!(
+            lookahead.peek().map(|token| &token.token_type),
+            Some(TokenType::DATATYPE(_))
+        );
+        let is_name = lookahead.peek().is_some_and(|token| {
+            token.token_type == TokenType::UNKNOWN
+                && self
+                    .resolve_variable(token.value.as_ref().unwrap())
+                    .is_none()
+        });
+        if !is_primitive && !is_name {
+            return Ok(None);
+        }
+        let Some(r#type) = VariableType::parse(&mut lookahead) else {
+            return Ok(None);
+        };
+        if lookahead
+            .next_if(|token| token.token_type == TokenType::CLOSE_BRACE)
+            .is_none()
+        {
+            return Ok(None);
+        }
+        // (a) + b is no cast, a cast to a class can only be followed by an operand
+        let is_operand = |token_type: &TokenType| {
+            matches!(
+                token_type,
+                TokenType::UNKNOWN
+                    | TokenType::OPEN_BRACE
+                    | TokenType::NUMBER_LITERAL(_)
+                    | TokenType::STRING_LITERAL(_)
+                    | TokenType::CHAR_LITERAL(_)
+                    | TokenType::TRUE
+                    | TokenType::FALSE
+                    | TokenType::NULL
+                    | TokenType::THIS
+                    | TokenType::NEW
+                    | TokenType::NOT
+                    | TokenType::BIT_NOT
+            )
+        };
+        match lookahead.peek().map(|token| &token.token_type) {
+            Some(TokenType::PLUS | TokenType::MINUS) if is_primitive => {}
+            Some(token_type) if is_operand(token_type) => {}
+            _ => return Ok(None),
+        }
+        *self.tokens = lookahead;
+        let expr = self.parse_unary()?;
+        let span = open.span.to(expr.span);
+        Ok(Some(Expr {
+            kind: ExprKind::Cast(r#type, Box::new(expr)),
+            span,
+        }))
+    }
+
+    /// Method calls, field access, array indexing and x++
+    fn parse_postfix(&mut self, negated: bool) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_primary(negated)?;
+        loop {
+            match self.peek_type() {
+                Some(TokenType::DOT) => {
+                    self.next()?;
+                    // Explicit type arguments like Util.<String>first(values), Rust infers them
+                    if self.peek_type() == Some(&TokenType::LESS)
+                        && VariableType::parse_type_args(self.tokens).is_none()
+                    {
+                        return Err(ParseError::new(
+                            self.next()?.span,
+                            "Invalid expression: expected type arguments",
+                        ));
+                    }
+                    let name_token = self.expect(TokenType::UNKNOWN, "name after .")?;
+                    expr = self.parse_member(expr, name_token)?;
+                }
+                Some(TokenType::OPEN_SQUARE) => {
+                    self.next()?;
+                    let index = self.parse_assignment()?;
+                    let close = self.expect(TokenType::CLOSE_SQUARE, "]")?;
+                    let span = expr.span.to(close.span);
+                    expr = Expr {
+                        kind: ExprKind::ArrayIndex(Box::new(expr), Box::new(index)),
+                        span,
+                    };
+                }
+                Some(TokenType::DOUBLE_COLON) => {
+                    self.next()?;
+                    return self.parse_method_reference(expr);
+                }
+                Some(TokenType::INCREMENT | TokenType::DECREMENT) => {
+                    let token = self.next()?;
+                    let op = match token.token_type {
+                        TokenType::INCREMENT => UnaryOp::PostIncrement,
+                        _ => UnaryOp::PostDecrement,
+                    };
+                    let span = expr.span.to(token.span);
+                    expr = Expr {
+                        kind: ExprKind::Unary(op, Box::new(expr)),
+                        span,
+                    };
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    /// The method call or field access behind target.
+    ///
+    /// name_token is the name of the member, the next token is the ( of the arguments of a call
+    fn parse_member(&mut self, target: Expr, name_token: &Token) -> Result<Expr, ParseError> {
+        let context = self.context;
+        let name = name_token.value.clone().unwrap();
+        if self.peek_type() == Some(&TokenType::OPEN_BRACE) {
+            let (args, end) =
+                self.parse_arguments(|parser, args| parser.method_signature(&target, &name, args))?;
+            let span = target.span.to(end);
+            let (r#type, params) = self.method_signature(&target, &name, &args);
+            let throws = self.throws(Some(&target), &name, &args);
+            let overload = self.overload(Some(&target), &name, &args);
+            let closure_params = self.closure_params(Some(&target), &name, &args);
+            return Ok(Expr {
+                kind: ExprKind::MethodCall {
+                    target: Some(Box::new(target)),
+                    name,
+                    args,
+                    r#type,
+                    params,
+                    throws,
+                    overload,
+                    closure_params,
+                },
+                span,
+            });
+        }
+        let span = target.span.to(name_token.span);
+        // A nested class, e.g. Outer.Inner
+        if let ExprKind::Class(class) = &target.kind {
+            let nested = format!("{}.{}", class, name);
+            if resolve_class(context, &nested).is_some() {
+                return Ok(Expr {
+                    kind: ExprKind::Class(nested),
+                    span,
+                });
+            }
+        }
+        let constant = match &target.kind {
+            ExprKind::Class(class) => self
+                .class_context
+                .get_enum_constant(context, class, &name)
+                .or_else(|| self.class_context.get_static_field(context, class, &name)),
+            _ => None,
+        };
+        let kind = match constant {
+            Some((variable, scope)) => ExprKind::Variable {
+                name,
+                r#type: variable.r#type,
+                scope,
+            },
+            None => {
+                let r#type = match (&target.kind, target.r#type()) {
+                    (ExprKind::This, _) => {
+                        self.class_context
+                            .field_type(context, &self.class_context.name, &name)
+                    }
+                    (_, VariableType::Class(class) | VariableType::Generic(class, _)) => {
+                        self.class_context.field_type(context, &class, &name)
+                    }
+                    _ => VariableType::Inferred,
+                };
+                ExprKind::FieldAccess(Box::new(target), name, r#type)
+            }
+        };
+        Ok(Expr { kind, span })
+    }
+
+    fn parse_primary(&mut self, negated: bool) -> Result<Expr, ParseError> {
+        let context = self.context;
+        let before = self.tokens.clone();
+        let token = self.next()?;
+        let literal = |literal| {
+            Ok(Expr {
+                kind: ExprKind::Literal(literal),
+                span: token.span,
+            })
+        };
+        match &token.token_type {
+            TokenType::NUMBER_LITERAL(data_type) => {
+                let raw = token.value.clone().unwrap();
+                // Checks the range, the conversion itself is done later
+                convert_number_literal(&raw, negated)
+                    .map_err(|error| ParseError::new(token.span, error))?;
+                literal(Literal::Number(*data_type, raw))
+            }
+            TokenType::STRING_LITERAL(_) => literal(Literal::String(token.value.clone().unwrap())),
+            TokenType::CHAR_LITERAL(_) => literal(Literal::Char(
+                token.value.as_ref().unwrap().chars().next().unwrap(),
+            )),
+            TokenType::TRUE => literal(Literal::Bool(true)),
+            TokenType::FALSE => literal(Literal::Bool(false)),
+            TokenType::NULL => literal(Literal::Null),
+            // Another constructor of the class, this(args) is the start of a constructor
+            TokenType::THIS if self.peek_type() == Some(&TokenType::OPEN_BRACE) => {
+                let r#type = VariableType::Class(self.class_context.name.clone());
+                let (args, end) = self.parse_arguments(|parser, args| {
+                    let (params, _) = parser.constructor_signature(&r#type, args);
+                    (VariableType::Inferred, params)
+                })?;
+                let (params, overload) = self.constructor_signature(&r#type, &args);
+                let throws = self.constructor_throws(&r#type, &args);
+                Ok(Expr {
+                    kind: ExprKind::New {
+                        r#type,
+                        args,
+                        params,
+                        overload,
+                        throws,
+                    },
+                    span: token.span.to(end),
+                })
+            }
+            TokenType::THIS => Ok(Expr {
+                kind: ExprKind::This,
+                span: token.span,
+            }),
+            TokenType::OPEN_BRACE => {
+                let mut expr = self.parse_assignment()?;
+                let close = self.expect(TokenType::CLOSE_BRACE, ")")?;
+                expr.span = token.span.to(close.span);
+                Ok(expr)
+            }
+            TokenType::OPEN_BRACKET => {
+                let (values, end) = self.parse_array_init(token)?;
+                Ok(Expr {
+                    kind: ExprKind::ArrayInit(VariableType::Inferred, values),
+                    span: token.span.to(end),
+                })
+            }
+            TokenType::NEW => self.parse_new(token),
+            TokenType::SWITCH => {
+                // The switch is parsed again by the statement parser, including its keyword
+                *self.tokens = before.clone();
+                let switch =
+                    Switch::parse(context, self.tokens, self.class_context, self.method_vars)?;
+                let end = consumed_tokens(&before, self.tokens).last().unwrap();
+                Ok(Expr {
+                    kind: ExprKind::Switch(Box::new(switch)),
+                    span: token.span.to(end.span),
+                })
+            }
+            TokenType::UNKNOWN => {
+                let name = token.value.clone().unwrap();
+                let is_call = self.peek_type() == Some(&TokenType::OPEN_BRACE);
+                let is_own = match is_call {
+                    true => self.class_context.get_method(&name).is_some(),
+                    false => self.resolve_variable(&name).is_some(),
+                };
+                // A static member of another class, which is imported with import static
+                if let Some(class) =
+                    static_import_class(context, &name, self.class_context).filter(|_| !is_own)
+                {
+                    if get_prelude_class(&class).is_none()
+                        && self.class_context.get_class(context, &class).is_none()
+                    {
+                        return Err(ParseError::new(
+                            token.span,
+                            format!(
+                                "Unknown identifier {}: only static members of the classes of the file can be imported",
+                                name
+                            ),
+                        ));
+                    }
+                    let target = Expr {
+                        kind: ExprKind::Class(class),
+                        span: token.span,
+                    };
+                    return self.parse_member(target, token);
+                }
+                if is_call {
+                    let (args, end) =
+                        self.parse_arguments(|parser, args| parser.own_signature(&name, args))?;
+                    // Static methods are called on the class, all others on the current object
+                    let target = self.called_method(None, &name, &args).map(|method| {
+                        let kind = match method.is_static {
+                            true => ExprKind::Class(self.class_context.name.clone()),
+                            false => ExprKind::This,
+                        };
+                        Box::new(Expr {
+                            kind,
+                            span: token.span,
+                        })
+                    });
+                    let (r#type, params) = self.own_signature(&name, &args);
+                    let throws = self.throws(target.as_deref(), &name, &args);
+                    let overload = self.overload(target.as_deref(), &name, &args);
+                    let closure_params = self.closure_params(target.as_deref(), &name, &args);
+                    return Ok(Expr {
+                        kind: ExprKind::MethodCall {
+                            target,
+                            r#type,
+                            params,
+                            name,
+                            args,
+                            throws,
+                            overload,
+                            closure_params,
+                        },
+                        span: token.span.to(end),
+                    });
+                }
+                if let Some((variable, scope)) = self.resolve_variable(&name) {
+                    let is_assigned_here = self.peek_type() == Some(&TokenType::EQUAL);
+                    if scope == VariableScope::Local && !variable.initialized && !is_assigned_here {
+                        return Err(ParseError::new(
+                            token.span,
+                            format!("Variable {} is uninitialized", name),
+                        ));
+                    }
+                    return Ok(Expr {
+                        kind: ExprKind::Variable {
+                            name,
+                            r#type: variable.r#type,
+                            scope,
+                        },
+                        span: token.span,
+                    });
+                }
+                if get_prelude_class(&name).is_some()
+                    || self.class_context.get_class(context, &name).is_some()
+                {
+                    return Ok(Expr {
+                        kind: ExprKind::Class(name),
+                        span: token.span,
+                    });
+                }
+                Err(ParseError::new(
+                    token.span,
+                    format!("Unknown identifier {}", name),
+                ))
+            }
+            _ => Err(ParseError::new(
+                token.span,
+                "Invalid expression: unexpected token",
+            )),
+        }
+    }
+
+    fn resolve_variable(&self, name: &str) -> Option<(NodeVariable, VariableScope)> {
+        let context = self.context;
+        if let Some(binding) = self.bindings.iter().rev().find(|var| var.name == name) {
+            return Some((binding.clone(), VariableScope::Local));
+        }
+        get_variable_in_method(
+            context,
+            name.to_string(),
+            self.class_context,
+            self.method_vars,
+        )
+    }
+
+    /// The return type and the argument types of a method which is called on target
+    ///
+    /// Only the methods of the classes of the file are known
+    fn method_signature(
+        &self,
+        target: &Expr,
+        name: &str,
+        args: &[Expr],
+    ) -> (VariableType, Vec<VariableType>) {
+        match &target.kind {
+            ExprKind::This => self.own_signature(name, args),
+            ExprKind::Class(class) if *class == self.class_context.name => {
+                self.own_signature(name, args)
+            }
+            ExprKind::Class(class) => self.signature(class, &[], name, args),
+            _ => self.type_signature(&target.r#type(), name, args),
+        }
+    }
+
+    /// The method of the file which a call of name on target with these arguments calls
+    fn called_method(
+        &self,
+        target: Option<&Expr>,
+        name: &str,
+        args: &[Expr],
+    ) -> Option<&ClassMethod> {
+        let context = self.context;
+        let class = match target.map(|target| &target.kind) {
+            None | Some(ExprKind::This) => None,
+            Some(ExprKind::Class(class)) => Some(class.clone()),
+            Some(_) => target.and_then(|target| target.r#type().class_name().map(str::to_string)),
+        };
+        match class {
+            Some(class) if class != self.class_context.name => {
+                let class = self.class_context.get_class(context, &class)?;
+                select_method(&class.methods, name, &class.type_params, args)
+            }
+            _ => select_method(&self.class_context.methods, name, &[], args),
+        }
+    }
+
+    /// Checks if the called method declares exceptions, only methods of the file can
+    fn throws(&self, target: Option<&Expr>, name: &str, args: &[Expr]) -> bool {
+        self.called_method(target, name, args)
+            .is_some_and(|method| method.throws)
+    }
+
+    /// The Rust name of the called method when it is an overload, see ClassMethod
+    fn overload(&self, target: Option<&Expr>, name: &str, args: &[Expr]) -> Option<String> {
+        self.called_method(target, name, args)
+            .and_then(|method| method.overload.clone())
+    }
+
+    /// Which arguments the called method takes as impl Fn, see ClassMethod
+    fn closure_params(&self, target: Option<&Expr>, name: &str, args: &[Expr]) -> Vec<bool> {
+        self.called_method(target, name, args)
+            .map(|method| method.closure_params.clone())
+            .unwrap_or_default()
+    }
+
+    /// The return type and the argument types of a method of the own class
+    fn own_signature(&self, name: &str, args: &[Expr]) -> (VariableType, Vec<VariableType>) {
+        let context = self.context;
+        let method = select_method(&self.class_context.methods, name, &[], args);
+        let class = resolve_class(context, &self.class_context.name).unwrap_or_default();
+        call_signature(context, &class, method, &[], &[], args)
+    }
+
+    /// Checks if the called constructor declares exceptions, only constructors of the file can
+    fn constructor_throws(&self, r#type: &VariableType, args: &[Expr]) -> bool {
+        let context = self.context;
+        r#type
+            .class_name()
+            .and_then(|name| self.class_context.get_class(context, name))
+            .and_then(|class| select_method(&class.constructors, "new", &class.type_params, args))
+            .is_some_and(|constructor| constructor.throws)
+    }
+
+    /// The argument types and the Rust name of the constructor of a class of the file which
+    /// new with these arguments calls, the types are empty for other classes
+    fn constructor_signature(
+        &self,
+        r#type: &VariableType,
+        args: &[Expr],
+    ) -> (Vec<VariableType>, Option<String>) {
+        let context = self.context;
+        let Some(class) = r#type
+            .class_name()
+            .and_then(|name| self.class_context.get_class(context, name))
+        else {
+            return (Vec::new(), None);
+        };
+        let type_args = match r#type {
+            VariableType::Generic(_, type_args) => type_args.as_slice(),
+            _ => &[],
+        };
+        // The canonical constructor of a record takes the components
+        if let Some(components) = self.class_context.record_components(context, r#type) {
+            let params = components
+                .into_iter()
+                .map(|component| component.r#type)
+                .collect();
+            return (params, None);
+        }
+        let constructor = select_method(&class.constructors, "new", &class.type_params, args);
+        let (_, params) = call_signature(
+            context,
+            &class.rust_name(),
+            constructor,
+            &class.type_params,
+            type_args,
+            args,
+        );
+        (
+            params,
+            constructor.and_then(|constructor| constructor.overload.clone()),
+        )
+    }
+
+    /// The return type and the argument types of a method of another class of the file,
+    /// type_args are the type arguments of the object
+    fn signature(
+        &self,
+        class: &str,
+        type_args: &[VariableType],
+        name: &str,
+        args: &[Expr],
+    ) -> (VariableType, Vec<VariableType>) {
+        let context = self.context;
+        match self.class_context.get_class(context, class) {
+            Some(class) => call_signature(
+                context,
+                &class.rust_name(),
+                select_method(&class.methods, name, &class.type_params, args),
+                &class.type_params,
+                type_args,
+                args,
+            ),
+            None => (VariableType::Inferred, Vec::new()),
+        }
+    }
+
+    /// The signature of a method of an object of this type, type parameters and wildcards have
+    /// the methods of their bound
+    fn type_signature(
+        &self,
+        r#type: &VariableType,
+        name: &str,
+        args: &[Expr],
+    ) -> (VariableType, Vec<VariableType>) {
+        let context = self.context;
+        if let Some(method) =
+            get_functional_method(context, r#type).filter(|method| method.name == name)
+        {
+            let return_type = match method.return_type {
+                MethodReturnType::TYPE(r#type) => r#type,
+                MethodReturnType::VOID => VariableType::Inferred,
+            };
+            return (return_type, method.params);
+        }
+        if r#type.is_string() {
+            let return_type = get_string_method_type(name).unwrap_or(VariableType::Inferred);
+            return (return_type, Vec::new());
+        }
+        match r#type {
+            VariableType::Class(class) if is_exception(context, class) => {
+                let return_type = get_exception_method_type(name).unwrap_or(VariableType::Inferred);
+                (return_type, Vec::new())
+            }
+            VariableType::Class(class) => match get_type_param(context, class) {
+                Some(param) => {
+                    if name == "compareTo"
+                        && param
+                            .bounds
+                            .iter()
+                            .any(|bound| is_comparable(context, bound))
+                    {
+                        return (VariableType::DataType(DataType::INT), vec![r#type.clone()]);
+                    }
+                    param
+                        .bounds
+                        .iter()
+                        .map(|bound| self.type_signature(bound, name, args))
+                        .find(|(return_type, _)| *return_type != VariableType::Inferred)
+                        .unwrap_or((VariableType::Inferred, Vec::new()))
+                }
+                None => self.signature(class, &[], name, args),
+            },
+            VariableType::Generic(class, type_args) => self.signature(class, type_args, name, args),
+            VariableType::Wildcard(WildcardBound::Extends(bound)) => {
+                self.type_signature(bound, name, args)
+            }
+            _ => (VariableType::Inferred, Vec::new()),
+        }
+    }
+
+    /// (a, b), returns the span of the closing brace
+    ///
+    /// signature gives the argument types of the called method, when it gets the arguments
+    /// before the current one, lambdas take their type from it
+    fn parse_arguments(
+        &mut self,
+        signature: impl Fn(&Self, &[Expr]) -> (VariableType, Vec<VariableType>),
+    ) -> Result<(Vec<Expr>, Span), ParseError> {
+        self.expect(TokenType::OPEN_BRACE, "(")?;
+        let mut args = Vec::new();
+        if let Some(close) = self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::CLOSE_BRACE)
+        {
+            return Ok((args, close.span));
+        }
+        loop {
+            let (_, params) = signature(self, &args);
+            let param = params.get(args.len()).unwrap_or(&VariableType::Inferred);
+            args.push(self.parse_value(param)?);
+            let token = self.next()?;
+            match token.token_type {
+                TokenType::COMMA => {}
+                TokenType::CLOSE_BRACE => return Ok((args, token.span)),
+                _ => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid expression: expected , or ) after argument",
+                    ))
+                }
+            }
+        }
+    }
+
+    /// {1, 2, 3}, the { must already be consumed, returns the span of the }
+    fn parse_array_init(&mut self, open: &Token) -> Result<(Vec<Expr>, Span), ParseError> {
+        let mut values = Vec::new();
+        loop {
+            if let Some(close) = self
+                .tokens
+                .next_if(|token| token.token_type == TokenType::CLOSE_BRACKET)
+            {
+                return Ok((values, close.span));
+            }
+            values.push(self.parse_assignment()?);
+            match self.next()? {
+                token if token.token_type == TokenType::COMMA => {}
+                token if token.token_type == TokenType::CLOSE_BRACKET => {
+                    return Ok((values, token.span))
+                }
+                token => {
+                    return Err(ParseError::new(
+                        open.span.to(token.span),
+                        "Invalid array initializer: expected , or }",
+                    ))
+                }
+            }
+        }
+    }
+
+    /// new Class(args), new int[2][3] or new int[] {1, 2}, the new must already be consumed
+    fn parse_new(&mut self, new: &Token) -> Result<Expr, ParseError> {
+        let element = match self.tokens.peek() {
+            Some(Token {
+                token_type: TokenType::DATATYPE(data_type),
+                ..
+            }) => {
+                self.next()?;
+                Some(VariableType::DataType(*data_type))
+            }
+            Some(token) if token.token_type == TokenType::UNKNOWN => {
+                let mut name = self.next()?.value.clone().unwrap();
+                while self.peek_type() == Some(&TokenType::DOT) {
+                    self.next()?;
+                    name.push('.');
+                    name.push_str(
+                        self.expect(TokenType::UNKNOWN, "class name")?
+                            .value
+                            .as_ref()
+                            .unwrap(),
+                    );
+                }
+                // new Box<>() has the type arguments of its declaration
+                let r#type = match self.peek_type() {
+                    Some(TokenType::LESS) => match VariableType::parse_type_args(self.tokens) {
+                        Some(args) if !args.is_empty() => VariableType::Generic(name.clone(), args),
+                        Some(_) => VariableType::Class(name.clone()),
+                        None => {
+                            return Err(ParseError::new(
+                                self.next()?.span,
+                                "Invalid expression: expected type arguments",
+                            ))
+                        }
+                    },
+                    _ => VariableType::Class(name.clone()),
+                };
+                if self.peek_type() == Some(&TokenType::OPEN_BRACE) {
+                    let (args, end) = self.parse_arguments(|parser, args| {
+                        let (params, _) = parser.constructor_signature(&r#type, args);
+                        (VariableType::Inferred, params)
+                    })?;
+                    let (params, overload) = self.constructor_signature(&r#type, &args);
+                    let throws = self.constructor_throws(&r#type, &args);
+                    return Ok(Expr {
+                        kind: ExprKind::New {
+                            r#type,
+                            args,
+                            params,
+                            overload,
+                            throws,
+                        },
+                        span: new.span.to(end),
+                    });
+                }
+                Some(VariableType::Class(name))
+            }
+            _ => None,
+        };
+        let Some(mut r#type) = element else {
+            return Err(ParseError::new(
+                new.span,
+                "Invalid expression: expected type after new",
+            ));
+        };
+
+        let mut dimensions = Vec::new();
+        let mut end = new.span;
+        while self.peek_type() == Some(&TokenType::OPEN_SQUARE) {
+            self.next()?;
+            r#type = VariableType::Array(Box::new(r#type));
+            if let Some(close) = self
+                .tokens
+                .next_if(|token| token.token_type == TokenType::CLOSE_SQUARE)
+            {
+                end = close.span;
+                continue;
+            }
+            dimensions.push(self.parse_assignment()?);
+            end = self.expect(TokenType::CLOSE_SQUARE, "]")?.span;
+        }
+        if !matches!(r#type, VariableType::Array(_)) {
+            return Err(ParseError::new(
+                new.span.to(end),
+                "Invalid expression: expected ( or [ after the type",
+            ));
+        }
+        if dimensions.is_empty() {
+            let open = self.expect(TokenType::OPEN_BRACKET, "array initializer")?;
+            let (values, end) = self.parse_array_init(open)?;
+            return Ok(Expr {
+                kind: ExprKind::ArrayInit(r#type, values),
+                span: new.span.to(end),
+            });
+        }
+        Ok(Expr {
+            kind: ExprKind::NewArray(r#type, dimensions),
+            span: new.span.to(end),
+        })
+    }
+
+    /// x -> x + 1, (a, b) -> a + b or (int a) -> { return a; }, None if no lambda starts here
+    ///
+    /// Parameters without a type get the types of the method of the functional interface
+    fn parse_lambda(&mut self, target: &VariableType) -> Result<Option<Expr>, ParseError> {
+        let context = self.context;
+        if self.in_case_label || !self.starts_lambda() {
+            return Ok(None);
+        }
+        let before = self.tokens.clone();
+        let start = self.next()?;
+        let declared = match start.token_type {
+            TokenType::OPEN_BRACE => self.parse_lambda_params()?,
+            _ => vec![(None, start.value.clone().unwrap())],
+        };
+        let arrow = self.expect(TokenType::ARROW, "->")?;
+        let (types, return_type) = match self.functional_method(target) {
+            Some((types, _)) if types.len() != declared.len() => {
+                return Err(ParseError::new(
+                    start.span.to(arrow.span),
+                    format!("Invalid lambda: expected {} parameters", types.len()),
+                ))
+            }
+            Some(method) => method,
+            None => (Vec::new(), MethodReturnType::TYPE(VariableType::Inferred)),
+        };
+        let mut params: Vec<NodeVariable> = declared
+            .into_iter()
+            .enumerate()
+            .map(|(i, (r#type, name))| NodeVariable {
+                name,
+                r#type: r#type
+                    .or_else(|| types.get(i).cloned())
+                    .unwrap_or(VariableType::Inferred),
+                value: None,
+                initialized: true,
+                mutable: false,
+                comments: NodeComments::default(),
+            })
+            .collect();
+
+        let mut vars = self.method_vars.to_vec();
+        vars.extend(self.bindings.iter().cloned());
+        vars.extend(params.iter().cloned());
+        let (body, assigned) = match self.peek_type() {
+            Some(TokenType::OPEN_BRACKET) => {
+                let mut errors = Vec::new();
+                let block = Block::parse(
+                    context,
+                    self.tokens,
+                    self.class_context,
+                    &mut vars,
+                    &return_type,
+                    &mut errors,
+                )?;
+                report_nested_errors(context, errors);
+                let mut body = Statement {
+                    kind: StatementKind::Block(block),
+                    comments: NodeComments::default(),
+                };
+                body.mark_mutable(context);
+                let assigned: Vec<String> = body
+                    .assigned_variables(context)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+                let StatementKind::Block(block) = body.kind else {
+                    unreachable!()
+                };
+                (LambdaBody::Block(block), assigned)
+            }
+            _ => {
+                let value_type = match &return_type {
+                    MethodReturnType::TYPE(r#type) => r#type.clone(),
+                    MethodReturnType::VOID => VariableType::Inferred,
+                };
+                let body = ExprParser::new(self.context, self.tokens, self.class_context, &vars)
+                    .parse_value(&value_type)?;
+                let assigned: Vec<String> = body
+                    .assigned_variables(context)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+                (LambdaBody::Expr(Box::new(body)), assigned)
+            }
+        };
+        for param in &mut params {
+            param.mutable = assigned.contains(&param.name);
+        }
+        let end = consumed_tokens(&before, self.tokens).last().unwrap();
+        Ok(Some(self.lambda(
+            params,
+            body,
+            target,
+            return_type,
+            start.span.to(end.span),
+        )))
+    }
+
+    /// Checks if the next tokens are the parameters of a lambda and its ->
+    fn starts_lambda(&self) -> bool {
+        let mut lookahead = self.tokens.clone();
+        match lookahead.next().map(|token| &token.token_type) {
+            Some(TokenType::UNKNOWN) => {}
+            Some(TokenType::OPEN_BRACE) => {
+                let mut depth = 0;
+                for token in lookahead.by_ref() {
+                    match token.token_type {
+                        TokenType::OPEN_BRACE => depth += 1,
+                        TokenType::CLOSE_BRACE if depth == 0 => break,
+                        TokenType::CLOSE_BRACE => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+            _ => return false,
+        }
+        lookahead
+            .next()
+            .is_some_and(|token| token.token_type == TokenType::ARROW)
+    }
+
+    /// (a, b) or (int a, var b) of a lambda, the ( must already be consumed
+    ///
+    /// The type is None when it is not declared
+    fn parse_lambda_params(&mut self) -> Result<Vec<(Option<VariableType>, String)>, ParseError> {
+        let mut params = Vec::new();
+        if self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::CLOSE_BRACE)
+            .is_some()
+        {
+            return Ok(params);
+        }
+        loop {
+            self.tokens
+                .next_if(|token| token.token_type == TokenType::FINAL);
+            let has_type = self.tokens.clone().nth(1).is_some_and(|token| {
+                !matches!(token.token_type, TokenType::COMMA | TokenType::CLOSE_BRACE)
+            });
+            let r#type = match self.tokens.peek() {
+                Some(token) if token.is_contextual_keyword("var") => {
+                    self.next()?;
+                    None
+                }
+                Some(_) if has_type => match VariableType::parse(self.tokens) {
+                    Some(r#type) => Some(r#type),
+                    None => {
+                        return Err(ParseError::new(
+                            self.next()?.span,
+                            "Invalid lambda: expected parameter",
+                        ))
+                    }
+                },
+                _ => None,
+            };
+            let name = self.expect(TokenType::UNKNOWN, "parameter name")?;
+            params.push((r#type, name.value.clone().unwrap()));
+            let token = self.next()?;
+            match token.token_type {
+                TokenType::COMMA => {}
+                TokenType::CLOSE_BRACE => return Ok(params),
+                _ => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid lambda: expected , or ) after parameter",
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Foo::bar, this::bar, value::bar or Foo::new, the :: must already be consumed
+    ///
+    /// It becomes a lambda which calls the method with the arguments of the lambda. The first
+    /// argument is the object for instance methods of a class, e.g. String::length
+    fn parse_method_reference(&mut self, receiver: Expr) -> Result<Expr, ParseError> {
+        let context = self.context;
+        let name_token = self.next()?;
+        let span = receiver.span.to(name_token.span);
+        let target = self.target.clone();
+        let Some((types, return_type)) = self.functional_method(&target) else {
+            return Err(ParseError::new(
+                span,
+                "Invalid method reference: the functional interface is not known",
+            ));
+        };
+        let params: Vec<NodeVariable> = types
+            .into_iter()
+            .map(|r#type| NodeVariable {
+                name: generate_ident(context, "arg"),
+                r#type,
+                value: None,
+                initialized: true,
+                mutable: false,
+                comments: NodeComments::default(),
+            })
+            .collect();
+        let mut args: Vec<Expr> = params
+            .iter()
+            .map(|param| Expr {
+                kind: ExprKind::Variable {
+                    name: param.name.clone(),
+                    r#type: param.r#type.clone(),
+                    scope: VariableScope::Local,
+                },
+                span,
+            })
+            .collect();
+        let kind = match (&name_token.token_type, &receiver.kind) {
+            (TokenType::NEW, ExprKind::Class(class)) => {
+                let r#type = VariableType::Class(class.clone());
+                let (params, overload) = self.constructor_signature(&r#type, &args);
+                let throws = self.constructor_throws(&r#type, &args);
+                ExprKind::New {
+                    r#type,
+                    args,
+                    params,
+                    overload,
+                    throws,
+                }
+            }
+            (TokenType::UNKNOWN, _) => {
+                let name = name_token.value.clone().unwrap();
+                let is_unbound = match &receiver.kind {
+                    ExprKind::Class(class) => match self.class_context.get_class(context, class) {
+                        Some(class) => class
+                            .methods
+                            .iter()
+                            .any(|method| method.name == name && !method.is_static),
+                        // Methods of other classes are only known by their arguments
+                        None => args
+                            .first()
+                            .is_some_and(|arg| arg.r#type().class_name() == Some(class.as_str())),
+                    },
+                    _ => false,
+                };
+                let target = match is_unbound && !args.is_empty() {
+                    true => args.remove(0),
+                    false => receiver,
+                };
+                let (r#type, params) = self.method_signature(&target, &name, &args);
+                let throws = self.throws(Some(&target), &name, &args);
+                let overload = self.overload(Some(&target), &name, &args);
+                let closure_params = self.closure_params(Some(&target), &name, &args);
+                ExprKind::MethodCall {
+                    target: Some(Box::new(target)),
+                    name,
+                    args,
+                    r#type,
+                    params,
+                    throws,
+                    overload,
+                    closure_params,
+                }
+            }
+            _ => {
+                return Err(ParseError::new(
+                    span,
+                    "Invalid method reference: expected method name",
+                ))
+            }
+        };
+        let body = LambdaBody::Expr(Box::new(Expr { kind, span }));
+        Ok(self.lambda(params, body, &target, return_type, span))
+    }
+
+    /// The argument types and the return type of the method which a lambda for this type
+    /// implements, None if the type is no functional interface
+    ///
+    /// Type arguments of a generic method which are not known yet stay Inferred, e.g. the R of
+    /// BiFunction<K, V, R>, Rust infers them from the closure
+    fn functional_method(
+        &self,
+        r#type: &VariableType,
+    ) -> Option<(Vec<VariableType>, MethodReturnType)> {
+        let context = self.context;
+        let name = r#type.class_name()?;
+        let Some(class) = self.class_context.get_class(context, name) else {
+            let method = get_functional_method(context, r#type)?;
+            return Some((method.params, method.return_type));
+        };
+        let mut abstract_methods = class.methods.iter().filter(|method| method.is_abstract);
+        let method = abstract_methods.next()?;
+        if class.kind != ClassKind::Interface || abstract_methods.next().is_some() {
+            return None;
+        }
+        // Raw types have no type arguments
+        let type_args: Vec<VariableType> = match r#type {
+            VariableType::Generic(_, args) if args.len() == class.type_params.len() => {
+                args.iter().map(VariableType::unboxed).collect()
+            }
+            _ => Vec::new(),
+        };
+        let return_type = match &method.return_type {
+            MethodReturnType::TYPE(r#type) => {
+                MethodReturnType::TYPE(r#type.substitute(&class.type_params, &type_args))
+            }
+            MethodReturnType::VOID => MethodReturnType::VOID,
+        };
+        let params = method
+            .params
+            .iter()
+            .map(|param| param.substitute(&class.type_params, &type_args))
+            .collect();
+        Some((params, return_type))
+    }
+
+    /// Creates a lambda, the local variables and the object which its body uses are captured
+    fn lambda(
+        &self,
+        params: Vec<NodeVariable>,
+        body: LambdaBody,
+        r#type: &VariableType,
+        return_type: MethodReturnType,
+        span: Span,
+    ) -> Expr {
+        let mut lambda = Lambda {
+            params,
+            body,
+            r#type: match self.functional_method(r#type) {
+                Some(_) => r#type.clone(),
+                None => VariableType::Inferred,
+            },
+            return_type,
+            captures: Vec::new(),
+            captures_self: false,
+        };
+        let mut captures: Vec<NodeVariable> = Vec::new();
+        let mut captures_self = false;
+        // Java does not allow to declare a variable with the name of a visible local variable,
+        // so every use of such a name is a captured variable
+        let mut capture = |expr: &Expr| match &expr.kind {
+            ExprKind::Variable {
+                name,
+                scope: VariableScope::Local,
+                ..
+            } => {
+                let outer = self
+                    .bindings
+                    .iter()
+                    .rev()
+                    .chain(self.method_vars.iter().rev())
+                    .find(|var| var.name == *name);
+                if let Some(outer) = outer {
+                    if !captures.iter().any(|var| var.name == *name) {
+                        captures.push(outer.clone());
+                    }
+                }
+            }
+            ExprKind::This
+            | ExprKind::Variable {
+                scope: VariableScope::Field,
+                ..
+            } => captures_self = true,
+            _ => {}
+        };
+        match &lambda.body {
+            LambdaBody::Expr(body) => body.for_each(&mut capture),
+            LambdaBody::Block(block) => {
+                for statement in &block.statements {
+                    statement.for_each_expr(&mut |expr| expr.for_each(&mut capture));
+                }
+            }
+        }
+        lambda.captures = captures;
+        lambda.captures_self = captures_self;
+        Expr {
+            kind: ExprKind::Lambda(Box::new(lambda)),
+            span,
+        }
+    }
+}
+
+/// Chooses the overload which Java calls with these arguments, args can also be the arguments
+/// which are parsed so far
+///
+/// Like in Java, exact argument types come first, then widening and boxing
+fn select_method<'a>(
+    methods: &'a [ClassMethod],
+    name: &str,
+    class_params: &[String],
+    args: &[Expr],
+) -> Option<&'a ClassMethod> {
+    let candidates: Vec<&ClassMethod> = methods
+        .iter()
+        .filter(|method| method.name == name)
+        .collect();
+    let arg_types: Vec<VariableType> = args.iter().map(Expr::r#type).collect();
+    let fits = |method: &ClassMethod, exact: bool| {
+        method.params.len() >= arg_types.len()
+            && method.params.iter().zip(&arg_types).all(|(param, arg)| {
+                *arg == VariableType::Inferred
+                    || param == arg
+                    || !exact
+                        && accepts(param, arg, |name| {
+                            method
+                                .type_params
+                                .iter()
+                                .chain(class_params)
+                                .any(|param| param == name)
+                        })
+            })
+    };
+    let complete = |method: &&&ClassMethod| method.params.len() == args.len();
+    candidates
+        .iter()
+        .filter(complete)
+        .find(|method| fits(method, true))
+        .or_else(|| {
+            candidates
+                .iter()
+                .filter(complete)
+                .find(|method| fits(method, false))
+        })
+        .or_else(|| candidates.iter().find(|method| fits(method, false)))
+        .or(candidates.first())
+        .copied()
+}
+
+/// Checks if a value of type arg can be passed for param with widening or boxing
+fn accepts(param: &VariableType, arg: &VariableType, is_type_param: impl Fn(&str) -> bool) -> bool {
+    match (param.unboxed(), arg.unboxed()) {
+        (VariableType::DataType(param), VariableType::DataType(arg)) => widens(arg, param),
+        // Primitives are boxed for Object and type parameters
+        (_, VariableType::DataType(_)) => {
+            param.is_object() || matches!(param, VariableType::Class(name) if is_type_param(name))
+        }
+        (VariableType::DataType(_), _) => false,
+        _ => true,
+    }
+}
+
+/// Checks if Java converts a value of type from implicitly to type to, e.g. int to double
+fn widens(from: DataType, to: DataType) -> bool {
+    let rank = |data_type| match data_type {
+        DataType::BYTE => Some(0),
+        DataType::SHORT => Some(1),
+        DataType::INT => Some(2),
+        DataType::LONG => Some(3),
+        DataType::FLOAT => Some(4),
+        DataType::DOUBLE => Some(5),
+        _ => None,
+    };
+    match (from, rank(to)) {
+        _ if from == to => true,
+        (DataType::CHAR, Some(to)) => to >= 2,
+        (from, Some(to)) => rank(from).is_some_and(|from| from <= to),
+        _ => false,
+    }
+}
+
+/// The return type and the argument types of a call, Inferred and empty for unknown methods
+///
+/// The type parameters of the class get the type arguments of the object, the ones of a generic
+/// method are inferred from the arguments. The type arguments need the bounds of the type
+/// parameters, class is the Rust name of the class of the method
+fn call_signature(
+    context: &Context,
+    class: &str,
+    method: Option<&ClassMethod>,
+    class_params: &[String],
+    class_args: &[VariableType],
+    args: &[Expr],
+) -> (VariableType, Vec<VariableType>) {
+    let Some(method) = method else {
+        return (VariableType::Inferred, Vec::new());
+    };
+    let mut type_params = Vec::new();
+    let mut type_args = Vec::new();
+    let mut unknown_params = Vec::new();
+    // Raw types and new Box<>() have no type arguments, Box<Integer> holds i32 values
+    match class_params.len() == class_args.len() {
+        true => {
+            type_params.extend(class_params.iter().cloned());
+            type_args.extend(class_args.iter().map(VariableType::unboxed));
+            for (i, arg) in class_args.iter().enumerate() {
+                require_param_bounds(context, arg, class, i);
+            }
+        }
+        false => unknown_params.extend(class_params.iter().cloned()),
+    }
+    let owner = format!(
+        "{}::{}",
+        class,
+        method.overload.as_deref().unwrap_or(&method.name)
+    );
+    for (i, type_param) in method.type_params.iter().enumerate() {
+        let inferred = method
+            .params
+            .iter()
+            .zip(args)
+            .find_map(|(param, arg)| param.infer(type_param, &arg.r#type()));
+        match inferred {
+            Some(inferred) => {
+                require_param_bounds(context, &inferred, &owner, i);
+                type_params.push(type_param.clone());
+                type_args.push(inferred);
+            }
+            None => unknown_params.push(type_param.clone()),
+        }
+    }
+    let return_type = match &method.return_type {
+        MethodReturnType::TYPE(r#type) => r#type.substitute(&type_params, &type_args),
+        MethodReturnType::VOID => VariableType::Inferred,
+    };
+    // The arguments behind the parsed ones can still give them a type
+    let unknown_args = vec![VariableType::Inferred; unknown_params.len()];
+    let params = method
+        .params
+        .iter()
+        .map(|param| {
+            param
+                .substitute(&type_params, &type_args)
+                .substitute(&unknown_params, &unknown_args)
+        })
+        .collect();
+    (return_type, params)
+}
@@ -0,0 +1,113 @@
+use std::{iter::Peekable, slice::Iter};
+
+use crate::{
+    parser::{context::Context, convert::data_type::resolve_class, ClassContext, ParseError},
+    token::{Token, TokenType},
+};
+
+use super::parse_qualified_name;
+
+/// Registers the static imports, has to be called before the first type is parsed
+pub fn declare_static_imports(context: &Context, imports: &[NodeImport]) {
+    let static_imports =
+        imports
+            .iter()
+            .filter(|import| import.is_static)
+            .map(|import| match import.on_demand {
+                true => (None, import.name.clone()),
+                false => {
+                    let (class, member) = import.name.rsplit_once('.').unwrap_or_default();
+                    (Some(member.to_string()), class.to_string())
+                }
+            });
+    context.static_imports.borrow_mut().extend(static_imports);
+}
+
+/// The class of a static member which is used without its class because of a static import
+///
+/// # Example
+///
+/// import static java.lang.Math.max; -> max is a member of Math
+///
+/// The members of import static a.B.* are only known when B is a class of the file
+pub fn static_import_class(
+    context: &Context,
+    name: &str,
+    class_context: &ClassContext,
+) -> Option<String> {
+    context
+        .static_imports
+        .borrow()
+        .iter()
+        .find(|(member, class)| match member {
+            Some(member) => member == name,
+            None => class_context
+                .get_class(context, class)
+                .is_some_and(|class| {
+                    class.constants.iter().any(|constant| constant == name)
+                        || class
+                            .methods
+                            .iter()
+                            .any(|method| method.is_static && method.name == name)
+                }),
+        })
+        // Classes of the file keep their qualified name, so nested ones can be found
+        .map(|(_, class)| match resolve_class(context, class) {
+            Some(_) => class.clone(),
+            None => class.rsplit('.').next().unwrap_or(class).to_string(),
+        })
+}
+
+/// import java.util.List;
+pub struct NodeImport {
+    /// The imported name without `.*`
+    ///
+    /// # Example
+    ///
+    /// import java.util.*; -> java.util
+    pub name: String,
+    /// import static java.lang.Math.max;
+    pub is_static: bool,
+    /// import java.util.*;
+    pub on_demand: bool,
+}
+
+impl NodeImport {
+    /// Parses everything after the import keyword, including the semicolon
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+    ) -> Result<Self, ParseError> {
+        let is_static = tokens
+            .next_if(|token| token.token_type == TokenType::STATIC)
+            .is_some();
+        let name = parse_qualified_name(context, tokens)?;
+        let mut on_demand = false;
+        if let Some(dot) = tokens.next_if(|token| token.token_type == TokenType::DOT) {
+            match tokens.next() {
+                Some(token) if token.token_type == TokenType::STAR => on_demand = true,
+                _ => {
+                    return Err(ParseError::new(
+                        dot.span,
+                        "Invalid import: expected name or *",
+                    ))
+                }
+            }
+        }
+        match tokens.next() {
+            Some(token) if token.token_type == TokenType::SEMICOLON => {}
+            Some(token) => return Err(ParseError::new(token.span, "Invalid import: expected ;")),
+            None => {
+                return Err(ParseError::end_of_input(
+                    context,
+                    format!("Invalid import {}: expected ;", name),
+                ))
+            }
+        }
+        Ok(Self {
+            name,
+            is_static,
+            on_demand,
+        })
+    }
+}
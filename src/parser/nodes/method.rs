@@ -1,299 +1,536 @@
 use std::{borrow::Cow, iter::Peekable, slice::Iter};
 
 use crate::{
-    parser::{convert::method::convert_method, ClassContext},
-    prelude::get_prelude_class,
-    token::{Token, TokenType, Visibility},
+    parser::{
+        context::Context,
+        convert::{
+            annotation::convert_annotations,
+            comment::{convert_leading_comments, convert_trailing_comments},
+            data_type::{in_shared_class, require_type_args, with_type_params},
+            exception::{is_exception, with_declared_exceptions},
+            expr::with_receiver,
+            method::convert_method,
+            statement::convert_block,
+        },
+        ClassContext, ClassMethod, ParseError,
+    },
+    token::{Token, TokenType, Trivia, Visibility},
 };
 
-use super::{variable::NodeVariable, FunctionArgument, MethodArgumentType, MethodReturnType};
+use super::{
+    annotation::NodeAnnotation,
+    consumed_tokens, parse_qualified_name,
+    statement::{Block, Statement, StatementKind},
+    variable::{NodeVariable, TypeParam, VariableType},
+    MethodReturnType, NodeComments,
+};
+
+/// The scanned signature of the method with these arguments
+fn find_method<'a>(
+    methods: &'a [ClassMethod],
+    name: &str,
+    args: &[MethodArgument],
+) -> Option<&'a ClassMethod> {
+    methods.iter().find(|method| {
+        method.name == name
+            && method.params.len() == args.len()
+            && method
+                .params
+                .iter()
+                .zip(args)
+                .all(|(param, arg)| param.non_null() == arg.r#type.non_null())
+    })
+}
+
+/// The Rust name of the overload with these arguments, see ClassMethod
+fn find_overload(methods: &[ClassMethod], name: &str, args: &[MethodArgument]) -> Option<String> {
+    find_method(methods, name, args).and_then(|method| method.overload.clone())
+}
 
-#[derive(Default)]
+/// Checks if the body of the constructor at tokens starts with a call of another constructor,
+/// e.g. Point() { this(0, 0); }
+fn starts_with_this_call(mut tokens: Peekable<Iter<Token>>) -> bool {
+    if !tokens.any(|token| token.token_type == TokenType::OPEN_BRACKET) {
+        return false;
+    }
+    tokens
+        .next()
+        .is_some_and(|token| token.token_type == TokenType::THIS)
+        && tokens
+            .next()
+            .is_some_and(|token| token.token_type == TokenType::OPEN_BRACE)
+}
+
+#[derive(Clone, Default)]
 pub struct NodeMethod {
     pub visibility: Visibility,
     pub return_type: MethodReturnType,
     pub r#static: bool,
     pub name: String,
+    /// The Rust name of an overloaded method or constructor, see ClassMethod
+    pub overload: Option<String>,
+    /// Which arguments take any closure as impl Fn, see ClassMethod
+    pub closure_params: Vec<bool>,
+    /// The type parameters of a generic method, e.g. T of <T> T first(T[] values)
+    pub type_params: Vec<TypeParam>,
     pub args: Vec<MethodArgument>,
+    /// The exception classes of the throws clause, the method returns a Result when there are
+    /// any
+    pub throws: Vec<String>,
     pub code: Vec<Cow<'static, str>>,
+    /// Changes fields of the current object, so it needs `&mut self`
+    pub modifies_self: bool,
+    /// Like modifies_self, but calls of methods of the current object are not included
+    pub modifies_fields: bool,
+    /// Names of the methods which are called on the current object
+    pub self_calls: Vec<String>,
+    /// Has no body, e.g. the methods of an interface without default
+    pub r#abstract: bool,
+    /// A constructor which starts with this(...), the other constructor creates the object
+    pub delegates: bool,
+    pub annotations: Vec<NodeAnnotation>,
+    pub comments: NodeComments,
+    /// Comments behind the } of the body
+    pub close_comments: Vec<Trivia>,
 }
 
+#[derive(Clone)]
 pub struct MethodArgument {
-    pub arg: MethodArgumentType,
-    pub array: bool,
+    pub r#type: VariableType,
     pub name: String,
+    /// Gets a new value in the body, so it has to be `mut`
+    pub mutable: bool,
+    pub annotations: Vec<NodeAnnotation>,
+}
+
+impl MethodArgument {
+    /// Arguments can be used like variables in the method body
+    fn to_variable(&self) -> NodeVariable {
+        NodeVariable {
+            name: self.name.clone(),
+            r#type: self.r#type.clone(),
+            value: None,
+            initialized: true,
+            mutable: false,
+            comments: NodeComments::default(),
+        }
+    }
 }
 
 impl NodeMethod {
+    #[allow(clippy::too_many_arguments)]
     pub fn parse(
+        context: &Context,
         tokens: &mut Peekable<Iter<Token>>,
         class_context: &ClassContext,
         visibility: Visibility,
         is_static: bool,
-        return_type: MethodReturnType,
-    ) -> Result<Self, String> {
-        let name = Self::parse_name(tokens)?;
-        let args = Self::parse_arguments(tokens)?;
-        let code = Self::parse_body(&args, class_context, tokens)?;
-        return Ok(Self {
+        mut return_type: MethodReturnType,
+        type_params: Vec<TypeParam>,
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let header_start = tokens.clone();
+        let name = Self::parse_name(context, tokens)?;
+        let mut args = Self::parse_arguments(context, tokens)?;
+        let throws = Self::parse_throws(context, tokens)?;
+        let overload = find_overload(&class_context.methods, &name, &args);
+        let closure_params = find_method(&class_context.methods, &name, &args)
+            .map(|method| method.closure_params.clone())
+            .unwrap_or_default();
+        // The scan found the arguments and return values which can be null
+        let scanned = match name == class_context.name {
+            true => find_method(class_context.constructors(context), "new", &args),
+            false => find_method(&class_context.methods, &name, &args),
+        };
+        if let Some(scanned) = scanned {
+            for (arg, param) in args.iter_mut().zip(&scanned.params) {
+                if param.is_nullable() {
+                    arg.r#type = param.clone();
+                }
+            }
+            if let MethodReturnType::TYPE(r#type @ VariableType::Nullable(_)) = &scanned.return_type
+            {
+                return_type = MethodReturnType::TYPE(r#type.clone());
+            }
+        }
+        // Comments in the header are placed behind the Rust fn header, including the ones after {
+        let comments = NodeComments::from_tokens(
+            consumed_tokens(&header_start, tokens).chain(tokens.peek().copied()),
+        );
+        let is_abstract = tokens
+            .next_if(|token| token.token_type == TokenType::SEMICOLON)
+            .is_some();
+        let body = match is_abstract {
+            true => Self {
+                args,
+                ..Default::default()
+            },
+            false => with_declared_exceptions(context, !throws.is_empty(), || {
+                let method = overload.as_deref().unwrap_or(&name);
+                with_type_params(context, Some(method), &type_params, || {
+                    // Generic types of the signature need the bounds of their type parameters
+                    for arg in &args {
+                        require_type_args(context, &arg.r#type);
+                    }
+                    if let MethodReturnType::TYPE(r#type) = &return_type {
+                        require_type_args(context, r#type);
+                    }
+                    Self::parse_body(context, &args, &return_type, class_context, tokens, errors)
+                })
+            })?,
+        };
+        Ok(Self {
             visibility,
             return_type,
             r#static: is_static,
             name,
-            args,
-            code,
-        });
+            overload,
+            closure_params,
+            type_params,
+            throws,
+            r#abstract: is_abstract,
+            comments,
+            ..body
+        })
+    }
+
+    /// Parses a constructor, starting at its name
+    ///
+    /// The body builds the new object in the local variable `this`, so it can't use `self`
+    pub fn parse_constructor(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        visibility: Visibility,
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let delegates = starts_with_this_call(tokens.clone());
+        let mut constructor = with_receiver(context, "this", || {
+            Self::parse(
+                context,
+                tokens,
+                class_context,
+                visibility,
+                true,
+                MethodReturnType::VOID,
+                Vec::new(),
+                errors,
+            )
+        })?;
+        // The object is returned as Ok(this) after the body, see convert_constructor
+        if !constructor.throws.is_empty()
+            && constructor.code.last().is_some_and(|line| line == "Ok(())")
+        {
+            constructor.code.pop();
+        }
+        Ok(Self {
+            overload: find_overload(
+                class_context.constructors(context),
+                "new",
+                &constructor.args,
+            ),
+            delegates,
+            ..constructor
+        })
+    }
+
+    /// Parses the compact constructor of a record, starting at its name, the arguments are the
+    /// components of the record
+    pub fn parse_compact_constructor(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        visibility: Visibility,
+        components: &[MethodArgument],
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let header_start = tokens.clone();
+        let name = Self::parse_name(context, tokens)?;
+        let comments = NodeComments::from_tokens(
+            consumed_tokens(&header_start, tokens).chain(tokens.peek().copied()),
+        );
+        // The fields are set after the body, so it can only use the arguments
+        let body = with_receiver(context, "this", || {
+            Self::parse_body(
+                context,
+                components,
+                &MethodReturnType::VOID,
+                class_context,
+                tokens,
+                errors,
+            )
+        })?;
+        Ok(Self {
+            visibility,
+            r#static: true,
+            name,
+            comments,
+            ..body
+        })
+    }
+
+    /// Parses an initializer block, the { is the next token
+    pub fn parse_initializer(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        is_static: bool,
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let receiver = if is_static { "self" } else { "this" };
+        let body = with_receiver(context, receiver, || {
+            Self::parse_body(
+                context,
+                &[],
+                &MethodReturnType::VOID,
+                class_context,
+                tokens,
+                errors,
+            )
+        })?;
+        Ok(Self {
+            r#static: is_static,
+            ..body
+        })
+    }
+
+    /// throws IOException, InterruptedException, returns the names of the exception classes
+    pub fn parse_throws(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+    ) -> Result<Vec<String>, ParseError> {
+        let mut throws = Vec::new();
+        if tokens
+            .next_if(|token| token.token_type == TokenType::THROWS)
+            .is_none()
+        {
+            return Ok(throws);
+        }
+        loop {
+            let start = tokens.peek().copied();
+            let name = parse_qualified_name(context, tokens)?;
+            if !is_exception(context, &name) {
+                return Err(ParseError::new(
+                    start.unwrap().span,
+                    format!("Invalid method: {} is no known exception class", name),
+                ));
+            }
+            throws.push(name);
+            if tokens
+                .next_if(|token| token.token_type == TokenType::COMMA)
+                .is_none()
+            {
+                return Ok(throws);
+            }
+        }
     }
 
-    pub fn parse_name(tokens: &mut Peekable<Iter<Token>>) -> Result<String, String> {
-        if let Some(token) = tokens.next() {
-            if token.token_type == TokenType::UNKNOWN {
-                return Ok(token.value.clone().unwrap());
+    pub fn parse_name(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+    ) -> Result<String, ParseError> {
+        match tokens.next() {
+            Some(token) if token.token_type == TokenType::UNKNOWN => {
+                Ok(token.value.clone().unwrap())
             }
+            Some(token) => Err(ParseError::new(
+                token.span,
+                "Invalid method: Expected method name",
+            )),
+            None => Err(ParseError::end_of_input(
+                context,
+                "Invalid method: Unexpected end of input while parsing name",
+            )),
         }
-        Err("Failed to get method name".to_string())
     }
 
     pub fn parse_arguments(
+        context: &Context,
         tokens: &mut Peekable<Iter<Token>>,
-    ) -> Result<Vec<MethodArgument>, String> {
-        if let Some(token) = tokens.next() {
-            if token.token_type == TokenType::OPEN_BRACE {
-                let mut args = vec![];
-                // parse arguments
-                for _ in 0..i32::MAX {
-                    if let Some(token) = tokens.next() {
-                        match &token.token_type {
-                            TokenType::CLOSE_BRACE => {
-                                // if we have a close brace we are done here :D
-                                break;
-                            }
-                            TokenType::DATATYPE(r#type) => {
-                                match Self::parse_argument(
-                                    tokens,
-                                    MethodArgumentType::DATATYPE(r#type.clone()),
-                                ) {
-                                    Ok(arg) => args.push(arg),
-                                    Err(error) => return Err(error),
-                                }
-                            }
-                            TokenType::UNKNOWN => {
-                                let class_name = token.value.as_ref().unwrap();
-                                // TODO: Don't only support prelude classes but also which are included using import
-                                let class = get_prelude_class(class_name).expect("Invalid class");
-                                match Self::parse_argument(tokens, MethodArgumentType::CLASS(class))
-                                {
-                                    Ok(arg) => args.push(arg),
-                                    Err(error) => return Err(error),
-                                }
-                            }
-                            _ => {}
-                        }
-                    } else {
-                        return Err(String::from(
-                            "Invalid method: Unexpected end of input while parsing expression",
-                        ));
-                    }
+    ) -> Result<Vec<MethodArgument>, ParseError> {
+        match tokens.next() {
+            Some(token) if token.token_type == TokenType::OPEN_BRACE => {}
+            Some(token) => {
+                return Err(ParseError::new(
+                    token.span,
+                    "Invalid method: Expected bracket",
+                ))
+            }
+            None => {
+                return Err(ParseError::end_of_input(
+                    context,
+                    "Invalid method: Expected bracket",
+                ))
+            }
+        }
+        let mut args = vec![];
+        if tokens
+            .next_if(|token| token.token_type == TokenType::CLOSE_BRACE)
+            .is_some()
+        {
+            return Ok(args);
+        }
+        loop {
+            args.push(Self::parse_argument(context, tokens)?);
+            match tokens.next() {
+                Some(token) if token.token_type == TokenType::COMMA => {}
+                // if we have a close brace we are done here :D
+                Some(token) if token.token_type == TokenType::CLOSE_BRACE => return Ok(args),
+                Some(token) => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid method: Expected , or ) after argument",
+                    ))
+                }
+                None => {
+                    return Err(ParseError::end_of_input(
+                        context,
+                        "Invalid method: Unexpected end of input while parsing expression",
+                    ))
                 }
-                return Ok(args);
             }
         }
-        Err(String::from("Invalid method: Expected bracket"))
     }
 
+    /// Parses one argument like `int x`, `String[] args` or `int... values`
     pub fn parse_argument(
+        context: &Context,
         tokens: &mut Peekable<Iter<Token>>,
-        typee: MethodArgumentType,
-    ) -> Result<MethodArgument, String> {
-        // lets check if this is an array or just use the name
-        if let Some(token) = tokens.next() {
-            match token.token_type {
-                TokenType::UNKNOWN => Ok(MethodArgument {
-                    name: token.value.clone().unwrap(),
-                    arg: typee,
-                    array: false,
-                }),
-                TokenType::ARRAY => {
-                    // great we know its an array, now lets get the name
-                    if let Some(token) = tokens.next() {
-                        if token.token_type == TokenType::UNKNOWN {
-                            // yay thats the name
-                            Ok(MethodArgument {
-                                name: token.value.clone().unwrap(),
-                                arg: typee,
-                                array: true,
-                            })
-                        } else {
-                            Err(String::from(
-                                "Invalid method: Got a array for an argument but now no name",
-                            ))
-                        }
-                    } else {
-                        Err(String::from(
-                            "Invalid method: Got a array for an argument but now no name",
-                        ))
-                    }
+    ) -> Result<MethodArgument, ParseError> {
+        // Annotations and final can be in any order
+        let mut annotations = NodeAnnotation::parse_list(context, tokens)?;
+        if tokens
+            .next_if(|token| token.token_type == TokenType::FINAL)
+            .is_some()
+        {
+            annotations.extend(NodeAnnotation::parse_list(context, tokens)?);
+        }
+        let Some(mut r#type) = VariableType::parse(tokens) else {
+            return Err(match tokens.peek() {
+                Some(token) => {
+                    ParseError::new(token.span, "Invalid method: Expected argument type")
+                }
+                None => ParseError::end_of_input(
+                    context,
+                    "Invalid method: Expected argument type but got nothing",
+                ),
+            });
+        };
+        // Varargs are an array in the method
+        if tokens
+            .next_if(|token| token.token_type == TokenType::ELLIPSIS)
+            .is_some()
+        {
+            r#type = VariableType::Array(Box::new(r#type));
+        }
+        match tokens.next() {
+            Some(token) if token.token_type == TokenType::UNKNOWN => {
+                // Java also allows the old C style, e.g. String args[]
+                if Self::parse_array_brackets(tokens)? {
+                    r#type = VariableType::Array(Box::new(r#type));
                 }
-                _ => Err(String::from(
-                    "Invalid method: Expected argument name or array but got something different",
-                )),
+                Ok(MethodArgument {
+                    name: token.value.clone().unwrap(),
+                    r#type,
+                    mutable: false,
+                    annotations,
+                })
             }
-        } else {
-            Err(String::from(
-                "Invalid method: Expected argument name or array but got nothing",
-            ))
+            Some(token) => Err(ParseError::new(
+                token.span,
+                "Invalid method: Expected argument name but got something different",
+            )),
+            None => Err(ParseError::end_of_input(
+                context,
+                "Invalid method: Expected argument name but got nothing",
+            )),
         }
     }
 
-    pub fn parse_body(
-        args: &Vec<MethodArgument>,
-        class_context: &ClassContext,
-        tokens: &mut Peekable<Iter<Token>>,
-    ) -> Result<Vec<Cow<'static, str>>, String> {
-        let mut final_code = vec![];
-        if let Some(token) = tokens.next() {
-            if token.token_type == TokenType::OPEN_BRACKET {
-                // here the magic happens
-                // this is for saftly the max method length
-                //
-                let mut current_vars = vec![];
-                let mut current_if_level = 0;
-                for _ in 0..i32::MAX {
-                    let mut cloned_tokens = tokens.clone();
-                    if let Ok(var) =
-                        NodeVariable::parse(&mut cloned_tokens, class_context, &current_vars)
-                    {
-                        final_code.push(var.to_code());
-                        current_vars.push(var);
-                        *tokens = cloned_tokens;
-                        continue;
-                    }
-
-                    if let Some(token) = tokens.next() {
-                        if token.token_type == TokenType::IF {
-                            current_if_level += 1;
-                        } else if token.token_type == TokenType::ELSE {
-                        } else if token.token_type == TokenType::UNKNOWN {
-                            // So this is something unknown, this either can be a Class or a Var, TODO: Check for vars
-                            let class_name = token.value.as_ref().unwrap();
-
-                            // TODO: Don't only support prelude classes but also which are included using import
-                            dbg!(class_name);
-                            let mut class = get_prelude_class(class_name).expect("Invalid class");
-                            let mut method_name = String::new();
-                            for _ in 0..i32::MAX {
-                                if let Some(token) = tokens.next() {
-                                    if token.token_type == TokenType::UNKNOWN {
-                                        // we are using field from method (e.g. System.out...)
-                                        let name = token.value.clone().unwrap();
-
-                                        if let Some(token) = tokens.peek() {
-                                            // this is the final function and not a field
-                                            if token.token_type == TokenType::OPEN_BRACE {
-                                                method_name = name;
-                                            } else {
-                                                let field = match class.get_field(&name) {
-                                                    Some(field) => field,
-                                                    None => {
-                                                        return Err(
-                                                            "Failed to get Class field".to_string()
-                                                        )
-                                                    }
-                                                };
-                                                class = field;
-                                            }
-                                        }
-
-                                        // lets continue searching, we also may use multiple fields
-                                    } else if token.token_type == TokenType::OPEN_BRACE {
-                                        // we want to call a method (e.g. System.println())
-                                        // lets parse the arguments
-                                        let args =
-                                            Self::parse_function_arguments(tokens, &current_vars)?;
-                                        let code = match class.code_from_method(&method_name, args)
-                                        {
-                                            Some(code) => code,
-                                            None => {
-                                                return Err(format!(
-                                                    "Failed to get method {}",
-                                                    method_name
-                                                ))
-                                            }
-                                        };
-                                        final_code.push(code);
-                                    } else if token.token_type == TokenType::SEMICOLON {
-                                        break;
-                                    }
-                                }
-                            }
-                        } else if token.token_type == TokenType::CLOSE_BRACKET {
-                            if current_if_level > 0 {
-                                current_if_level + -1;
-                            } else {
-                                // end of the method
-                                break;
-                            }
-                        }
-                    }
+    /// Parses `[]` and returns if there were brackets
+    fn parse_array_brackets(tokens: &mut Peekable<Iter<Token>>) -> Result<bool, ParseError> {
+        match tokens.peek().copied() {
+            Some(token) if token.token_type == TokenType::OPEN_SQUARE => {
+                tokens.next();
+                match tokens.next() {
+                    Some(next) if next.token_type == TokenType::CLOSE_SQUARE => Ok(true),
+                    _ => Err(ParseError::new(token.span, "Invalid method: Expected ]")),
                 }
             }
+            _ => Ok(false),
         }
-        Ok(final_code)
     }
 
-    fn parse_function_arguments(
+    /// Parses the body including the braces, returns a method with only the arguments, the code
+    /// and what the code changes
+    ///
+    /// The errors of skipped statements are added to errors
+    pub fn parse_body(
+        context: &Context,
+        args: &[MethodArgument],
+        return_type: &MethodReturnType,
+        class_context: &ClassContext,
         tokens: &mut Peekable<Iter<Token>>,
-        variables: &Vec<NodeVariable>,
-    ) -> Result<Vec<FunctionArgument>, String> {
-        let mut args = vec![];
-        for _ in 0..i32::MAX {
-            if let Some(token) = tokens.next() {
-                if token.token_type == TokenType::UNKNOWN {
-                    for var in variables {
-                        if &var.name == token.value.as_ref().unwrap() {
-                            if let Some(_val) = &var.value {
-                                args.push(FunctionArgument::VARIABLE((
-                                    var.r#type.clone(),
-                                    var.name.clone(),
-                                )));
-                            } else {
-                                return Err(format!("Variable {} is uninitialized", var.name));
-                            }
-                            break;
-                        }
-                    }
-                } else if token.token_type == TokenType::QUOTE {
-                    // we have a direct string
-                    let mut words = String::new();
-                    for _ in 0..i16::MAX {
-                        if let Some(token) = tokens.next() {
-                            if token.token_type == TokenType::QUOTE_STRING {
-                                words.push_str(&token.value.clone().unwrap());
-                            } else if token.token_type == TokenType::QUOTE {
-                                args.push(FunctionArgument::STRING(words));
-                                break;
-                            }
-                        }
-                    }
-                } else if token.token_type == TokenType::COMMA {
-                    // TODO: next arg
-                } else if token.token_type == TokenType::CLOSE_BRACE {
-                    return Ok(args);
-                }
-            }
-        }
-        Ok(args)
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut vars: Vec<NodeVariable> = args.iter().map(MethodArgument::to_variable).collect();
+        let mut body = Statement {
+            kind: StatementKind::Block(Block::parse(
+                context,
+                tokens,
+                class_context,
+                &mut vars,
+                return_type,
+                errors,
+            )?),
+            comments: NodeComments::default(),
+        };
+        body.mark_mutable(context);
+        let StatementKind::Block(block) = &body.kind else {
+            unreachable!()
+        };
+        let assigned = body.assigned_variables(context);
+        // The fields of shared objects are cells, which can be changed through &self
+        let is_shared = in_shared_class(context);
+        Ok(Self {
+            args: args
+                .iter()
+                .map(|arg| MethodArgument {
+                    mutable: assigned.contains(&arg.name.as_str()),
+                    ..arg.clone()
+                })
+                .collect(),
+            code: convert_block(context, block, return_type)?,
+            close_comments: block.close_comments.clone(),
+            modifies_self: !is_shared && body.modifies_self(context),
+            modifies_fields: !is_shared && body.modifies_fields(context),
+            self_calls: match is_shared {
+                true => Vec::new(),
+                false => body.self_calls().into_iter().map(str::to_string).collect(),
+            },
+            ..Default::default()
+        })
     }
 
-    pub fn get_full_code(&self) -> Cow<'static, str> {
-        let header = convert_method(self);
+    pub fn get_full_code(&self, context: &Context) -> Cow<'static, str> {
+        let header = convert_method(context, self);
         let code_lines = &self.code;
-        let mut final_code = header.to_string();
-        final_code.push_str("{\n");
+        let mut final_code = convert_leading_comments(&self.comments.leading, true);
+        final_code.push_str(&convert_annotations(&self.annotations, false));
+        final_code.push_str(&header);
+        if self.r#abstract {
+            final_code.push(';');
+            final_code.push_str(&convert_trailing_comments(&self.comments.trailing));
+            return final_code.into();
+        }
+        final_code.push('{');
+        final_code.push_str(&convert_trailing_comments(&self.comments.trailing));
+        final_code.push('\n');
         for line in code_lines {
             final_code.push_str(&format!("{}\n", line));
         }
-        final_code.push_str("}");
+        final_code.push('}');
+        final_code.push_str(&convert_trailing_comments(&self.close_comments));
 
         final_code.into()
     }
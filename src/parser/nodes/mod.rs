@@ -1,31 +1,182 @@
+use expr::VariableScope;
 use variable::{NodeVariable, VariableType};
 
-use crate::{prelude::Class, token::DataType};
+use std::{iter::Peekable, slice::Iter};
 
-use super::ClassContext;
+use crate::token::{DataType, Token, TokenType, Trivia};
 
+use super::{context::Context, ClassContext, ParseError};
+
+pub mod annotation;
 pub mod class;
+pub mod compilation_unit;
 pub mod expr;
+pub mod import;
 pub mod method;
+pub mod statement;
 pub mod variable;
 
 pub fn get_variable_in_method(
+    context: &Context,
     var_name: String,
     class_context: &ClassContext,
     method_vars: &[NodeVariable],
-) -> Option<(NodeVariable, bool)> {
-    // First search class variables
+) -> Option<(NodeVariable, VariableScope)> {
+    // Local variables hide class variables with the same name
+    for var in method_vars.iter().rev() {
+        if var.name == var_name {
+            return Some((var.clone(), VariableScope::Local));
+        }
+    }
     for var in &class_context.variables {
         if var.variable.name == var_name {
-            return Some((var.variable.clone(), true));
+            return Some((var.variable.clone(), class_context.scope_of(var)));
         }
     }
-    for var in method_vars {
-        if var.name == var_name {
-            return Some((var.clone(), false));
+    class_context.get_enum_constant(context, &class_context.name, &var_name)
+}
+
+/// Comments which belong to a node
+///
+/// Leading comments are placed above the generated code and trailing ones behind it
+#[derive(Default, Clone, Debug)]
+pub struct NodeComments {
+    pub leading: Vec<Trivia>,
+    pub trailing: Vec<Trivia>,
+}
+
+impl NodeComments {
+    /// Collects the comments of all tokens which belong to a node, only the leading comments
+    /// of the first token are placed above the code, all others are placed behind it
+    pub fn from_tokens<'a>(tokens: impl IntoIterator<Item = &'a Token>) -> Self {
+        let mut comments = Self::default();
+        for (i, token) in tokens.into_iter().enumerate() {
+            if i == 0 {
+                comments
+                    .leading
+                    .extend(token.leading_trivia.iter().cloned());
+            } else {
+                comments
+                    .trailing
+                    .extend(token.leading_trivia.iter().cloned());
+            }
+            comments
+                .trailing
+                .extend(token.trailing_trivia.iter().cloned());
+        }
+        comments
+    }
+}
+
+/// Parses a name like java.util.List, a `.` which is not followed by a name is not consumed
+pub fn parse_qualified_name(
+    context: &Context,
+    tokens: &mut Peekable<Iter<Token>>,
+) -> Result<String, ParseError> {
+    let mut name = match tokens.next() {
+        Some(token) if token.token_type == TokenType::UNKNOWN => token.value.clone().unwrap(),
+        Some(token) => {
+            return Err(ParseError::new(
+                token.span,
+                "Invalid name: expected identifier",
+            ))
+        }
+        None => {
+            return Err(ParseError::end_of_input(
+                context,
+                "Invalid name: Unexpected end of input",
+            ))
+        }
+    };
+    loop {
+        let mut lookahead = tokens.clone();
+        let is_segment = lookahead
+            .next()
+            .is_some_and(|token| token.token_type == TokenType::DOT)
+            && lookahead
+                .peek()
+                .is_some_and(|token| token.token_type == TokenType::UNKNOWN);
+        if !is_segment {
+            return Ok(name);
+        }
+        tokens.next();
+        name.push('.');
+        name.push_str(tokens.next().unwrap().value.as_ref().unwrap());
+    }
+}
+
+/// Returns the tokens which were consumed between two states of the same token iterator
+pub fn consumed_tokens<'a>(
+    before: &Peekable<Iter<'a, Token>>,
+    after: &Peekable<Iter<'a, Token>>,
+) -> impl Iterator<Item = &'a Token> {
+    before.clone().take(before.len() - after.len())
+}
+
+/// Skips a statement or member which could not be parsed, tokens has to be at its first token
+///
+/// Stops behind the ; or the { ... } which ends it, or before the } of the surrounding block,
+/// so parsing can go on with the next statement or member
+///
+/// # Example
+///
+/// if (a b) { ... } else { ... } x++; -> x++;
+pub fn skip_to_boundary(tokens: &mut Peekable<Iter<Token>>) {
+    #[derive(PartialEq)]
+    enum Open {
+        Block,
+        Paren,
+        /// The ( of a for, its ; don't end the statement
+        ForHeader,
+    }
+    // A } also closes the ( which were never closed
+    let mut open = Vec::new();
+    let mut after_for = false;
+    while let Some(token) = tokens.peek() {
+        match token.token_type {
+            TokenType::CLOSE_BRACKET if !open.contains(&Open::Block) => return,
+            TokenType::OPEN_BRACKET => open.push(Open::Block),
+            TokenType::OPEN_BRACE if after_for => open.push(Open::ForHeader),
+            TokenType::OPEN_BRACE => open.push(Open::Paren),
+            TokenType::CLOSE_BRACE if open.last().is_some_and(|last| *last != Open::Block) => {
+                open.pop();
+            }
+            TokenType::CLOSE_BRACKET => {
+                while open.pop().is_some_and(|last| last != Open::Block) {}
+                // A ( before the outermost { which was never closed, e.g. void f(int a {}
+                if !open.contains(&Open::Block) {
+                    open.clear();
+                }
+            }
+            // The statement ends in a ( which was never closed, e.g. f(a; x++;
+            TokenType::SEMICOLON => {
+                while open.last() == Some(&Open::Paren) {
+                    open.pop();
+                }
+            }
+            _ => {}
+        }
+        let token_type = &tokens.next().unwrap().token_type;
+        after_for = *token_type == TokenType::FOR;
+        let ends_block = open.is_empty() && *token_type == TokenType::CLOSE_BRACKET;
+        // A block can be followed by the rest of its statement, e.g. else, the ; of an array or
+        // the ) of a call
+        let continues = tokens.peek().is_some_and(|next| {
+            matches!(
+                next.token_type,
+                TokenType::ELSE
+                    | TokenType::CATCH
+                    | TokenType::FINALLY
+                    | TokenType::SEMICOLON
+                    | TokenType::DOT
+                    | TokenType::COMMA
+                    | TokenType::CLOSE_BRACE
+            )
+        });
+        if (open.is_empty() && *token_type == TokenType::SEMICOLON) || (ends_block && !continues) {
+            return;
         }
     }
-    None
 }
 
 /// System.out.println(-> ...)
@@ -36,21 +187,19 @@ pub enum FunctionArgument {
     DATATYPE((DataType, String)),
     /// Type, Var Name
     VARIABLE((VariableType, String)),
-    /// Value
-    CLASS(Box<dyn Class>),
-}
-
-/// void myMethod(int x) {
-pub enum MethodArgumentType {
-    STRING,
-    DATATYPE(DataType),
-    CLASS(Box<dyn Class>),
+    /// Type, Rust code of any other expression
+    EXPRESSION((VariableType, String)),
+    /// Format string and its arguments, e.g. from "a" + b
+    ///
+    /// # Example
+    ///
+    /// "a" + b -> "a{b}"
+    FORMAT(String),
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Debug)]
 pub enum MethodReturnType {
     #[default]
     VOID,
-    DATATYPE(DataType),
-    CLASS(Box<dyn Class>),
+    TYPE(VariableType),
 }
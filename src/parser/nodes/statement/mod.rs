@@ -0,0 +1,492 @@
+use crate::{parser::context::Context, source_map::Span, token::Trivia};
+
+use super::{
+    expr::{Expr, Pattern},
+    variable::{NodeVariable, VariableType},
+    NodeComments,
+};
+
+mod parse;
+
+/// A Java statement of a method body
+///
+/// # Example
+///
+/// while (i < 10) i++; -> While(i < 10, Expr(i++))
+#[derive(Clone, Debug)]
+pub struct Statement {
+    pub kind: StatementKind,
+    /// Comments of the tokens before the body of the statement
+    pub comments: NodeComments,
+}
+
+#[derive(Clone, Debug)]
+pub enum StatementKind {
+    Variable(NodeVariable),
+    Expr(Expr),
+    Return(Option<Expr>),
+    Block(Block),
+    If {
+        condition: Expr,
+        then: Box<Statement>,
+        otherwise: Option<Box<Statement>>,
+    },
+    While {
+        condition: Expr,
+        body: Box<Statement>,
+    },
+    DoWhile {
+        body: Box<Statement>,
+        condition: Expr,
+    },
+    /// for (init; condition; update) body
+    For {
+        init: Vec<Statement>,
+        condition: Option<Expr>,
+        update: Vec<Expr>,
+        body: Box<Statement>,
+    },
+    /// for (T variable : iterable) body
+    ForEach {
+        variable: NodeVariable,
+        iterable: Box<Expr>,
+        body: Box<Statement>,
+    },
+    /// label: statement
+    Labeled(String, Box<Statement>),
+    /// break; or break label;
+    Break(Option<String>),
+    /// continue; or continue label;
+    Continue(Option<String>),
+    Switch(Switch),
+    /// yield value; gives the value of a switch expression
+    Yield(Expr),
+    /// throw value;
+    Throw(Expr),
+    /// try { } catch (A | B e) { } finally { }
+    Try(Box<Try>),
+    /// ;
+    Empty,
+}
+
+/// { statements }
+#[derive(Clone, Debug)]
+pub struct Block {
+    pub statements: Vec<Statement>,
+    /// Comments behind the {
+    pub open_comments: Vec<Trivia>,
+    /// Comments before the }
+    pub end_comments: Vec<Trivia>,
+    /// Comments behind the }
+    pub close_comments: Vec<Trivia>,
+}
+
+impl Block {
+    /// Checks if the code after the block can be reached
+    pub fn completes_normally(&self) -> bool {
+        self.statements
+            .last()
+            .is_none_or(Statement::completes_normally)
+    }
+}
+
+/// A try statement, the body, the catch blocks and the finally block are blocks
+#[derive(Clone, Debug)]
+pub struct Try {
+    pub body: Statement,
+    pub catches: Vec<Catch>,
+    pub finally: Option<Statement>,
+}
+
+/// catch (A | B e) { }
+#[derive(Clone, Debug)]
+pub struct Catch {
+    /// The names of the caught exception classes with their spans, a multi-catch has more than
+    /// one
+    pub classes: Vec<(String, Span)>,
+    pub name: String,
+    pub body: Statement,
+    pub comments: NodeComments,
+}
+
+impl Try {
+    /// Checks if the code after the try statement can be reached
+    pub fn completes_normally(&self) -> bool {
+        let body_completes = self.body.completes_normally()
+            || self
+                .catches
+                .iter()
+                .any(|catch| catch.body.completes_normally());
+        body_completes
+            && self
+                .finally
+                .as_ref()
+                .is_none_or(Statement::completes_normally)
+    }
+
+    /// The body, the catch blocks and the finally block
+    fn blocks(&self) -> impl Iterator<Item = &Statement> {
+        std::iter::once(&self.body)
+            .chain(self.catches.iter().map(|catch| &catch.body))
+            .chain(&self.finally)
+    }
+
+    fn blocks_mut(&mut self) -> impl Iterator<Item = &mut Statement> {
+        std::iter::once(&mut self.body)
+            .chain(self.catches.iter_mut().map(|catch| &mut catch.body))
+            .chain(&mut self.finally)
+    }
+}
+
+/// A switch statement or expression
+///
+/// # Example
+///
+/// switch (day) { case 1, 7 -> "weekend"; default -> "workday"; }
+#[derive(Clone, Debug)]
+pub struct Switch {
+    pub selector: Expr,
+    pub cases: Vec<SwitchCase>,
+    /// case X -> instead of case X:, these cases never fall through
+    pub arrow: bool,
+    /// Comments before the }
+    pub end_comments: Vec<Trivia>,
+    /// Comments behind the }
+    pub close_comments: Vec<Trivia>,
+}
+
+/// case 1, 2: or default:, with the statements up to the next case
+#[derive(Clone, Debug)]
+pub struct SwitchCase {
+    pub labels: Vec<CaseLabel>,
+    /// The condition behind when, e.g. case Circle c when c.r > 0 ->
+    pub guard: Option<Expr>,
+    /// An arrow case has exactly one statement, a block or an expression
+    pub body: Vec<Statement>,
+    pub comments: NodeComments,
+}
+
+#[derive(Clone, Debug)]
+pub enum CaseLabel {
+    /// A constant like 1, 'a' or "text"
+    Value(Expr),
+    /// A type or record pattern like String s
+    Pattern(Pattern),
+    Default,
+}
+
+impl Switch {
+    /// The statements of all cases
+    pub fn statements(&self) -> impl Iterator<Item = &Statement> {
+        self.cases.iter().flat_map(|case| &case.body)
+    }
+
+    /// Checks if the cases match types instead of constants
+    pub fn has_patterns(&self) -> bool {
+        self.cases.iter().any(|case| {
+            case.labels
+                .iter()
+                .any(|label| matches!(label, CaseLabel::Pattern(_)))
+        })
+    }
+
+    /// The type of a switch expression, this is the type of the first yielded value
+    pub fn r#type(&self) -> VariableType {
+        self.statements()
+            .find_map(Statement::yield_type)
+            .unwrap_or(VariableType::Inferred)
+    }
+}
+
+impl Statement {
+    /// All direct sub statements
+    pub fn children(&self) -> Vec<&Statement> {
+        match &self.kind {
+            StatementKind::Block(block) => block.statements.iter().collect(),
+            StatementKind::If {
+                then, otherwise, ..
+            } => std::iter::once(&**then)
+                .chain(otherwise.as_deref())
+                .collect(),
+            StatementKind::While { body, .. }
+            | StatementKind::DoWhile { body, .. }
+            | StatementKind::ForEach { body, .. }
+            | StatementKind::Labeled(_, body) => vec![body],
+            StatementKind::For { init, body, .. } => init.iter().chain([&**body]).collect(),
+            StatementKind::Switch(switch) => switch.statements().collect(),
+            StatementKind::Try(r#try) => r#try.blocks().collect(),
+            _ => vec![],
+        }
+    }
+
+    /// The comments behind the } which ends this statement, e.g. } // end of loop
+    ///
+    /// Bodies which are no blocks are statements on their own, they have their own comments
+    pub fn close_comments(&self) -> &[Trivia] {
+        fn body_close_comments(body: &Statement) -> &[Trivia] {
+            match &body.kind {
+                StatementKind::Block(block) => &block.close_comments,
+                _ => &[],
+            }
+        }
+        match &self.kind {
+            StatementKind::Block(block) => &block.close_comments,
+            StatementKind::Switch(switch) => &switch.close_comments,
+            StatementKind::If {
+                then, otherwise, ..
+            } => match otherwise.as_deref() {
+                // else if chains are converted together
+                Some(
+                    otherwise @ Statement {
+                        kind: StatementKind::If { .. },
+                        ..
+                    },
+                ) => otherwise.close_comments(),
+                Some(otherwise) => body_close_comments(otherwise),
+                None => body_close_comments(then),
+            },
+            StatementKind::While { body, .. } | StatementKind::ForEach { body, .. } => {
+                body_close_comments(body)
+            }
+            StatementKind::For { body, .. } => body_close_comments(body),
+            StatementKind::Labeled(_, inner) => match &inner.kind {
+                StatementKind::While { .. }
+                | StatementKind::For { .. }
+                | StatementKind::ForEach { .. } => inner.close_comments(),
+                _ => body_close_comments(inner),
+            },
+            StatementKind::Try(r#try) => body_close_comments(r#try.blocks().last().unwrap()),
+            _ => &[],
+        }
+    }
+
+    /// The type of the first yield in this statement, yields of inner switch expressions
+    /// are not included
+    fn yield_type(&self) -> Option<VariableType> {
+        match &self.kind {
+            StatementKind::Yield(expr) => Some(expr.r#type()),
+            // A yield in a switch statement belongs to the switch expression around it
+            _ => self.children().into_iter().find_map(Statement::yield_type),
+        }
+    }
+
+    /// Calls f for all expressions of this statement and its sub statements
+    pub fn for_each_expr<'a>(&'a self, f: &mut impl FnMut(&'a Expr)) {
+        for expr in self.exprs() {
+            f(expr);
+        }
+        for child in self.children() {
+            child.for_each_expr(f);
+        }
+    }
+
+    /// Checks if the code after this statement can be reached, break, continue, return and
+    /// yield jump somewhere else, see JLS 14.22
+    pub fn completes_normally(&self) -> bool {
+        match &self.kind {
+            StatementKind::Return(_)
+            | StatementKind::Break(_)
+            | StatementKind::Continue(_)
+            | StatementKind::Yield(_)
+            | StatementKind::Throw(_) => false,
+            StatementKind::Block(block) => block.completes_normally(),
+            StatementKind::Try(r#try) => r#try.completes_normally(),
+            StatementKind::If {
+                then,
+                otherwise: Some(otherwise),
+                ..
+            } => then.completes_normally() || otherwise.completes_normally(),
+            _ => true,
+        }
+    }
+
+    /// All expressions of this statement, without the ones of sub statements
+    pub fn exprs(&self) -> Vec<&Expr> {
+        match &self.kind {
+            StatementKind::Variable(variable) => variable.value.iter().collect(),
+            StatementKind::Expr(expr) => vec![expr],
+            StatementKind::Return(expr) => expr.iter().collect(),
+            StatementKind::If { condition, .. }
+            | StatementKind::While { condition, .. }
+            | StatementKind::DoWhile { condition, .. } => vec![condition],
+            StatementKind::For {
+                condition, update, ..
+            } => condition.iter().chain(update).collect(),
+            StatementKind::ForEach { iterable, .. } => vec![&**iterable],
+            StatementKind::Switch(switch) => std::iter::once(&switch.selector)
+                .chain(switch.cases.iter().filter_map(|case| case.guard.as_ref()))
+                .collect(),
+            StatementKind::Yield(expr) | StatementKind::Throw(expr) => vec![expr],
+            _ => vec![],
+        }
+    }
+
+    /// Names of the local variables which get a new value in this statement or its sub statements
+    pub fn assigned_variables(&self, context: &Context) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .exprs()
+            .into_iter()
+            .flat_map(|expr| expr.assigned_variables(context))
+            .collect();
+        for child in self.children() {
+            names.extend(child.assigned_variables(context));
+        }
+        names
+    }
+
+    /// Checks if this statement changes a field of the current object
+    pub fn modifies_self(&self, context: &Context) -> bool {
+        self.exprs()
+            .into_iter()
+            .any(|expr| expr.modifies_self(context))
+            || self
+                .children()
+                .into_iter()
+                .any(|statement| statement.modifies_self(context))
+    }
+
+    /// Checks if this statement changes a field without calling a method of the current object
+    pub fn modifies_fields(&self, context: &Context) -> bool {
+        self.exprs()
+            .into_iter()
+            .any(|expr| expr.modifies_fields(context))
+            || self
+                .children()
+                .into_iter()
+                .any(|statement| statement.modifies_fields(context))
+    }
+
+    /// Names of the methods which are called on the current object
+    pub fn self_calls(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.for_each_expr(&mut |expr| names.extend(expr.self_calls()));
+        names
+    }
+
+    /// Checks if a continue in this statement jumps to the next iteration of the loop around it,
+    /// label is the label of this loop
+    pub fn continues_to(&self, label: Option<&str>) -> bool {
+        match &self.kind {
+            StatementKind::Continue(None) => true,
+            StatementKind::Continue(Some(target)) => Some(target.as_str()) == label,
+            // An unlabeled continue in an inner loop belongs to the inner loop
+            StatementKind::While { body, .. }
+            | StatementKind::DoWhile { body, .. }
+            | StatementKind::For { body, .. }
+            | StatementKind::ForEach { body, .. } => {
+                label.is_some() && body.continues_to_label(label)
+            }
+            _ => self
+                .children()
+                .into_iter()
+                .any(|child| child.continues_to(label)),
+        }
+    }
+
+    /// Only labeled continues to label
+    fn continues_to_label(&self, label: Option<&str>) -> bool {
+        match &self.kind {
+            StatementKind::Continue(Some(target)) => Some(target.as_str()) == label,
+            _ => self
+                .children()
+                .into_iter()
+                .any(|child| child.continues_to_label(label)),
+        }
+    }
+
+    /// Variables which get a new value after their declaration have to be `let mut`,
+    /// Java forbids to hide a local variable, so a name always means the same variable
+    pub fn mark_mutable(&mut self, context: &Context) {
+        match &mut self.kind {
+            StatementKind::Block(block) => mark_mutable_in(context, &mut block.statements, &[]),
+            StatementKind::For {
+                init,
+                condition,
+                update,
+                body,
+            } => {
+                let assigned: Vec<String> = body
+                    .assigned_variables(context)
+                    .into_iter()
+                    .chain(
+                        condition
+                            .iter()
+                            .flat_map(|expr| expr.assigned_variables(context)),
+                    )
+                    .chain(
+                        update
+                            .iter()
+                            .flat_map(|expr| expr.assigned_variables(context)),
+                    )
+                    .map(str::to_string)
+                    .collect();
+                mark_mutable_in(context, init, &assigned);
+                body.mark_mutable(context);
+            }
+            StatementKind::ForEach { variable, body, .. } => {
+                variable.mutable = body
+                    .assigned_variables(context)
+                    .contains(&variable.name.as_str());
+                body.mark_mutable(context);
+            }
+            StatementKind::Switch(switch) => switch.mark_mutable(context),
+            _ => {
+                for child in self.children_mut() {
+                    child.mark_mutable(context);
+                }
+            }
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Statement> {
+        match &mut self.kind {
+            StatementKind::Block(block) => block.statements.iter_mut().collect(),
+            StatementKind::If {
+                then, otherwise, ..
+            } => std::iter::once(&mut **then)
+                .chain(otherwise.as_deref_mut())
+                .collect(),
+            StatementKind::While { body, .. }
+            | StatementKind::DoWhile { body, .. }
+            | StatementKind::ForEach { body, .. }
+            | StatementKind::Labeled(_, body) => vec![body],
+            StatementKind::For { init, body, .. } => init.iter_mut().chain([&mut **body]).collect(),
+            StatementKind::Try(r#try) => r#try.blocks_mut().collect(),
+            _ => vec![],
+        }
+    }
+}
+
+impl Switch {
+    /// The variables of a case are visible in the following cases, a case can fall through
+    /// into them
+    pub fn mark_mutable(&mut self, context: &Context) {
+        for i in 0..self.cases.len() {
+            let (current, rest) = self.cases[i..].split_first_mut().unwrap();
+            let assigned_after: Vec<String> = rest
+                .iter()
+                .flat_map(|case| &case.body)
+                .flat_map(|statement| statement.assigned_variables(context))
+                .map(str::to_string)
+                .collect();
+            mark_mutable_in(context, &mut current.body, &assigned_after);
+        }
+    }
+}
+
+/// Marks the variables of a statement list, assigned_after are the variables which get a new
+/// value after the list
+fn mark_mutable_in(context: &Context, statements: &mut [Statement], assigned_after: &[String]) {
+    for i in 0..statements.len() {
+        let (current, rest) = statements[i..].split_first_mut().unwrap();
+        if let StatementKind::Variable(variable) = &mut current.kind {
+            variable.mutable = assigned_after.contains(&variable.name)
+                || rest.iter().any(|statement| {
+                    statement
+                        .assigned_variables(context)
+                        .contains(&variable.name.as_str())
+                });
+        }
+        current.mark_mutable(context);
+    }
+}
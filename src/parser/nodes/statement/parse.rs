@@ -0,0 +1,1036 @@
+use std::{iter::Peekable, slice::Iter};
+
+use crate::{
+    parser::{
+        context::Context,
+        convert::exception::is_exception,
+        nodes::{
+            annotation::NodeAnnotation,
+            consumed_tokens,
+            expr::{is_assign_op, Expr, ExprKind, Pattern},
+            parse_qualified_name, skip_to_boundary,
+            variable::{NodeVariable, VariableType},
+            MethodReturnType, NodeComments,
+        },
+        report_nested_errors, ClassContext, ParseError,
+    },
+    token::{Token, TokenType, Trivia},
+};
+
+use super::{Block, CaseLabel, Catch, Statement, StatementKind, Switch, SwitchCase, Try};
+
+impl Switch {
+    /// Parses a switch expression, starting at the switch keyword
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        vars: &[NodeVariable],
+    ) -> Result<Self, ParseError> {
+        let start = tokens.clone();
+        let mut vars = vars.to_vec();
+        let mut errors = Vec::new();
+        // A switch expression can't be left with return
+        let mut parser = StatementParser {
+            context,
+            tokens,
+            class_context,
+            vars: &mut vars,
+            return_type: &MethodReturnType::VOID,
+            errors: &mut errors,
+            jump_targets: Vec::new(),
+        };
+        let (mut switch, _) = parser.parse_switch(&start, true)?;
+        report_nested_errors(context, errors);
+        // The variables of the cases are only visible in the switch
+        switch.mark_mutable(context);
+        Ok(switch)
+    }
+}
+
+impl Block {
+    /// Parses a block including the braces
+    ///
+    /// vars are the variables which are visible in the block, variables of the block are
+    /// removed again at its end. return_type is the type of the method or lambda whose body
+    /// the block is
+    ///
+    /// Statements with syntax errors are skipped, their errors are added to errors
+    pub fn parse(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        vars: &mut Vec<NodeVariable>,
+        return_type: &MethodReturnType,
+        errors: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        StatementParser {
+            context,
+            tokens,
+            class_context,
+            vars,
+            return_type,
+            errors,
+            jump_targets: Vec::new(),
+        }
+        .parse_block()
+    }
+}
+
+struct StatementParser<'a, 'b> {
+    context: &'b Context,
+    tokens: &'b mut Peekable<Iter<'a, Token>>,
+    class_context: &'b ClassContext,
+    /// The variables which are visible at the current statement
+    vars: &'b mut Vec<NodeVariable>,
+    /// The type of the returned values
+    return_type: &'b MethodReturnType,
+    /// The errors of the statements which were skipped
+    errors: &'b mut Vec<ParseError>,
+    /// The statements around the current one which break and continue can leave
+    jump_targets: Vec<JumpTarget>,
+}
+
+/// A loop, switch or labeled statement
+struct JumpTarget {
+    label: Option<String>,
+    /// continue can only go to loops
+    is_loop: bool,
+    /// A break without label leaves loops and switches, but no labeled blocks
+    is_breakable: bool,
+}
+
+impl JumpTarget {
+    const LOOP: Self = Self {
+        label: None,
+        is_loop: true,
+        is_breakable: true,
+    };
+    const SWITCH: Self = Self {
+        label: None,
+        is_loop: false,
+        is_breakable: true,
+    };
+}
+
+impl<'a> StatementParser<'a, '_> {
+    fn next(&mut self) -> Result<&'a Token, ParseError> {
+        let context = self.context;
+        self.tokens.next().ok_or_else(|| {
+            ParseError::end_of_input(context, "Invalid statement: Unexpected end of input")
+        })
+    }
+
+    fn peek_type(&mut self) -> Option<&'a TokenType> {
+        self.tokens.peek().map(|token| &token.token_type)
+    }
+
+    fn expect(&mut self, token_type: TokenType, expected: &str) -> Result<&'a Token, ParseError> {
+        let token = self.next()?;
+        if token.token_type != token_type {
+            return Err(ParseError::new(
+                token.span,
+                format!("Invalid statement: expected {}", expected),
+            ));
+        }
+        Ok(token)
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        self.parse_expr_to(&VariableType::Inferred)
+    }
+
+    /// Parses an expression whose value gets the given type, e.g. the value of a return
+    fn parse_expr_to(&mut self, r#type: &VariableType) -> Result<Expr, ParseError> {
+        let context = self.context;
+        let expr = Expr::parse_to(context, self.tokens, self.class_context, self.vars, r#type)?;
+        // Only the variables are marked as initialized, if they are still used with the
+        // right value has to be checked by the Rust compiler
+        for name in expr.assigned_variables(context) {
+            if let Some(var) = self.vars.iter_mut().rev().find(|var| var.name == name) {
+                var.initialized = true;
+            }
+        }
+        Ok(expr)
+    }
+
+    /// { statements }, the variables of the block are only visible in the block
+    fn parse_block(&mut self) -> Result<Block, ParseError> {
+        let open = self.expect(TokenType::OPEN_BRACKET, "{")?;
+        let scope = self.vars.len();
+        let mut statements = Vec::new();
+        loop {
+            if let Some(close) = self
+                .tokens
+                .next_if(|token| token.token_type == TokenType::CLOSE_BRACKET)
+            {
+                self.vars.truncate(scope);
+                return Ok(Block {
+                    statements,
+                    open_comments: open
+                        .leading_trivia
+                        .iter()
+                        .chain(&open.trailing_trivia)
+                        .cloned()
+                        .collect(),
+                    end_comments: close.leading_trivia.clone(),
+                    close_comments: close.trailing_trivia.clone(),
+                });
+            }
+            if self.tokens.peek().is_none() {
+                return Err(ParseError::new(open.span, "Invalid block: expected }"));
+            }
+            statements.extend(self.parse_statement_or_skip());
+        }
+    }
+
+    /// Parses a statement, a statement with a syntax error is skipped and its error is kept,
+    /// so the errors of the following statements are found as well
+    fn parse_statement_or_skip(&mut self) -> Vec<Statement> {
+        let start = self.tokens.clone();
+        let scope = self.vars.len();
+        match self.parse_statements() {
+            Ok(statements) => statements,
+            Err(error) => {
+                self.vars.truncate(scope);
+                // The variable is still declared, so its uses don't report more errors
+                let mut declaration = start.clone();
+                if let Some(variable) = NodeVariable::parse_declared(&mut declaration) {
+                    self.vars.push(variable);
+                }
+                self.skip_from(start, error);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Keeps the error and skips the statement which starts at start
+    fn skip_from(&mut self, start: Peekable<Iter<'a, Token>>, error: ParseError) {
+        self.errors.push(error);
+        *self.tokens = start;
+        skip_to_boundary(self.tokens);
+    }
+
+    /// Parses a statement of a block, a declaration of several variables becomes a statement
+    /// for each of them
+    ///
+    /// # Example
+    ///
+    /// int a = 7, b = -3; -> let a = 7; let b = -3;
+    fn parse_statements(&mut self) -> Result<Vec<Statement>, ParseError> {
+        let context = self.context;
+        let start = self.tokens.clone();
+        let Some(token) = self.tokens.peek().copied() else {
+            return Err(ParseError::end_of_input(
+                context,
+                "Invalid statement: Unexpected end of input",
+            ));
+        };
+
+        // Annotations of local variables are kept as comments
+        if token.token_type == TokenType::AT {
+            let annotations = NodeAnnotation::parse_list(context, self.tokens)?;
+            if annotations.is_empty() {
+                return Err(ParseError::new(
+                    token.span,
+                    "Invalid statement: annotation types are not supported",
+                ));
+            }
+            let mut leading =
+                NodeComments::from_tokens(consumed_tokens(&start, self.tokens)).leading;
+            leading.extend(
+                annotations
+                    .into_iter()
+                    .map(|annotation| Trivia::LineComment(format!(" {}", annotation.text))),
+            );
+            let mut statements = self.parse_statements()?;
+            let comments = match &mut statements[0].kind {
+                StatementKind::Variable(variable) => &mut variable.comments,
+                _ => &mut statements[0].comments,
+            };
+            comments.leading.splice(0..0, leading);
+            return Ok(statements);
+        }
+
+        // A data type can only start a variable declaration, so this is a real error
+        let mut cloned_tokens = self.tokens.clone();
+        match NodeVariable::parse_list(context, &mut cloned_tokens, self.class_context, self.vars) {
+            Ok(variables) => {
+                *self.tokens = cloned_tokens;
+                self.vars.extend(variables.iter().cloned());
+                return Ok(variables
+                    .into_iter()
+                    .map(|variable| Statement {
+                        comments: variable.comments.clone(),
+                        kind: StatementKind::Variable(variable),
+                    })
+                    .collect());
+            }
+            Err(error) if matches!(token.token_type, TokenType::DATATYPE(_)) => return Err(error),
+            Err(_) => {}
+        }
+        Ok(vec![self.parse_statement()?])
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, ParseError> {
+        let context = self.context;
+        let start = self.tokens.clone();
+        let Some(token) = self.tokens.peek().copied() else {
+            return Err(ParseError::end_of_input(
+                context,
+                "Invalid statement: Unexpected end of input",
+            ));
+        };
+
+        // yield is only a keyword when no assignment or member access follows, see JLS 14.21
+        if token.is_contextual_keyword("yield")
+            && self.tokens.clone().nth(1).is_some_and(|next| {
+                !is_assign_op(&next.token_type)
+                    && !matches!(
+                        next.token_type,
+                        TokenType::DOT
+                            | TokenType::OPEN_SQUARE
+                            | TokenType::SEMICOLON
+                            | TokenType::INCREMENT
+                            | TokenType::DECREMENT
+                    )
+            })
+        {
+            self.next()?;
+            let expr = self.parse_expr()?;
+            self.expect_semicolon(&Some(expr.clone()))?;
+            return Ok(Statement {
+                kind: StatementKind::Yield(expr),
+                comments: self.header_comments(&start),
+            });
+        }
+
+        let kind = match &token.token_type {
+            TokenType::OPEN_BRACKET => {
+                let mut block = self.parse_block()?;
+                // The leading comments of the { belong above the block
+                let leading = token.leading_trivia.len();
+                block.open_comments.drain(..leading);
+                return Ok(Statement {
+                    kind: StatementKind::Block(block),
+                    comments: NodeComments {
+                        leading: token.leading_trivia.clone(),
+                        trailing: Vec::new(),
+                    },
+                });
+            }
+            TokenType::SEMICOLON => {
+                self.next()?;
+                StatementKind::Empty
+            }
+            TokenType::IF => return self.parse_if(start),
+            TokenType::WHILE => {
+                self.next()?;
+                let condition = self.parse_condition()?;
+                let comments = self.header_comments(&start);
+                let bindings = condition.bindings_when_true();
+                let body = Box::new(self.with_jump_target(JumpTarget::LOOP, |parser| {
+                    parser.parse_body_with(bindings)
+                })?);
+                return Ok(Statement {
+                    kind: StatementKind::While { condition, body },
+                    comments,
+                });
+            }
+            TokenType::DO => {
+                self.next()?;
+                let comments = self.header_comments(&start);
+                let body = Box::new(
+                    self.with_jump_target(JumpTarget::LOOP, |parser| parser.parse_body())?,
+                );
+                self.expect(TokenType::WHILE, "while")?;
+                let condition = self.parse_condition()?;
+                self.expect(TokenType::SEMICOLON, ";")?;
+                return Ok(Statement {
+                    kind: StatementKind::DoWhile { body, condition },
+                    comments,
+                });
+            }
+            TokenType::FOR => {
+                return self.with_jump_target(JumpTarget::LOOP, |parser| parser.parse_for(start))
+            }
+            TokenType::RETURN => {
+                self.next()?;
+                let expr = match (self.peek_type(), self.return_type) {
+                    (Some(TokenType::SEMICOLON), _) => None,
+                    (_, MethodReturnType::TYPE(r#type)) => Some(self.parse_expr_to(r#type)?),
+                    (_, MethodReturnType::VOID) => Some(self.parse_expr()?),
+                };
+                self.expect_semicolon(&expr)?;
+                StatementKind::Return(expr)
+            }
+            TokenType::BREAK | TokenType::CONTINUE => {
+                self.next()?;
+                let label_token = self
+                    .tokens
+                    .next_if(|token| token.token_type == TokenType::UNKNOWN);
+                let label = label_token.map(|label| label.value.clone().unwrap());
+                self.expect(TokenType::SEMICOLON, ";")?;
+                self.check_jump(token, label_token)?;
+                match token.token_type {
+                    TokenType::BREAK => StatementKind::Break(label),
+                    _ => StatementKind::Continue(label),
+                }
+            }
+            TokenType::UNKNOWN
+                if self
+                    .tokens
+                    .clone()
+                    .nth(1)
+                    .is_some_and(|next| next.token_type == TokenType::COLON) =>
+            {
+                let label = self.next()?.value.clone().unwrap();
+                self.next()?;
+                let comments = self.header_comments(&start);
+                let target = JumpTarget {
+                    label: Some(label.clone()),
+                    is_loop: matches!(
+                        self.peek_type(),
+                        Some(TokenType::WHILE | TokenType::DO | TokenType::FOR)
+                    ),
+                    is_breakable: false,
+                };
+                let statement = self.with_jump_target(target, |parser| parser.parse_statement())?;
+                return Ok(Statement {
+                    kind: StatementKind::Labeled(label, Box::new(statement)),
+                    comments,
+                });
+            }
+            TokenType::SWITCH => {
+                let (switch, comments) = self.with_jump_target(JumpTarget::SWITCH, |parser| {
+                    parser.parse_switch(&start, false)
+                })?;
+                return Ok(Statement {
+                    kind: StatementKind::Switch(switch),
+                    comments,
+                });
+            }
+            TokenType::THROW => {
+                self.next()?;
+                let expr = self.parse_expr()?;
+                self.expect_semicolon(&Some(expr.clone()))?;
+                StatementKind::Throw(expr)
+            }
+            TokenType::TRY => return self.parse_try(start),
+            // this(args) calls another constructor, which creates the object
+            TokenType::THIS
+                if self
+                    .tokens
+                    .clone()
+                    .nth(1)
+                    .is_some_and(|next| next.token_type == TokenType::OPEN_BRACE) =>
+            {
+                let value = self.parse_expr()?;
+                self.expect_semicolon(&Some(value.clone()))?;
+                let comments = NodeComments::from_tokens(consumed_tokens(&start, self.tokens));
+                let variable = NodeVariable {
+                    name: "this".to_string(),
+                    r#type: value.r#type(),
+                    value: Some(value),
+                    initialized: true,
+                    mutable: false,
+                    comments: comments.clone(),
+                };
+                return Ok(Statement {
+                    kind: StatementKind::Variable(variable),
+                    comments,
+                });
+            }
+            TokenType::SYNCHRONIZED | TokenType::ASSERT => {
+                return Err(ParseError::new(
+                    token.span,
+                    format!(
+                        "Invalid statement: {:?} is not supported yet",
+                        token.token_type
+                    ),
+                ))
+            }
+            _ => {
+                let expr = self.parse_expr()?;
+                self.expect_semicolon(&Some(expr.clone()))?;
+                StatementKind::Expr(expr)
+            }
+        };
+        Ok(Statement {
+            kind,
+            comments: NodeComments::from_tokens(consumed_tokens(&start, self.tokens)),
+        })
+    }
+
+    fn expect_semicolon(&mut self, expr: &Option<Expr>) -> Result<(), ParseError> {
+        let context = self.context;
+        match self.tokens.next() {
+            Some(token) if token.token_type == TokenType::SEMICOLON => Ok(()),
+            Some(token) => Err(ParseError::new(
+                expr.as_ref()
+                    .map_or(token.span, |expr| expr.span.to(token.span)),
+                "Invalid statement: expected ;",
+            )),
+            None => Err(ParseError::end_of_input(
+                context,
+                "Invalid statement: Unexpected end of input",
+            )),
+        }
+    }
+
+    /// Parses a statement which break or continue can leave
+    fn with_jump_target<T>(
+        &mut self,
+        target: JumpTarget,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        self.jump_targets.push(target);
+        let result = parse(self);
+        self.jump_targets.pop();
+        result
+    }
+
+    /// Checks that a break or continue has a statement which it leaves, jump is the keyword
+    fn check_jump(&self, jump: &Token, label: Option<&Token>) -> Result<(), ParseError> {
+        let is_break = jump.token_type == TokenType::BREAK;
+        let name = label.and_then(|label| label.value.as_deref());
+        let found = self.jump_targets.iter().any(|target| match name {
+            Some(name) => target.label.as_deref() == Some(name) && (is_break || target.is_loop),
+            None if is_break => target.is_breakable,
+            None => target.is_loop && target.label.is_none(),
+        });
+        match (found, label, is_break) {
+            (true, _, _) => Ok(()),
+            (false, Some(label), true) => Err(ParseError::new(
+                label.span,
+                format!("Invalid break: unknown label {}", name.unwrap()),
+            )),
+            (false, Some(label), false) => Err(ParseError::new(
+                label.span,
+                format!("Invalid continue: unknown loop label {}", name.unwrap()),
+            )),
+            (false, None, true) => Err(ParseError::new(
+                jump.span,
+                "Invalid break: break outside of a loop or switch",
+            )),
+            (false, None, false) => Err(ParseError::new(
+                jump.span,
+                "Invalid continue: continue outside of a loop",
+            )),
+        }
+    }
+
+    /// Comments of the tokens which were consumed since start
+    fn header_comments(&self, start: &Peekable<Iter<'a, Token>>) -> NodeComments {
+        NodeComments::from_tokens(consumed_tokens(start, self.tokens))
+    }
+
+    /// (condition)
+    fn parse_condition(&mut self) -> Result<Expr, ParseError> {
+        self.expect(TokenType::OPEN_BRACE, "(")?;
+        let condition = self.parse_expr()?;
+        self.expect(TokenType::CLOSE_BRACE, ")")?;
+        Ok(condition)
+    }
+
+    /// The body of an if or a loop, variables which are declared in it are only visible there
+    fn parse_body(&mut self) -> Result<Statement, ParseError> {
+        let scope = self.vars.len();
+        let body = self.parse_statement();
+        self.vars.truncate(scope);
+        body
+    }
+
+    /// A body which also sees the variables of the patterns of its condition
+    fn parse_body_with(&mut self, bindings: Vec<NodeVariable>) -> Result<Statement, ParseError> {
+        let scope = self.vars.len();
+        self.vars.extend(bindings);
+        let body = self.parse_body();
+        self.vars.truncate(scope);
+        body
+    }
+
+    /// if (condition) then else otherwise
+    fn parse_if(&mut self, start: Peekable<Iter<'a, Token>>) -> Result<Statement, ParseError> {
+        self.expect(TokenType::IF, "if")?;
+        let condition = self.parse_condition()?;
+        let comments = self.header_comments(&start);
+        let then = Box::new(self.parse_body_with(condition.bindings_when_true())?);
+        let otherwise = match self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::ELSE)
+        {
+            Some(_) => Some(Box::new(
+                self.parse_body_with(condition.bindings_when_false())?,
+            )),
+            None => None,
+        };
+        // if (!(o instanceof String s)) return; declares s for the following statements
+        if otherwise.is_none() && !then.completes_normally() {
+            self.vars.extend(condition.bindings_when_false());
+        }
+        Ok(Statement {
+            kind: StatementKind::If {
+                condition,
+                then,
+                otherwise,
+            },
+            comments,
+        })
+    }
+
+    /// try { } catch (A | B e) { } finally { }
+    fn parse_try(&mut self, start: Peekable<Iter<'a, Token>>) -> Result<Statement, ParseError> {
+        let token = self.expect(TokenType::TRY, "try")?;
+        if self.peek_type() == Some(&TokenType::OPEN_BRACE) {
+            return Err(ParseError::new(
+                token.span,
+                "Invalid statement: try-with-resources is not supported yet",
+            ));
+        }
+        let comments = self.header_comments(&start);
+        let body = self.parse_try_block(Vec::new())?;
+        let mut catches = Vec::new();
+        while self.peek_type() == Some(&TokenType::CATCH) {
+            catches.push(self.parse_catch()?);
+        }
+        let finally = match self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::FINALLY)
+        {
+            Some(_) => Some(self.parse_try_block(Vec::new())?),
+            None => None,
+        };
+        if catches.is_empty() && finally.is_none() {
+            return Err(ParseError::new(
+                token.span,
+                "Invalid statement: expected catch or finally",
+            ));
+        }
+        Ok(Statement {
+            kind: StatementKind::Try(Box::new(Try {
+                body,
+                catches,
+                finally,
+            })),
+            comments,
+        })
+    }
+
+    /// The blocks of a try statement need braces, bindings are the variables of a catch
+    fn parse_try_block(&mut self, bindings: Vec<NodeVariable>) -> Result<Statement, ParseError> {
+        match self.tokens.peek() {
+            Some(token) if token.token_type != TokenType::OPEN_BRACKET => {
+                Err(ParseError::new(token.span, "Invalid statement: expected {"))
+            }
+            _ => self.parse_body_with(bindings),
+        }
+    }
+
+    /// catch (final A | B e) { }, the variable gets the type of the first class
+    fn parse_catch(&mut self) -> Result<Catch, ParseError> {
+        let context = self.context;
+        let start = self.tokens.clone();
+        self.expect(TokenType::CATCH, "catch")?;
+        self.expect(TokenType::OPEN_BRACE, "(")?;
+        self.tokens
+            .next_if(|token| token.token_type == TokenType::FINAL);
+        let mut classes = Vec::new();
+        loop {
+            let mut name_tokens = self.tokens.clone();
+            let class = parse_qualified_name(context, self.tokens)?;
+            let count = name_tokens.len() - self.tokens.len();
+            let first = name_tokens.clone().next().unwrap().span;
+            let span = first.to(name_tokens.nth(count - 1).unwrap().span);
+            if !is_exception(context, &class) {
+                return Err(ParseError::new(
+                    span,
+                    format!("Invalid statement: {} is no known exception class", class),
+                ));
+            }
+            classes.push((class.rsplit('.').next().unwrap_or(&class).to_string(), span));
+            if self
+                .tokens
+                .next_if(|token| token.token_type == TokenType::BIT_OR)
+                .is_none()
+            {
+                break;
+            }
+        }
+        let name = self.expect(TokenType::UNKNOWN, "a name")?;
+        let name = name.value.clone().unwrap();
+        self.expect(TokenType::CLOSE_BRACE, ")")?;
+        let comments = self.header_comments(&start);
+        let variable = NodeVariable {
+            name: name.clone(),
+            r#type: VariableType::Class(classes[0].0.clone()),
+            value: None,
+            initialized: true,
+            mutable: false,
+            comments: NodeComments::default(),
+        };
+        let body = self.parse_try_block(vec![variable])?;
+        Ok(Catch {
+            classes,
+            name,
+            body,
+            comments,
+        })
+    }
+
+    /// for (init; condition; update) body or for (T variable : iterable) body
+    fn parse_for(&mut self, start: Peekable<Iter<'a, Token>>) -> Result<Statement, ParseError> {
+        self.expect(TokenType::FOR, "for")?;
+        self.expect(TokenType::OPEN_BRACE, "(")?;
+        // The variables of the header are only visible in the loop
+        let scope = self.vars.len();
+        let statement = self.parse_for_header(start);
+        self.vars.truncate(scope);
+        statement
+    }
+
+    fn parse_for_header(
+        &mut self,
+        start: Peekable<Iter<'a, Token>>,
+    ) -> Result<Statement, ParseError> {
+        let context = self.context;
+        if let Some(mut variable) = self.parse_for_each_variable()? {
+            let iterable = self.parse_expr()?;
+            // var takes the type of the elements
+            if variable.r#type == VariableType::Inferred {
+                if let VariableType::Array(element) = iterable.r#type() {
+                    variable.r#type = *element;
+                }
+            }
+            self.expect(TokenType::CLOSE_BRACE, ")")?;
+            let comments = self.header_comments(&start);
+            self.vars.push(variable.clone());
+            let body = Box::new(self.parse_body()?);
+            return Ok(Statement {
+                kind: StatementKind::ForEach {
+                    variable,
+                    iterable: Box::new(iterable),
+                    body,
+                },
+                comments,
+            });
+        }
+
+        let mut init = Vec::new();
+        if self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::SEMICOLON)
+            .is_none()
+        {
+            let mut cloned_tokens = self.tokens.clone();
+            match NodeVariable::parse_list(
+                context,
+                &mut cloned_tokens,
+                self.class_context,
+                self.vars,
+            ) {
+                Ok(variables) => {
+                    *self.tokens = cloned_tokens;
+                    for mut variable in variables {
+                        // The comments are part of the header of the loop
+                        variable.comments = NodeComments::default();
+                        self.vars.push(variable.clone());
+                        init.push(Statement {
+                            kind: StatementKind::Variable(variable),
+                            comments: NodeComments::default(),
+                        });
+                    }
+                }
+                Err(error) if matches!(self.peek_type(), Some(TokenType::DATATYPE(_))) => {
+                    return Err(error)
+                }
+                // for (i = 0, j = 10; ...)
+                Err(_) => loop {
+                    let expr = self.parse_expr()?;
+                    init.push(Statement {
+                        kind: StatementKind::Expr(expr),
+                        comments: NodeComments::default(),
+                    });
+                    match self.next()? {
+                        token if token.token_type == TokenType::COMMA => {}
+                        token if token.token_type == TokenType::SEMICOLON => break,
+                        token => {
+                            return Err(ParseError::new(token.span, "Invalid for: expected , or ;"))
+                        }
+                    }
+                },
+            }
+        }
+        let condition = match self.peek_type() {
+            Some(TokenType::SEMICOLON) => None,
+            _ => Some(self.parse_expr()?),
+        };
+        self.expect(TokenType::SEMICOLON, ";")?;
+        let mut update = Vec::new();
+        if self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::CLOSE_BRACE)
+            .is_none()
+        {
+            loop {
+                update.push(self.parse_expr()?);
+                match self.next()? {
+                    token if token.token_type == TokenType::COMMA => {}
+                    token if token.token_type == TokenType::CLOSE_BRACE => break,
+                    token => {
+                        return Err(ParseError::new(token.span, "Invalid for: expected , or )"))
+                    }
+                }
+            }
+        }
+        let comments = self.header_comments(&start);
+        let body = Box::new(self.parse_body()?);
+        Ok(Statement {
+            kind: StatementKind::For {
+                init,
+                condition,
+                update,
+                body,
+            },
+            comments,
+        })
+    }
+
+    /// switch (selector) { cases }, also returns the comments of the header
+    ///
+    /// Arrow cases of a switch expression which only have an expression yield it
+    fn parse_switch(
+        &mut self,
+        start: &Peekable<Iter<'a, Token>>,
+        is_expression: bool,
+    ) -> Result<(Switch, NodeComments), ParseError> {
+        let context = self.context;
+        self.expect(TokenType::SWITCH, "switch")?;
+        let selector = self.parse_condition()?;
+        let open = self.expect(TokenType::OPEN_BRACKET, "{")?;
+        let comments = self.header_comments(start);
+        // The variables of a case are visible in the following cases
+        let scope = self.vars.len();
+        let mut cases = Vec::new();
+        let mut arrow = None;
+        loop {
+            if let Some(close) = self
+                .tokens
+                .next_if(|token| token.token_type == TokenType::CLOSE_BRACKET)
+            {
+                self.vars.truncate(scope);
+                let switch = Switch {
+                    selector,
+                    cases,
+                    arrow: arrow.unwrap_or(false),
+                    end_comments: close.leading_trivia.clone(),
+                    close_comments: close.trailing_trivia.clone(),
+                };
+                return Ok((switch, comments));
+            }
+            let case_start = self.tokens.clone();
+            let case_scope = self.vars.len();
+            let token = self.next()?;
+            let labels = match token.token_type {
+                TokenType::CASE => {
+                    let selector_type = selector.r#type();
+                    let mut labels = vec![self.parse_case_label(&selector_type)?];
+                    while self
+                        .tokens
+                        .next_if(|token| token.token_type == TokenType::COMMA)
+                        .is_some()
+                    {
+                        labels.push(self.parse_case_label(&selector_type)?);
+                    }
+                    labels
+                }
+                TokenType::DEFAULT => vec![CaseLabel::Default],
+                _ => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid switch: expected case, default or }",
+                    ))
+                }
+            };
+            // The variables of a pattern are only visible in its own case
+            let patterns: Vec<&Pattern> = labels
+                .iter()
+                .filter_map(|label| match label {
+                    CaseLabel::Pattern(pattern) => Some(pattern),
+                    _ => None,
+                })
+                .collect();
+            for pattern in &patterns {
+                self.vars.extend(pattern.variables());
+            }
+            let has_patterns = !patterns.is_empty();
+            let guard = match self.tokens.peek() {
+                Some(token) if token.is_contextual_keyword("when") && has_patterns => {
+                    self.next()?;
+                    let guard = Expr::parse_case_label(
+                        context,
+                        self.tokens,
+                        self.class_context,
+                        self.vars,
+                    )?;
+                    self.vars.extend(guard.bindings_when_true());
+                    Some(guard)
+                }
+                _ => None,
+            };
+            let separator = self.next()?;
+            let is_arrow = match separator.token_type {
+                TokenType::ARROW => true,
+                TokenType::COLON => false,
+                _ => {
+                    return Err(ParseError::new(
+                        separator.span,
+                        "Invalid switch: expected -> or :",
+                    ))
+                }
+            };
+            if arrow.is_some_and(|arrow| arrow != is_arrow) {
+                return Err(ParseError::new(
+                    separator.span,
+                    "Invalid switch: -> and : cases can't be mixed",
+                ));
+            }
+            arrow = Some(is_arrow);
+            let comments = self.header_comments(&case_start);
+
+            let body = match is_arrow {
+                true => {
+                    let start = self.tokens.clone();
+                    match self.parse_arrow_body(is_expression) {
+                        Ok(body) => vec![body],
+                        Err(error) => {
+                            self.skip_from(start, error);
+                            vec![]
+                        }
+                    }
+                }
+                false => {
+                    let mut body = Vec::new();
+                    while !matches!(
+                        self.peek_type(),
+                        Some(TokenType::CASE | TokenType::DEFAULT | TokenType::CLOSE_BRACKET)
+                            | None
+                    ) {
+                        body.extend(self.parse_statement_or_skip());
+                    }
+                    body
+                }
+            };
+            if has_patterns {
+                self.vars.truncate(case_scope);
+            }
+            cases.push(SwitchCase {
+                labels,
+                guard,
+                body,
+                comments,
+            });
+            if self.tokens.peek().is_none() {
+                return Err(ParseError::new(open.span, "Invalid switch: expected }"));
+            }
+        }
+    }
+
+    /// A constant or pattern of a case, default can be combined with null
+    ///
+    /// The constants of an enum selector are used without the name of the enum
+    fn parse_case_label(&mut self, selector_type: &VariableType) -> Result<CaseLabel, ParseError> {
+        let context = self.context;
+        if self
+            .tokens
+            .next_if(|token| token.token_type == TokenType::DEFAULT)
+            .is_some()
+        {
+            return Ok(CaseLabel::Default);
+        }
+        if let (VariableType::Class(class), Some(token)) = (selector_type, self.tokens.peek()) {
+            let constant = token
+                .value
+                .as_ref()
+                .and_then(|name| self.class_context.get_enum_constant(context, class, name));
+            if let Some((variable, scope)) = constant {
+                let token = self.next()?;
+                return Ok(CaseLabel::Value(Expr {
+                    kind: ExprKind::Variable {
+                        name: variable.name,
+                        r#type: variable.r#type,
+                        scope,
+                    },
+                    span: token.span,
+                }));
+            }
+        }
+        match Pattern::parse(context, self.tokens, self.class_context)? {
+            Some(pattern) => Ok(CaseLabel::Pattern(pattern)),
+            None => Ok(CaseLabel::Value(Expr::parse_case_label(
+                context,
+                self.tokens,
+                self.class_context,
+                self.vars,
+            )?)),
+        }
+    }
+
+    /// The block or expression behind ->
+    fn parse_arrow_body(&mut self, is_expression: bool) -> Result<Statement, ParseError> {
+        let start = self.tokens.clone();
+        if matches!(
+            self.peek_type(),
+            Some(TokenType::OPEN_BRACKET | TokenType::THROW)
+        ) {
+            return self.parse_body();
+        }
+        let expr = self.parse_expr()?;
+        self.expect_semicolon(&Some(expr.clone()))?;
+        let kind = match is_expression {
+            true => StatementKind::Yield(expr),
+            false => StatementKind::Expr(expr),
+        };
+        Ok(Statement {
+            kind,
+            comments: self.header_comments(&start),
+        })
+    }
+
+    /// T variable : of an enhanced for, nothing is consumed if this is a classic for
+    fn parse_for_each_variable(&mut self) -> Result<Option<NodeVariable>, ParseError> {
+        let mut lookahead = self.tokens.clone();
+        lookahead.next_if(|token| token.token_type == TokenType::FINAL);
+        let is_var = lookahead
+            .peek()
+            .is_some_and(|token| token.is_contextual_keyword("var"));
+        let r#type = match is_var {
+            true => {
+                lookahead.next();
+                Some(VariableType::Inferred)
+            }
+            false => VariableType::parse(&mut lookahead),
+        };
+        let Some(r#type) = r#type else {
+            return Ok(None);
+        };
+        let Some(name) = lookahead.next_if(|token| token.token_type == TokenType::UNKNOWN) else {
+            return Ok(None);
+        };
+        if lookahead
+            .next_if(|token| token.token_type == TokenType::COLON)
+            .is_none()
+        {
+            return Ok(None);
+        }
+        *self.tokens = lookahead;
+        Ok(Some(NodeVariable {
+            name: name.value.clone().unwrap(),
+            r#type,
+            value: None,
+            initialized: true,
+            mutable: false,
+            comments: NodeComments::default(),
+        }))
+    }
+}
@@ -1,13 +1,25 @@
 use std::{borrow::Cow, iter::Peekable, slice::Iter};
 
 use crate::{
-    parser::ClassContext,
+    parser::{
+        context::Context,
+        convert::{
+            comment::convert_with_comments, data_type::convert_variable_type,
+            expr::convert_expr_to, ident::convert_ident,
+        },
+        ClassContext, ParseError,
+    },
+    source_map::Span,
     token::{DataType, Token, TokenType},
 };
 
-use super::{expr::int::IntExpression, get_variable_in_method};
+use super::{
+    consumed_tokens,
+    expr::{Expr, ExprKind},
+    NodeComments,
+};
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct NodeVariable {
     pub name: String,
     pub r#type: VariableType,
@@ -17,63 +29,451 @@ pub struct NodeVariable {
     ///
     /// int i; // Value will be None
     ///
-    /// int i = 10; // Value will be Direct
-    pub value: Option<VariableValue>,
+    /// int i = 10; // Value will be Some
+    pub value: Option<Expr>,
+    /// Has a value at the current point of the method, arguments always have one
+    pub initialized: bool,
+    /// Gets a new value after the declaration, so it has to be `let mut`
+    pub mutable: bool,
+    pub comments: NodeComments,
 }
 
 impl NodeVariable {
-    pub fn to_code(&self) -> Cow<'static, str> {
-        match &self.value {
+    pub fn to_code(&self, context: &Context) -> Result<Cow<'static, str>, ParseError> {
+        let name = convert_ident(context, &self.name);
+        let mutability = if self.mutable { "mut " } else { "" };
+        // Rust can't infer the type of None
+        let r#type = match &self.r#type {
+            r#type @ VariableType::Nullable(_) => {
+                format!(": {}", convert_variable_type(context, r#type))
+            }
+            _ => String::new(),
+        };
+        let code = match &self.value {
             Some(val) => {
-                let val = val.clone().get_value();
-                format!("let {} = {};", self.name, val).into()
+                let val = convert_expr_to(context, val, &self.r#type)?;
+                format!("let {}{}{} = {};", mutability, name, r#type, val)
             }
-            None => format!("let {};", self.name).into(),
-        }
+            None => format!("let {}{}{};", mutability, name, r#type),
+        };
+        Ok(convert_with_comments(&self.comments, &code, false).into())
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum VariableType {
     DataType(DataType),
-    Class,
-}
-
-#[derive(Clone)]
-pub enum VariableValue {
-    /// The value is directly hard-coded
-    /// Saves the value
+    /// Holds the class name, also used for type parameters like T
+    Class(String),
+    /// A class with type arguments, holds the class name and the arguments
     ///
     /// # Example
     ///
-    /// int i = 10;
-    Direct(String),
-    /// The value is set from an existing variable
-    /// Saves the var name
+    /// Map<String, Integer>
+    Generic(String, Vec<VariableType>),
+    /// A type argument which stands for an unknown type
     ///
     /// # Example
     ///
-    /// int o = 10;
-    /// int i = o;
-    ByVar(String),
-    /// The value is calculated by an other function
+    /// ?, ? extends Number or ? super Integer
+    Wildcard(WildcardBound),
+    /// Holds the type of the elements
+    Array(Box<VariableType>),
+    /// A reference which can be null, an Option in Rust. Java has no syntax for it, the
+    /// declarations which can get null are found by scan_nullable
+    Nullable(Box<VariableType>),
+    /// The type is inferred from the value
     ///
     /// # Example
     ///
-    /// int i = get_int_number();
-    /// TODO
-    Indirect,
+    /// var i = 10;
+    Inferred,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WildcardBound {
+    /// ?
+    None,
+    /// ? extends Number
+    Extends(Box<VariableType>),
+    /// ? super Integer
+    Super(Box<VariableType>),
 }
 
-impl VariableValue {
-    pub fn get_value(self) -> String {
+/// A type parameter of a class, interface or method with the types it extends
+///
+/// # Example
+///
+/// T extends Number & Comparable<T> -> TypeParam { name: T, bounds: [Number, Comparable<T>] }
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeParam {
+    pub name: String,
+    pub bounds: Vec<VariableType>,
+}
+
+impl TypeParam {
+    /// Parses <T, U extends Number>, no type parameters if there is no <
+    pub fn parse_list(tokens: &mut Peekable<Iter<Token>>) -> Result<Vec<Self>, ParseError> {
+        let mut type_params = Vec::new();
+        let Some(open) = tokens.next_if(|token| token.token_type == TokenType::LESS) else {
+            return Ok(type_params);
+        };
+        loop {
+            let name = match tokens.next() {
+                Some(token) if token.token_type == TokenType::UNKNOWN => {
+                    token.value.clone().unwrap()
+                }
+                Some(token) => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid type parameters: expected name",
+                    ))
+                }
+                None => {
+                    return Err(ParseError::new(
+                        open.span,
+                        "Invalid type parameters: expected >",
+                    ))
+                }
+            };
+            let mut bounds = Vec::new();
+            // >> can close the bound and the type parameters, e.g. T extends Comparable<T>>
+            let mut closed = 0;
+            if tokens
+                .next_if(|token| token.token_type == TokenType::EXTENDS)
+                .is_some()
+            {
+                // T extends A & B
+                loop {
+                    match VariableType::parse_nested(tokens, &mut closed) {
+                        Some(bound) => bounds.push(bound),
+                        None => {
+                            return Err(ParseError::new(
+                                open.span,
+                                "Invalid type parameters: expected bound",
+                            ))
+                        }
+                    }
+                    if closed > 0
+                        || tokens
+                            .next_if(|token| token.token_type == TokenType::BIT_AND)
+                            .is_none()
+                    {
+                        break;
+                    }
+                }
+            }
+            type_params.push(Self { name, bounds });
+            match closed {
+                0 => {}
+                1 => return Ok(type_params),
+                _ => {
+                    return Err(ParseError::new(
+                        open.span,
+                        "Invalid type parameters: too many >",
+                    ))
+                }
+            }
+            match tokens.next() {
+                Some(token) if token.token_type == TokenType::COMMA => {}
+                Some(token) if token.token_type == TokenType::GREATER => return Ok(type_params),
+                Some(token) => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid type parameters: expected , or >",
+                    ))
+                }
+                None => {
+                    return Err(ParseError::new(
+                        open.span,
+                        "Invalid type parameters: expected >",
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl VariableType {
+    pub fn string() -> Self {
+        VariableType::Class("String".to_string())
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, VariableType::Class(name) if name == "String" || name == "java.lang.String")
+    }
+
+    /// Object can hold a value of every type
+    pub fn is_object(&self) -> bool {
+        matches!(self, VariableType::Class(name) if name == "Object" || name == "java.lang.Object")
+    }
+
+    /// Objects, Strings and arrays can be null, primitives can't
+    pub fn is_reference(&self) -> bool {
+        matches!(
+            self,
+            VariableType::Class(_) | VariableType::Generic(..) | VariableType::Array(_)
+        )
+    }
+
+    /// The type of a reference which can be null, other types stay the same
+    pub fn nullable(self) -> Self {
+        match self.is_reference() {
+            true => VariableType::Nullable(Box::new(self)),
+            false => self,
+        }
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        matches!(self, VariableType::Nullable(_))
+    }
+
+    /// The type of the value when it isn't null
+    pub fn non_null(&self) -> &Self {
         match self {
-            VariableValue::Direct(string) => string,
-            // Gives the var name from which the var comes from
-            VariableValue::ByVar(string) => string,
-            VariableValue::Indirect => todo!(),
+            VariableType::Nullable(r#type) => r#type,
+            r#type => r#type,
+        }
+    }
+
+    /// The name of a class with or without type arguments
+    pub fn class_name(&self) -> Option<&str> {
+        match self {
+            VariableType::Class(name) | VariableType::Generic(name, _) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Replaces the type parameters with their type arguments
+    ///
+    /// # Example
+    ///
+    /// T[] with T = String -> String[]
+    pub fn substitute(&self, params: &[String], args: &[VariableType]) -> Self {
+        match self {
+            VariableType::Class(name) => match params.iter().position(|param| param == name) {
+                Some(i) if i < args.len() => args[i].clone(),
+                _ => self.clone(),
+            },
+            VariableType::Generic(name, type_args) => VariableType::Generic(
+                name.clone(),
+                type_args
+                    .iter()
+                    .map(|arg| arg.substitute(params, args))
+                    .collect(),
+            ),
+            VariableType::Wildcard(WildcardBound::Extends(bound)) => VariableType::Wildcard(
+                WildcardBound::Extends(Box::new(bound.substitute(params, args))),
+            ),
+            VariableType::Wildcard(WildcardBound::Super(bound)) => VariableType::Wildcard(
+                WildcardBound::Super(Box::new(bound.substitute(params, args))),
+            ),
+            VariableType::Array(element) => {
+                VariableType::Array(Box::new(element.substitute(params, args)))
+            }
+            VariableType::Nullable(r#type) => r#type.substitute(params, args).nullable(),
+            _ => self.clone(),
+        }
+    }
+
+    /// The type argument of the type parameter `param` when a value of type `arg` is passed
+    /// for this type, None if this type does not contain the parameter
+    ///
+    /// # Example
+    ///
+    /// T[] with String[] -> String
+    pub fn infer(&self, param: &str, arg: &VariableType) -> Option<VariableType> {
+        match (self, arg) {
+            (_, VariableType::Inferred) => None,
+            (VariableType::Nullable(r#type), _) => r#type.infer(param, arg.non_null()),
+            (_, VariableType::Nullable(arg)) => self.infer(param, arg),
+            (VariableType::Class(name), _) if name == param => Some(arg.clone()),
+            (VariableType::Array(element), VariableType::Array(arg)) => element.infer(param, arg),
+            (VariableType::Generic(name, args), VariableType::Generic(arg_name, arg_args))
+                if name == arg_name =>
+            {
+                args.iter()
+                    .zip(arg_args)
+                    .find_map(|(element, arg)| element.infer(param, arg))
+            }
+            _ => None,
+        }
+    }
+
+    /// The primitive type of a wrapper class like Integer, other types stay the same
+    pub fn unboxed(&self) -> Self {
+        let VariableType::Class(name) = self else {
+            return self.clone();
+        };
+        let data_type = match name.strip_prefix("java.lang.").unwrap_or(name) {
+            "Byte" => DataType::BYTE,
+            "Short" => DataType::SHORT,
+            "Integer" => DataType::INT,
+            "Long" => DataType::LONG,
+            "Float" => DataType::FLOAT,
+            "Double" => DataType::DOUBLE,
+            "Character" => DataType::CHAR,
+            "Boolean" => DataType::BOOLEAN,
+            _ => return self.clone(),
+        };
+        VariableType::DataType(data_type)
+    }
+
+    /// Parses a type like `int`, `String[]`, `java.util.List` or `Map<String, Integer>`, nothing
+    /// is consumed if there is no type
+    ///
+    /// A < which does not start complete type arguments is not consumed, so a < b stays a
+    /// comparison
+    pub fn parse(tokens: &mut Peekable<Iter<Token>>) -> Option<Self> {
+        let mut lookahead = tokens.clone();
+        let mut closed = 0;
+        let r#type = Self::parse_nested(&mut lookahead, &mut closed)?;
+        if closed > 0 {
+            // The > belongs to something else, e.g. a < b >> c
+            return Self::parse_without_args(tokens);
+        }
+        *tokens = lookahead;
+        Some(r#type)
+    }
+
+    /// Parses <String, Integer> or the <> of new Box<>(), the < is the next token
+    pub fn parse_type_args(tokens: &mut Peekable<Iter<Token>>) -> Option<Vec<Self>> {
+        let mut lookahead = tokens.clone();
+        let mut closed = 0;
+        let args = Self::parse_args(&mut lookahead, &mut closed)?;
+        if closed > 0 {
+            return None;
+        }
+        *tokens = lookahead;
+        Some(args)
+    }
+
+    /// closed is the number of > which were already consumed together with the > of inner
+    /// type arguments, because >> and >>> are single tokens
+    fn parse_nested(tokens: &mut Peekable<Iter<Token>>, closed: &mut usize) -> Option<Self> {
+        if tokens
+            .next_if(|token| token.token_type == TokenType::QUESTION)
+            .is_some()
+        {
+            let bound = match tokens.peek().map(|token| &token.token_type) {
+                Some(TokenType::EXTENDS) => WildcardBound::Extends,
+                Some(TokenType::SUPER) => WildcardBound::Super,
+                _ => return Some(VariableType::Wildcard(WildcardBound::None)),
+            };
+            tokens.next();
+            let bound_type = Self::parse_nested(tokens, closed)?;
+            return Some(VariableType::Wildcard(bound(Box::new(bound_type))));
+        }
+        let mut r#type = Self::parse_name(tokens)?;
+        if let VariableType::Class(name) = &r#type {
+            if tokens
+                .peek()
+                .is_some_and(|token| token.token_type == TokenType::LESS)
+            {
+                let mut lookahead = tokens.clone();
+                if let Some(args) = Self::parse_args(&mut lookahead, closed) {
+                    *tokens = lookahead;
+                    // The diamond operator leaves the arguments to the compiler
+                    if !args.is_empty() {
+                        r#type = VariableType::Generic(name.clone(), args);
+                    }
+                }
+            }
+        }
+        // Behind a >> the type is already complete
+        match *closed {
+            0 => Some(Self::parse_dimensions(tokens, r#type)),
+            _ => Some(r#type),
+        }
+    }
+
+    /// <A, B>, the < is the next token
+    fn parse_args(tokens: &mut Peekable<Iter<Token>>, closed: &mut usize) -> Option<Vec<Self>> {
+        tokens.next_if(|token| token.token_type == TokenType::LESS)?;
+        let mut args = Vec::new();
+        // The diamond operator
+        if tokens
+            .next_if(|token| token.token_type == TokenType::GREATER)
+            .is_some()
+        {
+            return Some(args);
+        }
+        loop {
+            args.push(Self::parse_nested(tokens, closed)?);
+            if *closed > 0 {
+                *closed -= 1;
+                return Some(args);
+            }
+            match tokens.next()?.token_type {
+                TokenType::COMMA => {}
+                TokenType::GREATER => return Some(args),
+                TokenType::SHIFT_RIGHT => {
+                    *closed = 1;
+                    return Some(args);
+                }
+                TokenType::UNSIGNED_SHIFT_RIGHT => {
+                    *closed = 2;
+                    return Some(args);
+                }
+                _ => return None,
+            }
         }
     }
+
+    /// Parses a type without type arguments, but with array brackets
+    fn parse_without_args(tokens: &mut Peekable<Iter<Token>>) -> Option<Self> {
+        let r#type = Self::parse_name(tokens)?;
+        Some(Self::parse_dimensions(tokens, r#type))
+    }
+
+    /// The [] behind a type, every pair makes it an array of the type before
+    fn parse_dimensions(tokens: &mut Peekable<Iter<Token>>, mut r#type: Self) -> Self {
+        while tokens
+            .peek()
+            .is_some_and(|token| token.token_type == TokenType::OPEN_SQUARE)
+        {
+            let mut lookahead = tokens.clone();
+            lookahead.next();
+            if lookahead
+                .next_if(|token| token.token_type == TokenType::CLOSE_SQUARE)
+                .is_none()
+            {
+                break;
+            }
+            *tokens = lookahead;
+            r#type = VariableType::Array(Box::new(r#type));
+        }
+        r#type
+    }
+
+    /// A primitive type or a class name with its package
+    fn parse_name(tokens: &mut Peekable<Iter<Token>>) -> Option<Self> {
+        let token = tokens.peek()?;
+        let mut r#type = match &token.token_type {
+            TokenType::DATATYPE(data_type) => VariableType::DataType(*data_type),
+            TokenType::UNKNOWN => VariableType::Class(token.value.clone().unwrap()),
+            _ => return None,
+        };
+        tokens.next();
+        if let VariableType::Class(name) = &mut r#type {
+            while tokens
+                .peek()
+                .is_some_and(|token| token.token_type == TokenType::DOT)
+            {
+                let mut lookahead = tokens.clone();
+                lookahead.next();
+                match lookahead.next() {
+                    Some(part) if part.token_type == TokenType::UNKNOWN => {
+                        name.push('.');
+                        name.push_str(part.value.as_ref().unwrap());
+                        *tokens = lookahead;
+                    }
+                    _ => break,
+                }
+            }
+        }
+        Some(r#type)
+    }
 }
 
 impl NodeVariable {
@@ -91,143 +491,246 @@ impl NodeVariable {
     ///         int o = 10;
     ///     }
     pub fn parse(
+        context: &Context,
         tokens: &mut Peekable<Iter<Token>>,
         class_context: &ClassContext,
         method_vars: &[NodeVariable],
-    ) -> Result<Self, String> {
-        let token = match tokens.next() {
-            Some(token) => token,
+    ) -> Result<Self, ParseError> {
+        let start = tokens.clone();
+        let mut variables = Self::parse_list(context, tokens, class_context, method_vars)?;
+        if variables.len() > 1 {
+            let span = consumed_tokens(&start, tokens)
+                .fold(None, |span: Option<Span>, token| {
+                    Some(span.map_or(token.span, |span| span.to(token.span)))
+                })
+                .unwrap();
+            return Err(ParseError::new(span, "Invalid expression: several variables in one declaration are only supported for local variables"));
+        }
+        Ok(variables.remove(0))
+    }
+
+    /// The variable of a declaration like int x = ..., without parsing its value. A declaration
+    /// with a syntax error still declares it, so its uses don't report more errors
+    pub fn parse_declared(tokens: &mut Peekable<Iter<Token>>) -> Option<Self> {
+        let r#type = VariableType::parse(tokens)?;
+        let name = tokens.next_if(|token| token.token_type == TokenType::UNKNOWN)?;
+        tokens
+            .peek()
+            .is_some_and(|token| {
+                matches!(
+                    token.token_type,
+                    TokenType::EQUAL | TokenType::SEMICOLON | TokenType::COMMA
+                )
+            })
+            .then(|| Self {
+                name: name.value.clone().unwrap(),
+                r#type,
+                value: None,
+                initialized: true,
+                mutable: false,
+                comments: NodeComments::default(),
+            })
+    }
+
+    /// Parses a declaration of one or more variables of the same type, a variable can already
+    /// use the ones before it
+    ///
+    /// # Example
+    ///
+    /// int i = 10, j = i; -> [i, j]
+    pub fn parse_list(
+        context: &Context,
+        tokens: &mut Peekable<Iter<Token>>,
+        class_context: &ClassContext,
+        method_vars: &[NodeVariable],
+    ) -> Result<Vec<Self>, ParseError> {
+        let mut start = tokens.clone();
+        let token = match tokens.peek() {
+            Some(token) => *token,
             None => {
-                return Err("Unexpected end of input while parsing expression".to_string());
+                return Err(ParseError::end_of_input(
+                    context,
+                    "Unexpected end of input while parsing expression",
+                ));
             }
         };
 
-        match &token.token_type {
-            // Variable is a data type
-            TokenType::DATATYPE(data_type) => Self::parse_variable_declaration(
-                tokens,
-                class_context,
-                method_vars,
-                VariableType::DataType(*data_type),
-                token,
-            ),
-            // Variable is a class
-            TokenType::UNKNOWN => Self::parse_variable_declaration(
+        // var is only a keyword when a variable name follows, otherwise its a normal name
+        let is_var = token.is_contextual_keyword("var")
+            && start
+                .clone()
+                .nth(1)
+                .is_some_and(|next| next.token_type == TokenType::UNKNOWN);
+        let r#type = if is_var {
+            tokens.next();
+            VariableType::Inferred
+        } else {
+            match VariableType::parse(tokens) {
+                Some(r#type) => r#type,
+                None => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid expression: expected Variable",
+                    ))
+                }
+            }
+        };
+
+        let mut visible_vars = method_vars.to_vec();
+        let mut variables = Vec::new();
+        loop {
+            let name_token = match tokens.next() {
+                Some(token) => token,
+                None => {
+                    return Err(ParseError::new(
+                        token.span,
+                        "Invalid expression: expected variable name",
+                    ));
+                }
+            };
+            let (mut variable, has_next) = Self::parse_variable_declaration(
+                context,
                 tokens,
                 class_context,
-                method_vars,
-                VariableType::Class,
-                token,
-            ),
-            _ => Err(format!("{}, Invalid expression: expected Variable", token)),
+                &visible_vars,
+                r#type.clone(),
+                name_token,
+            )?;
+            variable.comments = NodeComments::from_tokens(consumed_tokens(&start, tokens));
+            visible_vars.push(variable.clone());
+            variables.push(variable);
+            if !has_next {
+                return Ok(variables);
+            }
+            start = tokens.clone();
         }
     }
 
+    /// Parses the name and the value of a variable, also returns if another variable of the
+    /// same declaration follows after a ,
     fn parse_variable_declaration(
+        context: &Context,
         tokens: &mut Peekable<Iter<Token>>,
         class_context: &ClassContext,
         current_context_vars: &[NodeVariable],
         r#type: VariableType,
         name_token: &Token,
-    ) -> Result<Self, String> {
+    ) -> Result<(Self, bool), ParseError> {
         let name = match &name_token.token_type {
             TokenType::UNKNOWN => name_token.value.clone().unwrap(),
             _ => {
-                return Err(format!(
-                    "{}, Invalid expression: expected variable name",
-                    name_token
+                return Err(ParseError::new(
+                    name_token.span,
+                    "Invalid expression: expected variable name",
                 ));
             }
         };
 
         if current_context_vars.iter().any(|var| var.name == name) {
-            return Err(format!(
-                "{}, Variable with the same name is already defined",
-                name_token
+            return Err(ParseError::new(
+                name_token.span,
+                "Variable with the same name is already defined",
             ));
         }
 
         let next_token = match tokens.next() {
             Some(token) => token,
             None => {
-                return Err(format!(
-                    "{}, Invalid expression: Variable got no semicolon or value",
-                    name_token
+                return Err(ParseError::new(
+                    name_token.span,
+                    "Invalid expression: Variable got no semicolon or value",
                 ));
             }
         };
 
         match next_token.token_type {
-            TokenType::SEMICOLON => Ok(Self {
-                name,
-                r#type,
-                value: None,
-            }),
-            TokenType::EQUAL => {
-                return Self::parse_variable_value(
-                    tokens,
-                    class_context,
-                    current_context_vars,
-                    r#type,
-                    name_token,
+            TokenType::SEMICOLON | TokenType::COMMA => Ok((
+                Self {
                     name,
-                );
-            }
-            _ => Err(format!(
-                "{}, Invalid expression: Variable got no semicolon or value",
-                next_token
+                    r#type,
+                    value: None,
+                    initialized: false,
+                    mutable: false,
+                    comments: NodeComments::default(),
+                },
+                next_token.token_type == TokenType::COMMA,
+            )),
+            TokenType::EQUAL => Self::parse_variable_value(
+                context,
+                tokens,
+                class_context,
+                current_context_vars,
+                r#type,
+                name_token,
+                name,
+            ),
+            _ => Err(ParseError::new(
+                name_token.span.to(next_token.span),
+                "Invalid expression: Variable got no semicolon or value",
             )),
         }
     }
 
     fn parse_variable_value(
+        context: &Context,
         tokens: &mut Peekable<Iter<Token>>,
         class_context: &ClassContext,
         method_vars: &[NodeVariable],
         r#type: VariableType,
         name_token: &Token,
         name: String,
-    ) -> Result<Self, String> {
-        let mut value = None;
-
-        if let Ok(int) = IntExpression::parse(tokens, class_context, method_vars) {
-            if int.end_with_semi {
-                value = Some(VariableValue::Direct(int.final_code));
-            }
-        }
-        // TODO: try parse other things, like booleans, floats...
-
-        // match next_token.token_type {
-        //     TokenType::TRUE => value = Some(VariableValue::Direct("true".to_string())),
-        //     TokenType::FALSE => value = Some(VariableValue::Direct("false".to_string())),
-        //     TokenType::UNKNOWN => {
-        //         // First lets check if we set the value to an existing vars value
-        //         // TODO: allow non context vars here
-        //         for var in current_context_vars {
-        //             if &var.name == next_token.value.as_ref().unwrap() {
-        //                 value = Some(VariableValue::ByVar(name.clone()));
-        //                 break;
-        //             }
-        //         }
-        //         // TODO: implement indirect
-        //     }
-
-        //     _ => {
-        //         return Err(format!(
-        //             "{}, Invalid expression: Variable got no semicolon or value",
-        //             next_token
-        //         ))
-        //     }
-        // };
-
-        if value.is_some() {
-            Ok(Self {
+    ) -> Result<(Self, bool), ParseError> {
+        let mut value = Expr::parse_to(context, tokens, class_context, method_vars, &r#type)?;
+        // {1, 2} gets the type of the declaration
+        if let (ExprKind::ArrayInit(init_type, _), VariableType::Array(_)) =
+            (&mut value.kind, &r#type)
+        {
+            *init_type = r#type.clone();
+        }
+        // So does the diamond operator of new Box<>()
+        if let (
+            ExprKind::New {
+                r#type: new_type @ VariableType::Class(_),
+                ..
+            },
+            VariableType::Generic(..),
+        ) = (&mut value.kind, &r#type)
+        {
+            if new_type.class_name() == r#type.class_name() {
+                *new_type = r#type.clone();
+            }
+        }
+        let has_next = match tokens.next() {
+            Some(token) if token.token_type == TokenType::SEMICOLON => false,
+            Some(token) if token.token_type == TokenType::COMMA => true,
+            token => {
+                let span = token.map_or(value.span, |token| token.span);
+                return Err(ParseError::new(
+                    name_token.span.to(span),
+                    "Invalid expression: Variable got no semicolon",
+                ));
+            }
+        };
+        // var takes the type of its value, a variable which starts with a value that can be
+        // null can be null too
+        let r#type = match r#type {
+            VariableType::Inferred => value.r#type(),
+            r#type => r#type,
+        };
+        let r#type = match value.is_nullable() {
+            true => r#type.nullable(),
+            false => r#type,
+        };
+        Ok((
+            Self {
                 name,
                 r#type,
-                value,
-            })
-        } else {
-            Err(format!("Invalid expression: Variable got no semicolon",))
-        }
+                value: Some(value),
+                initialized: true,
+                mutable: false,
+                comments: NodeComments::default(),
+            },
+            has_next,
+        ))
     }
 }
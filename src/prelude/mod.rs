@@ -2,16 +2,19 @@ use std::borrow::Cow;
 
 use system::System;
 
-use crate::parser::nodes::FunctionArgument;
+use crate::parser::{context::Context, nodes::FunctionArgument};
 
 mod string;
 mod system;
 
+pub use string::{code_from_string_method, get_string_method_type};
+
 pub trait Class {
     fn get_field(&self, name: &str) -> Option<Box<dyn Class>>;
 
     fn code_from_method(
         &self,
+        context: &Context,
         name: &str,
         args: Vec<FunctionArgument>,
     ) -> Option<Cow<'static, str>>;
@@ -20,7 +23,7 @@ pub trait Class {
 pub fn get_prelude_class(input: &str) -> Option<Box<dyn Class>> {
     match input {
         "String" => Some(Box::new(string::String)),
-        "System" => Some(Box::new(System::new())),
+        "System" => Some(Box::new(System)),
         _ => None,
     }
 }
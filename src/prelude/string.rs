@@ -1,19 +1,56 @@
-use crate::parser::nodes::FunctionArgument;
+use std::borrow::Cow;
+
+use crate::{
+    parser::{
+        context::Context,
+        nodes::{variable::VariableType, FunctionArgument},
+    },
+    token::DataType,
+};
 
 use super::Class;
 
 pub struct String;
 
 impl Class for String {
-    fn get_field(&self, name: &str) -> Option<Box<dyn Class>> {
-        todo!()
+    fn get_field(&self, _name: &str) -> Option<Box<dyn Class>> {
+        None
     }
 
     fn code_from_method(
         &self,
-        name: &str,
-        args: Vec<FunctionArgument>,
+        _context: &Context,
+        _name: &str,
+        _args: Vec<FunctionArgument>,
     ) -> Option<std::borrow::Cow<'static, str>> {
-        todo!()
+        None
+    }
+}
+
+/// The return type of a method of String objects, None if it is not supported
+pub fn get_string_method_type(name: &str) -> Option<VariableType> {
+    match name {
+        "length" => Some(VariableType::DataType(DataType::INT)),
+        "isEmpty" => Some(VariableType::DataType(DataType::BOOLEAN)),
+        "toUpperCase" | "toLowerCase" => Some(VariableType::string()),
+        _ => None,
     }
 }
+
+/// Converts a call of a method without arguments of a String object, target is the code of
+/// the object
+///
+/// # Example
+///
+/// s.length() -> s.encode_utf16().count() as i32
+pub fn code_from_string_method(target: &str, name: &str) -> Option<Cow<'static, str>> {
+    let code = match name {
+        // Java counts UTF-16 code units
+        "length" => format!("{}.encode_utf16().count() as i32", target),
+        "isEmpty" => format!("{}.is_empty()", target),
+        "toUpperCase" => format!("{}.to_uppercase()", target),
+        "toLowerCase" => format!("{}.to_lowercase()", target),
+        _ => return None,
+    };
+    Some(code.into())
+}
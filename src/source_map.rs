@@ -0,0 +1,134 @@
+/// Index of a file in the source map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId(u32);
+
+/// A byte range in a source file, the end is exclusive
+///
+/// Offsets always point into the original file content, before unicode escapes are translated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub file: FileId,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Span {
+    pub fn new(file: FileId, start: usize, end: usize) -> Self {
+        Self {
+            file,
+            start: start as u32,
+            end: end as u32,
+        }
+    }
+
+    /// Span which covers both spans and everything between them
+    ///
+    /// # Example
+    ///
+    /// int i = 10; -> the span of `int` to the span of `;` covers the whole declaration
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            file: self.file,
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+/// Line and column of the first and last char of a span, both start at 1
+pub struct Location {
+    pub path: String,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.path, self.start_line, self.start_col)?;
+        if self.end_line != self.start_line {
+            write!(f, "-{}:{}", self.end_line, self.end_col)
+        } else if self.end_col != self.start_col {
+            write!(f, "-{}", self.end_col)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// All files which were read, tokens and errors only keep the id of their file
+#[derive(Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    /// Adds a file, the content is needed to resolve lines and columns later
+    pub fn add_file(&mut self, path: String, content: &str) -> FileId {
+        self.files.push(SourceFile::new(path, content));
+        FileId(self.files.len() as u32 - 1)
+    }
+
+    /// The source text of a span
+    pub fn text(&self, span: Span) -> String {
+        let file = &self.files[span.file.0 as usize];
+        file.content
+            .get(span.start as usize..span.end as usize)
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub fn location(&self, span: Span) -> Location {
+        let file = &self.files[span.file.0 as usize];
+        let (start_line, start_col) = file.line_col(span.start);
+        // The end is exclusive, but we want to show the last char of the span
+        let (end_line, end_col) = file.line_col(span.end.max(span.start + 1) - 1);
+        Location {
+            path: file.path.clone(),
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+        }
+    }
+}
+
+struct SourceFile {
+    path: String,
+    content: String,
+    /// Byte offset of the start of every line
+    line_starts: Vec<u32>,
+}
+
+impl SourceFile {
+    fn new(path: String, content: &str) -> Self {
+        let mut line_starts = vec![0];
+        let bytes = content.as_bytes();
+        for (i, byte) in bytes.iter().enumerate() {
+            // Java allows \n, \r and \r\n as line terminator
+            let is_line_end =
+                *byte == b'\n' || (*byte == b'\r' && bytes.get(i + 1) != Some(&b'\n'));
+            if is_line_end {
+                line_starts.push(i as u32 + 1);
+            }
+        }
+        Self {
+            path,
+            content: content.to_string(),
+            line_starts,
+        }
+    }
+
+    /// Converts a byte offset into a line and column, the column counts chars and not bytes
+    fn line_col(&self, offset: u32) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1] as usize;
+        let offset = (offset as usize).min(self.content.len());
+        let col = self
+            .content
+            .get(line_start..offset)
+            .map_or(offset - line_start, |text| text.chars().count());
+        (line, col + 1)
+    }
+}
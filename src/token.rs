@@ -1,17 +1,63 @@
+use crate::source_map::Span;
+
 #[derive(Debug, PartialEq)]
 pub enum TokenType {
     RETURN,
     VOID,
     CLASS,
     IF,
-    OR,
-    AND,
     ELSE,
-    INTLIT,
-    ARRAY,
+    ABSTRACT,
+    ASSERT,
+    BREAK,
+    CASE,
+    CATCH,
+    /// Reserved but not used by Java
+    CONST,
+    CONTINUE,
+    DEFAULT,
+    DO,
+    ENUM,
+    EXTENDS,
+    FINAL,
+    FINALLY,
+    FOR,
+    /// Reserved but not used by Java
+    GOTO,
+    IMPLEMENTS,
+    IMPORT,
+    INSTANCEOF,
+    INTERFACE,
+    NATIVE,
+    NEW,
+    PACKAGE,
+    STRICTFP,
+    SUPER,
+    SWITCH,
+    SYNCHRONIZED,
+    THIS,
+    THROW,
+    THROWS,
+    TRANSIENT,
+    TRY,
+    VOLATILE,
+    WHILE,
+    /// _, a keyword since Java 9
+    UNDERSCORE,
+    NULL,
+    /// 10, 0x1F, 10L, 1.5f, 2.0e10
+    ///
+    /// Holds the type of the literal, the raw source text is stored in the token value
+    NUMBER_LITERAL(DataType),
     STATIC,
-    QUOTE,
-    QUOTE_STRING,
+    /// "Hello, World!"
+    ///
+    /// Holds the raw source text between the quotes, the decoded value is stored in the token value
+    STRING_LITERAL(String),
+    /// 'a'
+    ///
+    /// Holds the raw source text between the quotes, the decoded char is stored in the token value
+    CHAR_LITERAL(String),
     /// (
     OPEN_BRACE,
     /// )
@@ -25,11 +71,94 @@ pub enum TokenType {
     FALSE,
     VISIBILITY(Visibility),
     DATATYPE(DataType),
+    /// =
     EQUAL,
-    ADD,
-    MUL,
-    SUB,
-    REMOVE,
+    /// +
+    PLUS,
+    /// -
+    MINUS,
+    /// *
+    STAR,
+    /// /
+    SLASH,
+    /// %
+    PERCENT,
+    /// ==
+    EQUAL_EQUAL,
+    /// !=
+    NOT_EQUAL,
+    /// <
+    LESS,
+    /// <=
+    LESS_EQUAL,
+    /// >
+    GREATER,
+    /// >=
+    GREATER_EQUAL,
+    /// &&
+    AND,
+    /// ||
+    OR,
+    /// !
+    NOT,
+    /// &
+    BIT_AND,
+    /// |
+    BIT_OR,
+    /// ^
+    BIT_XOR,
+    /// ~
+    BIT_NOT,
+    /// <<
+    SHIFT_LEFT,
+    /// >>
+    SHIFT_RIGHT,
+    /// >>>
+    UNSIGNED_SHIFT_RIGHT,
+    /// ++
+    INCREMENT,
+    /// --
+    DECREMENT,
+    /// +=
+    PLUS_EQUAL,
+    /// -=
+    MINUS_EQUAL,
+    /// *=
+    STAR_EQUAL,
+    /// /=
+    SLASH_EQUAL,
+    /// %=
+    PERCENT_EQUAL,
+    /// &=
+    AND_EQUAL,
+    /// |=
+    OR_EQUAL,
+    /// ^=
+    XOR_EQUAL,
+    /// <<=
+    SHIFT_LEFT_EQUAL,
+    /// >>=
+    SHIFT_RIGHT_EQUAL,
+    /// >>>=
+    UNSIGNED_SHIFT_RIGHT_EQUAL,
+    /// ?
+    QUESTION,
+    /// :
+    COLON,
+    /// ::
+    DOUBLE_COLON,
+    /// ->
+    ARROW,
+    /// .
+    DOT,
+    /// ...
+    ELLIPSIS,
+    /// @
+    AT,
+    /// [
+    OPEN_SQUARE,
+    /// ]
+    CLOSE_SQUARE,
     SEMICOLON,
     UNKNOWN,
 }
@@ -40,6 +169,7 @@ pub enum Visibility {
     #[default]
     NONE,
     PRIVATE,
+    PROTECTED,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -58,26 +188,80 @@ pub enum DataType {
 pub struct Token {
     pub token_type: TokenType,
     pub value: Option<String>,
-    pub char_info: CharLocationInfo,
+    pub span: Span,
+    /// Comments and blank lines before this token
+    pub leading_trivia: Vec<Trivia>,
+    /// Comments after this token on the same line
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+/// Everything in the source which is not a token but we still want to keep in the generated code
+#[derive(Debug, Clone, PartialEq)]
+pub enum Trivia {
+    /// // comment
+    ///
+    /// Holds the text after the slashes
+    LineComment(String),
+    /// /* comment */
+    ///
+    /// Holds the text between the delimiters
+    BlockComment(String),
+    /// /** Javadoc */
+    ///
+    /// Holds the text between the delimiters
+    DocComment(String),
+    /// An empty line, used to keep the spacing between statements
+    BlankLine,
+}
+
+/// Keywords which are only keywords in some places, everywhere else they are normal identifiers
+///
+/// # Example
+///
+/// var record = 10; // The first var is a keyword, record is just a variable name
+pub const CONTEXTUAL_KEYWORDS: [&str; 7] = [
+    "var",
+    "record",
+    "sealed",
+    "permits",
+    "yield",
+    "non-sealed",
+    "when",
+];
+
+impl Token {
+    pub fn new(token_type: TokenType, value: Option<String>, span: Span) -> Self {
+        Self {
+            token_type,
+            value,
+            span,
+            leading_trivia: Vec::new(),
+            trailing_trivia: Vec::new(),
+        }
+    }
+
+    /// Checks if this token is the given contextual keyword, the parser has to decide if
+    /// it is used as keyword at this place
+    ///
+    /// `non-sealed` is lexed as `non` `-` `sealed`, so only `non` is checked here
+    pub fn is_contextual_keyword(&self, keyword: &str) -> bool {
+        debug_assert!(CONTEXTUAL_KEYWORDS.contains(&keyword));
+        if self.token_type != TokenType::UNKNOWN {
+            return false;
+        }
+        let keyword = keyword.strip_suffix("-sealed").unwrap_or(keyword);
+        self.value.as_deref() == Some(keyword)
+    }
 }
 
 impl std::fmt::Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:?}:{} in {}:{}:{}",
+            "{:?}:{} in {:?}",
             self.token_type,
             self.value.as_ref().unwrap_or(&"None".to_string()),
-            self.char_info.file_path,
-            self.char_info.line_number,
-            self.char_info.line_col
+            self.span
         )
     }
 }
-
-#[derive(Debug, Clone)]
-pub struct CharLocationInfo {
-    pub file_path: String,
-    pub line_number: usize,
-    pub line_col: u32,
-}
@@ -0,0 +1,17 @@
+class Animal {
+    String sound() {
+        return "...";
+    }
+}
+
+class Dog extends Animal {
+    @Override
+    String sound() {
+        return "Woof";
+    }
+
+    @Override
+    String name() {
+        return "Dog";
+    }
+}
@@ -0,0 +1,68 @@
+// Functional interfaces are checked to have one abstract method
+@FunctionalInterface
+interface Operation {
+    int apply(int a, int b);
+
+    default int twice(int a) {
+        return apply(a, a);
+    }
+}
+
+class Square implements Operation {
+    /** The length of a side */
+    @Deprecated
+    int side;
+
+    @SuppressWarnings("unused")
+    private static final int SIDES = 4;
+
+    Square(int side) {
+        this.side = side;
+    }
+
+    // @Override is checked and dropped
+    @Override
+    public int apply(int a, int b) {
+        return a * b * side;
+    }
+
+    @Override
+    public String toString() {
+        return "Square " + side;
+    }
+
+    @Deprecated(since = "9", forRemoval = true)
+    int oldArea() {
+        return side * side;
+    }
+
+    @SuppressWarnings({"unused", "unchecked"})
+    static int sum(@SuppressWarnings("unused") int a, final int b) {
+        return a + b;
+    }
+}
+
+/* Annotations without a Rust attribute stay as comments */
+@Author(name = "someone", year = 2024)
+class Main {
+    // Annotations of arguments stay behind their argument
+    static String label(@Author(name = "someone") String text, @Deprecated int width) {
+        return text + width;
+    }
+
+    @Test
+    void checksSquare() {
+        Square square = new Square(2);
+        System.out.println(square.apply(3, 4));
+    }
+
+    public static void main(String[] args) {
+        @SuppressWarnings("unchecked")
+        Square square = new Square(3);
+        System.out.println(square.apply(2, 2) + " " + square.twice(5));
+        System.out.println(square.toString() + " " + Square.sum(1, 2));
+        new Main().checksSquare();
+        System.out.println(label("width ", 4));
+    }
+}
+
@@ -0,0 +1,116 @@
+// Functional interfaces are checked to have one abstract method
+trait Operation {
+    fn apply(&self, a: i32, b: i32) -> i32;
+
+    fn twice(&self, a: i32) -> i32 {
+        return self.apply(a, a);
+    }
+}
+
+#[derive(Clone, Default)]
+struct Square(std::rc::Rc<SquareFields>);
+
+#[derive(Default)]
+struct SquareFields {
+    /// The length of a side
+    #[deprecated]
+    side: std::cell::Cell<i32>,
+}
+
+impl std::ops::Deref for Square {
+    type Target = SquareFields;
+
+    fn deref(&self) -> &SquareFields {
+        &self.0
+    }
+}
+
+impl Square {
+    fn new(side: i32) -> Square {
+        let this = Self(std::rc::Rc::new(SquareFields {
+            side: std::cell::Cell::new(0),
+        }));
+        this.side.set(side);
+        this
+    }
+
+    #[allow(unused)]
+    const SIDES: i32 = 4;
+
+    pub fn toString(&self) -> String {
+        return format!("Square {}", self.side.get());
+    }
+
+    #[deprecated(since = "9", note = "This will be removed in a future version")]
+    fn oldArea(&self) -> i32 {
+        return i32::wrapping_mul(self.side.get(), self.side.get());
+    }
+
+    #[allow(unused)]
+    fn sum(#[allow(unused)] a: i32, b: i32) -> i32 {
+        return i32::wrapping_add(a, b);
+    }
+}
+
+impl std::fmt::Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.toString())
+    }
+}
+
+impl Operation for Square {
+    // @Override is checked and dropped
+    fn apply(&self, a: i32, b: i32) -> i32 {
+        return i32::wrapping_mul(i32::wrapping_mul(a, b), self.side.get());
+    }
+}
+
+/* Annotations without a Rust attribute stay as comments */
+// @Author(name = "someone", year = 2024)
+#[derive(Clone, Default)]
+struct Main {}
+
+impl Main {
+    pub fn new() -> Main {
+        let this = Self {};
+        this
+    }
+    // Annotations of arguments stay behind their argument
+    fn label(
+        text: String, // @Author(name = "someone")
+        width: i32,   // @Deprecated
+    ) -> String {
+        return format!("{text}{width}");
+    }
+
+    fn checksSquare(&self) {
+        let square = Square::new(2);
+        println!("{}", square.apply(3, 4));
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        // @SuppressWarnings("unchecked")
+        let square = Square::new(3);
+        println!("{} {}", square.apply(2, 2), square.twice(5));
+        println!("{} {}", square.toString(), Square::sum(1, 2));
+        Main::new().checksSquare();
+        println!("{}", Main::label("width ".to_string(), 4));
+    }
+}
+
+impl std::fmt::Display for Main {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Main@{:p}", self)
+    }
+}
+
+#[test]
+fn Main_checksSquare() {
+    Main::new().checksSquare();
+}
+
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
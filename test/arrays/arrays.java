@@ -0,0 +1,57 @@
+class Arrays {
+    static int[] totals = new int[2];
+
+    static int sum(int[] values) {
+        int total = 0;
+        for (int value : values) {
+            total += value;
+        }
+        return total;
+    }
+
+    static void fill(int[] values, int value) {
+        for (int i = 0; i < values.length; i++) {
+            values[i] = value;
+        }
+    }
+
+    public static void main(String[] args) {
+        int[] a = {1, 2, 3};
+        System.out.println(sum(a));
+        fill(a, 4);
+        System.out.println(a[0] + " " + sum(a));
+
+        // b is the same array as a
+        int[] b = a;
+        b[1] = 7;
+        System.out.println(a[1]);
+
+        // every row is its own array
+        int[][] grid = new int[2][2];
+        grid[0][1] = 5;
+        int[] row = grid[1];
+        row[0] = 6;
+        System.out.println(grid[0][1] + " " + grid[1][0] + " " + grid[1][1]);
+
+        // the loop sees the values which the body changes
+        int[] steps = {1, 1, 1, 1};
+        for (int step : steps) {
+            steps[3] += step;
+        }
+        System.out.println(steps[3]);
+        switch (steps[0]) {
+            case 1 -> steps[0] = steps.length;
+            default -> steps[0] = 0;
+        }
+        System.out.println(steps[0]);
+
+        String[] names = {"a", "b"};
+        names[0] += names[1];
+        System.out.println(names[0] + names.length);
+
+        totals[0]++;
+        int[] shared = totals;
+        shared[1] = 3;
+        System.out.println(totals[0] + totals[1]);
+    }
+}
@@ -0,0 +1,122 @@
+#[derive(Clone, Default)]
+struct Arrays {}
+
+impl Arrays {
+    fn sum(values: std::rc::Rc<std::cell::RefCell<Vec<i32>>>) -> i32 {
+        let mut total = 0;
+        for value in {
+            let array = values.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i])
+        } {
+            total = i32::wrapping_add(total, value);
+        }
+        return total;
+    }
+
+    fn fill(values: std::rc::Rc<std::cell::RefCell<Vec<i32>>>, value: i32) {
+        for i in {
+            let range = 0..values.borrow().len() as i32;
+            range
+        } {
+            values.borrow_mut()[i as usize] = value;
+        }
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let a = std::rc::Rc::new(std::cell::RefCell::new(vec![1, 2, 3]));
+        println!("{}", Arrays::sum(a.clone()));
+        Arrays::fill(a.clone(), 4);
+        println!("{} {}", a.borrow()[0], Arrays::sum(a.clone()));
+
+        // b is the same array as a
+        let b = a.clone();
+        b.borrow_mut()[1] = 7;
+        println!("{}", a.borrow()[1]);
+
+        // every row is its own array
+        let grid = std::rc::Rc::new(std::cell::RefCell::new(
+            (0..2)
+                .map(|_| std::rc::Rc::new(std::cell::RefCell::new(vec![0; 2])))
+                .collect::<Vec<_>>(),
+        ));
+        grid.borrow()[0].borrow_mut()[1] = 5;
+        let row = grid.borrow()[1].clone();
+        row.borrow_mut()[0] = 6;
+        println!(
+            "{} {} {}",
+            grid.borrow()[0].borrow()[1],
+            grid.borrow()[1].borrow()[0],
+            grid.borrow()[1].borrow()[1]
+        );
+
+        // the loop sees the values which the body changes
+        let steps = std::rc::Rc::new(std::cell::RefCell::new(vec![1, 1, 1, 1]));
+        for step in {
+            let array = steps.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i])
+        } {
+            {
+                let value1 = i32::wrapping_add(steps.borrow()[3], step);
+                steps.borrow_mut()[3] = value1;
+            };
+        }
+        println!("{}", steps.borrow()[3]);
+        match {
+            let selector = steps.borrow()[0];
+            selector
+        } {
+            1 => {
+                {
+                    let value2 = steps.borrow().len() as i32;
+                    steps.borrow_mut()[0] = value2;
+                };
+            }
+            _ => {
+                steps.borrow_mut()[0] = 0;
+            }
+        }
+        println!("{}", steps.borrow()[0]);
+
+        let names = std::rc::Rc::new(std::cell::RefCell::new(vec![
+            "a".to_string(),
+            "b".to_string(),
+        ]));
+        {
+            let value3 = format!("{}{}", names.borrow()[0], names.borrow()[1]);
+            names.borrow_mut()[0] = value3;
+        };
+        println!("{}{}", names.borrow()[0], names.borrow().len() as i32);
+
+        {
+            let value4 =
+                i32::wrapping_add(ARRAYS_TOTALS.with(|cell| *cell).borrow().borrow()[0], 1);
+            ARRAYS_TOTALS.with(|cell| *cell).borrow().borrow_mut()[0] = value4;
+        };
+        let shared = ARRAYS_TOTALS.with(|cell| *cell).borrow().clone();
+        shared.borrow_mut()[1] = 3;
+        println!(
+            "{}",
+            i32::wrapping_add(
+                ARRAYS_TOTALS.with(|cell| *cell).borrow().borrow()[0],
+                ARRAYS_TOTALS.with(|cell| *cell).borrow().borrow()[1]
+            )
+        );
+    }
+    pub fn static_init() {
+        ARRAYS_TOTALS
+            .with(|cell| *cell)
+            .replace(std::rc::Rc::new(std::cell::RefCell::new(vec![0; 2])));
+    }
+}
+
+thread_local! {
+static ARRAYS_TOTALS: &'static std::cell::RefCell<std::rc::Rc<std::cell::RefCell<Vec<i32>>>> = Box::leak(Box::new(std::cell::RefCell::new(std::rc::Rc::new(std::cell::RefCell::new(Vec::new())))));
+}
+fn main() {
+    Arrays::static_init();
+    Arrays::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
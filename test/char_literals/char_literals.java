@@ -0,0 +1,9 @@
+class CharLiterals {
+    public static void main(String[] args) {
+        char big = '\u0041';
+        char quote = '\'';
+        char tab = '\uuu0009';
+        System.out.println("Smile \uD83D\uDE00 and \\u0041 stays");
+        System.out.println(big);
+    }
+}
@@ -0,0 +1,18 @@
+#[derive(Clone, Default)]
+struct CharLiterals {}
+
+impl CharLiterals {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let big = 'A';
+        let quote = '\'';
+        let tab = '\t';
+        println!("Smile 😀 and \\u0041 stays");
+        println!("{big}");
+    }
+}
+
+fn main() {
+    CharLiterals::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,100 @@
+public class Classes {
+    static final int ORIGIN = 0;
+    static final String LABEL = "point";
+    static int created;
+    // Gets another Rust name than created
+    static int Created = 5;
+    static String history = "";
+    static int[] totals = new int[2];
+
+    static {
+        created = 0;
+    }
+
+    public static void main(String[] args) {
+        Point point = new Point(3, 4);
+        point.move(1, 2);
+        System.out.println(point.getX() + point.getY());
+        System.out.println(point.describe());
+        Point alias = point;
+        shift(alias);
+        System.out.println(point.getX() + " " + (alias == point));
+        Counter counter = new Counter();
+        counter.increment();
+        counter.increment();
+        System.out.println(counter.count);
+        System.out.println(LABEL);
+        System.out.println(created + ORIGIN + " " + Created);
+        System.out.println(half(5));
+        Point origin = new Point();
+        System.out.println(origin.describe() + " " + sum(1, 2) + " " + sum(1.5, 2));
+        history += "a";
+        totals[1]++;
+        totals[0] += Counter.made;
+        System.out.println(history + " " + totals[0] + totals[1] + " " + totals.length);
+    }
+
+    static int sum(int a, int b) {
+        return a + b;
+    }
+
+    static double sum(double a, double b) {
+        return a + b;
+    }
+
+    static void shift(Point point) {
+        point.move(10, 0);
+    }
+
+    static double half(int value) {
+        created++;
+        return value / 2.0;
+    }
+}
+
+/** A point on a grid */
+class Point {
+    private int x;
+    private int y;
+    private String name = "P";
+
+    Point() {
+        this(0, 0);
+    }
+
+    Point(int x, int y) {
+        this.x = x;
+        this.y = y;
+    }
+
+    void move(int dx, int dy) {
+        x += dx;
+        this.y = y + dy;
+    }
+
+    int getX() {
+        return x;
+    }
+
+    int getY() {
+        return this.y;
+    }
+
+    String describe() {
+        return name + "(" + x + ", " + y + ")";
+    }
+}
+
+class Counter {
+    static int made;
+    int count;
+
+    {
+        count = 10;
+        made++;
+    }
+
+    void increment() {
+        count++;
+    }
+}
@@ -0,0 +1,260 @@
+#[derive(Clone, Default)]
+pub struct Classes {}
+
+impl Classes {
+    const ORIGIN: i32 = 0;
+    const LABEL: &str = "point";
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let point = Point::new_int_int(3, 4);
+        point.r#move(1, 2);
+        println!("{}", i32::wrapping_add(point.getX(), point.getY()));
+        println!("{}", point.describe());
+        let alias = point.clone();
+        Classes::shift(alias.clone());
+        println!(
+            "{} {}",
+            point.getX(),
+            std::rc::Rc::ptr_eq(&alias.0, &point.0)
+        );
+        let counter = Counter::new();
+        counter.increment();
+        counter.increment();
+        println!("{}", counter.count.get());
+        println!("{}", Classes::LABEL);
+        println!(
+            "{} {}",
+            i32::wrapping_add(CLASSES_CREATED.with(|cell| *cell).get(), Classes::ORIGIN),
+            CLASSES_CREATED1.with(|cell| *cell).get()
+        );
+        println!("{}", double_to_string(Classes::half(5)));
+        let origin = Point::new();
+        println!(
+            "{} {} {}",
+            origin.describe(),
+            Classes::sum(1, 2),
+            double_to_string(Classes::sum_double_double(1.5, 2i32 as f64))
+        );
+        {
+            let value1 = format!("{}a", *CLASSES_HISTORY.with(|cell| *cell).borrow());
+            *CLASSES_HISTORY.with(|cell| *cell).borrow_mut() = value1;
+        };
+        {
+            let value2 =
+                i32::wrapping_add(CLASSES_TOTALS.with(|cell| *cell).borrow().borrow()[1], 1);
+            CLASSES_TOTALS.with(|cell| *cell).borrow().borrow_mut()[1] = value2;
+        };
+        {
+            let value3 = i32::wrapping_add(
+                CLASSES_TOTALS.with(|cell| *cell).borrow().borrow()[0],
+                COUNTER_MADE.with(|cell| *cell).get(),
+            );
+            CLASSES_TOTALS.with(|cell| *cell).borrow().borrow_mut()[0] = value3;
+        };
+        println!(
+            "{} {}{} {}",
+            *CLASSES_HISTORY.with(|cell| *cell).borrow(),
+            CLASSES_TOTALS.with(|cell| *cell).borrow().borrow()[0],
+            CLASSES_TOTALS.with(|cell| *cell).borrow().borrow()[1],
+            CLASSES_TOTALS.with(|cell| *cell).borrow().borrow().len() as i32
+        );
+    }
+
+    fn sum(a: i32, b: i32) -> i32 {
+        return i32::wrapping_add(a, b);
+    }
+
+    fn sum_double_double(a: f64, b: f64) -> f64 {
+        return a + b;
+    }
+
+    fn shift(point: Point) {
+        point.r#move(10, 0);
+    }
+
+    fn half(value: i32) -> f64 {
+        CLASSES_CREATED.with(|cell| *cell).set(i32::wrapping_add(
+            CLASSES_CREATED.with(|cell| *cell).get(),
+            1,
+        ));
+        return value as f64 / 2.0;
+    }
+    pub fn static_init() {
+        CLASSES_HISTORY.with(|cell| *cell).replace("".to_string());
+        CLASSES_TOTALS
+            .with(|cell| *cell)
+            .replace(std::rc::Rc::new(std::cell::RefCell::new(vec![0; 2])));
+
+        CLASSES_CREATED.with(|cell| *cell).set(0);
+    }
+}
+
+thread_local! {
+static CLASSES_CREATED: &'static std::cell::Cell<i32> = Box::leak(Box::new(std::cell::Cell::new(0)));
+}
+// Gets another Rust name than created
+thread_local! {
+static CLASSES_CREATED1: &'static std::cell::Cell<i32> = Box::leak(Box::new(std::cell::Cell::new(5)));
+}
+thread_local! {
+static CLASSES_HISTORY: &'static std::cell::RefCell<String> = Box::leak(Box::new(std::cell::RefCell::new(String::new())));
+}
+thread_local! {
+static CLASSES_TOTALS: &'static std::cell::RefCell<std::rc::Rc<std::cell::RefCell<Vec<i32>>>> = Box::leak(Box::new(std::cell::RefCell::new(std::rc::Rc::new(std::cell::RefCell::new(Vec::new())))));
+}
+
+/// A point on a grid
+#[derive(Clone, Default)]
+struct Point(std::rc::Rc<PointFields>);
+
+#[derive(Default)]
+struct PointFields {
+    x: std::cell::Cell<i32>,
+    y: std::cell::Cell<i32>,
+    name: std::cell::RefCell<String>,
+}
+
+impl std::ops::Deref for Point {
+    type Target = PointFields;
+
+    fn deref(&self) -> &PointFields {
+        &self.0
+    }
+}
+
+impl Point {
+    fn new() -> Point {
+        let this = Point::new_int_int(0, 0);
+        this
+    }
+
+    fn new_int_int(x: i32, y: i32) -> Point {
+        let this = Self(std::rc::Rc::new(PointFields {
+            x: std::cell::Cell::new(0),
+            y: std::cell::Cell::new(0),
+            name: std::cell::RefCell::new("P".to_string()),
+        }));
+        this.x.set(x);
+        this.y.set(y);
+        this
+    }
+
+    fn r#move(&self, dx: i32, dy: i32) {
+        self.x.set(i32::wrapping_add(self.x.get(), dx));
+        self.y.set(i32::wrapping_add(self.y.get(), dy));
+    }
+
+    fn getX(&self) -> i32 {
+        return self.x.get();
+    }
+
+    fn getY(&self) -> i32 {
+        return self.y.get();
+    }
+
+    fn describe(&self) -> String {
+        return format!(
+            "{}({}, {})",
+            *self.name.borrow(),
+            self.x.get(),
+            self.y.get()
+        );
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Point@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct Counter(std::rc::Rc<CounterFields>);
+
+#[derive(Default)]
+struct CounterFields {
+    count: std::cell::Cell<i32>,
+}
+
+impl std::ops::Deref for Counter {
+    type Target = CounterFields;
+
+    fn deref(&self) -> &CounterFields {
+        &self.0
+    }
+}
+
+impl Counter {
+    pub fn new() -> Counter {
+        let this = Self(std::rc::Rc::new(CounterFields {
+            count: std::cell::Cell::new(0),
+        }));
+        this.count.set(10);
+        COUNTER_MADE
+            .with(|cell| *cell)
+            .set(i32::wrapping_add(COUNTER_MADE.with(|cell| *cell).get(), 1));
+        this
+    }
+
+    fn increment(&self) {
+        self.count.set(i32::wrapping_add(self.count.get(), 1));
+    }
+}
+
+impl std::fmt::Display for Counter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Counter@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+thread_local! {
+static COUNTER_MADE: &'static std::cell::Cell<i32> = Box::leak(Box::new(std::cell::Cell::new(0)));
+}
+fn double_to_string<T: std::fmt::LowerExp>(value: T) -> String {
+    let scientific = format!("{:e}", value);
+    let (sign, scientific) = match scientific.strip_prefix('-') {
+        Some(scientific) => ("-", scientific),
+        None => ("", scientific.as_str()),
+    };
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return match scientific {
+            "inf" => format!("{}Infinity", sign),
+            _ => "NaN".to_string(),
+        };
+    };
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap();
+    if digits == "0" {
+        return format!("{}0.0", sign);
+    }
+    if !(-3..7).contains(&exponent) {
+        let fraction = if digits.len() > 1 { &digits[1..] } else { "0" };
+        return format!("{}{}.{}E{}", sign, &digits[..1], fraction, exponent);
+    }
+    if exponent < 0 {
+        return format!(
+            "{}0.{}{}",
+            sign,
+            "0".repeat((-exponent - 1) as usize),
+            digits
+        );
+    }
+    let point = exponent as usize + 1;
+    let digits = format!("{:0<1$}", digits, point + 1);
+    format!("{}{}.{}", sign, &digits[..point], &digits[point..])
+}
+
+fn main() {
+    Classes::static_init();
+    Classes::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
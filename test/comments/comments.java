@@ -0,0 +1,30 @@
+/*
+ * License header
+ * spanning lines
+ */
+class Comments {
+
+    static int twice(int value) {
+        return value * 2;
+    } // end of twice
+
+    /**
+     * Javadoc for main
+     * @param args the args
+     */
+    public static void main(String[] args) {
+        // a line comment with a "quote"
+        int number = 5 + 10; // trailing
+        /* inline */ System.out.println(number);
+        System.out.println("not // a comment /* either */");
+        if (number > 10) {
+            System.out.println(twice(number));
+        } // end of if
+        for (int i = 0; i < 2; i++) {
+            System.out.println(i);
+        } // end of for
+    } // end of main
+} // end of class
+
+// after the last class
+/* and a block comment */
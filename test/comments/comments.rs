@@ -0,0 +1,37 @@
+/*
+ * License header
+ * spanning lines
+ */
+#[derive(Clone, Default)]
+struct Comments {}
+
+impl Comments {
+    fn twice(value: i32) -> i32 {
+        return i32::wrapping_mul(value, 2);
+    } // end of twice
+
+    /// Javadoc for main
+    /// @param args the args
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        // a line comment with a "quote"
+        let number = i32::wrapping_add(5, 10); // trailing
+                                               /* inline */
+        println!("{number}");
+        println!("not // a comment /* either */");
+        if number > 10 {
+            println!("{}", Comments::twice(number));
+        } // end of if
+        for i in 0..2 {
+            println!("{i}");
+        } // end of for
+    } // end of main
+} // end of class
+
+fn main() {
+    Comments::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
+
+// after the last class
+/* and a block comment */
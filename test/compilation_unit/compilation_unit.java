@@ -0,0 +1,39 @@
+// Header comment
+package com.example.app; // the package of the app
+
+import java.util.List;
+import java.util.*;
+import static java.lang.Math.max;
+import static com.example.app.Main.Nested.twice;
+import static com.example.app.Other.*;
+import com.example.util.Helper;
+import com.example.model.*;
+
+/** The entry point */
+public class Main {
+    private static int count = 1;
+
+    public static void main(String[] args) {
+        int total = count + 2;
+        System.out.println(total);
+        System.out.println(twice(total));
+        greet();
+    }
+
+    Main() {
+        count = 0;
+    }
+
+    static class Nested {
+        static int twice(int value) {
+            return value * 2;
+        }
+    }
+    // end of Main
+}
+
+final class Other {
+    static void greet() {
+        System.out.println("Hi from Other");
+    }
+}
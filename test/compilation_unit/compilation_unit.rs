@@ -0,0 +1,59 @@
+// Header comment
+// the package of the app
+// The imported classes are not translated, add them to the crate:
+// use crate::com::example::util::Helper;
+// use crate::com::example::model::*;
+
+/// The entry point
+#[derive(Clone, Default)]
+pub struct Main {}
+
+impl Main {
+    fn new() -> Main {
+        let this = Self {};
+        MAIN_COUNT.with(|cell| *cell).set(0);
+        this
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let total = i32::wrapping_add(MAIN_COUNT.with(|cell| *cell).get(), 2);
+        println!("{total}");
+        println!("{}", MainNested::twice(total));
+        Other::greet();
+    }
+    // end of Main
+}
+
+impl std::fmt::Display for Main {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Main@{:p}", self)
+    }
+}
+
+thread_local! {
+static MAIN_COUNT: &'static std::cell::Cell<i32> = Box::leak(Box::new(std::cell::Cell::new(1)));
+}
+
+#[derive(Clone, Default)]
+struct MainNested {}
+
+impl MainNested {
+    fn twice(value: i32) -> i32 {
+        return i32::wrapping_mul(value, 2);
+    }
+}
+
+#[derive(Clone, Default)]
+struct Other {}
+
+impl Other {
+    fn greet() {
+        println!("Hi from Other");
+    }
+}
+
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
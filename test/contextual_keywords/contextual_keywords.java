@@ -0,0 +1,28 @@
+class ContextualKeywords {
+    // Contextual keywords are only keywords in their context, elsewhere they are names
+    static int record = 1;
+    static int yield = 2;
+
+    static int permits(int sealed) {
+        return sealed * 2;
+    }
+
+    public static void main(String[] args) {
+        int record = 5;
+        var var = 3;
+        int sealed = record + var;
+        String when = "when";
+        int module = permits(sealed);
+        int open = yield + 1;
+        System.out.println(record + " " + var + " " + sealed + " " + when + " " + module + " " + open);
+        System.out.println(yield);
+        int exports = switch (record) {
+            case 5 -> {
+                int with = 7;
+                yield with;
+            }
+            default -> 0;
+        };
+        System.out.println(exports);
+    }
+}
@@ -0,0 +1,40 @@
+#[derive(Clone, Default)]
+struct ContextualKeywords {}
+
+impl ContextualKeywords {
+    fn permits(sealed: i32) -> i32 {
+        return i32::wrapping_mul(sealed, 2);
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let record = 5;
+        let var = 3;
+        let sealed = i32::wrapping_add(record, var);
+        let when = "when".to_string();
+        let module = ContextualKeywords::permits(sealed);
+        let open = i32::wrapping_add(CONTEXTUAL_KEYWORDS_YIELD.with(|cell| *cell).get(), 1);
+        println!("{record} {var} {sealed} {when} {module} {open}");
+        println!("{}", CONTEXTUAL_KEYWORDS_YIELD.with(|cell| *cell).get());
+        let exports = match record {
+            5 => {
+                let with = 7;
+                with
+            }
+            _ => 0,
+        };
+        println!("{exports}");
+    }
+}
+
+// Contextual keywords are only keywords in their context, elsewhere they are names
+thread_local! {
+static CONTEXTUAL_KEYWORDS_RECORD: &'static std::cell::Cell<i32> = Box::leak(Box::new(std::cell::Cell::new(1)));
+}
+thread_local! {
+static CONTEXTUAL_KEYWORDS_YIELD: &'static std::cell::Cell<i32> = Box::leak(Box::new(std::cell::Cell::new(2)));
+}
+fn main() {
+    ContextualKeywords::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
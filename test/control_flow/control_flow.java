@@ -0,0 +1,89 @@
+class ControlFlow {
+    public static void main(String[] args) {
+        int[] values = {3, -1, 4, 0, 5};
+        String[] names = {"a", "b", "c"};
+
+        // classify every value
+        for (int i = 0; i < values.length; i++) {
+            if (values[i] > 0) {
+                System.out.println("positive");
+            } else if (values[i] < 0) {
+                System.out.println("negative");
+            } else {
+                System.out.println("zero");
+            }
+        }
+
+        for (int i = names.length - 1; i >= 0; i--)
+            System.out.println(names[i]);
+
+        int sum = 0;
+        for (int value : values) {
+            if (value == 0) continue;
+            sum += value;
+        }
+        System.out.println(sum);
+
+        for (String name : names) {
+            System.out.println(name);
+        }
+
+        int count = 0;
+        while (count < 3) {
+            count++;
+        }
+        System.out.println(count);
+
+        int tries = 0;
+        do {
+            tries++;
+            if (tries == 2) {
+                continue; // checked again by the condition
+            }
+            System.out.println(tries);
+        } while (tries < 4);
+
+        // skip the odd numbers
+        int total = 0;
+        for (int i = 0; i < 10; i += 3) {
+            if (i % 2 == 1) {
+                continue;
+            }
+            total += i;
+            System.out.println(total);
+        }
+
+        // several variables in one declaration
+        int low = 7, high = -3;
+        for (int i = 10, j = 0; i > j; i -= 3, j++) {
+            low += i - j;
+        }
+        System.out.println(low + " " + high);
+
+        outer:
+        for (int i = 0; i < 3; i++) {
+            for (int j = 0; j < 3; j++) {
+                if (j == 2) continue outer;
+                if (i == 2) break outer;
+                System.out.println(i * 10 + j);
+            }
+        }
+
+        {
+            int scoped = 1;
+            System.out.println(scoped);
+        }
+        int scoped = 2;
+
+        check: {
+            if (scoped > 1) break check;
+            System.out.println("unreachable");
+        }
+
+        int n = 0;
+        for (;;) {
+            if (++n > 2) break;
+        }
+        System.out.println(n + scoped);
+    }
+}
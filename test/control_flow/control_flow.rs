@@ -0,0 +1,148 @@
+#[derive(Clone, Default)]
+struct ControlFlow {}
+
+impl ControlFlow {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let values = std::rc::Rc::new(std::cell::RefCell::new(vec![3, -1, 4, 0, 5]));
+        let names = std::rc::Rc::new(std::cell::RefCell::new(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+        ]));
+
+        // classify every value
+        for i in {
+            let range = 0..values.borrow().len() as i32;
+            range
+        } {
+            if values.borrow()[i as usize] > 0 {
+                println!("positive");
+            } else if values.borrow()[i as usize] < 0 {
+                println!("negative");
+            } else {
+                println!("zero");
+            }
+        }
+
+        for i in {
+            let range = (0..=i32::wrapping_sub(names.borrow().len() as i32, 1)).rev();
+            range
+        } {
+            println!("{}", names.borrow()[i as usize]);
+        }
+
+        let mut sum = 0;
+        for value in {
+            let array = values.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i])
+        } {
+            if value == 0 {
+                continue;
+            }
+            sum = i32::wrapping_add(sum, value);
+        }
+        println!("{sum}");
+
+        for name in {
+            let array = names.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            println!("{name}");
+        }
+
+        let mut count = 0;
+        while count < 3 {
+            count = i32::wrapping_add(count, 1);
+        }
+        println!("{count}");
+
+        let mut tries = 0;
+        loop {
+            'loop6_body: {
+                tries = i32::wrapping_add(tries, 1);
+                if tries == 2 {
+                    break 'loop6_body; // checked again by the condition
+                }
+                println!("{tries}");
+            }
+            if !(tries < 4) {
+                break;
+            }
+        }
+
+        // skip the odd numbers
+        let mut total = 0;
+        {
+            let mut i = 0;
+            while i < 10 {
+                'loop7_body: {
+                    if i % 2 == 1 {
+                        break 'loop7_body;
+                    }
+                    total = i32::wrapping_add(total, i);
+                    println!("{total}");
+                }
+                i = i32::wrapping_add(i, 3);
+            }
+        }
+
+        // several variables in one declaration
+        let mut low = 7;
+        let high = -3;
+        {
+            let mut i = 10;
+            let mut j = 0;
+            while i > j {
+                low = i32::wrapping_add(low, i32::wrapping_sub(i, j));
+                i = i32::wrapping_sub(i, 3);
+                j = i32::wrapping_add(j, 1);
+            }
+        }
+        println!("{low} {high}");
+
+        'outer: for i in 0..3 {
+            for j in 0..3 {
+                if j == 2 {
+                    continue 'outer;
+                }
+                if i == 2 {
+                    break 'outer;
+                }
+                println!("{}", i32::wrapping_add(i32::wrapping_mul(i, 10), j));
+            }
+        }
+
+        {
+            let scoped = 1;
+            println!("{scoped}");
+        }
+        let scoped = 2;
+
+        'check: {
+            if scoped > 1 {
+                break 'check;
+            }
+            println!("unreachable");
+        }
+
+        let mut n = 0;
+        loop {
+            if {
+                n = i32::wrapping_add(n, 1);
+                n
+            } > 2
+            {
+                break;
+            }
+        }
+        println!("{}", i32::wrapping_add(n, scoped));
+    }
+}
+
+fn main() {
+    ControlFlow::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
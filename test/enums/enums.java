@@ -0,0 +1,104 @@
+// Enums become Rust enums, their fields belong to a struct which every constant has
+enum Color {
+    RED,
+    GREEN, // Comments of constants stay behind them
+    BLUE;
+
+    // Inside the enum the constants need no prefix
+    Color next() {
+        return this == BLUE ? RED : values()[ordinal() + 1];
+    }
+}
+
+enum Planet {
+    MERCURY(3.303e+23, 2.4397e6),
+    EARTH(5.976e+24, 6.37814e6);
+
+    private final double mass;
+    private final double radius;
+
+    Planet(double mass, double radius) {
+        this.mass = mass;
+        this.radius = radius;
+    }
+
+    double surfaceGravity() {
+        return 6.67300E-11 * mass / (radius * radius);
+    }
+}
+
+interface Operator {
+    int apply(int a, int b);
+}
+
+// Constants with their own body get a match arm in the method
+enum Operation implements Operator {
+    PLUS("+") {
+        public int apply(int a, int b) {
+            return a + b;
+        }
+    },
+    TIMES("*") {
+        public int apply(int x, int y) {
+            return x * y;
+        }
+    },
+    MINUS("-");
+
+    private final String symbol;
+
+    Operation(String symbol) {
+        this.symbol = symbol;
+    }
+
+    public int apply(int a, int b) {
+        return a - b;
+    }
+
+    String getSymbol() {
+        return symbol;
+    }
+
+    public String toString() {
+        return "op " + symbol;
+    }
+}
+
+class Main {
+    static Color favorite = Color.GREEN;
+
+    static String describe(Color color) {
+        switch (color) {
+            case RED:
+                return "warm";
+            case GREEN, BLUE:
+                return "cold";
+        }
+        return "unknown";
+    }
+
+    static int score(Color color) {
+        return switch (color) {
+            case RED -> 1;
+            case GREEN -> 2;
+            case BLUE -> 3;
+        };
+    }
+
+    public static void main(String[] args) {
+        for (Color color : Color.values()) {
+            System.out.println(color + " " + color.ordinal() + " " + describe(color) + " " + score(color));
+        }
+        Color red = Color.valueOf("RED");
+        System.out.println(red == Color.RED);
+        System.out.println(red.name());
+        System.out.println(Color.BLUE.compareTo(red));
+        System.out.println(favorite + " " + favorite.next() + " " + Color.BLUE.next());
+        System.out.println(Planet.EARTH.surfaceGravity() > Planet.MERCURY.surfaceGravity());
+        for (Operation operation : Operation.values()) {
+            System.out.println(operation + " " + operation.getSymbol() + " " + operation.apply(6, 3));
+        }
+        Operator operator = Operation.TIMES;
+        System.out.println(operator.apply(2, 5));
+    }
+}
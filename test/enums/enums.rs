@@ -0,0 +1,308 @@
+// Enums become Rust enums, their fields belong to a struct which every constant has
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Color {
+    #[default]
+    RED,
+    GREEN, // Comments of constants stay behind them
+    BLUE,
+}
+
+impl Color {
+    pub fn values() -> std::rc::Rc<std::cell::RefCell<Vec<Color>>> {
+        std::rc::Rc::new(std::cell::RefCell::new(vec![
+            Self::RED,
+            Self::GREEN,
+            Self::BLUE,
+        ]))
+    }
+    pub fn valueOf(name: String) -> Color {
+        match name.as_str() {
+            "RED" => Self::RED,
+            "GREEN" => Self::GREEN,
+            "BLUE" => Self::BLUE,
+            _ => panic!("No enum constant Color.{}", name),
+        }
+    }
+    pub fn ordinal(&self) -> i32 {
+        *self as i32
+    }
+    pub fn name(&self) -> String {
+        match self {
+            Self::RED => "RED",
+            Self::GREEN => "GREEN",
+            Self::BLUE => "BLUE",
+        }
+        .to_string()
+    }
+    pub fn compareTo(&self, other: Color) -> i32 {
+        self.ordinal() - other.ordinal()
+    }
+
+    // Inside the enum the constants need no prefix
+    fn next(&self) -> Color {
+        return if *self == Color::BLUE {
+            Color::RED
+        } else {
+            Color::values().borrow()[i32::wrapping_add(self.ordinal(), 1) as usize]
+        };
+    }
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Planet {
+    #[default]
+    MERCURY,
+    EARTH,
+}
+
+#[derive(Clone, Default)]
+struct PlanetFields {
+    mass: f64,
+    radius: f64,
+}
+
+impl PlanetFields {
+    fn new(mass: f64, radius: f64) -> PlanetFields {
+        let mut this = Self {
+            mass: 0.0,
+            radius: 0.0,
+        };
+        this.mass = mass;
+        this.radius = radius;
+        this
+    }
+}
+
+impl std::ops::Deref for Planet {
+    type Target = PlanetFields;
+
+    fn deref(&self) -> &PlanetFields {
+        thread_local! {
+        static FIELDS: &'static [PlanetFields] = vec![PlanetFields::new(3.303e23, 2.4397e6), PlanetFields::new(5.976e24, 6.37814e6)].leak();
+        }
+        &FIELDS.with(|fields| *fields)[*self as usize]
+    }
+}
+
+impl Planet {
+    pub fn values() -> std::rc::Rc<std::cell::RefCell<Vec<Planet>>> {
+        std::rc::Rc::new(std::cell::RefCell::new(vec![Self::MERCURY, Self::EARTH]))
+    }
+    pub fn valueOf(name: String) -> Planet {
+        match name.as_str() {
+            "MERCURY" => Self::MERCURY,
+            "EARTH" => Self::EARTH,
+            _ => panic!("No enum constant Planet.{}", name),
+        }
+    }
+    pub fn ordinal(&self) -> i32 {
+        *self as i32
+    }
+    pub fn name(&self) -> String {
+        match self {
+            Self::MERCURY => "MERCURY",
+            Self::EARTH => "EARTH",
+        }
+        .to_string()
+    }
+    pub fn compareTo(&self, other: Planet) -> i32 {
+        self.ordinal() - other.ordinal()
+    }
+
+    fn surfaceGravity(&self) -> f64 {
+        return 6.67300e-11 * self.mass / (self.radius * self.radius);
+    }
+}
+
+impl std::fmt::Display for Planet {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+trait Operator {
+    fn apply(&self, a: i32, b: i32) -> i32;
+}
+
+// Constants with their own body get a match arm in the method
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Operation {
+    #[default]
+    PLUS,
+    TIMES,
+    MINUS,
+}
+
+#[derive(Clone, Default)]
+struct OperationFields {
+    symbol: String,
+}
+
+impl OperationFields {
+    fn new(symbol: String) -> OperationFields {
+        let mut this = Self {
+            symbol: String::new(),
+        };
+        this.symbol = symbol;
+        this
+    }
+}
+
+impl std::ops::Deref for Operation {
+    type Target = OperationFields;
+
+    fn deref(&self) -> &OperationFields {
+        thread_local! {
+        static FIELDS: &'static [OperationFields] = vec![OperationFields::new("+".to_string()), OperationFields::new("*".to_string()), OperationFields::new("-".to_string())].leak();
+        }
+        &FIELDS.with(|fields| *fields)[*self as usize]
+    }
+}
+
+impl Operation {
+    pub fn values() -> std::rc::Rc<std::cell::RefCell<Vec<Operation>>> {
+        std::rc::Rc::new(std::cell::RefCell::new(vec![
+            Self::PLUS,
+            Self::TIMES,
+            Self::MINUS,
+        ]))
+    }
+    pub fn valueOf(name: String) -> Operation {
+        match name.as_str() {
+            "PLUS" => Self::PLUS,
+            "TIMES" => Self::TIMES,
+            "MINUS" => Self::MINUS,
+            _ => panic!("No enum constant Operation.{}", name),
+        }
+    }
+    pub fn ordinal(&self) -> i32 {
+        *self as i32
+    }
+    pub fn name(&self) -> String {
+        match self {
+            Self::PLUS => "PLUS",
+            Self::TIMES => "TIMES",
+            Self::MINUS => "MINUS",
+        }
+        .to_string()
+    }
+    pub fn compareTo(&self, other: Operation) -> i32 {
+        self.ordinal() - other.ordinal()
+    }
+
+    fn getSymbol(&self) -> String {
+        return self.symbol.clone();
+    }
+
+    pub fn toString(&self) -> String {
+        return format!("op {}", self.symbol);
+    }
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.toString())
+    }
+}
+
+impl Operator for Operation {
+    fn apply(&self, a: i32, b: i32) -> i32 {
+        match self {
+            Self::PLUS => {
+                return i32::wrapping_add(a, b);
+            }
+            Self::TIMES => {
+                let x = a;
+                let y = b;
+                return i32::wrapping_mul(x, y);
+            }
+            _ => {
+                return i32::wrapping_sub(a, b);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct Main {}
+
+impl Main {
+    fn describe(color: Color) -> String {
+        match color {
+            Color::RED => {
+                return "warm".to_string();
+            }
+            Color::GREEN | Color::BLUE => {
+                return "cold".to_string();
+            }
+            _ => {}
+        }
+        return "unknown".to_string();
+    }
+
+    fn score(color: Color) -> i32 {
+        return match color {
+            Color::RED => 1,
+            Color::GREEN => 2,
+            Color::BLUE => 3,
+            _ => unreachable!(),
+        };
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        for mut color in {
+            let array = Color::values();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            println!(
+                "{color} {} {} {}",
+                color.ordinal(),
+                Main::describe(color.clone()),
+                Main::score(color.clone())
+            );
+        }
+        let mut red = Color::valueOf("RED".to_string());
+        println!("{}", red == Color::RED);
+        println!("{}", red.name());
+        println!("{}", Color::BLUE.compareTo(red.clone()));
+        println!(
+            "{} {} {}",
+            *MAIN_FAVORITE.with(|cell| *cell).borrow(),
+            MAIN_FAVORITE.with(|cell| *cell).borrow().next(),
+            Color::BLUE.next()
+        );
+        println!(
+            "{}",
+            Planet::EARTH.surfaceGravity() > Planet::MERCURY.surfaceGravity()
+        );
+        for mut operation in {
+            let array = Operation::values();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            println!(
+                "{operation} {} {}",
+                operation.getSymbol(),
+                operation.apply(6, 3)
+            );
+        }
+        let mut operator = std::rc::Rc::new(Operation::TIMES) as std::rc::Rc<dyn Operator>;
+        println!("{}", operator.apply(2, 5));
+    }
+}
+
+thread_local! {
+static MAIN_FAVORITE: &'static std::cell::RefCell<Color> = Box::leak(Box::new(std::cell::RefCell::new(Color::GREEN)));
+}
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
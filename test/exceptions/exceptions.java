@@ -0,0 +1,123 @@
+// Exceptions become variants of one enum, methods which declare them return a Result
+class EmptyException extends Exception {
+    private static final long serialVersionUID = 1L;
+
+    public EmptyException(String message) {
+        super(message);
+    }
+}
+
+// Constructors which declare exceptions return a Result as well
+class Account {
+    private int balance;
+
+    Account(int balance) throws EmptyException {
+        if (balance == 0) {
+            throw new EmptyException("no balance");
+        }
+        this.balance = balance;
+    }
+
+    Account() throws EmptyException {
+        this(1);
+    }
+
+    int getBalance() {
+        return balance;
+    }
+}
+
+class Main {
+    static int parsePositive(int value) throws EmptyException {
+        if (value == 0) {
+            throw new EmptyException("value is zero");
+        }
+        if (value < 0) {
+            throw new IllegalArgumentException("negative: " + value);
+        }
+        return value * 2;
+    }
+
+    // The exception of the call is passed on with ?
+    static int sum(int a, int b) throws EmptyException {
+        return parsePositive(a) + parsePositive(b);
+    }
+
+    static String describe(int value) {
+        try {
+            int doubled = sum(value, 1);
+            return "ok " + doubled;
+        } catch (EmptyException | IllegalArgumentException e) {
+            return "failed: " + e.getMessage();
+        }
+    }
+
+    static int withFinally(int value) {
+        try {
+            if (value > 5) {
+                return value;
+            }
+            parsePositive(value);
+            System.out.println("parsed " + value);
+        } catch (EmptyException e) {
+            System.out.println("caught " + e);
+        } finally {
+            System.out.println("finally " + value);
+        }
+        return -1;
+    }
+
+    // Only a panic raises the ArithmeticException of the division, so the catch is reported
+    static int divide(int a, int b) {
+        try {
+            return a / b;
+        } catch (ArithmeticException e) {
+            return 0;
+        }
+    }
+
+    static void loop() throws EmptyException {
+        for (int i = 0; i < 4; i++) {
+            try {
+                if (i == 1) {
+                    continue;
+                }
+                if (i == 3) {
+                    break;
+                }
+                System.out.println("loop " + i);
+            } finally {
+                System.out.println("next " + i);
+            }
+        }
+        throw new EmptyException("done");
+    }
+
+    public static void main(String[] args) throws EmptyException {
+        System.out.println(describe(2));
+        System.out.println(describe(0));
+        System.out.println(describe(-3));
+        System.out.println(withFinally(7));
+        System.out.println(withFinally(0));
+        System.out.println(withFinally(3));
+        System.out.println(divide(7, 2));
+        try {
+            loop();
+        } catch (Exception e) {
+            System.out.println(e.getMessage());
+        }
+        try {
+            throw new IllegalStateException();
+        } catch (RuntimeException e) {
+            System.out.println(e);
+        }
+        try {
+            System.out.println(new Account(5).getBalance() + new Account().getBalance());
+            new Account(0);
+        } catch (EmptyException e) {
+            System.out.println("account: " + e.getMessage());
+        }
+        System.out.println(sum(1, 2));
+        sum(0, 1);
+    }
+}
@@ -0,0 +1,313 @@
+// Exceptions become variants of one enum, methods which declare them return a Result
+
+// Constructors which declare exceptions return a Result as well
+#[derive(Clone, Default)]
+struct Account(std::rc::Rc<AccountFields>);
+
+#[derive(Default)]
+struct AccountFields {
+    balance: std::cell::Cell<i32>,
+}
+
+impl std::ops::Deref for Account {
+    type Target = AccountFields;
+
+    fn deref(&self) -> &AccountFields {
+        &self.0
+    }
+}
+
+impl Account {
+    fn new(balance: i32) -> Result<Account, Exception> {
+        let this = Self(std::rc::Rc::new(AccountFields {
+            balance: std::cell::Cell::new(0),
+        }));
+        if balance == 0 {
+            return Err(Exception::EmptyException(Some("no balance".to_string())));
+        }
+        this.balance.set(balance);
+        Ok(this)
+    }
+
+    fn new_empty() -> Result<Account, Exception> {
+        let this = Account::new(1)?;
+        Ok(this)
+    }
+
+    fn getBalance(&self) -> i32 {
+        return self.balance.get();
+    }
+}
+
+impl std::fmt::Display for Account {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Account@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct Main {}
+
+impl Main {
+    fn parsePositive(value: i32) -> Result<i32, Exception> {
+        if value == 0 {
+            return Err(Exception::EmptyException(Some("value is zero".to_string())));
+        }
+        if value < 0 {
+            return Err(Exception::IllegalArgumentException(Some(format!(
+                "negative: {value}"
+            ))));
+        }
+        return Ok(i32::wrapping_mul(value, 2));
+    }
+
+    // The exception of the call is passed on with ?
+    fn sum(a: i32, b: i32) -> Result<i32, Exception> {
+        return Ok(i32::wrapping_add(
+            Main::parsePositive(a)?,
+            Main::parsePositive(b)?,
+        ));
+    }
+
+    fn describe(value: i32) -> String {
+        let result: Result<(), Exception> = 'try1: {
+            let doubled = match Main::sum(value, 1) {
+                Ok(value) => value,
+                Err(error) => break 'try1 Err(error),
+            };
+            return format!("ok {doubled}");
+        };
+        match result {
+            Err(e)
+                if e.is_instance_of("EmptyException")
+                    || e.is_instance_of("IllegalArgumentException") =>
+            {
+                return format!("failed: {}", e.getMessage());
+            }
+            Err(error) => {
+                panic!("{}", error);
+            }
+            Ok(()) => unreachable!(),
+        }
+    }
+
+    fn withFinally(value: i32) -> i32 {
+        let result1: Result<(), Exception> = 'try1: {
+            if value > 5 {
+                {
+                    let value1 = value;
+                    {
+                        println!("finally {value}");
+                    }
+                    return value1;
+                }
+            }
+            if let Err(error) = Main::parsePositive(value) {
+                break 'try1 Err(error);
+            };
+            println!("parsed {value}");
+            Ok(())
+        };
+        let result1: Result<(), Exception> = match result1 {
+            Err(e) if e.is_instance_of("EmptyException") => 'catch2: {
+                println!("caught {e}");
+                Ok(())
+            }
+            result1 => result1,
+        };
+        {
+            println!("finally {value}");
+        }
+        if let Err(error) = result1 {
+            panic!("{}", error);
+        }
+        return -1;
+    }
+
+    // Only a panic raises the ArithmeticException of the division, so the catch is reported
+    fn divide(a: i32, b: i32) -> i32 {
+        let result2: Result<(), Exception> = 'try1: {
+            return a / b;
+        };
+        match result2 {
+            Err(e) if e.is_instance_of("ArithmeticException") => {
+                return 0;
+            }
+            Err(error) => {
+                panic!("{}", error);
+            }
+            Ok(()) => unreachable!(),
+        }
+    }
+
+    fn r#loop() -> Result<(), Exception> {
+        'loop1: for i in 0..4 {
+            let result3: Result<(), Exception> = 'try2: {
+                if i == 1 {
+                    {
+                        println!("next {i}");
+                    }
+                    continue 'loop1;
+                }
+                if i == 3 {
+                    {
+                        println!("next {i}");
+                    }
+                    break 'loop1;
+                }
+                println!("loop {i}");
+                Ok(())
+            };
+            {
+                println!("next {i}");
+            }
+            if let Err(error) = result3 {
+                return Err(error);
+            }
+        }
+        return Err(Exception::EmptyException(Some("done".to_string())));
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) -> Result<(), Exception> {
+        println!("{}", Main::describe(2));
+        println!("{}", Main::describe(0));
+        println!("{}", Main::describe(-3));
+        println!("{}", Main::withFinally(7));
+        println!("{}", Main::withFinally(0));
+        println!("{}", Main::withFinally(3));
+        println!("{}", Main::divide(7, 2));
+        let result4: Result<(), Exception> = 'try1: {
+            if let Err(error) = Main::r#loop() {
+                break 'try1 Err(error);
+            };
+            Ok(())
+        };
+        match result4 {
+            Err(e) if e.is_instance_of("Exception") => {
+                println!("{}", e.getMessage());
+            }
+            Err(error) => {
+                return Err(error);
+            }
+            Ok(()) => {}
+        }
+        let result5: Result<(), Exception> = 'try2: {
+            break 'try2 Err(Exception::IllegalStateException(None));
+        };
+        match result5 {
+            Err(e) if e.is_instance_of("RuntimeException") => {
+                println!("{e}");
+            }
+            Err(error) => {
+                return Err(error);
+            }
+            Ok(()) => unreachable!(),
+        }
+        let result6: Result<(), Exception> = 'try3: {
+            println!(
+                "{}",
+                i32::wrapping_add(
+                    (match Account::new(5) {
+                        Ok(value) => value,
+                        Err(error) => break 'try3 Err(error),
+                    })
+                    .getBalance(),
+                    (match Account::new_empty() {
+                        Ok(value) => value,
+                        Err(error) => break 'try3 Err(error),
+                    })
+                    .getBalance()
+                )
+            );
+            match Account::new(0) {
+                Ok(value) => value,
+                Err(error) => break 'try3 Err(error),
+            };
+            Ok(())
+        };
+        match result6 {
+            Err(e) if e.is_instance_of("EmptyException") => {
+                println!("account: {}", e.getMessage());
+            }
+            Err(error) => {
+                return Err(error);
+            }
+            Ok(()) => {}
+        }
+        println!("{}", Main::sum(1, 2)?);
+        Main::sum(0, 1)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Exception {
+    EmptyException(Option<String>),
+    IllegalArgumentException(Option<String>),
+    IllegalStateException(Option<String>),
+}
+
+impl Exception {
+    pub fn getMessage(&self) -> String {
+        self.message().unwrap_or_else(|| "null".to_string())
+    }
+    pub fn toString(&self) -> String {
+        self.to_string()
+    }
+    pub fn printStackTrace(&self) {
+        eprintln!("{}", self);
+    }
+    fn message(&self) -> Option<String> {
+        match *self {
+            Self::EmptyException(ref message) => message.clone(),
+            Self::IllegalArgumentException(ref message) => message.clone(),
+            Self::IllegalStateException(ref message) => message.clone(),
+        }
+    }
+    fn class_name(&self) -> &'static str {
+        match *self {
+            Self::EmptyException(_) => "EmptyException",
+            Self::IllegalArgumentException(_) => "java.lang.IllegalArgumentException",
+            Self::IllegalStateException(_) => "java.lang.IllegalStateException",
+        }
+    }
+    fn is_instance_of(&self, class: &str) -> bool {
+        let classes: &[&str] = match *self {
+            Self::EmptyException(_) => &["EmptyException", "Exception", "Throwable"],
+            Self::IllegalArgumentException(_) => &[
+                "IllegalArgumentException",
+                "RuntimeException",
+                "Exception",
+                "Throwable",
+            ],
+            Self::IllegalStateException(_) => &[
+                "IllegalStateException",
+                "RuntimeException",
+                "Exception",
+                "Throwable",
+            ],
+        };
+        classes.contains(&class)
+    }
+}
+
+impl std::fmt::Display for Exception {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.message() {
+            Some(message) => write!(f, "{}: {}", self.class_name(), message),
+            None => write!(f, "{}", self.class_name()),
+        }
+    }
+}
+fn main() {
+    if let Err(error) = Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    ))) {
+        eprintln!("Exception in thread \"main\" {}", error);
+        std::process::exit(1);
+    }
+}
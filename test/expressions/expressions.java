@@ -0,0 +1,64 @@
+class Expressions {
+    public static void main(String[] args) {
+        int a = 7;
+        int b = 2;
+        long big = a;
+        double ratio = a / 2.0;
+        char letter = 'a';
+        int code = letter + 1;
+        char next = (char) code;
+        boolean flag = a > b && !(b == 3) || a < 0;
+        int bits = a & 3 | b ^ 1 << 2;
+        int shifted = -16 >>> 28;
+        int max = a > b ? a : b;
+        String name = "World";
+        String greeting = "Hello " + name + "! " + a + b + " " + (a + b);
+        int[] values = {1, 2, 3};
+        int[][] grid = new int[2][3];
+        a += 3;
+        b++;
+        --b;
+        big <<= 2;
+        values[0] = values[1] * -values[2];
+        grid[1][2] = values.length;
+        name += "!";
+        System.out.println(greeting);
+        System.out.println(ratio);
+        System.out.println(next);
+        System.out.println("flag: " + flag + ", bits: " + bits);
+        System.out.println(shifted + max);
+        System.out.println(big);
+        System.out.println(values[0]);
+        System.out.println(grid[1][2]);
+        System.out.println(name);
+        System.out.println(a % b);
+        // Doubles print like Double.toString
+        double tiny = 1e-300;
+        float largest = 3.4028235e38f;
+        System.out.println(tiny);
+        System.out.println(largest);
+        System.out.println(1e20);
+        System.out.println(1e7 + " " + 9999999.0 + " " + 0.001 + " " + 0.0001 + " " + -0.0);
+        System.out.println("ratio: " + ratio + ", third: " + 1.0f / 3 + ", big: " + 123456789.5);
+        System.out.println(0.0 / 0.0 + " " + -1.0 / 0.0);
+        // Compound assignments compute with the promoted type and cast back
+        int half = 7;
+        half /= 0.5;
+        half *= 1.5;
+        char c = 'a';
+        c += 2;
+        c++;
+        byte small = 127;
+        small++;
+        short wrapped = 10;
+        wrapped += 100000;
+        System.out.println(half + " " + c + " " + small + " " + wrapped);
+        // The target is evaluated before the value
+        int i = 0;
+        int[] slots = new int[2];
+        slots[i] = 7 + i++;
+        int j = 0;
+        j += j++ + ++j;
+        System.out.println(slots[0] + " " + slots[1] + " " + i + " " + j);
+    }
+}
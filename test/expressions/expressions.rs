@@ -0,0 +1,156 @@
+#[derive(Clone, Default)]
+struct Expressions {}
+
+impl Expressions {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let mut a = 7;
+        let mut b = 2;
+        let mut big = a as i64;
+        let ratio = a as f64 / 2.0;
+        let letter = 'a';
+        let code = i32::wrapping_add(letter as i32, 1);
+        let next = char::from_u32(code as u32 & 0xFFFF).unwrap_or(char::REPLACEMENT_CHARACTER);
+        let flag = a > b && !(b == 3) || a < 0;
+        let bits = a & 3 | b ^ i32::wrapping_shl(1, 2);
+        let shifted = u32::wrapping_shr(-16i32 as u32, 28) as i32;
+        let max = if a > b { a } else { b };
+        let mut name = "World".to_string();
+        let greeting = format!("Hello {name}! {a}{b} {}", i32::wrapping_add(a, b));
+        let values = std::rc::Rc::new(std::cell::RefCell::new(vec![1, 2, 3]));
+        let grid = std::rc::Rc::new(std::cell::RefCell::new(
+            (0..2)
+                .map(|_| std::rc::Rc::new(std::cell::RefCell::new(vec![0; 3])))
+                .collect::<Vec<_>>(),
+        ));
+        a = i32::wrapping_add(a, 3);
+        b = i32::wrapping_add(b, 1);
+        b = i32::wrapping_sub(b, 1);
+        big = i64::wrapping_shl(big, 2);
+        {
+            let value =
+                i32::wrapping_mul(values.borrow()[1], i32::wrapping_neg(values.borrow()[2]));
+            values.borrow_mut()[0] = value;
+        };
+        {
+            let value1 = values.borrow().len() as i32;
+            grid.borrow()[1].borrow_mut()[2] = value1;
+        };
+        name = format!("{name}!");
+        println!("{greeting}");
+        println!("{}", double_to_string(ratio));
+        println!("{next}");
+        println!("flag: {flag}, bits: {bits}");
+        println!("{}", i32::wrapping_add(shifted, max));
+        println!("{big}");
+        println!("{}", values.borrow()[0]);
+        println!("{}", grid.borrow()[1].borrow()[2]);
+        println!("{name}");
+        println!("{}", a % b);
+        // Doubles print like Double.toString
+        let tiny = 1.0e-300;
+        let largest = 3.4028235e38f32;
+        println!("{}", double_to_string(tiny));
+        println!("{}", double_to_string(largest));
+        println!("{}", double_to_string(1.0e20));
+        println!(
+            "{} {} {} {} {}",
+            double_to_string(1.0e7),
+            double_to_string(9999999.0),
+            double_to_string(0.001),
+            double_to_string(0.0001),
+            double_to_string(-0.0)
+        );
+        println!(
+            "ratio: {}, third: {}, big: {}",
+            double_to_string(ratio),
+            double_to_string(1.0f32 / 3i32 as f32),
+            double_to_string(123456789.5)
+        );
+        println!(
+            "{} {}",
+            double_to_string(0.0 / 0.0),
+            double_to_string(-1.0 / 0.0)
+        );
+        // Compound assignments compute with the promoted type and cast back
+        let mut half = 7;
+        half = (half as f64 / 0.5) as i32;
+        half = (half as f64 * 1.5) as i32;
+        let mut c = 'a';
+        c = char::from_u32(i32::wrapping_add(c as i32, 2) as u32 & 0xFFFF)
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        c = char::from_u32(i32::wrapping_add(c as i32, 1) as u32 & 0xFFFF)
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        let mut small = 127i32 as i8;
+        small = i32::wrapping_add(small as i32, 1) as i8;
+        let mut wrapped = 10i32 as i16;
+        wrapped = i32::wrapping_add(wrapped as i32, 100000) as i16;
+        println!("{half} {c} {small} {wrapped}");
+        // The target is evaluated before the value
+        let mut i = 0;
+        let slots = std::rc::Rc::new(std::cell::RefCell::new(vec![0; 2]));
+        {
+            let index = i;
+            slots.borrow_mut()[index as usize] = i32::wrapping_add(7, {
+                let old = i;
+                i = i32::wrapping_add(i, 1);
+                old
+            });
+        };
+        let mut j = 0;
+        j = i32::wrapping_add(
+            j,
+            i32::wrapping_add(
+                {
+                    let old = j;
+                    j = i32::wrapping_add(j, 1);
+                    old
+                },
+                {
+                    j = i32::wrapping_add(j, 1);
+                    j
+                },
+            ),
+        );
+        println!("{} {} {i} {j}", slots.borrow()[0], slots.borrow()[1]);
+    }
+}
+
+fn double_to_string<T: std::fmt::LowerExp>(value: T) -> String {
+    let scientific = format!("{:e}", value);
+    let (sign, scientific) = match scientific.strip_prefix('-') {
+        Some(scientific) => ("-", scientific),
+        None => ("", scientific.as_str()),
+    };
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return match scientific {
+            "inf" => format!("{}Infinity", sign),
+            _ => "NaN".to_string(),
+        };
+    };
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap();
+    if digits == "0" {
+        return format!("{}0.0", sign);
+    }
+    if !(-3..7).contains(&exponent) {
+        let fraction = if digits.len() > 1 { &digits[1..] } else { "0" };
+        return format!("{}{}.{}E{}", sign, &digits[..1], fraction, exponent);
+    }
+    if exponent < 0 {
+        return format!(
+            "{}0.{}{}",
+            sign,
+            "0".repeat((-exponent - 1) as usize),
+            digits
+        );
+    }
+    let point = exponent as usize + 1;
+    let digits = format!("{:0<1$}", digits, point + 1);
+    format!("{}{}.{}", sign, &digits[..point], &digits[point..])
+}
+
+fn main() {
+    Expressions::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,144 @@
+import java.util.function.*;
+
+// Generic interfaces become generic traits
+interface Container<T> {
+    T get();
+}
+
+// Type parameters get the bounds which their code needs, e.g. Display for string concatenation
+class Box<T> implements Container<T> {
+    private T value;
+
+    Box(T value) {
+        this.value = value;
+    }
+
+    public T get() {
+        return value;
+    }
+
+    void set(T value) {
+        this.value = value;
+    }
+
+    // The type arguments of generic methods are inferred from the arguments
+    <U> Pair<T, U> with(U other) {
+        return new Pair<>(value, other);
+    }
+
+    public String toString() {
+        return "Box(" + value + ")";
+    }
+}
+
+record Pair<A, B>(A first, B second) {
+    // Static methods of generic types can't use their type parameters
+    static <T> Pair<T, T> twin(T value) {
+        return new Pair<>(value, value);
+    }
+
+    // Lambdas for generic methods leave the result type to Rust
+    <R> R apply(BiFunction<A, B, R> f) {
+        return f.apply(first, second);
+    }
+}
+
+// Holder never prints or compares its values, so it can hold arrays
+class Holder<T> {
+    private T value;
+
+    Holder(T value) {
+        this.value = value;
+    }
+
+    T get() {
+        return value;
+    }
+}
+
+class Ones implements Container<Integer> {
+    public Integer get() {
+        return 1;
+    }
+}
+
+interface Shape {
+    double area();
+}
+
+record Square(double side) implements Shape {
+    public double area() {
+        return side * side;
+    }
+}
+
+class Main {
+    // Comparable becomes PartialOrd
+    static <T extends Comparable<T>> T max(T a, T b) {
+        return a.compareTo(b) > 0 ? a : b;
+    }
+
+    // Interfaces become trait bounds
+    static <T extends Shape> double doubled(T shape) {
+        return shape.area() * 2;
+    }
+
+    // Classes can't be bounds in Rust, so Number is dropped
+    static <N extends Number> String show(N number) {
+        return "number " + number;
+    }
+
+    // Wildcards become type parameters of the method
+    static double area(Box<? extends Shape> box) {
+        return box.get().area();
+    }
+
+    // A bound which can't be a trait is the type itself
+    static int sum(Container<? extends Integer> container, int times) {
+        int total = 0;
+        for (int i = 0; i < times; i++) {
+            total += container.get();
+        }
+        return total;
+    }
+
+    static <T, R> R map(T value, Function<T, R> f) {
+        return f.apply(value);
+    }
+
+    static void describe(Box<?> box) {
+        System.out.println("box of " + box.get());
+    }
+
+    // There are no lower bounds, so ? super Integer is Integer
+    static void fill(Box<? super Integer> box) {
+        box.set(42);
+        System.out.println("filled " + box.get());
+    }
+
+    public static void main(String[] args) {
+        Box<String> text = new Box<>("hello");
+        System.out.println(text.get());
+        text.set("world");
+        System.out.println(text.get() + "!");
+        Box<Integer> number = new Box<Integer>(1);
+        int twice = number.get() * 2;
+        System.out.println(twice);
+        describe(number);
+        fill(new Box<>(0));
+        System.out.println(max(3, 7) + " " + max("apple", "pear") + " " + max(2.5, 1.5));
+        Square square = new Square(3.0);
+        System.out.println(doubled(square) + " " + area(new Box<>(square)) + " " + show(5));
+        Pair<Integer, String> pair = number.with("answer");
+        System.out.println(pair + " " + pair.first() + " " + pair.second());
+        Box<Pair<Integer, String>> boxed = new Box<>(pair);
+        System.out.println(boxed.get().second());
+        System.out.println(sum(new Ones(), 3) + " " + Pair.twin("a").second());
+        Box<Box<Integer>> nested = new Box<>(new Box<>(5));
+        System.out.println(nested + " " + nested.get().get());
+        System.out.println(pair.apply((k, v) -> k + v) + " " + map("four", s -> s.length()));
+        Holder<int[]> numbers = new Holder<>(new int[] {1, 2, 3});
+        Holder<Holder<int[]>> outer = new Holder<>(numbers);
+        System.out.println(outer.get().get()[2]);
+    }
+}
@@ -0,0 +1,412 @@
+// Generic interfaces become generic traits
+trait Container<T> {
+    fn get(&self) -> T;
+}
+
+// Type parameters get the bounds which their code needs, e.g. Display for string concatenation
+#[derive(Clone, Default)]
+struct Box<T>(std::rc::Rc<BoxFields<T>>);
+
+#[derive(Default)]
+struct BoxFields<T> {
+    value: std::cell::RefCell<T>,
+}
+
+impl<T: Clone + Default + std::fmt::Display> std::ops::Deref for Box<T> {
+    type Target = BoxFields<T>;
+
+    fn deref(&self) -> &BoxFields<T> {
+        &self.0
+    }
+}
+
+impl<T: Clone + Default + std::fmt::Display> Box<T> {
+    fn new(value: T) -> Box<T> {
+        let this = Self(std::rc::Rc::new(BoxFields {
+            value: std::cell::RefCell::new(Default::default()),
+        }));
+        *this.value.borrow_mut() = value.clone();
+        this
+    }
+
+    fn set(&self, value: T) {
+        *self.value.borrow_mut() = value.clone();
+    }
+
+    // The type arguments of generic methods are inferred from the arguments
+    fn with<U: Clone>(&self, other: U) -> Pair<T, U> {
+        return Pair::new(self.value.borrow().clone(), other.clone());
+    }
+
+    pub fn toString(&self) -> String {
+        return format!("Box({})", *self.value.borrow());
+    }
+}
+
+impl<T: Clone + Default + std::fmt::Display> std::fmt::Display for Box<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.toString())
+    }
+}
+
+impl<T: Clone + Default + std::fmt::Display> Container<T> for Box<T> {
+    fn get(&self) -> T {
+        return self.value.borrow().clone();
+    }
+}
+
+#[derive(Clone, Default)]
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Clone, B: Clone> Pair<A, B> {
+    pub fn new(first: A, second: B) -> Pair<A, B> {
+        Self { first, second }
+    }
+    pub fn first(&self) -> A {
+        self.first.clone()
+    }
+    pub fn second(&self) -> B {
+        self.second.clone()
+    }
+    pub fn equals(&self, other: Pair<A, B>) -> bool
+    where
+        Self: PartialEq,
+    {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32
+    where
+        A: std::hash::Hash,
+        B: std::hash::Hash,
+    {
+        0i32.wrapping_mul(31)
+            .wrapping_add({
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                std::hash::Hash::hash(&self.first, &mut hasher);
+                std::hash::Hasher::finish(&hasher) as i32
+            })
+            .wrapping_mul(31)
+            .wrapping_add({
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                std::hash::Hash::hash(&self.second, &mut hasher);
+                std::hash::Hasher::finish(&hasher) as i32
+            })
+    }
+    pub fn toString(&self) -> String
+    where
+        Self: std::fmt::Debug,
+    {
+        format!("{:?}", self)
+    }
+
+    // Lambdas for generic methods leave the result type to Rust
+    fn apply<R>(&self, f: std::rc::Rc<dyn Fn(A, B) -> R>) -> R {
+        return f(self.first.clone(), self.second.clone());
+    }
+}
+
+impl Pair<(), ()> {
+    // Static methods of generic types can't use their type parameters
+    fn twin<T: Clone>(value: T) -> Pair<T, T> {
+        return Pair::new(value.clone(), value.clone());
+    }
+}
+
+impl<A: Clone + PartialEq, B: Clone + PartialEq> PartialEq for Pair<A, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.first == other.first && self.second == other.second
+    }
+}
+
+impl<A: Clone + Eq, B: Clone + Eq> Eq for Pair<A, B> {}
+
+impl<A: Clone + std::hash::Hash, B: Clone + std::hash::Hash> std::hash::Hash for Pair<A, B> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.first, state);
+        std::hash::Hash::hash(&self.second, state);
+    }
+}
+
+impl<A: Clone + std::fmt::Display, B: Clone + std::fmt::Display> std::fmt::Debug for Pair<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Pair[first={}, second={}]", self.first, self.second)
+    }
+}
+
+impl<A: Clone + std::fmt::Display, B: Clone + std::fmt::Display> std::fmt::Display for Pair<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// Holder never prints or compares its values, so it can hold arrays
+#[derive(Clone, Default)]
+struct Holder<T>(std::rc::Rc<HolderFields<T>>);
+
+#[derive(Default)]
+struct HolderFields<T> {
+    value: std::cell::RefCell<T>,
+}
+
+impl<T: Clone + Default> std::ops::Deref for Holder<T> {
+    type Target = HolderFields<T>;
+
+    fn deref(&self) -> &HolderFields<T> {
+        &self.0
+    }
+}
+
+impl<T: Clone + Default> Holder<T> {
+    fn new(value: T) -> Holder<T> {
+        let this = Self(std::rc::Rc::new(HolderFields {
+            value: std::cell::RefCell::new(Default::default()),
+        }));
+        *this.value.borrow_mut() = value.clone();
+        this
+    }
+
+    fn get(&self) -> T {
+        return self.value.borrow().clone();
+    }
+}
+
+impl<T: Clone + Default> std::fmt::Display for Holder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Holder@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct Ones {}
+
+impl Ones {
+    pub fn new() -> Ones {
+        let this = Self {};
+        this
+    }
+}
+
+impl std::fmt::Display for Ones {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Ones@{:p}", self)
+    }
+}
+
+impl Container<i32> for Ones {
+    fn get(&self) -> i32 {
+        return 1;
+    }
+}
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+#[derive(Clone, Default)]
+struct Square {
+    side: f64,
+}
+
+impl Square {
+    pub fn new(side: f64) -> Square {
+        Self { side }
+    }
+    pub fn side(&self) -> f64 {
+        self.side
+    }
+    pub fn equals(&self, other: Square) -> bool {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32 {
+        0i32.wrapping_mul(31).wrapping_add({
+            let bits = self.side.to_bits();
+            (bits ^ (bits >> 32)) as i32
+        })
+    }
+    pub fn toString(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl PartialEq for Square {
+    fn eq(&self, other: &Self) -> bool {
+        self.side.to_bits() == other.side.to_bits()
+    }
+}
+
+impl Eq for Square {}
+
+impl std::hash::Hash for Square {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.side.to_bits(), state);
+    }
+}
+
+impl std::fmt::Debug for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Square[side={}]", double_to_string(self.side))
+    }
+}
+
+impl std::fmt::Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        return self.side * self.side;
+    }
+}
+
+#[derive(Clone, Default)]
+struct Main {}
+
+impl Main {
+    // Comparable becomes PartialOrd
+    fn max<T: PartialOrd + Clone>(mut a: T, b: T) -> T {
+        return if a.partial_cmp(&b).map_or(0, |ordering| ordering as i32) > 0 {
+            a.clone()
+        } else {
+            b.clone()
+        };
+    }
+
+    // Interfaces become trait bounds
+    fn doubled<T: Shape>(mut shape: T) -> f64 {
+        return shape.area() * 2i32 as f64;
+    }
+
+    // Classes can't be bounds in Rust, so Number is dropped
+    fn show<N: std::fmt::Display>(number: N) -> String {
+        return format!("number {number}");
+    }
+
+    // Wildcards become type parameters of the method
+    fn area<W: Shape + Clone + Default + std::fmt::Display>(r#box: Box<W>) -> f64 {
+        return r#box.get().area();
+    }
+
+    // A bound which can't be a trait is the type itself
+    fn sum(mut container: std::rc::Rc<dyn Container<i32>>, times: i32) -> i32 {
+        let mut total = 0;
+        for i in 0..times {
+            total = total + container.get();
+        }
+        return total;
+    }
+
+    fn map<T: Clone, R>(value: T, f: impl Fn(T) -> R) -> R {
+        return f(value.clone());
+    }
+
+    fn describe<W1: Clone + Default + std::fmt::Display>(r#box: Box<W1>) {
+        println!("box of {}", r#box.get());
+    }
+
+    // There are no lower bounds, so ? super Integer is Integer
+    fn fill(r#box: Box<i32>) {
+        r#box.set(42);
+        println!("filled {}", r#box.get());
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let text = Box::<String>::new("hello".to_string());
+        println!("{}", text.get());
+        text.set("world".to_string());
+        println!("{}!", text.get());
+        let number = Box::<i32>::new(1);
+        let twice = i32::wrapping_mul(number.get(), 2);
+        println!("{twice}");
+        Main::describe(number.clone());
+        Main::fill(Box::new(0));
+        println!(
+            "{} {} {}",
+            Main::max(3, 7),
+            Main::max("apple".to_string(), "pear".to_string()),
+            double_to_string(Main::max(2.5, 1.5))
+        );
+        let square = Square::new(3.0);
+        println!(
+            "{} {} {}",
+            double_to_string(Main::doubled(square.clone())),
+            double_to_string(Main::area(Box::new(square.clone()))),
+            Main::show(5)
+        );
+        let mut pair = number.with("answer".to_string());
+        println!("{pair} {} {}", pair.first(), pair.second());
+        let boxed = Box::<Pair<i32, String>>::new(pair.clone());
+        println!("{}", boxed.get().second());
+        println!(
+            "{} {}",
+            Main::sum(
+                std::rc::Rc::new(Ones::new()) as std::rc::Rc<dyn Container<i32>>,
+                3
+            ),
+            Pair::twin("a".to_string()).second()
+        );
+        let nested = Box::<Box<i32>>::new(Box::new(5));
+        println!("{nested} {}", nested.get().get());
+        println!(
+            "{} {}",
+            pair.apply(std::rc::Rc::new(|k: i32, v: String| format!("{k}{v}"))
+                as std::rc::Rc<dyn Fn(i32, String) -> _>),
+            Main::map("four".to_string(), |s: String| s.encode_utf16().count()
+                as i32)
+        );
+        let numbers = Holder::<std::rc::Rc<std::cell::RefCell<Vec<i32>>>>::new(std::rc::Rc::new(
+            std::cell::RefCell::new(vec![1, 2, 3]),
+        ));
+        let outer =
+            Holder::<Holder<std::rc::Rc<std::cell::RefCell<Vec<i32>>>>>::new(numbers.clone());
+        println!("{}", outer.get().get().borrow()[2]);
+    }
+}
+
+fn double_to_string<T: std::fmt::LowerExp>(value: T) -> String {
+    let scientific = format!("{:e}", value);
+    let (sign, scientific) = match scientific.strip_prefix('-') {
+        Some(scientific) => ("-", scientific),
+        None => ("", scientific.as_str()),
+    };
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return match scientific {
+            "inf" => format!("{}Infinity", sign),
+            _ => "NaN".to_string(),
+        };
+    };
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap();
+    if digits == "0" {
+        return format!("{}0.0", sign);
+    }
+    if !(-3..7).contains(&exponent) {
+        let fraction = if digits.len() > 1 { &digits[1..] } else { "0" };
+        return format!("{}{}.{}E{}", sign, &digits[..1], fraction, exponent);
+    }
+    if exponent < 0 {
+        return format!(
+            "{}0.{}{}",
+            sign,
+            "0".repeat((-exponent - 1) as usize),
+            digits
+        );
+    }
+    let point = exponent as usize + 1;
+    let digits = format!("{:0<1$}", digits, point + 1);
+    format!("{}{}.{}", sign, &digits[..point], &digits[point..])
+}
+
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
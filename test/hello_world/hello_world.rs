@@ -1,3 +1,14 @@
-pub fn main() {
-println!("Hello, World!")
-}
\ No newline at end of file
+#[derive(Clone, Default)]
+struct HelloWorld {}
+
+impl HelloWorld {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        println!("Hello, World!");
+    }
+}
+
+fn main() {
+    HelloWorld::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
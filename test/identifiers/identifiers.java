@@ -0,0 +1,19 @@
+public class Identifiers {
+    public static void main(String[] args) {
+        int my_var = 1;
+        int _count = 2;
+        int $proxy = 3;
+        int Outer$Inner = my_var + _count;
+        int _dollar_proxy = 4;
+        int yield = $proxy * 2;
+        int fn = yield + 1;
+        int self = 5;
+        int café = 6;
+        System.out.println(yield);
+        System.out.println($proxy);
+        System.out.println(Outer$Inner);
+    }
+
+    public static void match() {
+    }
+}
@@ -0,0 +1,27 @@
+#[derive(Clone, Default)]
+pub struct Identifiers {}
+
+impl Identifiers {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let my_var = 1;
+        let _count = 2;
+        let _dollar_proxy1 = 3;
+        let Outer_dollar_Inner = i32::wrapping_add(my_var, _count);
+        let _dollar_proxy = 4;
+        let r#yield = i32::wrapping_mul(_dollar_proxy1, 2);
+        let r#fn = i32::wrapping_add(r#yield, 1);
+        let self_ = 5;
+        let café = 6;
+        println!("{}", r#yield);
+        println!("{_dollar_proxy1}");
+        println!("{Outer_dollar_Inner}");
+    }
+
+    pub fn r#match() {}
+}
+
+fn main() {
+    Identifiers::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
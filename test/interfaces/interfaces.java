@@ -0,0 +1,103 @@
+// Interfaces become traits, constants and static methods belong to the trait object type
+interface Shape {
+    int CORNERS_OF_SQUARE = 4;
+    double LIMIT = 10.0;
+
+    double area();
+
+    // Default methods can use the abstract ones
+    default boolean isLarge() {
+        return area() > LIMIT;
+    }
+
+    static String describe(Shape shape) {
+        if (shape.isLarge()) {
+            return "large";
+        }
+        return "small";
+    }
+}
+
+interface Named extends Shape {
+    String name();
+
+    // Inherited default methods are called on the current object as well
+    default String label() {
+        return isLarge() ? name() + "!" : name();
+    }
+}
+
+interface Ordered<T> {
+    int compareTo(T other);
+}
+
+class Square implements Named, Ordered<Square> {
+    double side;
+
+    Square(double side) {
+        this.side = side;
+    }
+
+    public double area() {
+        return side * side;
+    }
+
+    public String name() {
+        return "square";
+    }
+
+    public int compareTo(Square other) {
+        if (side < other.side) {
+            return -1;
+        }
+        if (side > other.side) {
+            return 1;
+        }
+        return 0;
+    }
+}
+
+class Circle implements Shape {
+    double radius;
+
+    Circle(double radius) {
+        this.radius = radius;
+    }
+
+    public double area() {
+        return 3.0 * radius * radius;
+    }
+
+    // A large circle is always large
+    public boolean isLarge() {
+        return true;
+    }
+}
+
+public class Main {
+    static double total(Shape[] shapes) {
+        double sum = 0;
+        for (Shape shape : shapes) {
+            sum += shape.area();
+        }
+        return sum;
+    }
+
+    public static void main(String[] args) {
+        Square small = new Square(2.0);
+        Square big = new Square(5.0);
+        Shape circle = new Circle(1.0);
+        Shape[] shapes = { small, big, circle };
+        System.out.println(total(shapes));
+        System.out.println(Shape.describe(small));
+        System.out.println(Shape.describe(big));
+        System.out.println(Shape.describe(circle));
+        System.out.println(small.compareTo(big));
+        System.out.println(big.name() + " " + small.label() + " " + big.label());
+        System.out.println(Shape.CORNERS_OF_SQUARE);
+        // == compares the identity
+        Shape[] copy = shapes;
+        Shape first = shapes[2];
+        System.out.println((first == circle) + " " + (shapes[0] != circle) + " " + (copy == shapes));
+    }
+}
@@ -0,0 +1,235 @@
+// Interfaces become traits, constants and static methods belong to the trait object type
+trait Shape {
+    fn area(&self) -> f64;
+
+    // Default methods can use the abstract ones
+    fn isLarge(&self) -> bool {
+        return self.area() > <dyn Shape>::LIMIT;
+    }
+}
+
+impl dyn Shape {
+    pub const CORNERS_OF_SQUARE: i32 = 4;
+    pub const LIMIT: f64 = 10.0;
+
+    pub fn describe(mut shape: std::rc::Rc<dyn Shape>) -> String {
+        if shape.isLarge() {
+            return "large".to_string();
+        }
+        return "small".to_string();
+    }
+}
+
+trait Named: Shape {
+    fn name(&self) -> String;
+
+    // Inherited default methods are called on the current object as well
+    fn label(&self) -> String {
+        return if self.isLarge() {
+            format!("{}!", self.name())
+        } else {
+            self.name()
+        };
+    }
+}
+
+trait Ordered<T> {
+    fn compareTo(&self, other: T) -> i32;
+}
+
+#[derive(Clone, Default)]
+struct Square(std::rc::Rc<SquareFields>);
+
+#[derive(Default)]
+struct SquareFields {
+    side: std::cell::Cell<f64>,
+}
+
+impl std::ops::Deref for Square {
+    type Target = SquareFields;
+
+    fn deref(&self) -> &SquareFields {
+        &self.0
+    }
+}
+
+impl Square {
+    fn new(side: f64) -> Square {
+        let this = Self(std::rc::Rc::new(SquareFields {
+            side: std::cell::Cell::new(0.0),
+        }));
+        this.side.set(side);
+        this
+    }
+}
+
+impl std::fmt::Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Square@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+impl Ordered<Square> for Square {
+    fn compareTo(&self, other: Square) -> i32 {
+        if self.side.get() < other.side.get() {
+            return -1;
+        }
+        if self.side.get() > other.side.get() {
+            return 1;
+        }
+        return 0;
+    }
+}
+
+impl Named for Square {
+    fn name(&self) -> String {
+        return "square".to_string();
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        return self.side.get() * self.side.get();
+    }
+}
+
+#[derive(Clone, Default)]
+struct Circle(std::rc::Rc<CircleFields>);
+
+#[derive(Default)]
+struct CircleFields {
+    radius: std::cell::Cell<f64>,
+}
+
+impl std::ops::Deref for Circle {
+    type Target = CircleFields;
+
+    fn deref(&self) -> &CircleFields {
+        &self.0
+    }
+}
+
+impl Circle {
+    fn new(radius: f64) -> Circle {
+        let this = Self(std::rc::Rc::new(CircleFields {
+            radius: std::cell::Cell::new(0.0),
+        }));
+        this.radius.set(radius);
+        this
+    }
+}
+
+impl std::fmt::Display for Circle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Circle@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        return 3.0 * self.radius.get() * self.radius.get();
+    }
+
+    // A large circle is always large
+    fn isLarge(&self) -> bool {
+        return true;
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Main {}
+
+impl Main {
+    fn total(shapes: std::rc::Rc<std::cell::RefCell<Vec<std::rc::Rc<dyn Shape>>>>) -> f64 {
+        let mut sum = 0i32 as f64;
+        for mut shape in {
+            let array = shapes.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            sum += shape.area();
+        }
+        return sum;
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let small = Square::new(2.0);
+        let big = Square::new(5.0);
+        let circle = std::rc::Rc::new(Circle::new(1.0)) as std::rc::Rc<dyn Shape>;
+        let shapes = std::rc::Rc::new(std::cell::RefCell::new(vec![
+            std::rc::Rc::new(small.clone()) as std::rc::Rc<dyn Shape>,
+            std::rc::Rc::new(big.clone()) as std::rc::Rc<dyn Shape>,
+            circle.clone(),
+        ]));
+        println!("{}", double_to_string(Main::total(shapes.clone())));
+        println!(
+            "{}",
+            <dyn Shape>::describe(std::rc::Rc::new(small.clone()) as std::rc::Rc<dyn Shape>)
+        );
+        println!(
+            "{}",
+            <dyn Shape>::describe(std::rc::Rc::new(big.clone()) as std::rc::Rc<dyn Shape>)
+        );
+        println!("{}", <dyn Shape>::describe(circle.clone()));
+        println!("{}", small.compareTo(big.clone()));
+        println!("{} {} {}", big.name(), small.label(), big.label());
+        println!("{}", <dyn Shape>::CORNERS_OF_SQUARE);
+        // == compares the identity
+        let copy = shapes.clone();
+        let first = shapes.borrow()[2].clone();
+        println!(
+            "{} {} {}",
+            std::rc::Rc::ptr_eq(&first, &circle),
+            !std::rc::Rc::ptr_eq(&shapes.borrow()[0], &circle),
+            std::rc::Rc::ptr_eq(&copy, &shapes)
+        );
+    }
+}
+
+fn double_to_string<T: std::fmt::LowerExp>(value: T) -> String {
+    let scientific = format!("{:e}", value);
+    let (sign, scientific) = match scientific.strip_prefix('-') {
+        Some(scientific) => ("-", scientific),
+        None => ("", scientific.as_str()),
+    };
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return match scientific {
+            "inf" => format!("{}Infinity", sign),
+            _ => "NaN".to_string(),
+        };
+    };
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap();
+    if digits == "0" {
+        return format!("{}0.0", sign);
+    }
+    if !(-3..7).contains(&exponent) {
+        let fraction = if digits.len() > 1 { &digits[1..] } else { "0" };
+        return format!("{}{}.{}E{}", sign, &digits[..1], fraction, exponent);
+    }
+    if exponent < 0 {
+        return format!(
+            "{}0.{}{}",
+            sign,
+            "0".repeat((-exponent - 1) as usize),
+            digits
+        );
+    }
+    let point = exponent as usize + 1;
+    let digits = format!("{:0<1$}", digits, point + 1);
+    format!("{}{}.{}", sign, &digits[..point], &digits[point..])
+}
+
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
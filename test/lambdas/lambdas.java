@@ -0,0 +1,98 @@
+import java.util.function.*;
+
+// Lambdas of functional interfaces of the input implement them as closures
+interface Transformer {
+    String transform(String value);
+}
+
+class Counter {
+    private int count;
+
+    Counter(int count) {
+        this.count = count;
+    }
+
+    int add(int value) {
+        return count + value;
+    }
+
+    // The lambda uses the current object
+    Function<Integer, Integer> adder() {
+        return value -> this.add(value) * 2;
+    }
+
+    Supplier<Integer> getter() {
+        return this::current;
+    }
+
+    int current() {
+        return count;
+    }
+}
+
+class Main {
+    static int twice(int value) {
+        return value * 2;
+    }
+
+    static int applyTwice(Function<Integer, Integer> f, int value) {
+        return f.apply(f.apply(value));
+    }
+
+    static String shout(Transformer transformer, String value) {
+        return transformer.transform(value) + "!";
+    }
+
+    public static void main(String[] args) {
+        Function<Integer, Integer> square = x -> x * x;
+        BiFunction<Integer, Integer, Integer> add = (a, b) -> a + b;
+        Supplier<String> greeting = () -> "hello";
+        Predicate<String> isEmpty = s -> s.isEmpty();
+        System.out.println(square.apply(4) + " " + add.apply(2, 3));
+        System.out.println(greeting.get() + " " + isEmpty.test("") + " " + isEmpty.test("a"));
+
+        // Captured variables are copied into the closure
+        String prefix = "value: ";
+        int offset = 10;
+        Function<Integer, String> describe = value -> prefix + (value + offset);
+        System.out.println(describe.apply(5));
+
+        // Block bodies
+        BinaryOperator<Integer> max = (a, b) -> {
+            if (a > b) {
+                return a;
+            }
+            return b;
+        };
+        System.out.println(max.apply(3, 7));
+
+        Consumer<String> printer = System.out::println;
+        printer.accept("printed");
+        Runnable runnable = () -> System.out.println("run");
+        runnable.run();
+
+        // Method references
+        Function<Integer, Integer> doubler = Main::twice;
+        Function<String, Integer> length = String::length;
+        UnaryOperator<String> upper = String::toUpperCase;
+        System.out.println(applyTwice(doubler, 3) + " " + length.apply("four") + " " + upper.apply("abc"));
+        System.out.println(applyTwice(x -> x + 1, 5));
+
+        Function<Integer, Counter> create = Counter::new;
+        Counter counter = create.apply(4);
+        System.out.println(counter.adder().apply(1) + " " + counter.getter().get());
+
+        // A lambda which changes what it captured
+        int[] calls = {0};
+        Supplier<Integer> next = () -> ++calls[0];
+        System.out.println(next.get() + " " + next.get());
+        // The captured array is the same one, so the changes are visible outside
+        Runnable tick = () -> calls[0]++;
+        tick.run();
+        tick.run();
+        System.out.println(calls[0]);
+
+        Transformer exclaim = value -> value + value;
+        System.out.println(shout(exclaim, "hey") + " " + shout(String::toLowerCase, "LOW"));
+    }
+}
@@ -0,0 +1,195 @@
+// Lambdas of functional interfaces of the input implement them as closures
+trait Transformer {
+    fn transform(&self, value: String) -> String;
+}
+
+impl<F: Fn(String) -> String> Transformer for F {
+    fn transform(&self, value: String) -> String {
+        self(value)
+    }
+}
+
+#[derive(Clone, Default)]
+struct Counter(std::rc::Rc<CounterFields>);
+
+#[derive(Default)]
+struct CounterFields {
+    count: std::cell::Cell<i32>,
+}
+
+impl std::ops::Deref for Counter {
+    type Target = CounterFields;
+
+    fn deref(&self) -> &CounterFields {
+        &self.0
+    }
+}
+
+impl Counter {
+    fn new(count: i32) -> Counter {
+        let this = Self(std::rc::Rc::new(CounterFields {
+            count: std::cell::Cell::new(0),
+        }));
+        this.count.set(count);
+        this
+    }
+
+    fn add(&self, value: i32) -> i32 {
+        return i32::wrapping_add(self.count.get(), value);
+    }
+
+    // The lambda uses the current object
+    fn adder(&self) -> std::rc::Rc<dyn Fn(i32) -> i32> {
+        return std::rc::Rc::new({
+            let this = self.clone();
+            move |value: i32| i32::wrapping_mul(this.add(value), 2)
+        }) as std::rc::Rc<dyn Fn(i32) -> i32>;
+    }
+
+    fn getter(&self) -> std::rc::Rc<dyn Fn() -> i32> {
+        return std::rc::Rc::new({
+            let this = self.clone();
+            move || this.current()
+        }) as std::rc::Rc<dyn Fn() -> i32>;
+    }
+
+    fn current(&self) -> i32 {
+        return self.count.get();
+    }
+}
+
+impl std::fmt::Display for Counter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Counter@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct Main {}
+
+impl Main {
+    fn twice(value: i32) -> i32 {
+        return i32::wrapping_mul(value, 2);
+    }
+
+    fn applyTwice(f: impl Fn(i32) -> i32, value: i32) -> i32 {
+        return f(f(value));
+    }
+
+    fn shout(mut transformer: std::rc::Rc<dyn Transformer>, value: String) -> String {
+        return format!("{}!", transformer.transform(value.clone()));
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let square =
+            std::rc::Rc::new(|x: i32| i32::wrapping_mul(x, x)) as std::rc::Rc<dyn Fn(i32) -> i32>;
+        let add = std::rc::Rc::new(|a: i32, b: i32| i32::wrapping_add(a, b))
+            as std::rc::Rc<dyn Fn(i32, i32) -> i32>;
+        let greeting = std::rc::Rc::new(|| "hello".to_string()) as std::rc::Rc<dyn Fn() -> String>;
+        let isEmpty =
+            std::rc::Rc::new(|s: String| s.is_empty()) as std::rc::Rc<dyn Fn(String) -> bool>;
+        println!("{} {}", square(4), add(2, 3));
+        println!(
+            "{} {} {}",
+            greeting(),
+            isEmpty("".to_string()),
+            isEmpty("a".to_string())
+        );
+
+        // Captured variables are copied into the closure
+        let prefix = "value: ".to_string();
+        let offset = 10;
+        let describe = std::rc::Rc::new({
+            let prefix = prefix.clone();
+            move |value: i32| format!("{prefix}{}", i32::wrapping_add(value, offset))
+        }) as std::rc::Rc<dyn Fn(i32) -> String>;
+        println!("{}", describe(5));
+
+        // Block bodies
+        let max = std::rc::Rc::new(|a: i32, b: i32| -> i32 {
+            if a > b {
+                return a;
+            }
+            return b;
+        }) as std::rc::Rc<dyn Fn(i32, i32) -> i32>;
+        println!("{}", max(3, 7));
+
+        let printer = std::rc::Rc::new(|arg: String| {
+            println!("{arg}");
+        }) as std::rc::Rc<dyn Fn(String)>;
+        printer("printed".to_string());
+        let runnable = std::rc::Rc::new(|| {
+            println!("run");
+        }) as std::rc::Rc<dyn Fn()>;
+        runnable();
+
+        // Method references
+        let doubler =
+            std::rc::Rc::new(|arg1: i32| Main::twice(arg1)) as std::rc::Rc<dyn Fn(i32) -> i32>;
+        let length = std::rc::Rc::new(|arg2: String| arg2.encode_utf16().count() as i32)
+            as std::rc::Rc<dyn Fn(String) -> i32>;
+        let upper = std::rc::Rc::new(|arg3: String| arg3.to_uppercase())
+            as std::rc::Rc<dyn Fn(String) -> String>;
+        println!(
+            "{} {} {}",
+            Main::applyTwice(&*doubler, 3),
+            length("four".to_string()),
+            upper("abc".to_string())
+        );
+        println!("{}", Main::applyTwice(|x: i32| i32::wrapping_add(x, 1), 5));
+
+        let create =
+            std::rc::Rc::new(|arg4: i32| Counter::new(arg4)) as std::rc::Rc<dyn Fn(i32) -> Counter>;
+        let counter = create(4);
+        println!("{} {}", (counter.adder())(1), (counter.getter())());
+
+        // A lambda which changes what it captured
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(vec![0]));
+        let next = std::rc::Rc::new({
+            let calls = calls.clone();
+            move || {
+                {
+                    let value1 = i32::wrapping_add(calls.borrow()[0], 1);
+                    calls.borrow_mut()[0] = value1;
+                };
+                calls.borrow()[0]
+            }
+        }) as std::rc::Rc<dyn Fn() -> i32>;
+        println!("{} {}", next(), next());
+        // The captured array is the same one, so the changes are visible outside
+        let tick = std::rc::Rc::new({
+            let calls = calls.clone();
+            move || {
+                {
+                    let value2 = i32::wrapping_add(calls.borrow()[0], 1);
+                    calls.borrow_mut()[0] = value2;
+                };
+            }
+        }) as std::rc::Rc<dyn Fn()>;
+        tick();
+        tick();
+        println!("{}", calls.borrow()[0]);
+
+        let exclaim = std::rc::Rc::new(|value: String| format!("{value}{value}"))
+            as std::rc::Rc<dyn Transformer>;
+        println!(
+            "{} {}",
+            Main::shout(exclaim.clone(), "hey".to_string()),
+            Main::shout(
+                std::rc::Rc::new(|arg5: String| arg5.to_lowercase())
+                    as std::rc::Rc<dyn Transformer>,
+                "LOW".to_string()
+            )
+        );
+    }
+}
+
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,14 @@
+class LexerErrors {
+    public static void main(String[] args) {
+        int a = 1 # 2;
+        String s = "bad \q escape";
+        char empty = '';
+        char two = 'ab';
+        int hex = 0x;
+        int octal = 09;
+        String broken = "no end;
+        String block = """text""";
+        char c = '\u12';
+    }
+}
+/* never closed
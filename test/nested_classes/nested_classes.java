@@ -0,0 +1,70 @@
+public class NestedClasses {
+    // Has the same simple name as the top-level class Inner
+    static class Inner {
+        int value = 1;
+
+        int get() {
+            return value;
+        }
+    }
+
+    static class Counter {
+        static int created;
+
+        static {
+            created = 100;
+        }
+
+        Counter() {
+            created++;
+        }
+
+        static int created() {
+            return created;
+        }
+    }
+
+    enum Color {
+        RED,
+        GREEN
+    }
+
+    static Inner make() {
+        return new Inner();
+    }
+
+    public static void main(String[] args) {
+        Inner a = make();
+        NestedClasses.Inner b = new NestedClasses.Inner();
+        Other.Inner c = new Other.Inner();
+        System.out.println(a.get() + b.get() + c.get() + Other.Inner.twice(3));
+        new Counter();
+        new Counter();
+        System.out.println(Counter.created());
+        Color color = Color.GREEN;
+        System.out.println(color);
+        System.out.println(new Inner().get() + new Other().get());
+    }
+}
+
+class Inner {
+    int get() {
+        return 1000;
+    }
+}
+
+class Other {
+    static class Inner {
+        int get() {
+            return 10;
+        }
+
+        static int twice(int x) {
+            return 2 * x;
+        }
+    }
+
+    int get() {
+        return new Inner().get();
+    }
+}
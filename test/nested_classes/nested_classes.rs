@@ -0,0 +1,211 @@
+#[derive(Clone, Default)]
+pub struct NestedClasses {}
+
+impl NestedClasses {
+    fn make() -> NestedClassesInner {
+        return NestedClassesInner::new();
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let a = NestedClasses::make();
+        let b = NestedClassesInner::new();
+        let mut c = OtherInner::new();
+        println!(
+            "{}",
+            i32::wrapping_add(
+                i32::wrapping_add(i32::wrapping_add(a.get(), b.get()), c.get()),
+                OtherInner::twice(3)
+            )
+        );
+        NestedClassesCounter::new();
+        NestedClassesCounter::new();
+        println!("{}", NestedClassesCounter::created());
+        let color = NestedClassesColor::GREEN;
+        println!("{color}");
+        println!(
+            "{}",
+            i32::wrapping_add(NestedClassesInner::new().get(), Other::new().get())
+        );
+    }
+}
+
+// Has the same simple name as the top-level class Inner
+#[derive(Clone, Default)]
+struct NestedClassesInner(std::rc::Rc<NestedClassesInnerFields>);
+
+#[derive(Default)]
+struct NestedClassesInnerFields {
+    value: std::cell::Cell<i32>,
+}
+
+impl std::ops::Deref for NestedClassesInner {
+    type Target = NestedClassesInnerFields;
+
+    fn deref(&self) -> &NestedClassesInnerFields {
+        &self.0
+    }
+}
+
+impl NestedClassesInner {
+    pub fn new() -> NestedClassesInner {
+        let this = Self(std::rc::Rc::new(NestedClassesInnerFields {
+            value: std::cell::Cell::new(1),
+        }));
+        this
+    }
+
+    fn get(&self) -> i32 {
+        return self.value.get();
+    }
+}
+
+impl std::fmt::Display for NestedClassesInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Inner@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct NestedClassesCounter {}
+
+impl NestedClassesCounter {
+    fn new() -> NestedClassesCounter {
+        let this = Self {};
+        NESTED_CLASSES_COUNTER_CREATED
+            .with(|cell| *cell)
+            .set(i32::wrapping_add(
+                NESTED_CLASSES_COUNTER_CREATED.with(|cell| *cell).get(),
+                1,
+            ));
+        this
+    }
+
+    fn created() -> i32 {
+        return NESTED_CLASSES_COUNTER_CREATED.with(|cell| *cell).get();
+    }
+    pub fn static_init() {
+        NESTED_CLASSES_COUNTER_CREATED.with(|cell| *cell).set(100);
+    }
+}
+
+impl std::fmt::Display for NestedClassesCounter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Counter@{:p}", self)
+    }
+}
+
+thread_local! {
+static NESTED_CLASSES_COUNTER_CREATED: &'static std::cell::Cell<i32> = Box::leak(Box::new(std::cell::Cell::new(0)));
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum NestedClassesColor {
+    #[default]
+    RED,
+    GREEN,
+}
+
+impl NestedClassesColor {
+    pub fn values() -> std::rc::Rc<std::cell::RefCell<Vec<NestedClassesColor>>> {
+        std::rc::Rc::new(std::cell::RefCell::new(vec![Self::RED, Self::GREEN]))
+    }
+    pub fn valueOf(name: String) -> NestedClassesColor {
+        match name.as_str() {
+            "RED" => Self::RED,
+            "GREEN" => Self::GREEN,
+            _ => panic!("No enum constant Color.{}", name),
+        }
+    }
+    pub fn ordinal(&self) -> i32 {
+        *self as i32
+    }
+    pub fn name(&self) -> String {
+        match self {
+            Self::RED => "RED",
+            Self::GREEN => "GREEN",
+        }
+        .to_string()
+    }
+    pub fn compareTo(&self, other: NestedClassesColor) -> i32 {
+        self.ordinal() - other.ordinal()
+    }
+}
+
+impl std::fmt::Display for NestedClassesColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Clone, Default)]
+struct Inner {}
+
+impl Inner {
+    pub fn new() -> Inner {
+        let this = Self {};
+        this
+    }
+    fn get(&self) -> i32 {
+        return 1000;
+    }
+}
+
+impl std::fmt::Display for Inner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Inner@{:p}", self)
+    }
+}
+
+#[derive(Clone, Default)]
+struct Other {}
+
+impl Other {
+    pub fn new() -> Other {
+        let this = Self {};
+        this
+    }
+
+    fn get(&self) -> i32 {
+        return OtherInner::new().get();
+    }
+}
+
+impl std::fmt::Display for Other {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Other@{:p}", self)
+    }
+}
+
+#[derive(Clone, Default)]
+struct OtherInner {}
+
+impl OtherInner {
+    pub fn new() -> OtherInner {
+        let this = Self {};
+        this
+    }
+    fn get(&self) -> i32 {
+        return 10;
+    }
+
+    fn twice(x: i32) -> i32 {
+        return i32::wrapping_mul(2, x);
+    }
+}
+
+impl std::fmt::Display for OtherInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Inner@{:p}", self)
+    }
+}
+
+fn main() {
+    NestedClassesCounter::static_init();
+    NestedClasses::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,130 @@
+// References which can be null become an Option, null is None
+class Node {
+    int value;
+    // A field without a value starts as null
+    Node next;
+
+    Node(int value) {
+        this.value = value;
+    }
+}
+
+class LinkedList {
+    private Node head;
+    private int size;
+
+    void push(int value) {
+        Node node = new Node(value);
+        node.next = head;
+        head = node;
+        size++;
+    }
+
+    void append(int value) {
+        Node node = new Node(value);
+        if (head == null) {
+            head = node;
+        } else {
+            Node last = head;
+            while (last.next != null) {
+                last = last.next;
+            }
+            last.next = node;
+        }
+        size++;
+    }
+
+    // null is returned when no node has the value
+    Node find(int value) {
+        Node node = head;
+        while (node != null) {
+            if (node.value == value) {
+                return node;
+            }
+            node = node.next;
+        }
+        return null;
+    }
+
+    Node first() {
+        return head;
+    }
+
+    int sum() {
+        int sum = 0;
+        for (Node node = head; node != null; node = node.next) {
+            sum += node.value;
+        }
+        return sum;
+    }
+
+    int size() {
+        return size;
+    }
+}
+
+class Tree {
+    private final int value;
+    private final Tree left;
+    private final Tree right;
+
+    // Arguments which are compared with null can be null
+    Tree(int value, Tree left, Tree right) {
+        this.value = value;
+        this.left = left;
+        this.right = right;
+    }
+
+    int sum() {
+        int sum = value;
+        if (left != null) {
+            sum += left.sum();
+        }
+        if (right != null) {
+            sum += right.sum();
+        }
+        return sum;
+    }
+
+    int depth() {
+        int left = this.left == null ? 0 : this.left.depth();
+        int right = null == this.right ? 0 : this.right.depth();
+        return 1 + (left > right ? left : right);
+    }
+}
+
+public class Main {
+    static String tag;
+
+    static String describe(String name) {
+        return name == null ? "nobody" : "name " + name;
+    }
+
+    public static void main(String[] args) {
+        LinkedList list = new LinkedList();
+        System.out.println(list.first() == null);
+        list.push(2);
+        list.push(1);
+        list.append(3);
+        System.out.println(list.size() + " " + list.sum() + " " + list.first().value);
+        Node found = list.find(3);
+        System.out.println(found != null);
+        System.out.println(found == list.first() ? "first" : "not first");
+        System.out.println(found.next == null);
+        System.out.println(list.find(4) == null);
+        Tree tree = new Tree(1, new Tree(2, null, null), new Tree(3, new Tree(4, null, null), null));
+        System.out.println(tree.sum() + " " + tree.depth());
+        System.out.println(tag);
+        System.out.println("tag=" + tag);
+        tag = "set";
+        System.out.println("tag=" + tag);
+        String t = null;
+        System.out.println("t=" + t);
+        String z = null;
+        System.out.println(z == null ? "z is null" : z);
+        z = "zed";
+        z += "!";
+        System.out.println(z == null ? "z is null" : z);
+        System.out.println(describe(null) + ", " + describe("Ada"));
+    }
+}
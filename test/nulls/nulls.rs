@@ -0,0 +1,328 @@
+// References which can be null become an Option, null is None
+#[derive(Clone, Default)]
+struct Node(std::rc::Rc<NodeFields>);
+
+#[derive(Default)]
+struct NodeFields {
+    value: std::cell::Cell<i32>,
+    // A field without a value starts as null
+    next: std::cell::RefCell<Option<Node>>,
+}
+
+impl std::ops::Deref for Node {
+    type Target = NodeFields;
+
+    fn deref(&self) -> &NodeFields {
+        &self.0
+    }
+}
+
+impl Node {
+    fn new(value: i32) -> Node {
+        let this = Self(std::rc::Rc::new(NodeFields {
+            value: std::cell::Cell::new(0),
+            next: std::cell::RefCell::new(None),
+        }));
+        this.value.set(value);
+        this
+    }
+}
+
+impl std::fmt::Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Node@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct LinkedList(std::rc::Rc<LinkedListFields>);
+
+#[derive(Default)]
+struct LinkedListFields {
+    head: std::cell::RefCell<Option<Node>>,
+    size: std::cell::Cell<i32>,
+}
+
+impl std::ops::Deref for LinkedList {
+    type Target = LinkedListFields;
+
+    fn deref(&self) -> &LinkedListFields {
+        &self.0
+    }
+}
+
+impl LinkedList {
+    pub fn new() -> LinkedList {
+        let this = Self(std::rc::Rc::new(LinkedListFields {
+            head: std::cell::RefCell::new(None),
+            size: std::cell::Cell::new(0),
+        }));
+        this
+    }
+
+    fn push(&self, value: i32) {
+        let node = Node::new(value);
+        {
+            let value1 = self.head.borrow().clone();
+            *node.next.borrow_mut() = value1;
+        };
+        *self.head.borrow_mut() = Some(node.clone());
+        self.size.set(i32::wrapping_add(self.size.get(), 1));
+    }
+
+    fn append(&self, value: i32) {
+        let node = Node::new(value);
+        if self.head.borrow().is_none() {
+            *self.head.borrow_mut() = Some(node.clone());
+        } else {
+            let mut last: Option<Node> = self.head.borrow().clone();
+            while last.clone().unwrap().next.borrow().is_some() {
+                last = last.clone().unwrap().next.borrow().clone();
+            }
+            *last.clone().unwrap().next.borrow_mut() = Some(node.clone());
+        }
+        self.size.set(i32::wrapping_add(self.size.get(), 1));
+    }
+
+    // null is returned when no node has the value
+    fn find(&self, value: i32) -> Option<Node> {
+        let mut node: Option<Node> = self.head.borrow().clone();
+        while node.is_some() {
+            if node.clone().unwrap().value.get() == value {
+                return node.clone();
+            }
+            node = node.clone().unwrap().next.borrow().clone();
+        }
+        return None;
+    }
+
+    fn first(&self) -> Option<Node> {
+        return self.head.borrow().clone();
+    }
+
+    fn sum(&self) -> i32 {
+        let mut sum = 0;
+        {
+            let mut node: Option<Node> = self.head.borrow().clone();
+            while node.is_some() {
+                sum = i32::wrapping_add(sum, node.clone().unwrap().value.get());
+                node = node.clone().unwrap().next.borrow().clone();
+            }
+        }
+        return sum;
+    }
+
+    fn size(&self) -> i32 {
+        return self.size.get();
+    }
+}
+
+impl std::fmt::Display for LinkedList {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "LinkedList@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+struct Tree(std::rc::Rc<TreeFields>);
+
+#[derive(Default)]
+struct TreeFields {
+    value: std::cell::Cell<i32>,
+    left: std::cell::RefCell<Option<Tree>>,
+    right: std::cell::RefCell<Option<Tree>>,
+}
+
+impl std::ops::Deref for Tree {
+    type Target = TreeFields;
+
+    fn deref(&self) -> &TreeFields {
+        &self.0
+    }
+}
+
+impl Tree {
+    // Arguments which are compared with null can be null
+    fn new(value: i32, left: Option<Tree>, right: Option<Tree>) -> Tree {
+        let this = Self(std::rc::Rc::new(TreeFields {
+            value: std::cell::Cell::new(0),
+            left: std::cell::RefCell::new(None),
+            right: std::cell::RefCell::new(None),
+        }));
+        this.value.set(value);
+        *this.left.borrow_mut() = left.clone();
+        *this.right.borrow_mut() = right.clone();
+        this
+    }
+
+    fn sum(&self) -> i32 {
+        let mut sum = self.value.get();
+        if self.left.borrow().is_some() {
+            sum = i32::wrapping_add(sum, self.left.borrow().clone().unwrap().sum());
+        }
+        if self.right.borrow().is_some() {
+            sum = i32::wrapping_add(sum, self.right.borrow().clone().unwrap().sum());
+        }
+        return sum;
+    }
+
+    fn depth(&self) -> i32 {
+        let left = if self.left.borrow().is_none() {
+            0
+        } else {
+            self.left.borrow().clone().unwrap().depth()
+        };
+        let right = if self.right.borrow().is_none() {
+            0
+        } else {
+            self.right.borrow().clone().unwrap().depth()
+        };
+        return i32::wrapping_add(1, if left > right { left } else { right });
+    }
+}
+
+impl std::fmt::Display for Tree {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Tree@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Main {}
+
+impl Main {
+    fn describe(name: Option<String>) -> String {
+        return if name.is_none() {
+            "nobody".to_string()
+        } else {
+            format!(
+                "name {}",
+                name.as_ref()
+                    .map_or("null".to_string(), |value| value.to_string())
+            )
+        };
+    }
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let list = LinkedList::new();
+        println!("{}", list.first().is_none());
+        list.push(2);
+        list.push(1);
+        list.append(3);
+        println!(
+            "{} {} {}",
+            list.size(),
+            list.sum(),
+            list.first().unwrap().value.get()
+        );
+        let found: Option<Node> = list.find(3);
+        println!("{}", found.is_some());
+        println!(
+            "{}",
+            if found
+                .as_ref()
+                .map(|value| std::rc::Rc::as_ptr(&value.0).cast::<()>())
+                == list
+                    .first()
+                    .as_ref()
+                    .map(|value| std::rc::Rc::as_ptr(&value.0).cast::<()>())
+            {
+                "first".to_string()
+            } else {
+                "not first".to_string()
+            }
+        );
+        println!("{}", found.clone().unwrap().next.borrow().is_none());
+        println!("{}", list.find(4).is_none());
+        let tree = Tree::new(
+            1,
+            Some(Tree::new(2, None, None)),
+            Some(Tree::new(3, Some(Tree::new(4, None, None)), None)),
+        );
+        println!("{} {}", tree.sum(), tree.depth());
+        println!(
+            "{}",
+            MAIN_TAG
+                .with(|cell| *cell)
+                .borrow()
+                .as_ref()
+                .map_or("null".to_string(), |value| value.to_string())
+        );
+        println!(
+            "tag={}",
+            MAIN_TAG
+                .with(|cell| *cell)
+                .borrow()
+                .as_ref()
+                .map_or("null".to_string(), |value| value.to_string())
+        );
+        *MAIN_TAG.with(|cell| *cell).borrow_mut() = Some("set".to_string());
+        println!(
+            "tag={}",
+            MAIN_TAG
+                .with(|cell| *cell)
+                .borrow()
+                .as_ref()
+                .map_or("null".to_string(), |value| value.to_string())
+        );
+        let t: Option<String> = None;
+        println!(
+            "t={}",
+            t.as_ref()
+                .map_or("null".to_string(), |value| value.to_string())
+        );
+        let mut z: Option<String> = None;
+        println!(
+            "{}",
+            (if z.is_none() {
+                Some("z is null".to_string())
+            } else {
+                z.clone()
+            })
+            .as_ref()
+            .map_or("null".to_string(), |value| value.to_string())
+        );
+        z = Some("zed".to_string());
+        z = Some(format!(
+            "{}!",
+            z.as_ref()
+                .map_or("null".to_string(), |value| value.to_string())
+        ));
+        println!(
+            "{}",
+            (if z.is_none() {
+                Some("z is null".to_string())
+            } else {
+                z.clone()
+            })
+            .as_ref()
+            .map_or("null".to_string(), |value| value.to_string())
+        );
+        println!(
+            "{}, {}",
+            Main::describe(None),
+            Main::describe(Some("Ada".to_string()))
+        );
+    }
+}
+
+thread_local! {
+static MAIN_TAG: &'static std::cell::RefCell<Option<String>> = Box::leak(Box::new(std::cell::RefCell::new(None)));
+}
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,12 @@
+class NumberLiterals {
+    public static void main(String[] args) {
+        int a = 0x1F + 0b1010 + 017 + 1_000_000;
+        long b = 10L + 0xFFFF_FFFF_FFFFL;
+        float c = 1.5f + .5f + 1e3f;
+        double d = 1.5 + 2.0e-3 + 1d + 0x1.8p1 + 3.;
+        int e = 0xFFFFFFFF;
+        int f = -2147483648;
+        long g = -9223372036854775808L;
+        System.out.println(a);
+    }
+}
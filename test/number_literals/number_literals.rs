@@ -0,0 +1,24 @@
+#[derive(Clone, Default)]
+struct NumberLiterals {}
+
+impl NumberLiterals {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let a = i32::wrapping_add(
+            i32::wrapping_add(i32::wrapping_add(0x1F, 0b1010), 0o17),
+            1000000,
+        );
+        let b = i64::wrapping_add(10i64, 0xFFFFFFFFFFFFi64);
+        let c = 1.5f32 + 0.5f32 + 1.0e3f32;
+        let d = 1.5 + 2.0e-3 + 1.0 + 3.0 + 3.0;
+        let e = (0xFFFFFFFFu32 as i32);
+        let f = -2147483648;
+        let g = -9223372036854775808i64;
+        println!("{a}");
+    }
+}
+
+fn main() {
+    NumberLiterals::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
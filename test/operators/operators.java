@@ -0,0 +1,40 @@
+class Operators {
+    public static void main(String[] args) {
+        int x = 10;
+        int y = 3;
+        // x-- - y
+        int a = x---y;
+        System.out.println(a + " " + x);
+        // x++ + y
+        int b = x+++y;
+        System.out.println(b + " " + x);
+        int c = -8;
+        c >>>= 28;
+        System.out.println(c);
+        int d = -16;
+        d>>=2;
+        System.out.println(d);
+        int e = 1;
+        e<<=4;
+        System.out.println(e);
+        System.out.println(-1>>>28);
+        System.out.println(x>=y&&y<=x);
+        System.out.println(x!=y||x==y);
+        int f = 5;
+        f^=3;
+        f|=8;
+        f&=~1;
+        f%=7;
+        System.out.println(f);
+        boolean g = !(x<y);
+        System.out.println(g?1:2);
+        // int and long overflow like in Java, shifts only use the lowest bits of the distance
+        int max = 2147483647;
+        max = max + 1;
+        int min = max;
+        long big = 9223372036854775807L;
+        big++;
+        System.out.println(max + " " + -min + " " + big + " " + max * 2);
+        System.out.println((1 << 33) + " " + (1L << 65) + " " + (-8 >> 35) + " " + (-8 >>> 33));
+    }
+}
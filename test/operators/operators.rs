@@ -0,0 +1,73 @@
+#[derive(Clone, Default)]
+struct Operators {}
+
+impl Operators {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let mut x = 10;
+        let y = 3;
+        // x-- - y
+        let a = i32::wrapping_sub(
+            {
+                let old = x;
+                x = i32::wrapping_sub(x, 1);
+                old
+            },
+            y,
+        );
+        println!("{a} {x}");
+        // x++ + y
+        let b = i32::wrapping_add(
+            {
+                let old = x;
+                x = i32::wrapping_add(x, 1);
+                old
+            },
+            y,
+        );
+        println!("{b} {x}");
+        let mut c = -8;
+        c = u32::wrapping_shr(c as u32, 28) as i32;
+        println!("{c}");
+        let mut d = -16;
+        d = i32::wrapping_shr(d, 2);
+        println!("{d}");
+        let mut e = 1;
+        e = i32::wrapping_shl(e, 4);
+        println!("{e}");
+        println!("{}", u32::wrapping_shr(-1i32 as u32, 28) as i32);
+        println!("{}", x >= y && y <= x);
+        println!("{}", x != y || x == y);
+        let mut f = 5;
+        f ^= 3;
+        f |= 8;
+        f &= !1;
+        f %= 7;
+        println!("{f}");
+        let g = !(x < y);
+        println!("{}", if g { 1 } else { 2 });
+        // int and long overflow like in Java, shifts only use the lowest bits of the distance
+        let mut max = 2147483647;
+        max = i32::wrapping_add(max, 1);
+        let min = max;
+        let mut big = 9223372036854775807i64;
+        big = i64::wrapping_add(big, 1i64);
+        println!(
+            "{max} {} {big} {}",
+            i32::wrapping_neg(min),
+            i32::wrapping_mul(max, 2)
+        );
+        println!(
+            "{} {} {} {}",
+            i32::wrapping_shl(1, 33),
+            i64::wrapping_shl(1i64, 65),
+            i32::wrapping_shr(-8, 35),
+            u32::wrapping_shr(-8i32 as u32, 33) as i32
+        );
+    }
+}
+
+fn main() {
+    Operators::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,120 @@
+public class Patterns {
+    public static void main(String[] args) {
+        Object[] values = {new Point(3, 4), "text", 42, new Point(-1, 2), 7};
+
+        for (Object value : values) {
+            System.out.println(describe(value));
+        }
+
+        int total = 0;
+        for (Object value : values) {
+            // only positive points count
+            if (value instanceof Point p && p.x > 0) {
+                total += p.x + p.y;
+            } else if (value instanceof Integer i) {
+                total += i;
+            } else {
+                total -= 1;
+            }
+        }
+        System.out.println(total);
+
+        for (Object value : values) {
+            if (!(value instanceof String s)) {
+                continue;
+            }
+            System.out.println("found " + s);
+        }
+
+        // Casts check the type like instanceof
+        String text = (String) values[1];
+        int number = (int) values[2];
+        Point point = (Point) values[0];
+        Integer boxed = (Integer) values[4];
+        System.out.println(text + " " + (number + boxed) + " " + point.x);
+
+        System.out.println(sign(5) + " " + sign(-5) + " " + sign("five"));
+        System.out.println(isLarge("short") + " " + isLarge(1234));
+        System.out.println(area(new Point(2, 3)));
+
+        // Record patterns read the components through their accessors
+        Object[] shapes = {new Rect(2, 2), new Rect(2, 3), new Labeled("door", new Rect(1, 2)), new Labeled("box", new Rect(3, 1))};
+        for (Object shape : shapes) {
+            System.out.println(shape(shape));
+        }
+        double wide = 0;
+        for (Object shape : shapes) {
+            if (shape instanceof Rect(double w, double h) && w < h) {
+                wide += h - w;
+            }
+        }
+        System.out.println(wide + " " + (shapes[3] instanceof Labeled(String name, Rect(var w, var h)) && w > h));
+    }
+
+    static String shape(Object shape) {
+        return switch (shape) {
+            case Rect(double w, var h) when w == h -> "square " + w;
+            case Rect(var w, var h) -> "rect " + w * h;
+            case Labeled(String name, Rect(var w, var h)) when w < h -> name + " " + w + "x" + h;
+            case Labeled(var label, Rect rect) -> label + " " + rect.h();
+            default -> "unknown";
+        };
+    }
+
+    static String describe(Object value) {
+        switch (value) {
+            case Point p when p.x < 0 -> {
+                return "point left of the axis";
+            }
+            case Point p -> {
+                return "point " + p.x + ", " + p.y;
+            }
+            case String s -> {
+                return "string " + s;
+            }
+            default -> {
+                return "something else";
+            }
+        }
+    }
+
+    static int sign(Object value) {
+        return switch (value) {
+            case Integer i when i > 0 -> 1;
+            case Integer i when i < 0 -> {
+                int negative = -1;
+                yield negative;
+            }
+            case Integer i -> 0;
+            default -> 2;
+        };
+    }
+
+    static boolean isLarge(Object value) {
+        boolean result = value instanceof Integer i && i > 100;
+        return result;
+    }
+
+    static int area(Object shape) {
+        if (!(shape instanceof Point p)) {
+            return 0;
+        }
+        return p.x * p.y;
+    }
+}
+
+record Rect(double w, double h) {
+}
+
+record Labeled(String label, Rect rect) {
+}
+
+class Point {
+    int x;
+    int y;
+
+    Point(int x, int y) {
+        this.x = x;
+        this.y = y;
+    }
+}
@@ -0,0 +1,440 @@
+#[derive(Clone, Default)]
+pub struct Patterns {}
+
+impl Patterns {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let values = std::rc::Rc::new(std::cell::RefCell::new(vec![
+            std::rc::Rc::new(Point::new(3, 4)) as std::rc::Rc<dyn std::any::Any>,
+            std::rc::Rc::new("text".to_string()) as std::rc::Rc<dyn std::any::Any>,
+            std::rc::Rc::new(42) as std::rc::Rc<dyn std::any::Any>,
+            std::rc::Rc::new(Point::new(-1, 2)) as std::rc::Rc<dyn std::any::Any>,
+            std::rc::Rc::new(7) as std::rc::Rc<dyn std::any::Any>,
+        ]));
+
+        for value in {
+            let array = values.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            println!("{}", Patterns::describe(value.clone()));
+        }
+
+        let mut total = 0;
+        for value in {
+            let array = values.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            // only positive points count
+            'if3: {
+                if let Some(p) = value.downcast_ref::<Point>() {
+                    if p.x.get() > 0 {
+                        total = i32::wrapping_add(total, i32::wrapping_add(p.x.get(), p.y.get()));
+                        break 'if3;
+                    }
+                }
+                if let Some(i) = value.downcast_ref::<i32>().copied() {
+                    total = i32::wrapping_add(total, i);
+                } else {
+                    total = i32::wrapping_sub(total, 1);
+                }
+            }
+        }
+        println!("{total}");
+
+        for value in {
+            let array = values.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            let Some(s) = value.downcast_ref::<String>() else {
+                continue;
+            };
+            println!("found {s}");
+        }
+
+        // Casts check the type like instanceof
+        let text = values.borrow()[1]
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| panic!("java.lang.ClassCastException: the value is no String"));
+        let number = values.borrow()[2]
+            .downcast_ref::<i32>()
+            .cloned()
+            .unwrap_or_else(|| panic!("java.lang.ClassCastException: the value is no i32"));
+        let point = values.borrow()[0]
+            .downcast_ref::<Point>()
+            .cloned()
+            .unwrap_or_else(|| panic!("java.lang.ClassCastException: the value is no Point"));
+        let boxed = values.borrow()[4]
+            .downcast_ref::<i32>()
+            .cloned()
+            .unwrap_or_else(|| panic!("java.lang.ClassCastException: the value is no Integer"));
+        println!("{text} {} {}", number + boxed, point.x.get());
+
+        println!(
+            "{} {} {}",
+            Patterns::sign(std::rc::Rc::new(5) as std::rc::Rc<dyn std::any::Any>),
+            Patterns::sign(std::rc::Rc::new(-5) as std::rc::Rc<dyn std::any::Any>),
+            Patterns::sign(std::rc::Rc::new("five".to_string()) as std::rc::Rc<dyn std::any::Any>)
+        );
+        println!(
+            "{} {}",
+            Patterns::isLarge(
+                std::rc::Rc::new("short".to_string()) as std::rc::Rc<dyn std::any::Any>
+            ),
+            Patterns::isLarge(std::rc::Rc::new(1234) as std::rc::Rc<dyn std::any::Any>)
+        );
+        println!(
+            "{}",
+            Patterns::area(std::rc::Rc::new(Point::new(2, 3)) as std::rc::Rc<dyn std::any::Any>)
+        );
+
+        // Record patterns read the components through their accessors
+        let shapes = std::rc::Rc::new(std::cell::RefCell::new(vec![
+            std::rc::Rc::new(Rect::new(2i32 as f64, 2i32 as f64)) as std::rc::Rc<dyn std::any::Any>,
+            std::rc::Rc::new(Rect::new(2i32 as f64, 3i32 as f64)) as std::rc::Rc<dyn std::any::Any>,
+            std::rc::Rc::new(Labeled::new(
+                "door".to_string(),
+                Rect::new(1i32 as f64, 2i32 as f64),
+            )) as std::rc::Rc<dyn std::any::Any>,
+            std::rc::Rc::new(Labeled::new(
+                "box".to_string(),
+                Rect::new(3i32 as f64, 1i32 as f64),
+            )) as std::rc::Rc<dyn std::any::Any>,
+        ]));
+        for shape in {
+            let array = shapes.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            println!("{}", Patterns::shape(shape.clone()));
+        }
+        let mut wide = 0i32 as f64;
+        for shape in {
+            let array = shapes.clone();
+            let length = array.borrow().len();
+            (0..length).map(move |i| array.borrow()[i].clone())
+        } {
+            if let Some((w, h)) = shape
+                .downcast_ref::<Rect>()
+                .map(|record| (record.w(), record.h()))
+            {
+                if w < h {
+                    wide += h - w;
+                }
+            }
+        }
+        println!(
+            "{} {}",
+            double_to_string(wide),
+            shapes.borrow()[3]
+                .downcast_ref::<Labeled>()
+                .map(|record| (record.label(), {
+                    let record = record.rect();
+                    (record.w(), record.h())
+                }))
+                .is_some_and(|(name, (w, h))| w > h)
+        );
+    }
+
+    fn shape(shape: std::rc::Rc<dyn std::any::Any>) -> String {
+        return 'switch1: {
+            if let Some((w, h)) = shape
+                .downcast_ref::<Rect>()
+                .map(|record| (record.w(), record.h()))
+            {
+                if w == h {
+                    break 'switch1 format!("square {}", double_to_string(w));
+                }
+            }
+            if let Some((w, h)) = shape
+                .downcast_ref::<Rect>()
+                .map(|record| (record.w(), record.h()))
+            {
+                break 'switch1 format!("rect {}", double_to_string(w * h));
+            }
+            if let Some((name, (w, h))) = shape.downcast_ref::<Labeled>().map(|record| {
+                (record.label(), {
+                    let record = record.rect();
+                    (record.w(), record.h())
+                })
+            }) {
+                if w < h {
+                    break 'switch1 format!(
+                        "{name} {}x{}",
+                        double_to_string(w),
+                        double_to_string(h)
+                    );
+                }
+            }
+            if let Some((label, rect)) = shape
+                .downcast_ref::<Labeled>()
+                .map(|record| (record.label(), record.rect()))
+            {
+                break 'switch1 format!("{label} {}", double_to_string(rect.h()));
+            }
+            "unknown".to_string()
+        };
+    }
+
+    fn describe(value: std::rc::Rc<dyn std::any::Any>) -> String {
+        'switch1: {
+            if let Some(p) = value.downcast_ref::<Point>() {
+                if p.x.get() < 0 {
+                    return "point left of the axis".to_string();
+                }
+            }
+            if let Some(p) = value.downcast_ref::<Point>() {
+                return format!("point {}, {}", p.x.get(), p.y.get());
+            }
+            if let Some(s) = value.downcast_ref::<String>() {
+                return format!("string {s}");
+            }
+            return "something else".to_string();
+        }
+    }
+
+    fn sign(value: std::rc::Rc<dyn std::any::Any>) -> i32 {
+        return 'switch1: {
+            if let Some(i) = value.downcast_ref::<i32>().copied() {
+                if i > 0 {
+                    break 'switch1 1;
+                }
+            }
+            if let Some(i) = value.downcast_ref::<i32>().copied() {
+                if i < 0 {
+                    let negative = -1;
+                    break 'switch1 negative;
+                }
+            }
+            if let Some(i) = value.downcast_ref::<i32>().copied() {
+                break 'switch1 0;
+            }
+            2
+        };
+    }
+
+    fn isLarge(value: std::rc::Rc<dyn std::any::Any>) -> bool {
+        let result = value
+            .downcast_ref::<i32>()
+            .copied()
+            .is_some_and(|i| i > 100);
+        return result;
+    }
+
+    fn area(shape: std::rc::Rc<dyn std::any::Any>) -> i32 {
+        let Some(p) = shape.downcast_ref::<Point>() else {
+            return 0;
+        };
+        return i32::wrapping_mul(p.x.get(), p.y.get());
+    }
+}
+
+#[derive(Clone, Default)]
+struct Rect {
+    w: f64,
+    h: f64,
+}
+
+impl Rect {
+    pub fn new(w: f64, h: f64) -> Rect {
+        Self { w, h }
+    }
+    pub fn w(&self) -> f64 {
+        self.w
+    }
+    pub fn h(&self) -> f64 {
+        self.h
+    }
+    pub fn equals(&self, other: Rect) -> bool {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32 {
+        0i32.wrapping_mul(31)
+            .wrapping_add({
+                let bits = self.w.to_bits();
+                (bits ^ (bits >> 32)) as i32
+            })
+            .wrapping_mul(31)
+            .wrapping_add({
+                let bits = self.h.to_bits();
+                (bits ^ (bits >> 32)) as i32
+            })
+    }
+    pub fn toString(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl PartialEq for Rect {
+    fn eq(&self, other: &Self) -> bool {
+        self.w.to_bits() == other.w.to_bits() && self.h.to_bits() == other.h.to_bits()
+    }
+}
+
+impl Eq for Rect {}
+
+impl std::hash::Hash for Rect {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.w.to_bits(), state);
+        std::hash::Hash::hash(&self.h.to_bits(), state);
+    }
+}
+
+impl std::fmt::Debug for Rect {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Rect[w={}, h={}]",
+            double_to_string(self.w),
+            double_to_string(self.h)
+        )
+    }
+}
+
+impl std::fmt::Display for Rect {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Default)]
+struct Labeled {
+    label: String,
+    rect: Rect,
+}
+
+impl Labeled {
+    pub fn new(label: String, rect: Rect) -> Labeled {
+        Self { label, rect }
+    }
+    pub fn label(&self) -> String {
+        self.label.clone()
+    }
+    pub fn rect(&self) -> Rect {
+        self.rect.clone()
+    }
+    pub fn equals(&self, other: Labeled) -> bool {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32 {
+        0i32.wrapping_mul(31)
+            .wrapping_add(
+                self.label
+                    .encode_utf16()
+                    .fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as i32)),
+            )
+            .wrapping_mul(31)
+            .wrapping_add(self.rect.hashCode())
+    }
+    pub fn toString(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl PartialEq for Labeled {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label && self.rect == other.rect
+    }
+}
+
+impl Eq for Labeled {}
+
+impl std::hash::Hash for Labeled {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.label, state);
+        std::hash::Hash::hash(&self.rect, state);
+    }
+}
+
+impl std::fmt::Debug for Labeled {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Labeled[label={}, rect={}]", self.label, self.rect)
+    }
+}
+
+impl std::fmt::Display for Labeled {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Default)]
+struct Point(std::rc::Rc<PointFields>);
+
+#[derive(Default)]
+struct PointFields {
+    x: std::cell::Cell<i32>,
+    y: std::cell::Cell<i32>,
+}
+
+impl std::ops::Deref for Point {
+    type Target = PointFields;
+
+    fn deref(&self) -> &PointFields {
+        &self.0
+    }
+}
+
+impl Point {
+    fn new(x: i32, y: i32) -> Point {
+        let this = Self(std::rc::Rc::new(PointFields {
+            x: std::cell::Cell::new(0),
+            y: std::cell::Cell::new(0),
+        }));
+        this.x.set(x);
+        this.y.set(y);
+        this
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Point@{:x}",
+            std::rc::Rc::as_ptr(&self.0) as *const () as usize
+        )
+    }
+}
+
+fn double_to_string<T: std::fmt::LowerExp>(value: T) -> String {
+    let scientific = format!("{:e}", value);
+    let (sign, scientific) = match scientific.strip_prefix('-') {
+        Some(scientific) => ("-", scientific),
+        None => ("", scientific.as_str()),
+    };
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return match scientific {
+            "inf" => format!("{}Infinity", sign),
+            _ => "NaN".to_string(),
+        };
+    };
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap();
+    if digits == "0" {
+        return format!("{}0.0", sign);
+    }
+    if !(-3..7).contains(&exponent) {
+        let fraction = if digits.len() > 1 { &digits[1..] } else { "0" };
+        return format!("{}{}.{}E{}", sign, &digits[..1], fraction, exponent);
+    }
+    if exponent < 0 {
+        return format!(
+            "{}0.{}{}",
+            sign,
+            "0".repeat((-exponent - 1) as usize),
+            digits
+        );
+    }
+    let point = exponent as usize + 1;
+    let digits = format!("{:0<1$}", digits, point + 1);
+    format!("{}{}.{}", sign, &digits[..point], &digits[point..])
+}
+
+fn main() {
+    Patterns::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,54 @@
+// Records become structs with an accessor for every component
+record Point(int x, int y) {
+    static Point of(int value) {
+        return new Point(value, value);
+    }
+
+    int sum() {
+        return x + y;
+    }
+}
+
+interface Shape {
+    double area();
+}
+
+// The compact constructor runs before the fields are set
+record Circle(String name, double radius) implements Shape {
+    Circle {
+        if (radius < 0) {
+            radius = -radius;
+        }
+        name = "circle " + name;
+    }
+
+    public double area() {
+        return 3.0 * radius * radius;
+    }
+}
+
+record Line(Point start, Point end) {
+    public String toString() {
+        return start + " -> " + end;
+    }
+}
+
+record Flags(boolean on, char letter, long big, float part) {}
+
+class Main {
+    public static void main(String[] args) {
+        Point a = new Point(1, 2);
+        Point b = Point.of(3);
+        System.out.println(a);
+        System.out.println("b = " + b + " " + b.x() + " " + b.sum());
+        System.out.println(a.equals(new Point(1, 2)) + " " + a.equals(b));
+        Circle circle = new Circle("unit", -1.0);
+        System.out.println(circle + " " + circle.name() + " " + circle.area());
+        Shape shape = circle;
+        System.out.println(shape.area());
+        System.out.println(new Line(a, b));
+        System.out.println(a.toString() + " " + new Line(b, a).toString());
+        System.out.println(a.hashCode() + " " + new Line(a, b).hashCode() + " " + circle.hashCode());
+        System.out.println(new Flags(true, 'x', 5000000000L, 0.5f).hashCode());
+    }
+}
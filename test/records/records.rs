@@ -0,0 +1,368 @@
+// Records become structs with an accessor for every component
+#[derive(Clone, Default)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Self { x, y }
+    }
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+    pub fn equals(&self, other: Point) -> bool {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32 {
+        0i32.wrapping_mul(31)
+            .wrapping_add(self.x as i32)
+            .wrapping_mul(31)
+            .wrapping_add(self.y as i32)
+    }
+    pub fn toString(&self) -> String {
+        format!("{:?}", self)
+    }
+    fn of(value: i32) -> Point {
+        return Point::new(value, value);
+    }
+
+    fn sum(&self) -> i32 {
+        return i32::wrapping_add(self.x, self.y);
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl Eq for Point {}
+
+impl std::hash::Hash for Point {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.x, state);
+        std::hash::Hash::hash(&self.y, state);
+    }
+}
+
+impl std::fmt::Debug for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Point[x={}, y={}]", self.x, self.y)
+    }
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+// The compact constructor runs before the fields are set
+#[derive(Clone, Default)]
+struct Circle {
+    name: String,
+    radius: f64,
+}
+
+impl Circle {
+    fn new(mut name: String, mut radius: f64) -> Circle {
+        if radius < 0i32 as f64 {
+            radius = -radius;
+        }
+        name = format!("circle {name}");
+        Self { name, radius }
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+    pub fn equals(&self, other: Circle) -> bool {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32 {
+        0i32.wrapping_mul(31)
+            .wrapping_add(
+                self.name
+                    .encode_utf16()
+                    .fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as i32)),
+            )
+            .wrapping_mul(31)
+            .wrapping_add({
+                let bits = self.radius.to_bits();
+                (bits ^ (bits >> 32)) as i32
+            })
+    }
+    pub fn toString(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl PartialEq for Circle {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.radius.to_bits() == other.radius.to_bits()
+    }
+}
+
+impl Eq for Circle {}
+
+impl std::hash::Hash for Circle {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.name, state);
+        std::hash::Hash::hash(&self.radius.to_bits(), state);
+    }
+}
+
+impl std::fmt::Debug for Circle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Circle[name={}, radius={}]",
+            self.name,
+            double_to_string(self.radius)
+        )
+    }
+}
+
+impl std::fmt::Display for Circle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        return 3.0 * self.radius * self.radius;
+    }
+}
+
+#[derive(Clone, Default)]
+struct Line {
+    start: Point,
+    end: Point,
+}
+
+impl Line {
+    pub fn new(start: Point, end: Point) -> Line {
+        Self { start, end }
+    }
+    pub fn start(&self) -> Point {
+        self.start.clone()
+    }
+    pub fn end(&self) -> Point {
+        self.end.clone()
+    }
+    pub fn equals(&self, other: Line) -> bool {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32 {
+        0i32.wrapping_mul(31)
+            .wrapping_add(self.start.hashCode())
+            .wrapping_mul(31)
+            .wrapping_add(self.end.hashCode())
+    }
+    pub fn toString(&self) -> String {
+        return format!("{} -> {}", self.start, self.end);
+    }
+}
+
+impl PartialEq for Line {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.end == other.end
+    }
+}
+
+impl Eq for Line {}
+
+impl std::hash::Hash for Line {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.start, state);
+        std::hash::Hash::hash(&self.end, state);
+    }
+}
+
+impl std::fmt::Debug for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Line[start={}, end={}]", self.start, self.end)
+    }
+}
+
+impl std::fmt::Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.toString())
+    }
+}
+
+#[derive(Clone, Default)]
+struct Flags {
+    on: bool,
+    letter: char,
+    big: i64,
+    part: f32,
+}
+
+impl Flags {
+    pub fn new(on: bool, letter: char, big: i64, part: f32) -> Flags {
+        Self {
+            on,
+            letter,
+            big,
+            part,
+        }
+    }
+    pub fn on(&self) -> bool {
+        self.on
+    }
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+    pub fn big(&self) -> i64 {
+        self.big
+    }
+    pub fn part(&self) -> f32 {
+        self.part
+    }
+    pub fn equals(&self, other: Flags) -> bool {
+        *self == other
+    }
+    pub fn hashCode(&self) -> i32 {
+        0i32.wrapping_mul(31)
+            .wrapping_add(if self.on { 1231 } else { 1237 })
+            .wrapping_mul(31)
+            .wrapping_add(self.letter as i32)
+            .wrapping_mul(31)
+            .wrapping_add((self.big ^ (self.big as u64 >> 32) as i64) as i32)
+            .wrapping_mul(31)
+            .wrapping_add(self.part.to_bits() as i32)
+    }
+    pub fn toString(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl PartialEq for Flags {
+    fn eq(&self, other: &Self) -> bool {
+        self.on == other.on
+            && self.letter == other.letter
+            && self.big == other.big
+            && self.part.to_bits() == other.part.to_bits()
+    }
+}
+
+impl Eq for Flags {}
+
+impl std::hash::Hash for Flags {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.on, state);
+        std::hash::Hash::hash(&self.letter, state);
+        std::hash::Hash::hash(&self.big, state);
+        std::hash::Hash::hash(&self.part.to_bits(), state);
+    }
+}
+
+impl std::fmt::Debug for Flags {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Flags[on={}, letter={}, big={}, part={}]",
+            self.on,
+            self.letter,
+            self.big,
+            double_to_string(self.part)
+        )
+    }
+}
+
+impl std::fmt::Display for Flags {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Default)]
+struct Main {}
+
+impl Main {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        let mut a = Point::new(1, 2);
+        let mut b = Point::of(3);
+        println!("{a}");
+        println!("b = {b} {} {}", b.x(), b.sum());
+        println!("{} {}", a.equals(Point::new(1, 2)), a.equals(b.clone()));
+        let mut circle = Circle::new("unit".to_string(), -1.0);
+        println!(
+            "{circle} {} {}",
+            circle.name(),
+            double_to_string(circle.area())
+        );
+        let mut shape = std::rc::Rc::new(circle.clone()) as std::rc::Rc<dyn Shape>;
+        println!("{}", double_to_string(shape.area()));
+        println!("{}", Line::new(a.clone(), b.clone()));
+        println!(
+            "{} {}",
+            a.toString(),
+            Line::new(b.clone(), a.clone()).toString()
+        );
+        println!(
+            "{} {} {}",
+            a.hashCode(),
+            Line::new(a.clone(), b.clone()).hashCode(),
+            circle.hashCode()
+        );
+        println!(
+            "{}",
+            Flags::new(true, 'x', 5000000000i64, 0.5f32).hashCode()
+        );
+    }
+}
+
+fn double_to_string<T: std::fmt::LowerExp>(value: T) -> String {
+    let scientific = format!("{:e}", value);
+    let (sign, scientific) = match scientific.strip_prefix('-') {
+        Some(scientific) => ("-", scientific),
+        None => ("", scientific.as_str()),
+    };
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return match scientific {
+            "inf" => format!("{}Infinity", sign),
+            _ => "NaN".to_string(),
+        };
+    };
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap();
+    if digits == "0" {
+        return format!("{}0.0", sign);
+    }
+    if !(-3..7).contains(&exponent) {
+        let fraction = if digits.len() > 1 { &digits[1..] } else { "0" };
+        return format!("{}{}.{}E{}", sign, &digits[..1], fraction, exponent);
+    }
+    if exponent < 0 {
+        return format!(
+            "{}0.{}{}",
+            sign,
+            "0".repeat((-exponent - 1) as usize),
+            digits
+        );
+    }
+    let point = exponent as usize + 1;
+    let digits = format!("{:0<1$}", digits, point + 1);
+    format!("{}{}.{}", sign, &digits[..point], &digits[point..])
+}
+
+fn main() {
+    Main::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
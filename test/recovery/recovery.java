@@ -0,0 +1,31 @@
+class Recovery {
+    int count = ;
+
+    static void print(int value) {
+        System.out.println(value;
+        value++;
+        for (int i = 0; i < value; i++) {
+            break outer;
+        }
+        continue;
+    }
+
+    void broken( {
+    }
+
+    public static void main(String[] args) {
+        int a = 1 +;
+        print(a);
+    }
+
+    // The field is kept without its value, so this use is no error
+    int next() {
+        return count + 1;
+    }
+
+    // Only variables which start with a value that can be null can be null
+    void clear() {
+        String name = "name";
+        name = null;
+    }
+}
@@ -0,0 +1,8 @@
+class Spans {
+    public static void main(String[] args) {
+        char a = '\u0041'; int b = 1 # 2;
+        String s = "äöü€"; int c = 2 # 3;
+        String emoji = "😀"; int d = 3 # 4;
+        int e = 0x;
+    }
+}
@@ -0,0 +1,104 @@
+class Switch {
+    static final int WEEKEND = 7;
+
+    public static void main(String[] args) {
+        for (int day = 1; day <= 7; day++) {
+            System.out.println(name(day) + " " + kind(day));
+        }
+
+        // classic switch with fallthrough
+        for (int i = 0; i < 4; i++) {
+            int score = 0;
+            switch (i) {
+                case 0:
+                    score += 1;
+                case 1:
+                    score += 10;
+                    break;
+                case 2:
+                case 3: {
+                    if (score == 0) {
+                        score = 100;
+                        break;
+                    }
+                    score = -1;
+                }
+                default:
+                    score += 1000;
+            }
+            System.out.println(score);
+        }
+
+        char grade = 'B';
+        switch (grade) {
+            case 'A' -> System.out.println("excellent");
+            case 'B', 'C' -> {
+                System.out.println("good");
+            }
+            default -> System.out.println("unknown");
+        }
+
+        String command = "stop";
+        switch (command) {
+            case "go":
+                System.out.println("going");
+                break;
+            case "stop":
+                System.out.println("stopping");
+                break;
+        }
+
+        int total = 0;
+        for (int i = 0; i < 5; i++) {
+            switch (i) {
+                case 1:
+                    continue;
+                case 3:
+                    break;
+                default:
+                    total += i;
+            }
+        }
+        System.out.println(total);
+        System.out.println(describe(-1) + describe(2) + describe(50));
+    }
+
+    static String name(int day) {
+        return switch (day) {
+            case 1 -> "Monday";
+            case 2 -> "Tuesday";
+            case 3, 4, 5 -> {
+                String prefix = "Mid";
+                yield prefix + "week";
+            }
+            case 6, WEEKEND -> "Weekend";
+            default -> "Unknown";
+        };
+    }
+
+    static int kind(int day) {
+        int value = switch (day) {
+            case 6:
+            case 7:
+                yield 0;
+            default:
+                if (day == 1) {
+                    yield 2;
+                }
+                yield 1;
+        };
+        return value;
+    }
+
+    static String describe(int value) {
+        String text = switch (value) {
+            case -1 -> "negative";
+            case 0, 1, 2 -> "small";
+            default -> {
+                if (value > 10) yield "large";
+                yield "medium";
+            }
+        };
+        return text + ";";
+    }
+}
@@ -0,0 +1,136 @@
+#[derive(Clone, Default)]
+struct Switch {}
+
+impl Switch {
+    const WEEKEND: i32 = 7;
+
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        for day in 1..=7 {
+            println!("{} {}", Switch::name(day), Switch::kind(day));
+        }
+
+        // classic switch with fallthrough
+        for i in 0..4 {
+            let mut score = 0;
+            'switch3: {
+                match i {
+                    0 => {
+                        score = i32::wrapping_add(score, 1);
+                        score = i32::wrapping_add(score, 10);
+                    }
+                    1 => {
+                        score = i32::wrapping_add(score, 10);
+                    }
+                    2 | 3 => {
+                        {
+                            if score == 0 {
+                                score = 100;
+                                break 'switch3;
+                            }
+                            score = -1;
+                        }
+                        score = i32::wrapping_add(score, 1000);
+                    }
+                    _ => {
+                        score = i32::wrapping_add(score, 1000);
+                    }
+                }
+            }
+            println!("{score}");
+        }
+
+        let grade = 'B';
+        match grade {
+            'A' => {
+                println!("excellent");
+            }
+            'B' | 'C' => {
+                println!("good");
+            }
+            _ => {
+                println!("unknown");
+            }
+        }
+
+        let command = "stop".to_string();
+        match command.as_str() {
+            "go" => {
+                println!("going");
+            }
+            "stop" => {
+                println!("stopping");
+            }
+            _ => {}
+        }
+
+        let mut total = 0;
+        'loop6: for i in 0..5 {
+            match i {
+                1 => {
+                    continue 'loop6;
+                }
+                3 => {}
+                _ => {
+                    total = i32::wrapping_add(total, i);
+                }
+            }
+        }
+        println!("{total}");
+        println!(
+            "{}{}{}",
+            Switch::describe(-1),
+            Switch::describe(2),
+            Switch::describe(50)
+        );
+    }
+
+    fn name(day: i32) -> String {
+        return match day {
+            1 => "Monday".to_string(),
+            2 => "Tuesday".to_string(),
+            3 | 4 | 5 => {
+                let prefix = "Mid".to_string();
+                format!("{prefix}week")
+            }
+            6 | Switch::WEEKEND => "Weekend".to_string(),
+            _ => "Unknown".to_string(),
+        };
+    }
+
+    fn kind(day: i32) -> i32 {
+        let value = 'switch1: {
+            match day {
+                6 | 7 => 0,
+                _ => {
+                    if day == 1 {
+                        break 'switch1 2;
+                    }
+                    1
+                }
+            }
+        };
+        return value;
+    }
+
+    fn describe(value: i32) -> String {
+        let text = 'switch1: {
+            match value {
+                -1 => "negative".to_string(),
+                0 | 1 | 2 => "small".to_string(),
+                _ => {
+                    if value > 10 {
+                        break 'switch1 "large".to_string();
+                    }
+                    "medium".to_string()
+                }
+            }
+        };
+        return format!("{text};");
+    }
+}
+
+fn main() {
+    Switch::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
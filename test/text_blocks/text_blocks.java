@@ -0,0 +1,18 @@
+class TextBlocks {
+    public static void main(String[] args) {
+        System.out.println("""
+            SELECT name
+              FROM users
+             WHERE id = {id}
+            """);
+        System.out.println("""
+            {
+                "name": "j2rust",\s
+                "long": "first \
+            second"
+            }""");
+        System.out.println("""
+            tab\there \"\"\" done
+            """);
+    }
+}
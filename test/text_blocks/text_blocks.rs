@@ -0,0 +1,26 @@
+#[derive(Clone, Default)]
+struct TextBlocks {}
+
+impl TextBlocks {
+    pub fn main(args: std::rc::Rc<std::cell::RefCell<Vec<String>>>) {
+        println!(
+            r"SELECT name
+  FROM users
+ WHERE id = {{id}}
+"
+        );
+        println!(
+            r#"{{
+    "name": "j2rust", 
+    "long": "first second"
+}}"#
+        );
+        println!("tab\there \"\"\" done\n");
+    }
+}
+
+fn main() {
+    TextBlocks::main(std::rc::Rc::new(std::cell::RefCell::new(
+        std::env::args().skip(1).collect(),
+    )));
+}
@@ -0,0 +1,146 @@
+//! Translates every fixture in test/ and compares the result with the checked in output
+//!
+//! test/<name>/<name>.java is translated to test/<name>/<name>.rs, unless there is a
+//! test/<name>/<name>.err, then translating has to fail with that error output. The translated
+//! file has to compile, and when there is a test/<name>/<name>.out, the program has to print it.
+//! The warnings of a translated file are compared with test/<name>/<name>.warnings, a file
+//! without it mustn't have warnings
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+#[test]
+fn fixtures() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // The translator writes next to its input, so it runs on a copy with the same relative path
+    let work_dir = env::temp_dir().join(format!("j2rust-fixtures-{}", std::process::id()));
+    let mut failures = Vec::new();
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(root.join("test"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    fixtures.sort();
+    for dir in fixtures {
+        let name = dir.file_name().unwrap().to_str().unwrap();
+        let input = Path::new("test").join(name).join(format!("{name}.java"));
+        if !root.join(&input).exists() {
+            continue;
+        }
+        fs::create_dir_all(work_dir.join(input.parent().unwrap())).unwrap();
+        fs::copy(root.join(&input), work_dir.join(&input)).unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_j2rust"))
+            .arg(&input)
+            .current_dir(&work_dir)
+            .output()
+            .unwrap();
+        let expected_errors = root.join(input.with_extension("err"));
+        if expected_errors.exists() {
+            let errors = String::from_utf8_lossy(&output.stderr);
+            let expected = fs::read_to_string(expected_errors).unwrap();
+            if output.status.success() {
+                failures.push(format!("{name}: translated, expected errors"));
+            } else if errors != expected {
+                failures.push(format!(
+                    "{name}: different errors\n{}",
+                    diff(&expected, &errors)
+                ));
+            }
+            continue;
+        }
+        if !output.status.success() {
+            let errors = String::from_utf8_lossy(&output.stderr);
+            failures.push(format!("{name}: failed to translate\n{errors}"));
+            continue;
+        }
+        let warnings = String::from_utf8_lossy(&output.stderr);
+        let expected_warnings =
+            fs::read_to_string(root.join(input.with_extension("warnings"))).unwrap_or_default();
+        if warnings != expected_warnings {
+            failures.push(format!(
+                "{name}: different warnings\n{}",
+                diff(&expected_warnings, &warnings)
+            ));
+        }
+        let translated = work_dir.join(input.with_extension("rs"));
+        let expected = fs::read_to_string(root.join(input.with_extension("rs"))).unwrap();
+        let actual = fs::read_to_string(&translated).unwrap();
+        if actual != expected {
+            failures.push(format!(
+                "{name}: different output, regenerate the fixture\n{}",
+                diff(&expected, &actual)
+            ));
+            continue;
+        }
+        if let Err(failure) = run(&translated, &root.join(input.with_extension("out"))) {
+            failures.push(format!("{name}: {failure}"));
+        }
+    }
+    let _ = fs::remove_dir_all(&work_dir);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Compiles the translated file and compares what it prints with the expected output, when
+/// there is one
+fn run(translated: &Path, expected_output: &Path) -> Result<(), String> {
+    let binary = translated.with_extension("bin");
+    let compiled = Command::new("rustc")
+        .args(["--edition", "2024", "-A", "warnings", "-o"])
+        .arg(&binary)
+        .arg(translated)
+        .output()
+        .unwrap();
+    if !compiled.status.success() {
+        return Err(format!(
+            "the output doesn't compile\n{}",
+            String::from_utf8_lossy(&compiled.stderr)
+        ));
+    }
+    let Ok(expected) = fs::read_to_string(expected_output) else {
+        return Ok(());
+    };
+    let output = Command::new(&binary).output().unwrap();
+    let actual = String::from_utf8_lossy(&output.stdout);
+    match actual == expected {
+        true => Ok(()),
+        false => Err(format!(
+            "different program output\n{}",
+            diff(&expected, &actual)
+        )),
+    }
+}
+
+/// The lines which are only in expected with -, the ones which are only in actual with +, each
+/// with its line number
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    // lengths[i][j] is the length of the longest common subsequence of expected[i..], actual[j..]
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = match expected[i] == actual[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            (i, j) = (i + 1, j + 1);
+        } else if j == actual.len()
+            || (i < expected.len() && lengths[i + 1][j] >= lengths[i][j + 1])
+        {
+            lines.push(format!("{:>4} - {}", i + 1, expected[i]));
+            i += 1;
+        } else {
+            lines.push(format!("{:>4} + {}", j + 1, actual[j]));
+            j += 1;
+        }
+    }
+    lines.join("\n")
+}